@@ -0,0 +1,86 @@
+//! Build script embedding build provenance for `--version`.
+//!
+//! Sets `MCGRAVITY_GIT_HASH` (short commit hash, or `unknown` outside a git
+//! checkout) and `MCGRAVITY_BUILD_DATE` (UTC `YYYY-MM-DD`, honoring
+//! `SOURCE_DATE_EPOCH` for reproducible builds).
+
+use std::path::Path;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+fn main() {
+    watch_git_head();
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    println!(
+        "cargo:rustc-env=MCGRAVITY_GIT_HASH={}",
+        git(&["rev-parse", "--short", "HEAD"]).unwrap_or_else(|| "unknown".to_string())
+    );
+    println!("cargo:rustc-env=MCGRAVITY_BUILD_DATE={}", build_date());
+}
+
+/// Reruns the build script when `HEAD` or a ref moves.
+///
+/// The git directories are asked from git, since `.git` is a file in a
+/// worktree, and only existing paths are watched, so a source tarball
+/// without `.git` does not rebuild every time.
+fn watch_git_head() {
+    let Some(dirs) = git(&["rev-parse", "--git-dir", "--git-common-dir"]) else {
+        return;
+    };
+    let mut dirs = dirs.lines();
+    let (Some(git_dir), Some(common_dir)) = (dirs.next(), dirs.next()) else {
+        return;
+    };
+    let watched = [
+        Path::new(git_dir).join("HEAD"),
+        Path::new(common_dir).join("refs"),
+        Path::new(common_dir).join("packed-refs"),
+    ];
+    for path in watched.iter().filter(|path| path.exists()) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+/// Runs `git <args>` and returns its trimmed output, or `None` if git is
+/// unavailable, fails or prints nothing.
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|output| output.trim().to_string())
+        .filter(|output| !output.is_empty())
+}
+
+/// Returns the build date as `YYYY-MM-DD` in UTC.
+fn build_date() -> String {
+    let secs = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs())
+        });
+    let (year, month, day) = civil_from_days(secs / 86_400);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Converts days since 1970-01-01 to a `(year, month, day)` date.
+///
+/// Howard Hinnant's `civil_from_days`, restricted to dates after the epoch.
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
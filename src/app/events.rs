@@ -3,15 +3,21 @@
 use std::time::Instant;
 
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use tokio::sync::mpsc;
 
 use super::App;
 use crate::app::input::RapidInputDetector;
-use crate::app::state::{EnterBehavior, InitialSetupField, SettingsItem};
-use crate::app::{AppMode, FlowEvent};
-use crate::core::{FlowPhase, run_flow};
-use crate::file_search::SearchResult;
+use crate::app::state::{EnterBehavior, InitialSetupField, PhaseMarker, SettingsItem, scroll_text};
+use crate::app::{AppMode, FlowEvent, Focus};
+use crate::core::git::{SystemGit, current_head};
+use crate::core::{FlowPhase, Model, PromptStats, run_flow};
+use crate::file_search::{FileMatch, SearchResult, merge_matches};
 use crate::fs::PersistedSettings;
-use crate::tui::widgets::{MAX_OUTPUT_LINES, OutputLine, calculate_visual_line_count};
+use crate::tui::notification;
+use crate::tui::widgets::output::text_width;
+use crate::tui::widgets::{
+    MAX_OUTPUT_LINES, OutputLine, OutputLineType, calculate_visual_line_count,
+};
 
 /// Scroll page size for navigation.
 const SCROLL_PAGE_SIZE: usize = 10;
@@ -110,6 +116,10 @@ impl App {
             AppMode::Settings => self.handle_settings_key(key),
             AppMode::Finished => self.handle_finished_key(key),
             AppMode::InitialSetup => self.handle_initial_setup_key(key),
+            AppMode::ConfirmStart => self.handle_confirm_start_key(key),
+            AppMode::PromptPreview => self.handle_prompt_preview_key(key),
+            AppMode::TaskView => self.handle_task_view_key(key),
+            AppMode::ModelPicker => self.handle_model_picker_key(key),
         }
     }
 
@@ -130,19 +140,64 @@ impl App {
     }
 
     /// Closes the settings panel and returns to Chat mode.
-    /// Settings are auto-saved to .mcgravity/settings.json.
+    /// Settings are auto-saved to .mcgravity/settings.json, and the chosen
+    /// theme (only previewed while the panel is open) takes effect.
     pub(crate) fn close_settings(&mut self) {
-        // Save settings before closing
+        self.theme = self.settings.color_theme.theme();
+
+        // Save settings before closing (a failure is reported, not blocking)
+        self.save_settings();
+
+        // Always return to Chat mode (the only non-settings mode)
+        self.settings.previous_mode = None;
+        self.mode = AppMode::Chat;
+    }
+
+    /// Switches plain Enter between submitting and inserting a newline.
+    ///
+    /// The new behavior is saved to `.mcgravity/settings.json` right away, as
+    /// there is no settings panel to close.
+    pub(crate) fn toggle_enter_behavior(&mut self) {
+        self.settings.enter_behavior = self.settings.enter_behavior.next();
+        self.save_settings();
+    }
+
+    /// Cycles the output verbosity (Quiet, Normal, Verbose).
+    ///
+    /// A running flow picks the change up from its next output line, and the
+    /// new verbosity is saved to `.mcgravity/settings.json` right away.
+    pub(crate) fn cycle_verbosity(&mut self) {
+        self.settings.verbosity = self.settings.verbosity.next();
+        self.verbosity_tx.send_replace(self.settings.verbosity);
+        self.save_settings();
+        self.push_output(OutputLine::info(format!(
+            "Verbosity: {}",
+            self.settings.verbosity.name()
+        )));
+        self.scroll_output_to_bottom();
+    }
+
+    /// Saves the settings to `.mcgravity/settings.json`, reporting a failure
+    /// as a warning in the output.
+    fn save_settings(&mut self) {
         let persisted = PersistedSettings::from(&self.settings);
         if let Err(e) = self.paths.save_settings(&persisted) {
-            // Log warning but don't prevent closing
-            self.flow_ui
-                .output
-                .push(OutputLine::warning(format!("Failed to save settings: {e}")));
+            self.push_output(OutputLine::warning(format!("Failed to save settings: {e}")));
         }
+    }
 
-        // Always return to Chat mode (the only non-settings mode)
-        self.settings.previous_mode = None;
+    /// Opens the model picker with the planning model highlighted.
+    pub(crate) fn open_model_picker(&mut self) {
+        self.model_picker_index = Model::all()
+            .iter()
+            .position(|&model| model == self.settings.planning_model)
+            .unwrap_or(0);
+        self.mode = AppMode::ModelPicker;
+    }
+
+    /// Closes the model picker, saving the chosen models.
+    fn close_model_picker(&mut self) {
+        self.save_settings();
         self.mode = AppMode::Chat;
     }
 
@@ -151,13 +206,16 @@ impl App {
     /// Key event priorities:
     /// 1. File popup handling (when popup is visible)
     /// 2. Command popup handling (when popup is visible)
-    /// 3. Output scrolling (Ctrl+Arrow keys, PageUp/PageDown)
-    /// 4. Quit shortcuts (Esc, Ctrl+C)
-    /// 5. Text input handling (default)
+    /// 3. Focus toggle (Tab)
+    /// 4. Output scrolling (Ctrl+Arrow keys, PageUp/PageDown), or line
+    ///    selection and copying with unmodified keys while the output is focused
+    /// 5. Quit shortcuts (Esc, Ctrl+C)
+    /// 6. History recall (Up/Down at the first/last input line)
+    /// 7. Text input handling (default, only while the input is focused)
     #[allow(clippy::too_many_lines)]
     fn handle_chat_key(&mut self, key: KeyEvent) {
         // Priority 1: File popup handling (when popup is visible)
-        if !self.is_running && self.should_show_file_popup() {
+        if !self.input_locked() && self.should_show_file_popup() {
             match key.code {
                 KeyCode::Up | KeyCode::Char('k') if key.modifiers.is_empty() => {
                     self.file_popup_up();
@@ -177,7 +235,8 @@ impl App {
                     }
                     // No matches - fall through to normal handling
                 }
-                KeyCode::Enter if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                // Modified Enter keeps its explicit submit/newline meaning
+                KeyCode::Enter if key.modifiers.is_empty() => {
                     if self.has_file_matches() {
                         self.select_file_from_popup();
                         return;
@@ -194,7 +253,7 @@ impl App {
         }
 
         // Priority 1.5: Command popup handling (when popup is visible)
-        if !self.is_running && self.should_show_command_popup() {
+        if !self.input_locked() && self.should_show_command_popup() {
             match key.code {
                 KeyCode::Up | KeyCode::Char('k') if key.modifiers.is_empty() => {
                     self.command_popup_up();
@@ -213,7 +272,7 @@ impl App {
                     // No matches - fall through to normal handling
                 }
                 // Enter selects from popup AND submits the command
-                KeyCode::Enter if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                KeyCode::Enter if key.modifiers.is_empty() => {
                     if self.has_command_matches() {
                         self.select_command_from_popup();
                         self.submit_text_input();
@@ -230,6 +289,15 @@ impl App {
             }
         }
 
+        // Tab (outside the popups) moves focus between the input and output
+        if matches!(key.code, KeyCode::Tab | KeyCode::BackTab)
+            && !key.modifiers.contains(KeyModifiers::CONTROL)
+            && !key.modifiers.contains(KeyModifiers::ALT)
+        {
+            self.focus = self.focus.toggle();
+            return;
+        }
+
         // Priority 2: Output scrolling with Ctrl modifier (doesn't conflict with text navigation)
         if key.modifiers.contains(KeyModifiers::CONTROL) {
             match key.code {
@@ -249,12 +317,25 @@ impl App {
                     self.scroll_output_to_bottom();
                     return;
                 }
+                KeyCode::Char('o') => {
+                    self.cycle_verbosity();
+                    return;
+                }
                 _ => {}
             }
         }
 
         // Priority 3: Page scrolling (unambiguous keys that don't conflict with text editing)
+        // Alt+PageUp/PageDown jump between phase boundaries instead
         match key.code {
+            KeyCode::PageUp if key.modifiers.contains(KeyModifiers::ALT) => {
+                self.goto_adjacent_phase_marker(false);
+                return;
+            }
+            KeyCode::PageDown if key.modifiers.contains(KeyModifiers::ALT) => {
+                self.goto_adjacent_phase_marker(true);
+                return;
+            }
             KeyCode::PageUp => {
                 self.page_up_output();
                 return;
@@ -266,9 +347,61 @@ impl App {
             _ => {}
         }
 
+        // Line selection while the output is focused
+        if self.focus == Focus::Output && !key.modifiers.contains(KeyModifiers::CONTROL) {
+            match key.code {
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.move_output_selection(false);
+                    return;
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.move_output_selection(true);
+                    return;
+                }
+                KeyCode::Home | KeyCode::Char('g') => {
+                    self.select_output_line(0);
+                    return;
+                }
+                KeyCode::End | KeyCode::Char('G') => {
+                    self.select_output_line(usize::MAX);
+                    return;
+                }
+                KeyCode::Enter | KeyCode::Char('y') => {
+                    self.copy_selected_line();
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        // Error navigation while the input is locked by a running flow
+        // or the output is focused
+        if self.input_locked() || self.focus == Focus::Output {
+            match key.code {
+                KeyCode::Char('n') if key.modifiers.is_empty() => {
+                    self.goto_adjacent_error(true);
+                    return;
+                }
+                KeyCode::Char('N') => {
+                    self.goto_adjacent_error(false);
+                    return;
+                }
+                KeyCode::Char('t') if key.modifiers.is_empty() => {
+                    self.open_task_view();
+                    return;
+                }
+                _ => {}
+            }
+        }
+
         // Priority 4: Quit shortcuts
         if self.is_running && key.code == KeyCode::Esc {
-            self.trigger_shutdown();
+            // Esc first discards a command typed mid-run, then cancels the flow
+            if self.typing_command() {
+                self.text_input.clear();
+            } else {
+                self.cancel_flow();
+            }
             return;
         }
         match key.code {
@@ -283,21 +416,45 @@ impl App {
             _ => {}
         }
 
-        // Priority 5: Text input handling (default)
-        if self.is_running {
+        // Typing is ignored while the output is focused
+        if self.focus == Focus::Output {
             return;
         }
+
+        // Priority 5: History recall (Up on the first line, Down on the last line)
+        if self.input_locked() {
+            // A `/` opens a command line, so `/cancel` can be typed mid-run
+            if key.code == KeyCode::Char('/') {
+                self.handle_text_input(key);
+            }
+            return;
+        }
+        let recalled = match key.code {
+            KeyCode::Up if key.modifiers.is_empty() && self.text_input.at_history_top() => {
+                self.recall_history_prev()
+            }
+            KeyCode::Down if key.modifiers.is_empty() && self.text_input.at_history_bottom() => {
+                self.recall_history_next()
+            }
+            _ => false,
+        };
+        if recalled {
+            return;
+        }
+
+        // Priority 6: Text input handling (default)
         self.handle_text_input(key);
     }
 
     /// Handles text input key events.
     ///
     /// Key bindings:
-    /// - `Enter` - Submit task (traditional chat behavior)
+    /// - `Enter` - Submit task or insert newline, depending on the Enter behavior setting
     /// - `Shift+Enter` - Insert newline
     /// - `Alt+Enter` - Insert newline (alternative for terminal compatibility)
     /// - `Ctrl+Enter` - Submit task (alternative)
     /// - `Ctrl+D` - Submit task (alternative)
+    /// - `Ctrl+T` - Toggle the Enter behavior between Submit and Newline
     /// - Other keys - Delegated to `tui-textarea` for handling
     ///
     /// # Design Decision: Traditional Chat Behavior
@@ -308,14 +465,17 @@ impl App {
     ///
     /// # Rapid Input Detection
     ///
-    /// Rapid input detection is still tracked for potential future use cases.
+    /// On terminals without keyboard enhancement, pastes may arrive as plain
+    /// key events. There, a plain Enter within a rapid key sequence inserts a
+    /// newline so a multi-line paste is assembled instead of submitted.
     #[allow(clippy::match_same_arms)] // Separate arms for clarity and documentation
     #[allow(clippy::too_many_lines)] // Debug logging adds necessary lines; core logic is extracted to RapidInputDetector
     fn handle_text_input(&mut self, key: KeyEvent) {
         let now = Instant::now();
 
-        // Process rapid input detection
+        // Process rapid input detection (only acted on without bracketed paste)
         let rapid_result = self.text_input.rapid_input.process_key(&key);
+        let is_paste_fallback = rapid_result.is_rapid && !self.text_input.bracketed_paste;
 
         // Log state transitions (enable with MCGRAVITY_DEBUG_KEYS=1)
         if std::env::var("MCGRAVITY_DEBUG_KEYS").is_ok() {
@@ -347,8 +507,10 @@ impl App {
                 "no-op (Ctrl+Enter with empty input)"
             } else if has_shift || has_alt {
                 "newline (Shift+Enter or Alt+Enter)"
-            } else if rapid_result.is_rapid {
+            } else if is_paste_fallback {
                 "newline (rapid input - paste detected)"
+            } else if self.settings.enter_behavior == EnterBehavior::Newline {
+                "newline (Enter in Newline mode)"
             } else if input_not_empty {
                 "submit (Enter)"
             } else {
@@ -380,6 +542,10 @@ impl App {
             KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.submit_text_input();
             }
+            // Toggle Enter behavior: Ctrl+T
+            KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.toggle_enter_behavior();
+            }
             // Newline: Ctrl+J (universal - works on ALL terminals)
             // Ctrl+J = ASCII 10 (LF), the standard newline character.
             // This works reliably because it's a control character, not a modifier+key combo.
@@ -423,7 +589,7 @@ impl App {
                     return;
                 }
 
-                if rapid_result.is_rapid {
+                if is_paste_fallback {
                     // During paste operation detected via rapid input, treat Enter
                     // as newline to prevent accidental submission of multi-line paste.
                     // Also activate rapid input mode if not already active, so subsequent
@@ -516,6 +682,52 @@ impl App {
         }
     }
 
+    /// Handles key events in the model picker.
+    ///
+    /// ## Key Bindings
+    ///
+    /// - `Up` / `k`, `Down` / `j` - Move between models
+    /// - `p` - Use the highlighted model for planning
+    /// - `e` - Use the highlighted model for execution
+    /// - `Enter` / `Space` - Use the highlighted model for both
+    /// - `Esc` / `q` / `Ctrl+C` - Close and save
+    ///
+    /// Models whose CLI is unavailable cannot be picked.
+    fn handle_model_picker_key(&mut self, key: KeyEvent) {
+        let models = Model::all();
+        let max_index = models.len().saturating_sub(1);
+
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.close_model_picker();
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.model_picker_index = self.model_picker_index.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.model_picker_index = (self.model_picker_index + 1).min(max_index);
+            }
+            KeyCode::Char('p' | 'e' | ' ') | KeyCode::Enter => {
+                let Some(&model) = models.get(self.model_picker_index) else {
+                    return;
+                };
+                if !self.settings.is_model_available(model) {
+                    return;
+                }
+                if key.code != KeyCode::Char('e') {
+                    self.settings.planning_model = model;
+                }
+                if key.code != KeyCode::Char('p') {
+                    self.settings.execution_model = model;
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.close_model_picker();
+            }
+            _ => {}
+        }
+    }
+
     /// Handles key events in finished mode.
     fn handle_finished_key(&mut self, key: KeyEvent) {
         match key.code {
@@ -526,10 +738,85 @@ impl App {
                 self.trigger_shutdown();
                 self.should_quit = true;
             }
+            KeyCode::Char('c') if self.flow_ui.last_error.is_some() => {
+                self.copy_last_error();
+            }
+            KeyCode::Char('n') => {
+                self.goto_adjacent_error(true);
+            }
+            KeyCode::Char('N') => {
+                self.goto_adjacent_error(false);
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles key events in the unlimited-iterations confirmation modal.
+    ///
+    /// `Enter` / `y` starts the flow; `Esc` / `n` cancels and restores the
+    /// task text into the input so it can be adjusted.
+    fn handle_confirm_start_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter | KeyCode::Char('y' | 'Y') => {
+                self.mode = AppMode::Chat;
+                self.start_flow();
+            }
+            KeyCode::Esc | KeyCode::Char('n' | 'N') => {
+                self.mode = AppMode::Chat;
+                self.load_saved_task();
+                self.push_output(OutputLine::info("Flow not started"));
+            }
             _ => {}
         }
     }
 
+    /// Handles key events in the `/preview` prompt modal.
+    ///
+    /// `Up`/`k`, `Down`/`j`, `PageUp`/`PageDown` and `Home`/`End` scroll;
+    /// `Esc` / `q` closes the modal.
+    fn handle_prompt_preview_key(&mut self, key: KeyEvent) {
+        let delta = self.text_modal_scroll_delta(key.code);
+        let Some(preview) = self.prompt_preview.as_mut() else {
+            self.mode = AppMode::Chat;
+            return;
+        };
+        if let Some(delta) = delta {
+            preview.scroll_by(delta);
+        } else if matches!(key.code, KeyCode::Esc | KeyCode::Char('q')) {
+            self.prompt_preview = None;
+            self.mode = AppMode::Chat;
+        }
+    }
+
+    /// Handles key events in the task text modal.
+    ///
+    /// Uses the same bindings as the prompt preview; `t` also closes it, so
+    /// the key that opened it during a run toggles it.
+    fn handle_task_view_key(&mut self, key: KeyEvent) {
+        if let Some(delta) = self.text_modal_scroll_delta(key.code) {
+            self.task_view_scroll =
+                scroll_text(self.task_view_scroll, delta, &self.flow.input_text);
+        } else if matches!(key.code, KeyCode::Esc | KeyCode::Char('q' | 't')) {
+            self.mode = AppMode::Chat;
+        }
+    }
+
+    /// Returns how many lines a scroll key moves a text modal, or `None` for
+    /// other keys. A page is the height of the output panel; `Home`/`End`
+    /// move past either end and are clamped by the caller.
+    fn text_modal_scroll_delta(&self, code: KeyCode) -> Option<i32> {
+        let page = i32::try_from(self.layout.output_visible_height().max(1)).unwrap_or(i32::MAX);
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => Some(-1),
+            KeyCode::Down | KeyCode::Char('j') => Some(1),
+            KeyCode::PageUp => Some(-page),
+            KeyCode::PageDown => Some(page),
+            KeyCode::Home => Some(i32::MIN),
+            KeyCode::End => Some(i32::MAX),
+            _ => None,
+        }
+    }
+
     /// Handles key events in initial setup mode.
     ///
     /// The initial setup modal cannot be dismissed with Esc - the user must
@@ -624,6 +911,15 @@ impl App {
 
         // Clear initial_setup state
         self.initial_setup = None;
+
+        // Start the flow for an input file now that its models are chosen
+        if !self.flow.input_paths.is_empty()
+            && !self.is_running
+            && self.paths.find_unwritable_dir().is_none()
+            && !self.open_settings_for_missing_cli()
+        {
+            self.request_start_flow();
+        }
     }
 
     // =========================================================================
@@ -671,6 +967,206 @@ impl App {
         );
     }
 
+    /// Records a phase boundary, rendered as a separator line in the output.
+    ///
+    /// The idle phase is not a boundary, and a phase repeated right after its
+    /// own marker (such as attempt 1 being announced twice) is only marked once.
+    fn mark_phase_boundary(&mut self, phase: &FlowPhase) {
+        if *phase == FlowPhase::Idle {
+            return;
+        }
+        let label = phase.description().into_owned();
+        if self
+            .flow_ui
+            .phase_markers
+            .last()
+            .is_some_and(|marker| marker.label == label)
+        {
+            return;
+        }
+        self.push_output(OutputLine::phase_separator(label.clone()));
+        let line = self.flow_ui.output.len() - 1;
+        self.flow_ui.phase_markers.push(PhaseMarker { line, label });
+    }
+
+    /// Returns the scroll offset that puts output line `line` at the top of
+    /// the output panel, clamped to the maximum scroll offset.
+    fn output_offset_of_line(&self, line: usize) -> usize {
+        let width = text_width(
+            self.layout.output_content_width(),
+            self.settings.show_timestamps,
+        );
+        let offset = calculate_visual_line_count(&self.flow_ui.output[..line], width);
+        let max_offset = self
+            .output_visual_line_count()
+            .saturating_sub(self.layout.output_visible_height());
+        offset.min(max_offset)
+    }
+
+    /// Returns the index of the output line shown on visual row `offset`.
+    fn output_line_at_offset(&self, offset: usize) -> usize {
+        let width = text_width(
+            self.layout.output_content_width(),
+            self.settings.show_timestamps,
+        );
+        let mut rows = 0;
+        for (index, line) in self.flow_ui.output.iter().enumerate() {
+            rows += calculate_visual_line_count(std::slice::from_ref(line), width);
+            if rows > offset {
+                return index;
+            }
+        }
+        self.flow_ui.output.len().saturating_sub(1)
+    }
+
+    /// Moves the line selection one line down (`forward`) or up.
+    ///
+    /// Without a selection, the first press selects the last visible line.
+    fn move_output_selection(&mut self, forward: bool) {
+        let line = match self.flow_ui.selected_line {
+            Some(line) if forward => line.saturating_add(1),
+            Some(line) => line.saturating_sub(1),
+            None => {
+                let bottom = self.flow_ui.output_scroll.offset
+                    + self.layout.output_visible_height().saturating_sub(1);
+                self.output_line_at_offset(bottom)
+            }
+        };
+        self.select_output_line(line);
+    }
+
+    /// Selects output line `line`, clamped to the buffer, and scrolls so all
+    /// of its wrapped rows are visible (its first row if it is taller than
+    /// the panel).
+    pub(crate) fn select_output_line(&mut self, line: usize) {
+        let Some(last) = self.flow_ui.output.len().checked_sub(1) else {
+            return;
+        };
+        let line = line.min(last);
+        self.flow_ui.selected_line = Some(line);
+
+        let width = text_width(
+            self.layout.output_content_width(),
+            self.settings.show_timestamps,
+        );
+        let start = calculate_visual_line_count(&self.flow_ui.output[..line], width);
+        let rows = calculate_visual_line_count(&self.flow_ui.output[line..=line], width);
+        let height = self.layout.output_visible_height();
+        let scroll = &mut self.flow_ui.output_scroll;
+        if start < scroll.offset {
+            scroll.offset = start;
+        } else if start + rows > scroll.offset + height {
+            scroll.offset = (start + rows).saturating_sub(height).min(start);
+        }
+        scroll.auto_scroll = false;
+        self.repin_output_if_at_bottom();
+    }
+
+    /// Returns the text of the selected output line, including the part
+    /// wrapped onto continuation rows.
+    #[must_use]
+    pub(crate) fn selected_line_text(&self) -> Option<&str> {
+        self.flow_ui
+            .selected_line
+            .and_then(|line| self.flow_ui.output.get(line))
+            .map(|line| line.text.as_str())
+    }
+
+    /// Copies the selected output line to the clipboard.
+    fn copy_selected_line(&mut self) {
+        let Some(text) = self.selected_line_text().map(str::to_string) else {
+            self.push_output(OutputLine::info(
+                "No line selected. Move the selection with Up/Down first.",
+            ));
+            return;
+        };
+        match crate::tui::clipboard::copy(&text) {
+            Ok(()) => self.push_output(OutputLine::success("Copied line to clipboard")),
+            Err(e) => self.push_output(OutputLine::warning(format!(
+                "Failed to copy to clipboard: {e}"
+            ))),
+        }
+    }
+
+    /// Scrolls the output so the `number`th phase marker (1-based) is the top
+    /// visible line, disabling auto-scroll unless that is the bottom.
+    ///
+    /// Returns false if there is no such marker.
+    pub(crate) fn goto_phase_marker(&mut self, number: usize) -> bool {
+        let Some(marker) = number
+            .checked_sub(1)
+            .and_then(|index| self.flow_ui.phase_markers.get(index))
+        else {
+            return false;
+        };
+        self.flow_ui.output_scroll.offset = self.output_offset_of_line(marker.line);
+        self.flow_ui.output_scroll.auto_scroll = false;
+        self.repin_output_if_at_bottom();
+        true
+    }
+
+    /// Scrolls the output to the nearest phase marker above (`forward` false)
+    /// or below (`forward` true) the current scroll position.
+    fn goto_adjacent_phase_marker(&mut self, forward: bool) {
+        let current = self.flow_ui.output_scroll.offset;
+        let offsets = self
+            .flow_ui
+            .phase_markers
+            .iter()
+            .map(|marker| self.output_offset_of_line(marker.line));
+        let target = if forward {
+            offsets.filter(|&offset| offset > current).min()
+        } else {
+            offsets.filter(|&offset| offset < current).max()
+        };
+        if let Some(offset) = target {
+            self.flow_ui.output_scroll.offset = offset;
+            self.flow_ui.output_scroll.auto_scroll = false;
+            self.repin_output_if_at_bottom();
+        }
+    }
+
+    /// Scrolls the output to the next (`forward` true) or previous error or
+    /// warning line, wrapping around at the ends.
+    ///
+    /// The first jump starts from the current scroll position; later jumps
+    /// continue from the last line jumped to.
+    pub(crate) fn goto_adjacent_error(&mut self, forward: bool) {
+        let count = self.flow_ui.error_lines.len();
+        if count == 0 {
+            return;
+        }
+        let index = match self.flow_ui.error_cursor {
+            Some(cursor) if forward => (cursor + 1) % count,
+            Some(cursor) => (cursor + count - 1) % count,
+            None => {
+                let current = self.flow_ui.output_scroll.offset;
+                let offsets: Vec<usize> = self
+                    .flow_ui
+                    .error_lines
+                    .iter()
+                    .map(|&line| self.output_offset_of_line(line))
+                    .collect();
+                if forward {
+                    offsets
+                        .iter()
+                        .position(|&offset| offset >= current)
+                        .unwrap_or(0)
+                } else {
+                    offsets
+                        .iter()
+                        .rposition(|&offset| offset <= current)
+                        .unwrap_or(count - 1)
+                }
+            }
+        };
+        self.flow_ui.error_cursor = Some(index);
+        self.flow_ui.output_scroll.offset =
+            self.output_offset_of_line(self.flow_ui.error_lines[index]);
+        self.flow_ui.output_scroll.auto_scroll = false;
+        self.repin_output_if_at_bottom();
+    }
+
     /// Cycles through options for the currently selected setting.
     fn cycle_current_setting(&mut self) {
         let items = SettingsItem::all();
@@ -694,6 +1190,31 @@ impl App {
             SettingsItem::SummaryGeneration => {
                 self.settings.summary_generation = self.settings.summary_generation.next();
             }
+            SettingsItem::ArchiveMode => {
+                self.settings.archive_mode = self
+                    .settings
+                    .archive_mode
+                    .next(self.settings.archive_dir.as_deref());
+            }
+            SettingsItem::ResetClearsTask => {
+                self.settings.reset_clears_task = !self.settings.reset_clears_task;
+            }
+            SettingsItem::FlowMode => {
+                self.settings.flow_mode = self.settings.flow_mode.next();
+            }
+            SettingsItem::Theme => {
+                self.settings.color_theme = self.settings.color_theme.next();
+            }
+        }
+    }
+
+    /// Starts the flow, first asking for confirmation if it would run with
+    /// unlimited iterations (unless disabled in settings).
+    pub(super) fn request_start_flow(&mut self) {
+        if self.settings.confirm_unlimited && self.settings.max_iterations.value().is_none() {
+            self.mode = AppMode::ConfirmStart;
+        } else {
+            self.start_flow();
         }
     }
 
@@ -703,90 +1224,240 @@ impl App {
     /// `settings.execution_model`. If never configured, uses defaults.
     pub(super) fn start_flow(&mut self) {
         self.reset_shutdown();
+        // A followed log would interleave with the flow's output
+        self.stop_tail();
+        self.flow.phase = FlowPhase::Idle;
+        self.flow.cycle_count = 0;
+        self.flow_ui.prompt_stats = PromptStats::default();
+        self.flow_ui.last_error = None;
+        self.flow_ui.todo_progress = None;
+        self.flow_ui.up_next = None;
         let tx = self.event_sender();
         let shutdown_rx = self.shutdown_receiver();
-        let input_path = self.flow.input_path.clone();
+        let input_paths = self.flow.input_paths.clone();
         let input_text = self.flow.input_text.clone();
         let paths = self.paths.clone();
+        // Set once the flow task has asked git for it
+        self.flow_start_commit = None;
 
         // Get models from settings (they always have valid values)
         let planning_model = self.settings.planning_model;
         let execution_model = self.settings.execution_model;
-        let max_iterations = self.settings.max_iterations.value();
+        let max_iterations = self.max_iterations_receiver(self.settings.max_iterations.value());
 
         // Create executor instances for the selected models
-        let planning_executor = planning_model.executor();
-        let execution_executor = execution_model.executor();
-
-        // Determine whether to use model fallback for summary generation
-        let use_model_summary = self.settings.summary_generation.uses_model_fallback();
+        let planning_executor = self.settings.executor(planning_model);
+        let execution_executor = self.settings.executor(execution_model);
+        let summary_executor = self
+            .settings
+            .executor(self.settings.effective_summary_model());
+
+        let options = self.flow_options();
+        let verbosity = self.verbosity_receiver();
+        // /next and /stop also answer the pause before flagged todos
+        let step_rx = if options.step_mode || options.confirm_suspicious_todos {
+            let (step_tx, step_rx) = mpsc::channel(1);
+            self.step_tx = Some(step_tx);
+            Some(step_rx)
+        } else {
+            self.step_tx = None;
+            None
+        };
 
         self.set_running(true);
         tokio::spawn(async move {
+            // Git runs off the UI thread, before the flow changes anything
+            let base = paths.base().to_path_buf();
+            let head = tokio::task::spawn_blocking(move || current_head(&SystemGit, &base))
+                .await
+                .ok()
+                .flatten();
+            tx.send(FlowEvent::FlowStartCommit(head)).await.ok();
             let _ = run_flow(
-                input_path,
+                input_paths,
                 input_text,
                 tx,
                 shutdown_rx,
                 planning_executor.as_ref(),
                 execution_executor.as_ref(),
+                summary_executor.as_ref(),
                 max_iterations,
+                verbosity,
+                step_rx,
                 paths,
-                use_model_summary,
+                options,
             )
             .await;
         });
     }
 
+    /// Counts `line` as a repeat of the last output line if their text and
+    /// type match, rewriting the last line as `text (×N)`.
+    ///
+    /// Returns false (and starts tracking `line`) if it is not a repeat.
+    fn collapse_repeat(&mut self, line: &OutputLine) -> bool {
+        let ui = &mut self.flow_ui;
+        if let Some(last) = ui.output.last_mut()
+            && let Some((text, count)) = ui.last_line_repeat.as_mut()
+            && *text == line.text
+            && last.line_type == line.line_type
+            && last.text == repeated_line_text(text, *count)
+        {
+            *count += 1;
+            last.text = repeated_line_text(text, *count);
+            return true;
+        }
+        ui.last_line_repeat = Some((line.text.clone(), 1));
+        false
+    }
+
+    /// Appends a line to the output panel, trimming the oldest lines once
+    /// the buffer exceeds `MAX_OUTPUT_LINES`.
+    ///
+    /// With `collapse_repeated_lines` on, a line identical to the previous
+    /// one bumps a `(×N)` count on it instead of being appended.
+    pub(crate) fn push_output(&mut self, line: OutputLine) {
+        if self.settings.collapse_repeated_lines && self.collapse_repeat(&line) {
+            return;
+        }
+        self.append_output(line);
+    }
+
+    /// Appends a line to the output panel without collapsing repeats.
+    fn append_output(&mut self, line: OutputLine) {
+        if line.line_type == OutputLineType::SystemError {
+            let text = line.text.strip_prefix("✗ ").unwrap_or(&line.text);
+            self.flow_ui.last_error = Some(text.to_string());
+        }
+        if is_error_line(&line) {
+            self.flow_ui.error_lines.push(self.flow_ui.output.len());
+        }
+        self.flow_ui.output.push(line);
+        // Trim buffer if too large
+        if self.flow_ui.output.len() > MAX_OUTPUT_LINES {
+            let drain_count = self.flow_ui.output.len() - MAX_OUTPUT_LINES;
+            self.flow_ui.output.drain(0..drain_count);
+            self.flow_ui.phase_markers.retain_mut(|marker| {
+                match marker.line.checked_sub(drain_count) {
+                    Some(line) => {
+                        marker.line = line;
+                        true
+                    }
+                    None => false,
+                }
+            });
+            let dropped_errors = self
+                .flow_ui
+                .error_lines
+                .iter()
+                .take_while(|&&line| line < drain_count)
+                .count();
+            self.flow_ui.error_lines.drain(..dropped_errors);
+            for line in &mut self.flow_ui.error_lines {
+                *line -= drain_count;
+            }
+            self.flow_ui.error_cursor = self
+                .flow_ui
+                .error_cursor
+                .and_then(|cursor| cursor.checked_sub(dropped_errors));
+            self.flow_ui.selected_line = self
+                .flow_ui
+                .selected_line
+                .and_then(|line| line.checked_sub(drain_count));
+            self.flow_ui.partial_line = self
+                .flow_ui
+                .partial_line
+                .and_then(|line| line.checked_sub(drain_count));
+            self.flow_ui.output_scroll.offset = self
+                .flow_ui
+                .output_scroll
+                .offset
+                .saturating_sub(drain_count);
+            self.flow_ui.output_truncated = true;
+        }
+        self.auto_scroll_output_if_at_bottom();
+    }
+
     /// Processes pending flow events.
     pub fn process_events(&mut self) {
         while let Ok(event) = self.event_rx.try_recv() {
             match event {
+                // A cancelled flow keeps showing as cancelled while it winds down
+                FlowEvent::PhaseChanged(_) if self.flow.phase == FlowPhase::Cancelled => {}
                 FlowEvent::PhaseChanged(phase) => {
+                    if let FlowPhase::Failed { reason } = &phase {
+                        self.flow_ui.last_error = Some(reason.clone());
+                    }
+                    self.mark_phase_boundary(&phase);
                     self.flow.phase = phase;
                 }
                 FlowEvent::Output(line) => {
-                    self.flow_ui.output.push(line);
-                    // Trim buffer if too large
-                    if self.flow_ui.output.len() > MAX_OUTPUT_LINES {
-                        let drain_count = self.flow_ui.output.len() - MAX_OUTPUT_LINES;
-                        self.flow_ui.output.drain(0..drain_count);
-                        self.flow_ui.output_scroll.offset = self
-                            .flow_ui
-                            .output_scroll
-                            .offset
-                            .saturating_sub(drain_count);
-                        self.flow_ui.output_truncated = true;
+                    let line = self.style_markdown(line, false);
+                    self.push_output(line);
+                }
+                FlowEvent::PartialOutput(line) => {
+                    let line = self.style_markdown(line, false);
+                    // The partial line is rewritten in place later, so it
+                    // must get its own line rather than collapse into the
+                    // previous one
+                    self.flow_ui.last_line_repeat = None;
+                    self.append_output(line);
+                    self.flow_ui.partial_line = self.flow_ui.output.len().checked_sub(1);
+                }
+                FlowEvent::ReplaceLastOutput(line) => {
+                    let line = self.style_markdown(line, true);
+                    self.replace_partial_output(line);
+                }
+                FlowEvent::CycleStarted(cycle) => {
+                    self.flow.cycle_count = cycle;
+                    self.flow_ui.todo_progress = None;
+                    self.flow_ui.up_next = None;
+                }
+                FlowEvent::Progress(progress) => {
+                    self.flow_ui.todo_progress = Some(progress);
+                    // Nothing runs after a finished batch
+                    if progress.completed >= progress.total {
+                        self.flow_ui.up_next = None;
                     }
-                    self.auto_scroll_output_if_at_bottom();
+                }
+                FlowEvent::PromptSent(kind, size) => {
+                    self.flow_ui.prompt_stats.record(kind, size);
                 }
                 FlowEvent::TodoFilesUpdated(files) => {
+                    self.flow_ui
+                        .todo_counts
+                        .get_or_insert_default()
+                        .set_pending(files.len());
                     self.flow.todo_files = files;
                 }
+                FlowEvent::TodoCompleted => {
+                    self.flow_ui.todo_counts.get_or_insert_default().complete();
+                }
+                FlowEvent::TodoFileCreated(path) => {
+                    let name = path.file_name().map_or_else(
+                        || path.display().to_string(),
+                        |n| n.to_string_lossy().into_owned(),
+                    );
+                    self.push_output(OutputLine::info(format!("Created {name}")));
+                }
                 FlowEvent::CurrentFile(file) => {
                     self.flow_ui.current_file = file;
                 }
+                FlowEvent::UpNext(next) => {
+                    self.flow_ui.up_next = Some(next.unwrap_or_else(|| "last task".to_string()));
+                }
                 FlowEvent::RetryWait(wait) => {
                     self.flow_ui.retry_wait = wait;
                 }
                 FlowEvent::ClearOutput => {
-                    self.flow_ui.output.clear();
-                    self.flow_ui.output_scroll.reset();
-                    self.flow_ui.output_truncated = false;
-                }
-                FlowEvent::Done => {
-                    match self.flow.phase {
-                        FlowPhase::Completed | FlowPhase::NoTodoFiles => {
-                            self.mode = AppMode::Finished;
-                        }
-                        _ => {
-                            self.mode = AppMode::Chat;
-                            // Restore task text from .mcgravity/task.md after cancellation or failure
-                            // so the user can modify and retry their task
-                            self.load_saved_task();
-                        }
-                    }
-                    self.is_running = false;
+                    self.flow_ui.clear_output();
+                }
+                FlowEvent::Done => self.finish_flow(),
+                FlowEvent::SearchPartial {
+                    generation,
+                    matches,
+                } => {
+                    self.handle_search_partial(generation, matches);
                 }
                 FlowEvent::SearchResult { generation, result } => {
                     self.handle_search_result(generation, result);
@@ -799,8 +1470,51 @@ impl App {
                     // any user edits in progress.
                     self.flow.input_text = text;
                 }
+                FlowEvent::TaskSummarized(summary) => {
+                    self.flow_ui.last_summary = Some(summary);
+                }
+                FlowEvent::ResummarizeDone => {
+                    self.is_running = false;
+                }
+                FlowEvent::FlowStartCommit(commit) => self.flow_start_commit = commit,
+                FlowEvent::DiffOutput(lines) => {
+                    for line in lines {
+                        self.push_output(line);
+                    }
+                }
+                FlowEvent::PromptPreview(kind, prompt) => self.show_prompt_preview(kind, prompt),
+                FlowEvent::DoctorChecks(checks) => self.report_doctor_checks(&checks),
+                FlowEvent::CliResolution(model, resolution) => {
+                    self.report_cli_resolution(model, &resolution);
+                }
+            }
+        }
+    }
+
+    /// Handles the end of a flow: notifies the user and leaves running mode.
+    fn finish_flow(&mut self) {
+        if let Some(notification) = notification::flow_notification(
+            self.settings.desktop_notifications,
+            &self.flow.phase,
+            self.flow.cycle_count,
+        ) {
+            notification::send(&notification);
+        }
+        if notification::should_bell(self.settings.bell_on_complete, &self.flow.phase) {
+            notification::ring_bell();
+        }
+        match self.flow.phase {
+            FlowPhase::Completed | FlowPhase::NoTodoFiles => {
+                self.mode = AppMode::Finished;
+            }
+            _ => {
+                self.mode = AppMode::Chat;
+                // Restore task text from .mcgravity/task.md after cancellation or failure
+                // so the user can modify and retry their task
+                self.load_saved_task();
             }
         }
+        self.is_running = false;
     }
 
     /// Sets the running flag.
@@ -808,6 +1522,74 @@ impl App {
         self.is_running = running;
     }
 
+    /// Styles a model output line as markdown when the `render_markdown`
+    /// setting is on.
+    ///
+    /// A partial line being `replace`d is rendered from the markdown state
+    /// before it, so a fence it opens is not counted twice.
+    fn style_markdown(&mut self, line: OutputLine, replace: bool) -> OutputLine {
+        if !self.settings.render_markdown
+            || line.source.is_none()
+            || line.line_type != OutputLineType::Stdout
+        {
+            return line;
+        }
+        if replace {
+            self.flow_ui.markdown = self.flow_ui.markdown_before_last;
+        } else {
+            self.flow_ui.markdown_before_last = self.flow_ui.markdown;
+        }
+        line.with_markdown_styles(&mut self.flow_ui.markdown)
+    }
+
+    /// Replaces the most recent partial CLI line in place, as for progress
+    /// updates. Without one (e.g. it was trimmed or cleared), the line is
+    /// added instead.
+    fn replace_partial_output(&mut self, line: OutputLine) {
+        let Some(index) = self
+            .flow_ui
+            .partial_line
+            .filter(|&index| index < self.flow_ui.output.len())
+        else {
+            self.push_output(line);
+            return;
+        };
+        match (
+            self.flow_ui.error_lines.binary_search(&index),
+            is_error_line(&line),
+        ) {
+            (Ok(position), false) => {
+                self.flow_ui.error_lines.remove(position);
+            }
+            (Err(position), true) => self.flow_ui.error_lines.insert(position, index),
+            _ => {}
+        }
+        self.flow_ui.output[index] = line;
+        self.auto_scroll_output_if_at_bottom();
+    }
+
+    /// Handles a batch of matches from a file search that is still running.
+    ///
+    /// The batch is merged into the popup, which stays sorted and capped, so
+    /// large trees show matches before the walk finishes. Batches from older
+    /// searches are ignored.
+    fn handle_search_partial(&mut self, generation: u64, batch: Vec<FileMatch>) {
+        use crate::tui::widgets::PopupState;
+
+        if generation != self.text_input.search_generation || batch.is_empty() {
+            return;
+        }
+        let (mut matches, selected) = match std::mem::take(&mut self.text_input.file_popup_state) {
+            PopupState::Showing { matches, selected } => (matches, selected),
+            PopupState::Loading | PopupState::NoMatches => (Vec::new(), 0),
+            // The popup was dismissed while the search ran
+            PopupState::Hidden => return,
+        };
+        merge_matches(&mut matches, batch);
+        let selected = selected.min(matches.len().saturating_sub(1));
+        self.text_input.file_popup_state = PopupState::Showing { matches, selected };
+    }
+
     /// Handles a search result from the background task.
     ///
     /// This method is called when a `FlowEvent::SearchResult` is received.
@@ -850,14 +1632,55 @@ impl App {
 
     /// Calculates the total visual line count for output after wrapping.
     fn output_visual_line_count(&self) -> usize {
-        calculate_visual_line_count(&self.flow_ui.output, self.layout.output_content_width())
+        calculate_visual_line_count(
+            &self.flow_ui.output,
+            text_width(
+                self.layout.output_content_width(),
+                self.settings.show_timestamps,
+            ),
+        )
     }
 
-    /// Auto-scrolls output panel if auto-scroll is enabled.
+    /// Scrolls the output panel to follow new output.
+    ///
+    /// With `sticky_scroll` on, this only happens while the view is pinned to
+    /// the bottom, so reading earlier output is not interrupted.
     fn auto_scroll_output_if_at_bottom(&mut self) {
+        let content_len = self.output_visual_line_count();
+        let visible_height = self.layout.output_visible_height();
+        if self.settings.sticky_scroll {
+            self.flow_ui
+                .output_scroll
+                .auto_scroll_if_enabled(content_len, visible_height);
+        } else {
+            self.flow_ui
+                .output_scroll
+                .scroll_to_bottom(content_len, visible_height);
+        }
+    }
+
+    /// Re-pins the output panel if it is scrolled to the bottom.
+    fn repin_output_if_at_bottom(&mut self) {
         let content_len = self.output_visual_line_count();
         self.flow_ui
             .output_scroll
-            .auto_scroll_if_enabled(content_len, self.layout.output_visible_height());
+            .repin_if_at_bottom(content_len, self.layout.output_visible_height());
+    }
+}
+
+/// Returns true if `line` is indexed for `n`/`N` error navigation.
+fn is_error_line(line: &OutputLine) -> bool {
+    matches!(
+        line.line_type,
+        OutputLineType::SystemError | OutputLineType::SystemWarning
+    )
+}
+
+/// Returns the text of a line shown `count` times in a row.
+fn repeated_line_text(text: &str, count: u32) -> String {
+    if count > 1 {
+        format!("{text} (×{count})")
+    } else {
+        text.to_string()
     }
 }
@@ -0,0 +1,214 @@
+//! Command input history.
+//!
+//! Keeps a bounded list of previously submitted inputs (tasks and slash
+//! commands) that can be recalled with Up/Down, shell-style. While browsing,
+//! the text that was being typed before recall started is kept as a draft and
+//! restored when navigating past the newest entry.
+
+use std::collections::VecDeque;
+
+/// Default maximum number of history entries kept.
+pub const DEFAULT_MAX_HISTORY: usize = 100;
+
+/// Bounded history of submitted inputs with recall navigation.
+#[derive(Debug, Clone)]
+pub struct InputHistory {
+    /// Entries from oldest to newest.
+    entries: VecDeque<String>,
+    /// Maximum number of entries kept; older entries are dropped first.
+    cap: usize,
+    /// Index of the entry currently recalled, `None` when not browsing.
+    position: Option<usize>,
+    /// Input that was in the textarea when browsing started.
+    draft: String,
+}
+
+impl Default for InputHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_HISTORY)
+    }
+}
+
+impl InputHistory {
+    /// Creates an empty history holding at most `cap` entries.
+    #[must_use]
+    pub fn new(cap: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            cap,
+            position: None,
+            draft: String::new(),
+        }
+    }
+
+    /// Creates a history from existing entries (oldest first), keeping only
+    /// the newest `cap` of them.
+    #[must_use]
+    pub fn with_entries(entries: impl IntoIterator<Item = String>, cap: usize) -> Self {
+        let mut history = Self::new(cap);
+        for entry in entries {
+            history.push(entry);
+        }
+        history
+    }
+
+    /// Records a submitted input and stops browsing.
+    ///
+    /// Blank inputs and immediate repeats of the newest entry are ignored.
+    /// Returns `true` if the entry was added.
+    pub fn push(&mut self, entry: impl Into<String>) -> bool {
+        self.reset_navigation();
+        let entry = entry.into();
+        if self.cap == 0
+            || entry.trim().is_empty()
+            || self.entries.back().is_some_and(|last| *last == entry)
+        {
+            return false;
+        }
+        self.entries.push_back(entry);
+        while self.entries.len() > self.cap {
+            self.entries.pop_front();
+        }
+        true
+    }
+
+    /// Steps back to the previous (older) entry.
+    ///
+    /// `current` is the textarea content, saved as the draft when browsing
+    /// starts. Returns `None` when there is no older entry.
+    pub fn recall_prev(&mut self, current: &str) -> Option<&str> {
+        let index = match self.position {
+            None => {
+                let newest = self.entries.len().checked_sub(1)?;
+                self.draft = current.to_string();
+                newest
+            }
+            Some(index) => index.checked_sub(1)?,
+        };
+        self.position = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Steps forward to the next (newer) entry.
+    ///
+    /// Moving past the newest entry ends browsing and returns the draft.
+    /// Returns `None` when not browsing.
+    pub fn recall_next(&mut self) -> Option<&str> {
+        let index = self.position?;
+        if index + 1 < self.entries.len() {
+            self.position = Some(index + 1);
+            return self.entries.get(index + 1).map(String::as_str);
+        }
+        self.position = None;
+        Some(&self.draft)
+    }
+
+    /// Returns true while an entry is being recalled.
+    #[must_use]
+    pub const fn is_browsing(&self) -> bool {
+        self.position.is_some()
+    }
+
+    /// Stops browsing without changing the entries.
+    pub fn reset_navigation(&mut self) {
+        self.position = None;
+        self.draft.clear();
+    }
+
+    /// Returns the entries from oldest to newest.
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// Returns the number of entries.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if no entries have been recorded.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history_of(entries: &[&str]) -> InputHistory {
+        InputHistory::with_entries(entries.iter().map(ToString::to_string), 10)
+    }
+
+    #[test]
+    fn recall_prev_walks_from_newest_to_oldest() {
+        let mut history = history_of(&["first", "second", "third"]);
+
+        assert_eq!(history.recall_prev(""), Some("third"));
+        assert_eq!(history.recall_prev(""), Some("second"));
+        assert_eq!(history.recall_prev(""), Some("first"));
+        // Stops at the oldest entry
+        assert_eq!(history.recall_prev(""), None);
+        assert!(history.is_browsing());
+    }
+
+    #[test]
+    fn recall_next_returns_to_draft() {
+        let mut history = history_of(&["first", "second"]);
+
+        assert_eq!(history.recall_prev("half typed"), Some("second"));
+        assert_eq!(history.recall_prev("ignored"), Some("first"));
+        assert_eq!(history.recall_next(), Some("second"));
+        assert_eq!(history.recall_next(), Some("half typed"));
+        assert!(!history.is_browsing());
+        assert_eq!(history.recall_next(), None);
+    }
+
+    #[test]
+    fn recall_on_empty_history_does_nothing() {
+        let mut history = InputHistory::default();
+        assert_eq!(history.recall_prev("text"), None);
+        assert_eq!(history.recall_next(), None);
+        assert!(!history.is_browsing());
+    }
+
+    #[test]
+    fn push_drops_oldest_beyond_cap() {
+        let mut history = InputHistory::new(2);
+        history.push("a");
+        history.push("b");
+        history.push("c");
+
+        assert_eq!(history.entries().collect::<Vec<_>>(), ["b", "c"]);
+    }
+
+    #[test]
+    fn push_skips_blank_and_repeated_entries() {
+        let mut history = InputHistory::default();
+        assert!(history.push("task"));
+        assert!(!history.push("task"));
+        assert!(!history.push("  \n"));
+        assert!(history.push("other"));
+        assert!(history.push("task"));
+
+        assert_eq!(history.len(), 3);
+    }
+
+    #[test]
+    fn push_resets_navigation() {
+        let mut history = history_of(&["first"]);
+        history.recall_prev("draft");
+        history.push("second");
+
+        assert!(!history.is_browsing());
+        assert_eq!(history.recall_prev(""), Some("second"));
+    }
+
+    #[test]
+    fn zero_cap_keeps_nothing() {
+        let mut history = InputHistory::new(0);
+        assert!(!history.push("task"));
+        assert!(history.is_empty());
+    }
+}
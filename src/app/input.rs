@@ -8,12 +8,14 @@
 //! - Visual line wrapping calculations
 //! - Rapid input detection for paste fallback
 
+use std::path::PathBuf;
 use std::time::Instant;
 
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
-use unicode_width::UnicodeWidthChar;
 
 use super::App;
+use crate::core::FlowPhase;
+use crate::core::attachments::{attach_files, attachment_candidates};
 
 // === Rapid Input Detection Constants ===
 // These thresholds help detect paste operations when bracketed paste mode
@@ -203,7 +205,7 @@ impl RapidInputDetector {
     }
 }
 use crate::app::AtToken;
-use crate::tui::widgets::OutputLine;
+use crate::tui::widgets::{OutputLine, char_display_width};
 
 /// Result of wrapping lines for display.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -262,12 +264,10 @@ pub fn wrap_lines_for_display(
 
         for ch in line.chars() {
             let char_byte_len = ch.len_utf8();
-            let char_display_width = ch.width().unwrap_or(1);
+            let char_width = char_display_width(ch);
 
             // Check if adding this character would exceed the width
-            if current_visual_width + char_display_width > effective_width
-                && !current_visual.is_empty()
-            {
+            if current_visual_width + char_width > effective_width && !current_visual.is_empty() {
                 // Before pushing: check if cursor is in this visual line
                 if is_cursor_line && !found_cursor && cursor_col < byte_pos {
                     // Cursor was in the line we're about to push
@@ -289,7 +289,7 @@ pub fn wrap_lines_for_display(
             }
 
             current_visual.push(ch);
-            current_visual_width += char_display_width;
+            current_visual_width += char_width;
             byte_pos += char_byte_len;
         }
 
@@ -314,7 +314,7 @@ pub fn wrap_lines_for_display(
         visual_cursor_row = visual_lines.len().saturating_sub(1);
         visual_cursor_col = visual_lines
             .last()
-            .map_or(0, |l| l.chars().map(|c| c.width().unwrap_or(1)).sum());
+            .map_or(0, |l| l.chars().map(char_display_width).sum());
     }
 
     WrapResult {
@@ -347,7 +347,7 @@ fn calculate_visual_col(
         if byte_offset >= cursor_offset_in_segment {
             break;
         }
-        visual_col += ch.width().unwrap_or(1);
+        visual_col += char_display_width(ch);
         byte_offset += ch.len_utf8();
     }
 
@@ -363,7 +363,7 @@ fn calculate_visual_col_from_start(visual_line: &str, cursor_byte_col: usize) ->
         if byte_offset >= cursor_byte_col {
             break;
         }
-        visual_col += ch.width().unwrap_or(1);
+        visual_col += char_display_width(ch);
         byte_offset += ch.len_utf8();
     }
 
@@ -390,13 +390,38 @@ impl App {
             return;
         }
 
+        self.record_history(&text);
+
         // Check for slash command first
         if self.try_execute_slash_command() {
             // Command was executed, input already cleared
             return;
         }
 
-        // Not a command - proceed with normal task submission
+        // A running flow only unlocks the input for commands
+        if self.is_running {
+            let hint = if matches!(self.flow.phase, FlowPhase::Paused { .. }) {
+                "The flow is paused: type /next to continue or /stop to end it"
+            } else {
+                "The flow is running: type /cancel to stop it"
+            };
+            self.push_output(OutputLine::warning(hint));
+            return;
+        }
+
+        self.start_flow_with_input(text);
+    }
+
+    /// Starts a flow on `text`, the current content of the text input.
+    ///
+    /// Refuses to start a flow that cannot persist its progress, keeping the
+    /// input so nothing is lost.
+    pub(super) fn start_flow_with_input(&mut self, text: String) {
+        if !self.check_state_writable() {
+            return;
+        }
+
+        // Proceed with normal task submission
         // Save task text to task.md for future reference
         // Errors are displayed in the TUI but don't prevent flow execution
         if let Err(e) = self.save_current_task() {
@@ -405,6 +430,7 @@ impl App {
                 .push(OutputLine::warning(format!("Failed to save task.md: {e}")));
         }
 
+        self.flow.attached_files = self.attach_mentioned_files(&text);
         self.flow.set_input_text(text);
 
         // Clear input for next task (chat-like behavior)
@@ -413,8 +439,93 @@ impl App {
         // Reset rapid input detection so subsequent keys don't think they're part of a paste
         self.text_input.reset_rapid_input_state();
 
-        // Start flow (stays in Chat mode)
-        self.start_flow();
+        // Start flow (stays in Chat mode unless confirmation is needed)
+        self.request_start_flow();
+    }
+
+    /// Returns the files mentioned in `text` whose contents the planning
+    /// prompt attaches, when the `attach_mentioned_files` setting is on.
+    ///
+    /// Files picked from the `@` popup count as long as their path is still
+    /// in the text, alongside mentions typed as `@path`. Files outside the
+    /// workspace or that cannot be read are reported as warnings and left
+    /// out. The contents are read again for every planning prompt, so the
+    /// task text itself never holds them.
+    pub(super) fn attach_mentioned_files(&mut self, text: &str) -> Vec<PathBuf> {
+        if !self.settings.attach_mentioned_files {
+            return Vec::new();
+        }
+        let paths = attachment_candidates(text, self.paths.base(), &self.text_input.selected_files);
+
+        let result = attach_files(self.paths.base(), &self.settings.extra_roots, &paths);
+        for warning in result.warnings {
+            self.push_output(OutputLine::warning(warning));
+        }
+        match result.attached.len() {
+            0 => {}
+            1 => self.push_output(OutputLine::info("Attached 1 file to the task")),
+            n => self.push_output(OutputLine::info(format!("Attached {n} files to the task"))),
+        }
+        result.attached
+    }
+
+    // ===== Input History =====
+
+    /// Adds a submitted input to the history and persists it to
+    /// `.mcgravity/history`.
+    ///
+    /// Persistence failures are reported as a warning; the in-memory history
+    /// is still updated.
+    pub(super) fn record_history(&mut self, text: &str) {
+        if !self.text_input.history.push(text) {
+            return;
+        }
+        let result = std::fs::create_dir_all(self.paths.mcgravity_dir()).and_then(|()| {
+            crate::fs::history::write_history(
+                &self.paths.history_file(),
+                self.text_input.history.entries(),
+            )
+        });
+        if let Err(e) = result {
+            self.push_output(OutputLine::warning(format!(
+                "Failed to save input history: {e}"
+            )));
+        }
+    }
+
+    /// Replaces the input with the previous (older) history entry.
+    ///
+    /// Returns `false` if there is no older entry, so the key can fall
+    /// through to normal cursor movement.
+    pub(super) fn recall_history_prev(&mut self) -> bool {
+        let current = self.text_input.collect_text();
+        let Some(entry) = self.text_input.history.recall_prev(&current) else {
+            return false;
+        };
+        let entry = entry.to_string();
+        self.set_recalled_text(&entry);
+        true
+    }
+
+    /// Replaces the input with the next (newer) history entry, or the
+    /// original draft after the newest entry.
+    ///
+    /// Returns `false` when not browsing history.
+    pub(super) fn recall_history_next(&mut self) -> bool {
+        let Some(entry) = self.text_input.history.recall_next() else {
+            return false;
+        };
+        let entry = entry.to_string();
+        self.set_recalled_text(&entry);
+        true
+    }
+
+    fn set_recalled_text(&mut self, text: &str) {
+        self.text_input.set_text(text);
+        self.text_input.is_dirty = true;
+        self.text_input.last_edit_time = Some(Instant::now());
+        self.update_at_token();
+        self.update_slash_command_popup();
     }
 
     // ===== @ Token Detection =====
@@ -550,6 +661,19 @@ impl App {
     }
 }
 
+/// Returns true if `path` contains whitespace or shell metacharacters, so
+/// [`escape_file_path`] quotes it.
+#[must_use]
+pub(crate) fn needs_quoting(path: &str) -> bool {
+    path.chars().any(|c| {
+        c.is_whitespace()
+            || matches!(
+                c,
+                '"' | '\'' | '`' | '$' | '\\' | '!' | '*' | '?' | '[' | ']' | '(' | ')' | '{' | '}'
+            )
+    })
+}
+
 /// Escapes a file path for safe insertion into text.
 ///
 /// This function handles:
@@ -567,16 +691,7 @@ impl App {
 /// meaning of all characters except the single quote itself in POSIX shells.
 #[must_use]
 pub fn escape_file_path(path: &str) -> String {
-    // Characters that need special handling (shell metacharacters + whitespace)
-    let needs_quoting = path.chars().any(|c| {
-        c.is_whitespace()
-            || matches!(
-                c,
-                '"' | '\'' | '`' | '$' | '\\' | '!' | '*' | '?' | '[' | ']' | '(' | ')' | '{' | '}'
-            )
-    });
-
-    if !needs_quoting {
+    if !needs_quoting(path) {
         // Simple case: no special characters
         return format!("{path} ");
     }
@@ -695,6 +810,43 @@ mod tests {
         assert_eq!(result.visual_cursor_col, 2);
     }
 
+    #[test]
+    fn test_wrap_mixed_cjk_on_column_boundaries() {
+        // "a中b中" is 6 columns; at width 3 "a中" fills a row and "b中" the next
+        let lines = vec!["a中b中".to_string()];
+        let result = wrap_lines_for_display(&lines, 0, 4, 3);
+        assert_eq!(result.visual_lines, ["a中", "b中"]);
+        // Cursor before 'b' (byte 4) starts the second row
+        assert_eq!(result.visual_cursor_row, 1);
+        assert_eq!(result.visual_cursor_col, 0);
+    }
+
+    #[test]
+    fn test_wrap_emoji_cursor_column_counts_display_width() {
+        // The cursor after "👍👍" sits at column 4, not at character 2
+        let lines = vec!["👍👍x".to_string()];
+        let result = wrap_lines_for_display(&lines, 0, 8, 10);
+        assert_eq!(result.visual_lines, ["👍👍x"]);
+        assert_eq!(result.visual_cursor_col, 4);
+
+        // At width 3 each emoji gets its own row and 'x' joins the second
+        let result = wrap_lines_for_display(&lines, 0, 8, 3);
+        assert_eq!(result.visual_lines, ["👍", "👍x"]);
+        assert_eq!(result.visual_cursor_row, 1);
+        assert_eq!(result.visual_cursor_col, 2);
+    }
+
+    #[test]
+    fn test_wrap_one_column_width() {
+        // Every character gets its own row; a wide character overflows its row
+        // instead of producing empty rows
+        let lines = vec!["ab中".to_string(), String::new()];
+        let result = wrap_lines_for_display(&lines, 0, 2, 1);
+        assert_eq!(result.visual_lines, ["a", "b", "中", ""]);
+        assert_eq!(result.visual_cursor_row, 2);
+        assert_eq!(result.visual_cursor_col, 0);
+    }
+
     #[test]
     fn test_wrap_wide_chars_causes_wrap() {
         // "中中中" with width 4 should wrap: "中中" (4 cols), "中" (2 cols)
@@ -817,7 +969,7 @@ mod path_escaping_tests {
 #[allow(clippy::unwrap_used, clippy::expect_used)]
 mod at_token_tests {
     use super::*;
-    use crate::app::{AppMode, FlowUiState, LayoutState, SettingsState, TextInputState};
+    use crate::app::{AppMode, FlowUiState, Focus, LayoutState, SettingsState, TextInputState};
     use tui_textarea::{CursorMove, TextArea};
 
     /// Helper to create a minimal App for testing token detection.
@@ -848,11 +1000,15 @@ mod at_token_tests {
             flow: crate::core::FlowState::new_without_file(),
             theme: crate::tui::Theme::default(),
             mode: AppMode::Chat,
+            focus: Focus::Input,
             should_quit: false,
             is_running: false,
             event_rx: tokio::sync::mpsc::channel(1).1,
             event_tx: tokio::sync::mpsc::channel(1).0,
             shutdown_tx: tokio::sync::watch::channel(false).0,
+            max_iterations_tx: tokio::sync::watch::channel(None).0,
+            verbosity_tx: tokio::sync::watch::channel(crate::app::state::Verbosity::default()).0,
+            step_tx: None,
             text_input: TextInputState {
                 textarea,
                 at_token: None,
@@ -861,19 +1017,30 @@ mod at_token_tests {
                 last_search_time: None,
                 search_tx,
                 search_generation: 0,
+                search_cancel: std::sync::Arc::default(),
                 rapid_input: RapidInputDetector::new(),
+                bracketed_paste: false,
                 // Autosave state
                 last_edit_time: None,
                 is_dirty: false,
                 // Slash command state
                 command_popup_state: crate::tui::widgets::CommandPopupState::default(),
                 slash_token: None,
+                // History state
+                history: crate::app::history::InputHistory::default(),
+                selected_files: Vec::new(),
             },
             settings: SettingsState::default(),
             flow_ui: FlowUiState::default(),
             layout: LayoutState::default(),
             initial_setup: None,
             command_registry: crate::core::CommandRegistry::with_builtins(),
+            pending_edit: None,
+            flow_start_commit: None,
+            prompt_preview: None,
+            task_view_scroll: 0,
+            model_picker_index: 0,
+            log_tail: None,
         }
     }
 
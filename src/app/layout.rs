@@ -5,6 +5,9 @@
 //! and rendering in `App::render` are always in sync.
 //!
 //! The main layout used is [`ChatLayout`] for the unified chat mode interface.
+//! Terminals shorter than [`COMPACT_LAYOUT_HEIGHT_THRESHOLD`] rows get a
+//! compact variant that folds the header into the footer line and shrinks the
+//! input so the output keeps most of the screen.
 
 use ratatui::layout::{Constraint, Layout, Rect};
 
@@ -37,8 +40,21 @@ pub struct ChatLayout {
     pub input_inner_width: usize,
     /// Inner content height for input area (excluding borders).
     pub input_inner_height: usize,
+    /// Whether the compact variant is used (header folded into the footer).
+    pub compact: bool,
 }
 
+/// Terminal heights below this many rows use the compact layout.
+pub const COMPACT_LAYOUT_HEIGHT_THRESHOLD: u16 = 25;
+
+/// Narrowest terminal (in columns) the chat UI is rendered in; narrower
+/// terminals only show a "terminal too small" message.
+pub const MIN_TERMINAL_WIDTH: u16 = 20;
+
+/// Shortest terminal (in rows) the chat UI is rendered in: the compact
+/// layout's input, status, and footer plus a few output rows.
+pub const MIN_TERMINAL_HEIGHT: u16 = 8;
+
 /// Layout constraints for chat mode when idle (editable input).
 const CHAT_LAYOUT_IDLE_CONSTRAINTS: [Constraint; 6] = [
     Constraint::Length(1), // Header (minimal)
@@ -59,18 +75,68 @@ const CHAT_LAYOUT_RUNNING_CONSTRAINTS: [Constraint; 6] = [
     Constraint::Length(1), // Footer (key hints)
 ];
 
+/// Rows reserved for the input in the compact layout: one usable line
+/// inside its borders.
+const COMPACT_INPUT_HEIGHT: u16 = 3;
+
+/// Calculates the compact layout constraints for an area `height` rows tall.
+///
+/// The header is folded into the footer and the progress bar is hidden while
+/// idle. Rows are handed out in priority order (input, footer, status,
+/// progress) and the output takes whatever is left, so the input keeps its
+/// usable row even on very short terminals. When running, the readonly task
+/// shrinks to the same single line.
+fn compact_constraints(height: u16, is_running: bool) -> [Constraint; 6] {
+    let mut remaining = height;
+    let mut take = |wanted: u16| {
+        let rows = wanted.min(remaining);
+        remaining -= rows;
+        rows
+    };
+    let input = take(COMPACT_INPUT_HEIGHT);
+    let footer = take(1);
+    let status = take(2);
+    let progress = take(u16::from(is_running));
+    let output = remaining;
+
+    if is_running {
+        [
+            Constraint::Length(0),
+            Constraint::Length(output),
+            Constraint::Length(input),
+            Constraint::Length(status),
+            Constraint::Length(progress),
+            Constraint::Length(footer),
+        ]
+    } else {
+        [
+            Constraint::Length(0),
+            Constraint::Length(output),
+            Constraint::Length(status),
+            Constraint::Length(progress),
+            Constraint::Length(input),
+            Constraint::Length(footer),
+        ]
+    }
+}
+
 /// Calculates the layout for chat mode.
 ///
 /// This function should be used by both `update_layout_heights` and
 /// `render_chat` to ensure consistent dimension calculations.
 /// When `is_running` is true, the output and task panels split the main space.
+/// Areas shorter than [`COMPACT_LAYOUT_HEIGHT_THRESHOLD`] use the compact variant.
 #[must_use]
 pub fn calculate_chat_layout(area: Rect, is_running: bool) -> ChatLayout {
-    let chunks = if is_running {
-        Layout::vertical(CHAT_LAYOUT_RUNNING_CONSTRAINTS).split(area)
+    let compact = area.height < COMPACT_LAYOUT_HEIGHT_THRESHOLD;
+    let constraints = if compact {
+        compact_constraints(area.height, is_running)
+    } else if is_running {
+        CHAT_LAYOUT_RUNNING_CONSTRAINTS
     } else {
-        Layout::vertical(CHAT_LAYOUT_IDLE_CONSTRAINTS).split(area)
+        CHAT_LAYOUT_IDLE_CONSTRAINTS
     };
+    let chunks = Layout::vertical(constraints).split(area);
 
     let (output, input, status, progress, footer) = if is_running {
         (chunks[1], chunks[2], chunks[3], chunks[4], chunks[5])
@@ -97,6 +163,7 @@ pub fn calculate_chat_layout(area: Rect, is_running: bool) -> ChatLayout {
         output_content_width,
         input_inner_width,
         input_inner_height,
+        compact,
     }
 }
 
@@ -106,9 +173,10 @@ mod tests {
 
     #[test]
     fn test_chat_layout_calculation() {
-        let area = Rect::new(0, 0, 80, 24);
+        let area = Rect::new(0, 0, 80, 30);
         let layout = calculate_chat_layout(area, false);
 
+        assert!(!layout.compact);
         // Header should be 1 line (minimal)
         assert_eq!(layout.header.height, 1);
         // Status should be 2 lines
@@ -119,11 +187,11 @@ mod tests {
         assert_eq!(layout.input.height, 5);
         // Footer should be 1 line
         assert_eq!(layout.footer.height, 1);
-        // Output should take the rest (24 - 1 - 2 - 1 - 5 - 1 = 14)
-        assert_eq!(layout.output.height, 14);
+        // Output should take the rest (30 - 1 - 2 - 1 - 5 - 1 = 20)
+        assert_eq!(layout.output.height, 20);
 
         // Inner dimensions account for borders
-        assert_eq!(layout.output_visible_height, 12); // 14 - 2
+        assert_eq!(layout.output_visible_height, 18); // 20 - 2
         assert_eq!(layout.output_content_width, 77); // 80 - 3
         assert_eq!(layout.input_inner_width, 78); // 80 - 2
         assert_eq!(layout.input_inner_height, 3); // 5 - 2
@@ -131,22 +199,99 @@ mod tests {
 
     #[test]
     fn test_chat_layout_small_terminal() {
-        // Test with minimum viable terminal size
+        // Small terminals switch to the compact layout
         let area = Rect::new(0, 0, 40, 15);
         let layout = calculate_chat_layout(area, false);
 
-        // Should not panic and should produce valid layout
-        // Fixed height elements: 1 + 2 + 1 + 5 + 1 = 10
-        // Remaining for output: 15 - 10 = 5
-        assert_eq!(layout.header.height, 1);
+        // Fixed height elements: 2 (status) + 3 (input) + 1 (footer) = 6
+        // Remaining for output: 15 - 6 = 9
+        assert!(layout.compact);
+        assert_eq!(layout.header.height, 0);
         assert_eq!(layout.status.height, 2);
-        assert_eq!(layout.progress.height, 1);
-        assert_eq!(layout.input.height, 5);
+        assert_eq!(layout.progress.height, 0);
+        assert_eq!(layout.input.height, 3);
         assert_eq!(layout.footer.height, 1);
-        assert_eq!(layout.output.height, 5);
+        assert_eq!(layout.output.height, 9);
 
-        // Output visible height should be 3 (5 - 2 for borders)
-        assert_eq!(layout.output_visible_height, 3);
+        // Output visible height should be 7 (9 - 2 for borders)
+        assert_eq!(layout.output_visible_height, 7);
+    }
+
+    /// Asserts that the regions stack top to bottom without gaps or overlap
+    /// and exactly fill the area.
+    fn assert_regions_fill_area(layout: &ChatLayout, area: Rect, is_running: bool) {
+        let order = if is_running {
+            [
+                layout.header,
+                layout.output,
+                layout.input,
+                layout.status,
+                layout.progress,
+                layout.footer,
+            ]
+        } else {
+            [
+                layout.header,
+                layout.output,
+                layout.status,
+                layout.progress,
+                layout.input,
+                layout.footer,
+            ]
+        };
+        let mut y = area.y;
+        for region in order {
+            assert_eq!(region.y, y, "{order:?}");
+            y += region.height;
+        }
+        assert_eq!(y, area.bottom());
+    }
+
+    #[test]
+    fn test_compact_layout_gives_output_most_rows() {
+        let area = Rect::new(0, 0, 80, 24);
+        for is_running in [false, true] {
+            let layout = calculate_chat_layout(area, is_running);
+
+            assert!(layout.compact);
+            assert_eq!(layout.header.height, 0);
+            assert_eq!(layout.footer.height, 1);
+            assert!(
+                layout.output.height > area.height / 2,
+                "output has {} of {} rows (running: {is_running})",
+                layout.output.height,
+                area.height
+            );
+            assert_eq!(layout.input_inner_height, 1);
+            assert_regions_fill_area(&layout, area, is_running);
+        }
+    }
+
+    #[test]
+    fn test_compact_layout_tiny_terminal_keeps_input_row() {
+        for height in [4, 6, 8, 12] {
+            let area = Rect::new(0, 0, 40, height);
+            for is_running in [false, true] {
+                let layout = calculate_chat_layout(area, is_running);
+
+                assert!(layout.input_inner_height >= 1, "height {height}");
+                assert_regions_fill_area(&layout, area, is_running);
+            }
+        }
+    }
+
+    #[test]
+    fn test_layout_threshold_boundary() {
+        let compact = calculate_chat_layout(
+            Rect::new(0, 0, 80, COMPACT_LAYOUT_HEIGHT_THRESHOLD - 1),
+            false,
+        );
+        let normal =
+            calculate_chat_layout(Rect::new(0, 0, 80, COMPACT_LAYOUT_HEIGHT_THRESHOLD), false);
+
+        assert!(compact.compact);
+        assert!(!normal.compact);
+        assert_eq!(normal.header.height, 1);
     }
 
     #[test]
@@ -179,7 +324,7 @@ mod tests {
 
     #[test]
     fn test_chat_layout_running_balanced_split() {
-        let area = Rect::new(0, 0, 80, 24);
+        let area = Rect::new(0, 0, 80, 30);
         let layout = calculate_chat_layout(area, true);
 
         assert_eq!(layout.header.height, 1);
@@ -27,6 +27,7 @@
 //! Changes are applied immediately and persist for the session.
 
 pub mod events;
+pub mod history;
 mod input;
 mod layout;
 mod render;
@@ -38,36 +39,44 @@ mod tests;
 
 pub use layout::{ChatLayout, calculate_chat_layout};
 
+use input::needs_quoting;
 pub use input::{WrapResult, escape_file_path, wrap_lines_for_display};
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use ratatui::layout::Rect;
 use tokio::sync::{mpsc, watch};
 
-use crate::core::{CommandContext, CommandRegistry, CommandResult, FlowState, Model};
-use crate::file_search::FileMatch;
-use crate::fs::McgravityPaths;
+use crate::core::channels::{EVENT_CHANNEL_SIZE, SEARCH_CHANNEL_SIZE};
+use crate::core::doctor::{DoctorCheck, doctor_summary, run_doctor};
+use crate::core::git::{GitRunner, SystemGit, collect_diff, is_git_repo};
+use crate::core::log_tail::TAIL_POLL_INTERVAL;
+use crate::core::{
+    CommandContext, CommandRegistry, CommandResolution, CommandResult, FileRef, FlowOptions,
+    FlowPhase, FlowState, LogTail, Model, PromptKind, RetryConfig, StepSignal, describe_resolution,
+    extract_file_refs, preview_prompt, resolve_cli_command, resummarize_last_task,
+};
+use crate::file_search::{FileMatch, SearchOptions, split_scoped_query};
+use crate::fs::{McgravityPaths, PersistedSettings};
 use crate::tui::Theme;
-use crate::tui::widgets::{CommandPopupState, OutputLine, PopupState};
+use crate::tui::widgets::{CommandPopupState, OutputLine, PopupState, SPINNER_FRAMES};
 
+use self::history::{DEFAULT_MAX_HISTORY, InputHistory};
 pub use self::slash_commands::{SlashToken, detect_slash_token, parse_slash_command};
 pub use self::state::{
-    AppMode, AtToken, FlowEvent, FlowUiState, InitialSetupField, InitialSetupState, LayoutState,
-    ScrollState, SearchQuery, SettingsItem, SettingsState, TextInputState,
+    AppMode, AtToken, FlowEvent, FlowUiState, Focus, InitialSetupField, InitialSetupState,
+    LayoutState, PhaseMarker, PromptPreview, ScrollState, SearchQuery, SettingsItem, SettingsState,
+    SummarizedTask, TextInputState, Verbosity,
 };
 
-/// Channel buffer size for flow events.
-const EVENT_CHANNEL_SIZE: usize = 1000;
-
 /// Minimum time between file searches (debounce) in milliseconds.
 const FILE_SEARCH_DEBOUNCE_MS: u64 = 50;
 
-/// Autosave debounce time in milliseconds (saves after 1 second of inactivity).
-const AUTOSAVE_DEBOUNCE_MS: u64 = 1000;
-
 /// Main application state.
 ///
 /// Organized into component sub-structs for better separation of concerns:
@@ -87,6 +96,8 @@ pub struct App {
     pub(crate) theme: Theme,
     /// Current application mode.
     pub(crate) mode: AppMode,
+    /// Chat region that receives unmodified keys (toggled by Tab).
+    pub(crate) focus: Focus,
     /// Should quit flag.
     should_quit: bool,
     /// Is flow running.
@@ -101,6 +112,17 @@ pub struct App {
     event_tx: mpsc::Sender<FlowEvent>,
     /// Shutdown signal sender (to kill child processes on exit).
     shutdown_tx: watch::Sender<bool>,
+    /// Iteration cap shared with the running flow (`None` = unlimited).
+    ///
+    /// Seeded from settings when a flow starts; `/cycles` updates it mid-run.
+    max_iterations_tx: watch::Sender<Option<u32>>,
+    /// Output verbosity shared with the running flow.
+    ///
+    /// Seeded from settings when a flow starts; Ctrl+O updates it mid-run.
+    verbosity_tx: watch::Sender<Verbosity>,
+    /// Sends `/next` and `/stop` to the running flow when it was started in
+    /// step mode (`None` otherwise).
+    step_tx: Option<mpsc::Sender<StepSignal>>,
 
     // =========================================================================
     // Component States
@@ -123,32 +145,70 @@ pub struct App {
 
     /// Registry of available slash commands.
     pub(crate) command_registry: CommandRegistry,
+    /// File selected by `/edit` or `/open`, waiting for the main loop to
+    /// open it.
+    pub(crate) pending_edit: Option<FileRef>,
+    /// Commit checked out when the last flow started, used as the `/diff` base.
+    pub(crate) flow_start_commit: Option<String>,
+    /// Prompt shown by `/preview` while in [`AppMode::PromptPreview`].
+    pub(crate) prompt_preview: Option<PromptPreview>,
+    /// First visible line of the task text while in [`AppMode::TaskView`].
+    pub(crate) task_view_scroll: u16,
+    /// Highlighted row of [`AppMode::ModelPicker`], an index into [`Model::all`].
+    pub(crate) model_picker_index: usize,
+    /// Log file followed by `/tail`, until `/untail` or the next flow start.
+    pub(crate) log_tail: Option<LogTail>,
 }
 
 /// Spawns a background task that handles file search queries.
 ///
 /// This function creates an async task that:
 /// 1. Listens for `SearchQuery` messages
-/// 2. Runs `search_files` in a blocking task (since `ignore` crate is blocking)
-/// 3. Sends results back via the event channel
+/// 2. Runs `search_files_streaming` in a blocking task (since `ignore` crate is blocking)
+/// 3. Sends partial batches while the walk runs, then the final result, via the event channel
 /// 4. Uses generation counters for cancellation (stale results are ignored)
+/// 5. Skips or cuts short the walk of a query whose cancel flag is set
 fn spawn_search_task(
     mut search_rx: mpsc::Receiver<SearchQuery>,
     event_tx: mpsc::Sender<FlowEvent>,
 ) {
     tokio::spawn(async move {
         while let Some(query) = search_rx.recv().await {
+            // Superseded while it waited in the queue
+            if query.cancelled.load(Ordering::Relaxed) {
+                continue;
+            }
             let generation = query.generation;
             let query_str = query.query.clone();
             let working_dir = query.working_dir.clone();
+            let options = query.options;
+            let cancelled = Arc::clone(&query.cancelled);
 
-            // Run the blocking search in a separate thread
+            // Run the blocking search in a separate thread, streaming matches
+            // as they are found so large trees fill the popup progressively
+            let batch_tx = event_tx.clone();
             let search_result = tokio::task::spawn_blocking(move || {
-                crate::file_search::search_files(&query_str, &working_dir)
+                crate::file_search::search_files_streaming(
+                    &query_str,
+                    &working_dir,
+                    &options,
+                    &cancelled,
+                    &mut |matches| {
+                        batch_tx
+                            .blocking_send(FlowEvent::SearchPartial {
+                                generation,
+                                matches,
+                            })
+                            .is_ok()
+                    },
+                )
             })
             .await;
 
-            // Send result back to the UI thread
+            // Send result back to the UI thread; a cancelled walk is incomplete
+            if query.cancelled.load(Ordering::Relaxed) {
+                continue;
+            }
             if let Ok(result) = search_result {
                 let _ = event_tx
                     .send(FlowEvent::SearchResult { generation, result })
@@ -158,22 +218,29 @@ fn spawn_search_task(
     });
 }
 
+/// Formats a list of PIDs for display, e.g. `"123, 456"`.
+fn format_pids(pids: &[u32]) -> String {
+    pids.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 impl App {
     /// Creates a new application instance using the current working directory.
     ///
-    /// If `input_path` is `Some`, validates the file exists and auto-starts the flow
-    /// using default model settings.
-    /// If `input_path` is `None`, starts in text input mode.
+    /// If `input_paths` is non-empty, validates the files exist and auto-starts
+    /// the flow using default model settings; the files are planned together.
+    /// If `input_paths` is empty, starts in text input mode.
     ///
     /// The application always starts in Chat mode, which provides a unified interface
     /// for both text input and output display.
     ///
     /// # Errors
     ///
-    /// Returns an error if the input file is provided but cannot be found.
-    #[allow(clippy::needless_pass_by_value)] // Takes Option<PathBuf> for ergonomic API
-    pub fn new(input_path: Option<PathBuf>) -> Result<Self> {
-        Self::new_with_paths(input_path, McgravityPaths::from_cwd())
+    /// Returns an error if an input file is provided but cannot be found.
+    pub fn new(input_paths: Vec<PathBuf>) -> Result<Self> {
+        Self::new_with_paths(input_paths, McgravityPaths::from_cwd())
     }
 
     /// Creates a new application instance with custom paths.
@@ -183,38 +250,66 @@ impl App {
     ///
     /// # Arguments
     ///
-    /// * `input_path` - Optional path to an input file to process
+    /// * `input_paths` - Input files to process (empty for text input mode)
     /// * `paths` - The mcgravity paths configuration (typically from a temp dir for tests)
     ///
     /// # Errors
     ///
-    /// Returns an error if the input file is provided but cannot be found.
-    #[allow(clippy::needless_pass_by_value)] // Takes Option<PathBuf> for ergonomic API
-    pub fn new_with_paths(input_path: Option<PathBuf>, paths: McgravityPaths) -> Result<Self> {
-        let has_input_file = input_path.is_some();
+    /// Returns an error if an input file is provided but cannot be found.
+    pub fn new_with_paths(input_paths: Vec<PathBuf>, paths: McgravityPaths) -> Result<Self> {
+        Self::new_with_settings(input_paths, paths, SettingsState::default())
+    }
+
+    /// Creates an application that reports the given CLI availability
+    /// instead of checking the system, so tests don't depend on `PATH`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an input file is provided but cannot be found.
+    #[cfg(test)]
+    pub(crate) fn new_with_availability(
+        input_paths: Vec<PathBuf>,
+        paths: McgravityPaths,
+        model_availability: crate::core::ModelAvailability,
+    ) -> Result<Self> {
+        let settings = SettingsState {
+            model_availability,
+            ..SettingsState::default()
+        };
+        Self::new_with_settings(input_paths, paths, settings)
+    }
+
+    /// Creates an application starting from `settings`, before the persisted
+    /// settings are applied.
+    fn new_with_settings(
+        input_paths: Vec<PathBuf>,
+        paths: McgravityPaths,
+        settings: SettingsState,
+    ) -> Result<Self> {
+        let has_input_file = !input_paths.is_empty();
+
+        if let Some(missing) = input_paths.iter().find(|path| !path.exists()) {
+            anyhow::bail!("Input file not found: {}", missing.display());
+        }
 
         // Always start in Chat mode - it's the only non-settings mode now
-        let flow = match &input_path {
-            Some(path) => {
-                if !path.exists() {
-                    anyhow::bail!("Input file not found: {}", path.display());
-                }
-                FlowState::new(path.clone())
-            }
-            None => FlowState::new_without_file(),
+        let flow = if has_input_file {
+            FlowState::new(input_paths)
+        } else {
+            FlowState::new_without_file()
         };
 
         let (event_tx, event_rx) = mpsc::channel(EVENT_CHANNEL_SIZE);
         let (shutdown_tx, _shutdown_rx) = watch::channel(false);
+        let (max_iterations_tx, _max_iterations_rx) = watch::channel(None);
+        let (verbosity_tx, _verbosity_rx) = watch::channel(Verbosity::default());
 
-        // Ensure .mcgravity directory structure exists
-        if let Err(e) = paths.ensure_todo_dirs() {
-            // Log warning but continue - directories might be created later
-            eprintln!("Warning: Failed to create .mcgravity directories: {e}");
-        }
+        // Ensure .mcgravity directory structure exists and can be written.
+        // Failures are reported once the app exists to display them.
+        let unwritable_dir = paths.find_unwritable_dir();
 
         // Create search channel and spawn background search task
-        let (search_tx, search_rx) = mpsc::channel(16);
+        let (search_tx, search_rx) = mpsc::channel(SEARCH_CHANNEL_SIZE);
         spawn_search_task(search_rx, event_tx.clone());
 
         // Detect first-run condition before loading settings
@@ -240,40 +335,70 @@ impl App {
             flow,
             theme: Theme::default(),
             mode: initial_mode,
+            focus: Focus::Input,
             should_quit: false,
             is_running: false,
             // Event channels
             event_rx,
             event_tx,
             shutdown_tx,
+            max_iterations_tx,
+            verbosity_tx,
+            step_tx: None,
             // Component states
             text_input: TextInputState::new(search_tx),
-            settings: SettingsState::default(),
+            settings,
             flow_ui: FlowUiState::default(),
             layout: LayoutState::default(),
             initial_setup: initial_setup_state,
             command_registry: CommandRegistry::with_builtins(),
+            pending_edit: None,
+            flow_start_commit: None,
+            prompt_preview: None,
+            task_view_scroll: 0,
+            model_picker_index: 0,
+            log_tail: None,
         };
 
+        if let Some((dir, e)) = &unwritable_dir {
+            app.push_output(OutputLine::error(crate::fs::unwritable_state_message(
+                dir, e,
+            )));
+        }
+
         // Load persisted settings if available (only when not first run)
         if !first_run {
             match app.paths.load_settings() {
                 Ok(persisted) => {
-                    persisted.apply_to(&mut app.settings);
-                }
-                Err(e) => {
-                    // Log warning but continue with defaults
-                    app.flow_ui
-                        .output
-                        .push(crate::tui::widgets::OutputLine::warning(format!(
-                            "Failed to load settings: {e}"
-                        )));
+                    for warning in persisted.apply_to(&mut app.settings) {
+                        app.push_output(OutputLine::warning(warning));
+                    }
                 }
+                Err(e) => app.recover_from_settings_error(&e),
             }
         }
+        // Availability was first checked without the binary path overrides
+        if Model::all()
+            .iter()
+            .any(|&model| app.settings.cli_path(model).is_some())
+        {
+            app.settings.refresh_model_availability();
+        }
+        app.theme = app.settings.color_theme.theme();
+
+        // Send the user to Settings rather than failing mid-flow on a missing CLI
+        let missing_cli = app.mode == AppMode::Chat && app.open_settings_for_missing_cli();
+
+        // Restore the command input history from previous sessions
+        app.text_input.history = InputHistory::with_entries(
+            crate::fs::history::read_history(&app.paths.history_file()),
+            DEFAULT_MAX_HISTORY,
+        );
+
+        app.warn_about_orphaned_pids();
 
         // Load task.md content if starting without an input file
-        if input_path.is_none() && app.load_saved_task() {
+        if !has_input_file && app.load_saved_task() {
             app.flow_ui
                 .output
                 .push(crate::tui::widgets::OutputLine::info(
@@ -281,14 +406,89 @@ impl App {
                 ));
         }
 
-        // Auto-start flow if input file was provided
-        if has_input_file {
-            app.start_flow();
+        // Auto-start flow if input file was provided and its state can be saved.
+        // During initial setup it starts once the models are confirmed.
+        if has_input_file
+            && unwritable_dir.is_none()
+            && !missing_cli
+            && app.mode != AppMode::InitialSetup
+        {
+            app.request_start_flow();
         }
 
         Ok(app)
     }
 
+    /// Warns about CLI processes left running by a previous crashed session.
+    fn warn_about_orphaned_pids(&mut self) {
+        let orphaned = crate::fs::pids::find_orphaned_pids(&self.paths.pids_file());
+        if !orphaned.is_empty() {
+            self.push_output(OutputLine::warning(format!(
+                "Found {} orphaned CLI process(es) from a previous session (PIDs: {}). \
+                 Run /reap to terminate them.",
+                orphaned.len(),
+                format_pids(&orphaned)
+            )));
+        }
+    }
+
+    /// Opens the settings panel when a configured model's CLI is not
+    /// installed, explaining which ones are missing. Does nothing when the
+    /// `open_settings_on_missing_cli` setting is off.
+    ///
+    /// Returns whether the panel was opened.
+    fn open_settings_for_missing_cli(&mut self) -> bool {
+        let unavailable = self.settings.unavailable_models();
+        if !self.settings.open_settings_on_missing_cli || unavailable.is_empty() {
+            return false;
+        }
+        let names = unavailable
+            .iter()
+            .map(|model| format!("{} ({})", model.name(), model.command()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.push_output(OutputLine::warning(format!(
+            "CLI not found for {names}. Pick an available model in Settings."
+        )));
+        self.open_settings();
+        true
+    }
+
+    /// Records which terminal event modes could be enabled.
+    ///
+    /// Without bracketed paste, multi-line pastes are assembled from rapid key
+    /// input. Each missing mode is reported once with its workaround.
+    pub fn set_terminal_modes(&mut self, bracketed_paste: bool, keyboard_enhancement: bool) {
+        self.text_input.bracketed_paste = bracketed_paste;
+        if !keyboard_enhancement {
+            self.push_output(OutputLine::info(
+                "Keyboard enhancement unavailable: Shift+Enter may not work, use Ctrl+J or \\ \
+                 then Enter for newlines.",
+            ));
+        }
+        if !bracketed_paste {
+            self.push_output(OutputLine::info(
+                "Bracketed paste unavailable: pastes are detected from rapid typing.",
+            ));
+        }
+    }
+
+    /// Starts a flow on task text read from stdin (`mcgravity -`).
+    ///
+    /// The text is placed in the input and submitted as if it had been typed,
+    /// so it is saved to `.mcgravity/task.md` before the flow starts. Blank
+    /// text is ignored. While the settings panel was opened for a missing CLI,
+    /// the text is only placed in the input.
+    pub fn start_with_input_text(&mut self, text: &str) {
+        if text.trim().is_empty() || !self.set_input_content(text) {
+            return;
+        }
+        if self.mode == AppMode::Settings {
+            return;
+        }
+        self.start_flow_with_input(text.to_string());
+    }
+
     /// Returns true if the application should quit.
     #[must_use]
     pub const fn should_quit(&self) -> bool {
@@ -307,10 +507,81 @@ impl App {
         self.event_tx.clone()
     }
 
-    /// Gets the input path (None if text was entered directly).
+    /// Gets the input paths (empty if text was entered directly).
     #[must_use]
-    pub fn input_path(&self) -> Option<&PathBuf> {
-        self.flow.input_path.as_ref()
+    pub fn input_paths(&self) -> &[PathBuf] {
+        &self.flow.input_paths
+    }
+
+    /// Handles a settings file that failed to load at startup.
+    ///
+    /// A file that cannot be parsed is moved to `settings.json.bak` and the
+    /// initial setup modal is shown, so the user can reconfigure instead of
+    /// silently running on defaults with the corrupt file left in place.
+    /// Other errors only produce a warning.
+    fn recover_from_settings_error(&mut self, err: &anyhow::Error) {
+        if !crate::fs::is_parse_error(err) {
+            self.push_output(OutputLine::warning(format!(
+                "Failed to load settings: {err:#}"
+            )));
+            return;
+        }
+        match self.paths.backup_settings() {
+            Ok(backup) => {
+                self.push_output(OutputLine::warning(format!(
+                    "settings.json could not be parsed ({err:#}). It was moved to {} and \
+                     defaults are used; copy any values you need back from the backup.",
+                    backup.display()
+                )));
+                self.mode = AppMode::InitialSetup;
+                self.initial_setup = Some(InitialSetupState {
+                    selected_field: InitialSetupField::default(),
+                    planning_model: Model::default(),
+                    execution_model: Model::default(),
+                });
+            }
+            Err(backup_err) => {
+                self.push_output(OutputLine::warning(format!(
+                    "settings.json could not be parsed ({err:#}) and could not be backed up \
+                     ({backup_err}). Using defaults."
+                )));
+            }
+        }
+    }
+
+    /// Returns true while a running flow locks the text input.
+    ///
+    /// A step-mode flow paused after a cycle unlocks it so `/next` and
+    /// `/stop` can be typed. Typing `/` unlocks it for a single command,
+    /// such as `/cancel`.
+    #[must_use]
+    pub fn input_locked(&self) -> bool {
+        self.is_running
+            && !matches!(self.flow.phase, FlowPhase::Paused { .. })
+            && !self.typing_command()
+    }
+
+    /// Returns true while the text input holds a slash command.
+    fn typing_command(&self) -> bool {
+        self.text_input
+            .textarea
+            .lines()
+            .first()
+            .is_some_and(|line| line.starts_with('/'))
+    }
+
+    /// Cancels the running flow, as ESC and `/cancel` do.
+    ///
+    /// The flow shows as cancelled right away and reports `Done` once its
+    /// running CLI has been stopped.
+    pub(crate) fn cancel_flow(&mut self) {
+        self.trigger_shutdown();
+        if self.flow.phase != FlowPhase::Cancelled {
+            self.flow.phase = FlowPhase::Cancelled;
+            self.push_output(OutputLine::warning(
+                "Cancelling the flow; the task text will be restored",
+            ));
+        }
     }
 
     /// Gets a shutdown receiver for the flow task.
@@ -319,6 +590,46 @@ impl App {
         self.shutdown_tx.subscribe()
     }
 
+    /// Verifies that flow state can be persisted under `.mcgravity/`.
+    ///
+    /// Pushes a prominent error and returns `false` if a state directory is
+    /// not writable, so a flow is never started that would lose its progress.
+    pub(crate) fn check_state_writable(&mut self) -> bool {
+        let Some((dir, e)) = self.paths.find_unwritable_dir() else {
+            return true;
+        };
+        self.push_output(OutputLine::error(crate::fs::unwritable_state_message(
+            &dir, &e,
+        )));
+        false
+    }
+
+    /// Sets the iteration cap for a flow about to start and returns a receiver
+    /// the flow reads at each cycle boundary.
+    pub(crate) fn max_iterations_receiver(&self, max: Option<u32>) -> watch::Receiver<Option<u32>> {
+        // send_replace updates the value even when no flow is subscribed
+        self.max_iterations_tx.send_replace(max);
+        self.max_iterations_tx.subscribe()
+    }
+
+    /// Publishes the verbosity setting for a flow about to start and returns
+    /// a receiver the flow reads for every output line.
+    pub(crate) fn verbosity_receiver(&self) -> watch::Receiver<Verbosity> {
+        self.verbosity_tx.send_replace(self.settings.verbosity);
+        self.verbosity_tx.subscribe()
+    }
+
+    /// Returns the iteration cap in effect: the live value while a flow is
+    /// running, otherwise the configured setting.
+    #[must_use]
+    pub fn effective_max_iterations(&self) -> Option<u32> {
+        if self.is_running {
+            *self.max_iterations_tx.borrow()
+        } else {
+            self.settings.max_iterations.value()
+        }
+    }
+
     /// Triggers shutdown to kill any running child processes.
     ///
     /// Uses `send_modify` to update the value even when no receivers exist,
@@ -354,10 +665,15 @@ impl App {
     /// The task.md file and done folder are NOT cleared when:
     /// - The user cancels with ESC (task text should persist for retry)
     /// - The flow fails (task text should persist for retry)
+    ///
+    /// When `settings.reset_clears_task` is `false`, task.md is rewritten with
+    /// only the original task description (the `<COMPLETED_TASKS>` block is
+    /// stripped) and restored into the text input instead of being deleted.
     pub(crate) fn reset_session(&mut self) {
-        // Clear task.md file for fresh session
+        // Clear (or strip) task.md for fresh session
         // Do this first so any error can be logged to output before we clear it
-        if let Err(e) = std::fs::remove_file(self.paths.task_file()) {
+        let keep_task = !self.settings.reset_clears_task && self.keep_original_task();
+        if !keep_task && let Err(e) = std::fs::remove_file(self.paths.task_file()) {
             // Only warn if it's not a "file not found" error (file may not exist yet)
             if e.kind() != std::io::ErrorKind::NotFound {
                 self.flow_ui
@@ -394,8 +710,37 @@ impl App {
         self.mode = AppMode::Chat;
         self.is_running = false;
 
+        self.cancel_file_search();
         let search_tx = self.text_input.search_tx.clone();
+        let history = std::mem::take(&mut self.text_input.history);
+        let bracketed_paste = self.text_input.bracketed_paste;
         self.text_input = TextInputState::new(search_tx);
+        self.text_input.history = history;
+        self.text_input.bracketed_paste = bracketed_paste;
+        if keep_task {
+            self.load_saved_task();
+        }
+    }
+
+    /// Rewrites task.md with only the original task description, stripping the
+    /// `<COMPLETED_TASKS>` block.
+    ///
+    /// Returns `true` if there was task text to keep and it was written back.
+    fn keep_original_task(&mut self) -> bool {
+        let Ok(content) = std::fs::read_to_string(self.paths.task_file()) else {
+            return false;
+        };
+        let original = crate::core::task_utils::strip_completed_tasks_block(&content);
+        if original.is_empty() {
+            return false;
+        }
+        if let Err(e) = crate::fs::atomic::write_atomic(&self.paths.task_file(), &original) {
+            self.push_output(OutputLine::warning(format!(
+                "Failed to keep task text in task.md: {e}"
+            )));
+            return false;
+        }
+        true
     }
 
     /// Calculates and caches the layout based on terminal dimensions.
@@ -445,9 +790,21 @@ impl App {
             // No @ token, hide popup
             self.text_input.file_popup_state = PopupState::Hidden;
             self.text_input.last_search_query = None;
+            self.cancel_file_search();
         }
     }
 
+    /// Stops the in-flight file search, if any.
+    ///
+    /// Its walk is cut short, and bumping the generation discards any of its
+    /// results already on the way to the UI.
+    pub(crate) fn cancel_file_search(&mut self) {
+        if self.text_input.search_cancel.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        self.text_input.search_generation = self.text_input.search_generation.wrapping_add(1);
+    }
+
     /// Initiates an async file search by sending a query to the background task.
     ///
     /// This method:
@@ -455,9 +812,10 @@ impl App {
     /// 2. Sets the popup state to Loading
     /// 3. Sends the query to the background search task
     ///
-    /// Results arrive via `FlowEvent::SearchResult` and are processed in `process_events`.
+    /// Partial batches arrive via `FlowEvent::SearchPartial` and the final results via
+    /// `FlowEvent::SearchResult`; both are processed in `process_events`.
     pub(crate) fn perform_file_search(&mut self, query: &str) {
-        let working_dir = std::env::current_dir().unwrap_or_default();
+        let working_dir = self.paths.base().to_path_buf();
 
         // Reset selection when query changes
         if self.text_input.last_search_query.as_deref() != Some(query)
@@ -470,17 +828,28 @@ impl App {
         self.text_input.last_search_query = Some(query.to_string());
         self.text_input.last_search_time = Some(Instant::now());
 
-        // Increment generation for this search
+        // Supersede the previous search and increment generation for this one
+        self.text_input.search_cancel.store(true, Ordering::Relaxed);
+        self.text_input.search_cancel = Arc::default();
         self.text_input.search_generation = self.text_input.search_generation.wrapping_add(1);
 
         // Set popup to loading state
         self.text_input.file_popup_state = PopupState::Loading;
 
-        // Send query to background task
+        // Send query to background task; `@dir/...` searches inside `dir`
+        let (scope, scoped_query) = split_scoped_query(query, &working_dir);
         let search_query = SearchQuery {
-            query: query.to_string(),
+            query: scoped_query.to_string(),
             working_dir,
             generation: self.text_input.search_generation,
+            cancelled: Arc::clone(&self.text_input.search_cancel),
+            options: SearchOptions {
+                ignore_globs: self.settings.ignore_globs.clone(),
+                include_hidden: self.settings.search_hidden,
+                scope,
+                max_depth: self.settings.search_max_depth,
+                extra_roots: self.settings.extra_roots.clone(),
+            },
         };
 
         // Use try_send to avoid blocking; if channel is full, the oldest query
@@ -561,7 +930,9 @@ impl App {
     /// Selects the currently highlighted file from the popup.
     ///
     /// This replaces the @ token with the full file path and dismisses the popup.
-    /// For directories, a trailing slash is appended to the path.
+    /// A directory instead becomes a new `@dir/` token and its contents are
+    /// searched right away, so the user can keep drilling down; one whose path
+    /// needs quoting is inserted with a trailing slash like a file.
     pub(crate) fn select_file_from_popup(&mut self) {
         // Get the selected file path and is_dir flag
         let selected_info = match &self.text_input.file_popup_state {
@@ -587,8 +958,19 @@ impl App {
             path
         };
 
+        let dir_token = format!("@{}", path_with_slash.display());
+        if is_dir && !needs_quoting(&dir_token) {
+            // Keep an @ token at the cursor and list the directory's contents
+            self.replace_at_token_with_text(&token, &dir_token);
+            self.update_at_token();
+            return;
+        }
+
         // Replace the @ token with the file path
         self.replace_at_token_with_path(&token, &path_with_slash);
+        if !is_dir {
+            self.text_input.selected_files.push(path_with_slash);
+        }
 
         // Dismiss the popup
         self.dismiss_file_popup();
@@ -604,6 +986,14 @@ impl App {
     /// * `token` - The @ token to replace
     /// * `path` - The file path to insert
     pub(crate) fn replace_at_token_with_path(&mut self, token: &AtToken, path: &std::path::Path) {
+        // Convert path to string and escape properly
+        let path_str = path.display().to_string();
+        self.replace_at_token_with_text(token, &escape_file_path(&path_str));
+    }
+
+    /// Replaces an @ token with `insert_str` verbatim, leaving the cursor
+    /// right after it.
+    fn replace_at_token_with_text(&mut self, token: &AtToken, insert_str: &str) {
         use tui_textarea::{CursorMove, TextArea};
 
         // Get the lines from the textarea
@@ -614,10 +1004,6 @@ impl App {
             return;
         };
 
-        // Convert path to string and escape properly
-        let path_str = path.display().to_string();
-        let insert_str = escape_file_path(&path_str);
-
         // The token includes everything from start_byte to end_byte (the @ is at start_byte)
         let start = token.start_byte;
         let end = token.end_byte;
@@ -629,7 +1015,7 @@ impl App {
 
         // Create a modified line with the replacement
         let mut new_line = line.clone();
-        new_line.replace_range(start..end, &insert_str);
+        new_line.replace_range(start..end, insert_str);
 
         // Calculate the new cursor position (character-wise)
         // Count characters up to start_byte, then add insert_str length
@@ -662,6 +1048,7 @@ impl App {
         self.text_input.file_popup_state = PopupState::Hidden;
         self.text_input.at_token = None;
         self.text_input.last_search_query = None;
+        self.cancel_file_search();
     }
 
     // =========================================================================
@@ -733,14 +1120,22 @@ impl App {
     /// Processes periodic tasks like autosaving.
     ///
     /// This method should be called regularly (e.g., on each event loop tick).
-    /// It checks if there are unsaved changes and if sufficient time has passed
-    /// since the last edit, then triggers an autosave.
+    /// While the flow runs it advances the status spinner. It then checks if
+    /// there are unsaved changes and if sufficient time has passed since the
+    /// last edit, then triggers an autosave.
     ///
     /// Autosave is debounced to avoid excessive disk writes during rapid typing.
-    /// The save only occurs after `AUTOSAVE_DEBOUNCE_MS` milliseconds of inactivity.
+    /// The save only occurs after `settings.autosave_debounce_ms` milliseconds of
+    /// inactivity (configurable via `autosave_debounce_ms` in settings.json).
     pub fn tick(&mut self) {
-        // Check if there are unsaved changes
-        if !self.text_input.is_dirty {
+        // Keep the status spinner moving while the flow runs
+        if self.is_running {
+            self.flow_ui.spinner_frame = (self.flow_ui.spinner_frame + 1) % SPINNER_FRAMES.len();
+        }
+
+        // Check if there are unsaved changes. While a paused flow runs, the
+        // input holds a command rather than the task, so it is not saved.
+        if !self.text_input.is_dirty || self.is_running {
             return;
         }
 
@@ -749,7 +1144,7 @@ impl App {
             return;
         };
 
-        if last_edit.elapsed() < Duration::from_millis(AUTOSAVE_DEBOUNCE_MS) {
+        if last_edit.elapsed() < Duration::from_millis(self.settings.autosave_debounce_ms) {
             return;
         }
 
@@ -787,8 +1182,6 @@ impl App {
     /// `true` if `.mcgravity/task.md` was successfully read and content was loaded,
     /// `false` if the file doesn't exist, is empty, or couldn't be read.
     fn load_saved_task(&mut self) -> bool {
-        use tui_textarea::{CursorMove, TextArea};
-
         let Ok(content) = std::fs::read_to_string(self.paths.task_file()) else {
             return false;
         };
@@ -797,6 +1190,42 @@ impl App {
             return false;
         }
 
+        self.set_input_content(&content)
+    }
+
+    /// Loads the content of an arbitrary file into the text input.
+    ///
+    /// Relative paths are resolved against the project directory, and a
+    /// leading `@` (as in `@` file mentions) is ignored. The cursor is placed
+    /// at the end of the loaded content.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is empty. The input is
+    /// left unchanged in that case.
+    pub fn load_task_from_path(&mut self, path: &Path) -> std::io::Result<()> {
+        let path = path
+            .to_str()
+            .and_then(|p| p.strip_prefix('@'))
+            .map_or(path, Path::new);
+        let content = std::fs::read_to_string(self.paths.base().join(path))?;
+        if content.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "file is empty",
+            ));
+        }
+        self.text_input.clear();
+        self.set_input_content(&content);
+        Ok(())
+    }
+
+    /// Replaces the text input with `content`, placing the cursor at the end.
+    ///
+    /// Returns `false` (leaving the input unchanged) if there are no lines.
+    fn set_input_content(&mut self, content: &str) -> bool {
+        use tui_textarea::{CursorMove, TextArea};
+
         // Use split('\n') to preserve trailing newline as empty line
         let lines: Vec<String> = content.split('\n').map(String::from).collect();
         if lines.is_empty() {
@@ -819,8 +1248,8 @@ impl App {
     /// Saves the current task text to `.mcgravity/task.md`.
     ///
     /// This method writes the current text input content to `.mcgravity/task.md`
-    /// for future reference. The `.mcgravity/` directory is created if it doesn't
-    /// exist.
+    /// for future reference, replacing it atomically. The `.mcgravity/` directory
+    /// is created if it doesn't exist.
     ///
     /// # Errors
     ///
@@ -829,7 +1258,7 @@ impl App {
         // Ensure the .mcgravity directory exists
         std::fs::create_dir_all(self.paths.mcgravity_dir())?;
         let text = self.text_input.collect_text();
-        std::fs::write(self.paths.task_file(), &text)
+        crate::fs::atomic::write_atomic(&self.paths.task_file(), &text)
     }
 
     // =========================================================================
@@ -843,7 +1272,7 @@ impl App {
         let input = self.text_input.collect_text();
 
         // Parse the input to see if it's a slash command
-        let Some((name, _args)) = parse_slash_command(&input) else {
+        let Some((name, args)) = parse_slash_command(&input) else {
             return false;
         };
 
@@ -861,11 +1290,17 @@ impl App {
         let ctx = CommandContext {
             is_running: self.is_running,
             mode: &self.mode,
+            args,
         };
 
         if !cmd.can_execute(&ctx) {
+            let reason = if self.is_running {
+                "while flow is running"
+            } else {
+                "when no flow is running"
+            };
             self.flow_ui.output.push(OutputLine::warning(format!(
-                "Cannot execute /{name} while flow is running"
+                "Cannot execute /{name} {reason}"
             )));
             return true;
         }
@@ -873,11 +1308,17 @@ impl App {
         // Execute the command
         let result = cmd.execute(&ctx);
 
+        // Clear input after command execution. `/save` keeps the task text it
+        // persisted, and `/load` replaces the input itself only on success, so
+        // a failed load keeps the command for correcting the path.
+        let keeps_input = matches!(result, CommandResult::Save | CommandResult::LoadTask(_));
+
         // Handle the result
         self.handle_command_result(result);
 
-        // Clear input after command execution
-        self.text_input.clear();
+        if !keeps_input {
+            self.text_input.clear();
+        }
 
         true
     }
@@ -896,9 +1337,716 @@ impl App {
             CommandResult::Clear => {
                 self.execute_clear_command();
             }
-            CommandResult::Message(msg) => {
-                self.flow_ui.output.push(OutputLine::info(msg));
+            CommandResult::ClearOutput => self.flow_ui.clear_output(),
+            CommandResult::CancelFlow => self.cancel_flow(),
+            CommandResult::Message(msg) => self.push_output(OutputLine::info(msg)),
+            CommandResult::Warning(msg) => self.push_output(OutputLine::warning(msg)),
+            CommandResult::ReapOrphans => {
+                self.reap_orphaned_processes();
+            }
+            CommandResult::Doctor => {
+                self.run_doctor_checks();
+            }
+            CommandResult::Save => {
+                self.execute_save_command();
+            }
+            CommandResult::EditTodoFile(selection) => {
+                self.execute_edit_command(selection);
+            }
+            CommandResult::ShowCycles => {
+                self.show_cycles();
+            }
+            CommandResult::SetMaxIterations(max) => {
+                self.set_max_iterations(max);
+            }
+            CommandResult::ShowDiff => {
+                self.show_diff(Arc::new(SystemGit));
             }
+            CommandResult::ShowStats => {
+                self.show_prompt_stats();
+            }
+            CommandResult::Resummarize => {
+                self.resummarize_last_task();
+            }
+            CommandResult::GotoPhase(selection) => {
+                self.execute_goto_command(selection);
+            }
+            CommandResult::CopyLastError => {
+                self.copy_last_error();
+            }
+            CommandResult::ResetSettings => {
+                self.reset_settings();
+            }
+            CommandResult::PreviewPrompt(kind) => {
+                self.open_prompt_preview(kind);
+            }
+            CommandResult::LoadTask(path) => {
+                self.execute_load_command(&path);
+            }
+            CommandResult::Step(signal) => {
+                self.send_step_signal(signal);
+            }
+            CommandResult::ShowTaskText => {
+                self.open_task_view();
+            }
+            CommandResult::ShowWorkingDir => {
+                self.show_working_dir();
+            }
+            CommandResult::Snapshot => {
+                self.take_snapshot();
+            }
+            CommandResult::RestoreSnapshot(name) => {
+                self.restore_snapshot(name.as_deref());
+            }
+            CommandResult::OpenFile(file) => {
+                self.execute_open_command(file);
+            }
+            CommandResult::ListTodos => {
+                self.list_todos();
+            }
+            CommandResult::RemoveTodo(number) => {
+                self.remove_todo(number);
+            }
+            CommandResult::ShowConfig => {
+                self.show_config();
+            }
+            CommandResult::OpenModelPicker => {
+                self.open_model_picker();
+            }
+            CommandResult::TailFile(path) => {
+                self.start_tail(&path);
+            }
+            CommandResult::StopTail => {
+                if !self.stop_tail() {
+                    self.push_output(OutputLine::info("Not tailing a file"));
+                }
+            }
+            CommandResult::WhichCli(model) => self.resolve_cli_in_background(model),
+        }
+    }
+
+    /// Snapshots task.md and the todo files, reporting the snapshot name.
+    fn take_snapshot(&mut self) {
+        match crate::fs::snapshot::create_snapshot(&self.paths) {
+            Ok(name) => self.push_output(OutputLine::success(format!(
+                "Saved snapshot {name}. Run /restore {name} confirm to roll back to it."
+            ))),
+            Err(e) => self.push_output(OutputLine::warning(format!(
+                "Failed to create snapshot: {e}"
+            ))),
+        }
+    }
+
+    /// Restores the snapshot `name` and reloads the task text, or lists the
+    /// snapshots when `name` is `None`.
+    fn restore_snapshot(&mut self, name: Option<&str>) {
+        let Some(name) = name else {
+            let snapshots = crate::fs::snapshot::list_snapshots(&self.paths);
+            if snapshots.is_empty() {
+                self.push_output(OutputLine::info(
+                    "No snapshots yet. Run /snapshot to take one.",
+                ));
+            } else {
+                self.push_output(OutputLine::info(format!(
+                    "Snapshots: {}. Run /restore <name> confirm to restore one.",
+                    snapshots.join(", ")
+                )));
+            }
+            return;
+        };
+        match crate::fs::snapshot::restore_snapshot(&self.paths, name) {
+            Ok(()) => {
+                self.load_saved_task();
+                self.push_output(OutputLine::success(format!("Restored snapshot {name}")));
+            }
+            Err(e) => self.push_output(OutputLine::warning(format!(
+                "Failed to restore snapshot {name}: {e}"
+            ))),
+        }
+    }
+
+    /// Reports the project directory flows run in.
+    fn show_working_dir(&mut self) {
+        self.push_output(OutputLine::info(format!(
+            "Working directory: {} (change it with `mcgravity --working-dir <DIR>`)",
+            self.paths.base().display()
+        )));
+    }
+
+    /// Releases a flow paused after a cycle (step mode) or before flagged
+    /// todos, or warns if no flow is paused.
+    fn send_step_signal(&mut self, signal: StepSignal) {
+        let paused = self.is_running && matches!(self.flow.phase, FlowPhase::Paused { .. });
+        let Some(step_tx) = self.step_tx.as_ref().filter(|_| paused) else {
+            self.push_output(OutputLine::warning(
+                "No flow is paused. Set \"step_mode\": true in settings.json to pause after each cycle.",
+            ));
+            return;
+        };
+        // A full channel already holds a signal the flow has yet to read
+        let _ = step_tx.try_send(signal);
+    }
+
+    /// Loads a file into the text input, reporting the outcome in the output.
+    fn execute_load_command(&mut self, path: &Path) {
+        match self.load_task_from_path(path) {
+            Ok(()) => self.push_output(OutputLine::success(format!(
+                "Loaded task text from {}",
+                path.display()
+            ))),
+            Err(e) => self.push_output(OutputLine::warning(format!(
+                "Failed to load {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Follows `path` (relative to the working directory, optionally written
+    /// as an `@` mention) in the output panel, replacing any earlier tail.
+    fn start_tail(&mut self, path: &Path) {
+        let path = path
+            .to_str()
+            .and_then(|p| p.strip_prefix('@'))
+            .map_or(path, Path::new);
+        let full_path = self.paths.base().join(path);
+        match LogTail::spawn(full_path, TAIL_POLL_INTERVAL, self.event_sender()) {
+            Ok(tail) => {
+                self.stop_tail();
+                self.log_tail = Some(tail);
+                self.push_output(OutputLine::success(format!(
+                    "Tailing {}. Run /untail to stop.",
+                    path.display()
+                )));
+            }
+            Err(e) => self.push_output(OutputLine::warning(format!(
+                "Failed to tail {}: {e}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Stops the `/tail` in progress, reporting it. Returns `false` if no
+    /// file was being followed.
+    pub(crate) fn stop_tail(&mut self) -> bool {
+        let Some(tail) = self.log_tail.take() else {
+            return false;
+        };
+        self.push_output(OutputLine::info(format!(
+            "Stopped tailing {}",
+            tail.path().display()
+        )));
+        tail.stop();
+        true
+    }
+
+    /// Opens the prompt preview modal for the current task.
+    ///
+    /// The task is the text of the current (or last) flow, falling back to
+    /// the saved task.md. The prompt reads files to inline them, so it is
+    /// built on a blocking thread and the modal opens when the
+    /// [`FlowEvent::PromptPreview`] arrives.
+    fn open_prompt_preview(&mut self, kind: PromptKind) {
+        let task = self.flow.input_text.clone();
+        let task_file = self.paths.task_file();
+        let base = self.paths.base().to_path_buf();
+        let options = self.flow_options();
+        let tx = self.event_sender();
+        tokio::task::spawn_blocking(move || {
+            let task = if task.trim().is_empty() {
+                std::fs::read_to_string(task_file).unwrap_or_default()
+            } else {
+                task
+            };
+            // Autosave may have stored the command being typed as the task
+            let prompt = (!task.trim().is_empty() && !task.trim_start().starts_with('/'))
+                .then(|| preview_prompt(kind, &task, &base, &options));
+            tx.blocking_send(FlowEvent::PromptPreview(kind, prompt))
+                .ok();
+        });
+    }
+
+    /// Opens the modal for a prompt built by [`Self::open_prompt_preview`],
+    /// unless another view was opened while it was being built.
+    pub(crate) fn show_prompt_preview(&mut self, kind: PromptKind, prompt: Option<String>) {
+        let Some(prompt) = prompt else {
+            self.push_output(OutputLine::info("No task to preview"));
+            return;
+        };
+        if self.mode == AppMode::Chat {
+            self.prompt_preview = Some(PromptPreview::new(kind, prompt));
+            self.mode = AppMode::PromptPreview;
+        }
+    }
+
+    /// Returns the options a flow started now runs with: the settings, plus
+    /// the files attached to the current task.
+    fn flow_options(&self) -> FlowOptions {
+        FlowOptions {
+            attached_files: self.flow.attached_files.clone(),
+            ..self.settings.flow_options()
+        }
+    }
+
+    /// Opens the task text modal, which follows `TaskTextUpdated` events so
+    /// completed-task summaries show up as they are recorded.
+    fn open_task_view(&mut self) {
+        if self.flow.input_text.trim().is_empty() {
+            self.push_output(OutputLine::info(
+                "No task text yet. Start a flow to follow task.md here.",
+            ));
+            return;
+        }
+        self.task_view_scroll = 0;
+        self.mode = AppMode::TaskView;
+    }
+
+    /// Restores all settings to their defaults and saves them to disk.
+    fn reset_settings(&mut self) {
+        let defaults = PersistedSettings::defaults();
+        for warning in defaults.apply_to(&mut self.settings) {
+            self.push_output(OutputLine::warning(warning));
+        }
+        self.settings.refresh_model_availability();
+        self.theme = self.settings.color_theme.theme();
+        match self.paths.save_settings(&defaults) {
+            Ok(()) => self.push_output(OutputLine::info("Settings restored to defaults")),
+            Err(e) => self.push_output(OutputLine::warning(format!(
+                "Settings restored to defaults but could not be saved: {e}"
+            ))),
+        }
+    }
+
+    /// Copies the most recent flow error to the clipboard, reporting the
+    /// outcome in the output panel.
+    pub(crate) fn copy_last_error(&mut self) {
+        let Some(error) = self.flow_ui.last_error.clone() else {
+            self.push_output(OutputLine::info("No error to copy"));
+            return;
+        };
+        match crate::tui::clipboard::copy(&error) {
+            Ok(()) => self.push_output(OutputLine::success("Copied last error to clipboard")),
+            Err(e) => self.push_output(OutputLine::warning(format!(
+                "Failed to copy to clipboard: {e}"
+            ))),
+        }
+    }
+
+    /// Executes `/goto`: lists the phase boundaries in the output, or scrolls
+    /// to the one numbered `selection`.
+    fn execute_goto_command(&mut self, selection: Option<usize>) {
+        let count = self.flow_ui.phase_markers.len();
+        if count == 0 {
+            self.push_output(OutputLine::info("No phase boundaries in the output yet"));
+            return;
+        }
+
+        let Some(number) = selection else {
+            let mut lines = vec![OutputLine::info("Phase boundaries (jump with /goto <n>):")];
+            lines.extend(
+                self.flow_ui
+                    .phase_markers
+                    .iter()
+                    .enumerate()
+                    .map(|(i, marker)| OutputLine::info(format!("  {}. {}", i + 1, marker.label))),
+            );
+            for line in lines {
+                self.push_output(line);
+            }
+            return;
+        };
+
+        if !self.goto_phase_marker(number) {
+            self.push_output(OutputLine::warning(format!(
+                "No phase boundary numbered {number} (found {count})"
+            )));
+        }
+    }
+
+    /// Executes `/resummarize`: regenerates the newest `<COMPLETED_TASKS>`
+    /// entry from the last archived todo file using the summary model (the
+    /// execution model unless overridden).
+    ///
+    /// Marks the app as running until the background task sends
+    /// [`FlowEvent::ResummarizeDone`], so no flow can start meanwhile.
+    fn resummarize_last_task(&mut self) {
+        let Some(last) = self.flow_ui.last_summary.clone() else {
+            self.push_output(OutputLine::warning(
+                "No archived task has been summarized in this session",
+            ));
+            return;
+        };
+
+        self.reset_shutdown();
+        let tx = self.event_sender();
+        let shutdown_rx = self.shutdown_receiver();
+        let paths = self.paths.clone();
+        let executor = self
+            .settings
+            .executor(self.settings.effective_summary_model());
+        let summary_max_len = self.settings.summary_max_len;
+        self.push_output(OutputLine::info(format!(
+            "Resummarizing {}",
+            last.file.display()
+        )));
+
+        self.set_running(true);
+        tokio::spawn(async move {
+            resummarize_last_task(
+                last,
+                executor.as_ref(),
+                &paths,
+                summary_max_len,
+                tx,
+                shutdown_rx,
+            )
+            .await;
+        });
+    }
+
+    /// Executes `/cycles` without arguments: reports the current cycle and cap.
+    fn show_cycles(&mut self) {
+        let line = if self.is_running {
+            let cap = self
+                .effective_max_iterations()
+                .map_or_else(|| "unlimited".to_string(), |max| max.to_string());
+            OutputLine::info(format!(
+                "Cycle {} (max iterations: {cap})",
+                self.flow.cycle_count
+            ))
+        } else {
+            OutputLine::info(format!(
+                "No flow is running. Max iterations: {}",
+                self.settings.max_iterations.name()
+            ))
+        };
+        self.push_output(line);
+    }
+
+    /// Executes `/cycles <n>`: changes the running flow's iteration cap.
+    ///
+    /// The flow reads the cap at each cycle boundary, so a cap at or below the
+    /// current cycle stops the flow after the current cycle.
+    fn set_max_iterations(&mut self, max: Option<u32>) {
+        if !self.is_running {
+            self.push_output(OutputLine::warning(
+                "No flow is running; change the default in settings (Ctrl+S)",
+            ));
+            return;
+        }
+
+        self.max_iterations_tx.send_replace(max);
+        let message = match max {
+            None => "Max iterations set to unlimited".to_string(),
+            Some(max) if max <= self.flow.cycle_count => {
+                format!("Max iterations set to {max}; stopping after the current cycle")
+            }
+            Some(max) => format!("Max iterations set to {max}"),
+        };
+        self.push_output(OutputLine::info(message));
+    }
+
+    /// Executes the `/edit` command.
+    ///
+    /// Without a selection, lists the numbered todo files. With one, records
+    /// the chosen file in `pending_edit`; the main loop then suspends the TUI
+    /// and opens it via [`take_pending_edit`](Self::take_pending_edit).
+    fn execute_edit_command(&mut self, selection: Option<usize>) {
+        use crate::fs::{list_editable_todo_files, select_numbered_file};
+
+        let files = list_editable_todo_files(&self.paths.todo_dir(), &self.paths.done_dir());
+        if files.is_empty() {
+            self.push_output(OutputLine::info("No todo files to edit"));
+            return;
+        }
+
+        let Some(number) = selection else {
+            self.push_output(OutputLine::info("Todo files (open one with /edit <n>):"));
+            for (i, path) in files.iter().enumerate() {
+                self.push_output(OutputLine::info(format!("  {}. {}", i + 1, path.display())));
+            }
+            return;
+        };
+
+        match select_numbered_file(&files, number) {
+            Some(path) => self.pending_edit = Some(FileRef::new(path)),
+            None => self.push_output(OutputLine::warning(format!(
+                "No todo file numbered {number} (found {})",
+                files.len()
+            ))),
+        }
+    }
+
+    /// Lists the numbered pending todo files for `/todos`.
+    fn list_todos(&mut self) {
+        let files = crate::fs::list_todo_files(&self.paths.todo_dir());
+        if files.is_empty() {
+            self.push_output(OutputLine::info("No pending todo files"));
+            return;
+        }
+        self.push_output(OutputLine::info(
+            "Pending todo files (delete one with /rm-todo <n> confirm):",
+        ));
+        for (i, path) in files.iter().enumerate() {
+            self.push_output(OutputLine::info(format!("  {}. {}", i + 1, path.display())));
+        }
+    }
+
+    /// Deletes the pending todo file numbered `number` for `/rm-todo`.
+    fn remove_todo(&mut self, number: usize) {
+        match crate::fs::remove_todo_file(&self.paths.todo_dir(), number) {
+            Ok(path) => {
+                self.push_output(OutputLine::success(format!("Removed {}", path.display())));
+            }
+            Err(e) => self.push_output(OutputLine::warning(format!("{e:#}"))),
+        }
+    }
+
+    /// Executes the `/open` command.
+    ///
+    /// Without a path, opens the first existing file mentioned in the most
+    /// recent output line that mentions one. Relative paths are resolved
+    /// against the project directory.
+    fn execute_open_command(&mut self, file: Option<FileRef>) {
+        if let Some(file) = file {
+            let file = self.resolve_file_ref(file);
+            if file.path.is_file() {
+                self.pending_edit = Some(file);
+            } else {
+                self.push_output(OutputLine::warning(format!(
+                    "No such file: {}",
+                    file.path.display()
+                )));
+            }
+            return;
+        }
+
+        let found = self.flow_ui.output.iter().rev().find_map(|line| {
+            extract_file_refs(&line.text)
+                .into_iter()
+                .map(|file| self.resolve_file_ref(file))
+                .find(|file| file.path.is_file())
+        });
+        match found {
+            Some(file) => self.pending_edit = Some(file),
+            None => self.push_output(OutputLine::info(
+                "No file paths found in the output. Usage: /open [path[:line]]",
+            )),
+        }
+    }
+
+    /// Resolves a relative file reference against the project directory.
+    fn resolve_file_ref(&self, file: FileRef) -> FileRef {
+        FileRef {
+            path: self.paths.base().join(file.path),
+            line: file.line,
+        }
+    }
+
+    /// Takes the file queued by `/edit` or `/open`, if any.
+    ///
+    /// The main loop calls this each iteration and, when a file is returned,
+    /// hands the terminal to the user's editor.
+    pub fn take_pending_edit(&mut self) -> Option<FileRef> {
+        self.pending_edit.take()
+    }
+
+    /// Reports the outcome of editing a file in an external editor.
+    pub fn finish_edit(&mut self, path: &std::path::Path, result: std::io::Result<ExitStatus>) {
+        let line = match result {
+            Ok(status) if status.success() => {
+                OutputLine::success(format!("Finished editing {}", path.display()))
+            }
+            Ok(status) => OutputLine::warning(format!(
+                "Editor exited with {status} while editing {}",
+                path.display()
+            )),
+            Err(e) => OutputLine::error(format!("Failed to launch editor: {e}")),
+        };
+        self.push_output(line);
+    }
+
+    /// Executes `/stats`: reports per-phase and cumulative prompt sizes.
+    fn show_prompt_stats(&mut self) {
+        let stats = self.flow_ui.prompt_stats;
+        let cumulative = stats.cumulative();
+        if cumulative.count == 0 {
+            self.push_output(OutputLine::info("No prompts sent yet"));
+            return;
+        }
+        self.push_output(OutputLine::info("Prompt sizes (~4 bytes per token):"));
+        for kind in [
+            PromptKind::Planning,
+            PromptKind::Execution,
+            PromptKind::Summary,
+        ] {
+            let totals = stats.totals(kind);
+            self.push_output(OutputLine::info(format!(
+                "  {}: {} prompt(s), {}",
+                kind.name(),
+                totals.count,
+                totals.size
+            )));
+        }
+        self.push_output(OutputLine::info(format!(
+            "  Total: {} prompt(s), {}",
+            cumulative.count, cumulative.size
+        )));
+    }
+
+    /// Executes `/config`: shows the active models and flow settings.
+    fn show_config(&mut self) {
+        let retry = RetryConfig::default();
+        let settings = &self.settings;
+        let lines = [
+            format!("Planning model: {}", settings.planning_model.name()),
+            format!("Execution model: {}", settings.execution_model.name()),
+            format!(
+                "Summary model: {}",
+                settings.effective_summary_model().name()
+            ),
+            format!("Enter behavior: {}", settings.enter_behavior.name()),
+            format!("Max iterations: {}", settings.max_iterations.name()),
+            format!("Flow mode: {}", settings.flow_mode.name()),
+            format!(
+                "Retries: up to {} attempts, waiting {}s plus {}s per failed attempt",
+                retry.max_attempts, retry.base_interval_secs, retry.interval_increment_secs
+            ),
+        ];
+        self.push_output(OutputLine::info("Current configuration:"));
+        for line in lines {
+            self.push_output(OutputLine::info(format!("  {line}")));
+        }
+    }
+
+    /// Executes `/diff`: shows `git status` and the diff since the flow started.
+    ///
+    /// Without a recorded starting commit, uncommitted changes are shown. Git
+    /// runs on a blocking thread and its output arrives as a
+    /// [`FlowEvent::DiffOutput`], so a slow repository never stalls the UI.
+    pub(crate) fn show_diff(&mut self, git: Arc<dyn GitRunner + Send + Sync>) {
+        let dir = self.paths.base().to_path_buf();
+        if !is_git_repo(&dir) {
+            self.push_output(OutputLine::warning(format!(
+                "/diff requires a git repository; {} is not inside one",
+                dir.display()
+            )));
+            return;
+        }
+        let base = self.flow_start_commit.clone();
+        let tx = self.event_sender();
+        tokio::task::spawn_blocking(move || {
+            let lines = collect_diff(git.as_ref(), &dir, base.as_deref());
+            tx.blocking_send(FlowEvent::DiffOutput(lines)).ok();
+        });
+    }
+
+    /// Executes the `/save` command: writes task text to `.mcgravity/task.md`
+    /// immediately, regardless of the autosave debounce or dirty state.
+    fn execute_save_command(&mut self) {
+        // Drop only the command token so "/save" itself is not persisted.
+        // A bare "/save" saves the task of the current (or last) flow.
+        let input = self.text_input.collect_text();
+        let mut text = input
+            .trim_start()
+            .strip_prefix("/save")
+            .map_or(input.as_str(), str::trim_start)
+            .to_string();
+        if text.trim().is_empty() {
+            text.clone_from(&self.flow.input_text);
+        }
+        self.text_input.set_text(&text);
+        if text.trim().is_empty() {
+            self.push_output(OutputLine::warning("No task text to save"));
+            return;
+        }
+
+        let result = self.save_current_task();
+        self.text_input.is_dirty = false;
+        match result {
+            Ok(()) => self.push_output(OutputLine::success(format!(
+                "Saved task text to {}",
+                self.paths.task_file().display()
+            ))),
+            Err(e) => self.push_output(OutputLine::warning(format!("Failed to save task: {e}"))),
+        }
+    }
+
+    /// Executes the `/which` command. Resolving may start a login shell, so
+    /// it runs on a blocking thread and is reported when the
+    /// [`FlowEvent::CliResolution`] arrives.
+    fn resolve_cli_in_background(&mut self, model: Model) {
+        let path_override = self.settings.cli_path(model).cloned();
+        let tx = self.event_sender();
+        tokio::task::spawn_blocking(move || {
+            let resolution = resolve_cli_command(model.command(), path_override.as_deref());
+            tx.blocking_send(FlowEvent::CliResolution(model, resolution))
+                .ok();
+        });
+    }
+
+    /// Reports where `model`'s CLI command resolves, for `/which`.
+    pub(crate) fn report_cli_resolution(&mut self, model: Model, resolution: &CommandResolution) {
+        let line = describe_resolution(model.command(), resolution);
+        self.push_output(if resolution.is_available() {
+            OutputLine::info(line)
+        } else {
+            OutputLine::warning(line)
+        });
+    }
+
+    /// Executes the `/doctor` command. The checks look up CLIs and run git,
+    /// so they run on a blocking thread and are reported when the
+    /// [`FlowEvent::DoctorChecks`] arrives.
+    fn run_doctor_checks(&mut self) {
+        let paths = self.paths.clone();
+        let settings = self.settings.clone();
+        let tx = self.event_sender();
+        tokio::task::spawn_blocking(move || {
+            let checks = run_doctor(&paths, &settings);
+            tx.blocking_send(FlowEvent::DoctorChecks(checks)).ok();
+        });
+    }
+
+    /// Reports each `/doctor` check in the output, followed by a summary.
+    pub(crate) fn report_doctor_checks(&mut self, checks: &[DoctorCheck]) {
+        for check in checks {
+            let text = format!("{}: {}", check.label, check.detail);
+            self.push_output(match (check.passed, check.critical) {
+                (true, _) => OutputLine::success(text),
+                (false, true) => OutputLine::error(text),
+                (false, false) => OutputLine::warning(text),
+            });
+        }
+        let summary = doctor_summary(checks);
+        self.push_output(if checks.iter().any(DoctorCheck::is_blocking) {
+            OutputLine::error(summary)
+        } else {
+            OutputLine::success(summary)
+        });
+    }
+
+    /// Executes the `/reap` command: terminates CLI processes orphaned by a
+    /// previous session, as recorded in `.mcgravity/pids`.
+    fn reap_orphaned_processes(&mut self) {
+        let outcome = crate::fs::pids::reap_orphaned_pids(&self.paths.pids_file());
+        let (reaped, remaining) = (outcome.reaped, outcome.failed);
+        if reaped.is_empty() && remaining.is_empty() {
+            self.push_output(OutputLine::info("No orphaned CLI processes found"));
+            return;
+        }
+
+        if !reaped.is_empty() {
+            self.push_output(OutputLine::success(format!(
+                "Terminated {} orphaned CLI process(es) (PIDs: {})",
+                reaped.len(),
+                format_pids(&reaped)
+            )));
+        }
+        if !remaining.is_empty() {
+            self.push_output(OutputLine::warning(format!(
+                "Failed to terminate orphaned CLI process(es) (PIDs: {})",
+                format_pids(&remaining)
+            )));
         }
     }
 
@@ -919,13 +2067,16 @@ impl App {
         self.clear_todo_folder();
 
         // Clear output buffer
-        self.flow_ui.output.clear();
-        self.flow_ui.output_scroll.reset();
-        self.flow_ui.output_truncated = false;
+        self.flow_ui.clear_output();
 
         // Clear the text input
+        self.cancel_file_search();
         let search_tx = self.text_input.search_tx.clone();
+        let history = std::mem::take(&mut self.text_input.history);
+        let bracketed_paste = self.text_input.bracketed_paste;
         self.text_input = TextInputState::new(search_tx);
+        self.text_input.history = history;
+        self.text_input.bracketed_paste = bracketed_paste;
 
         // Also clear flow.input_text to reset the Task Text panel
         self.flow.input_text = String::new();
@@ -5,13 +5,13 @@
 
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     text::{Line, Span},
     widgets::{Block, LineGauge, Paragraph},
 };
 use tui_textarea::TextArea;
 
-use crate::app::{App, wrap_lines_for_display};
+use crate::app::{App, Focus, wrap_lines_for_display};
 use crate::core::FlowPhase;
 use crate::tui::widgets::{CommandPopup, FileSuggestionPopup, OutputWidget, StatusIndicatorWidget};
 
@@ -26,6 +26,8 @@ impl App {
     /// - Text input area (composer)
     /// - Footer with key hints
     ///
+    /// On short terminals the compact layout folds the header into the footer.
+    ///
     /// Uses the cached layout from `self.layout.chat` which is calculated
     /// once per frame in `update_layout()`.
     pub(crate) fn render_chat(&self, frame: &mut Frame) {
@@ -60,7 +62,12 @@ impl App {
 
     /// Renders the chat header (minimal, single line).
     fn render_chat_header(&self, frame: &mut Frame, area: Rect) {
-        let header = Line::from(vec![
+        frame.render_widget(Paragraph::new(Line::from(self.chat_header_spans())), area);
+    }
+
+    /// Builds the header spans: app name and the selected models.
+    fn chat_header_spans(&self) -> Vec<Span<'static>> {
+        vec![
             Span::styled(" McGravity ", self.theme.header_style()),
             Span::styled("[", self.theme.muted_style()),
             Span::styled(
@@ -73,8 +80,7 @@ impl App {
                 self.theme.normal_style(),
             ),
             Span::styled("]", self.theme.muted_style()),
-        ]);
-        frame.render_widget(Paragraph::new(header), area);
+        ]
     }
 
     /// Renders the chat output area (reuses `OutputWidget`).
@@ -91,6 +97,13 @@ impl App {
             title,
             &self.theme,
             self.flow_ui.output_truncated,
+        )
+        .with_timestamps(self.settings.show_timestamps)
+        .with_focus(self.focus == Focus::Output)
+        .with_selected_line(
+            self.flow_ui
+                .selected_line
+                .filter(|_| self.focus == Focus::Output),
         );
         frame.render_widget(output_widget, area);
     }
@@ -104,8 +117,10 @@ impl App {
             self.flow_ui.retry_wait,
             self.is_running,
             &self.theme,
-            self.settings.max_iterations.value(),
-        );
+            self.effective_max_iterations(),
+        )
+        .with_spinner_frame(self.flow_ui.spinner_frame)
+        .with_todo_counts(self.flow_ui.todo_counts);
         frame.render_widget(status_widget, area);
     }
 
@@ -118,14 +133,30 @@ impl App {
             return;
         }
 
-        // Reuse existing render_compact_progress logic
-        self.render_compact_progress(frame, area);
+        // While a todo runs, the right half previews the todo after it
+        let Some(up_next) = &self.flow_ui.up_next else {
+            self.render_compact_progress(frame, area);
+            return;
+        };
+        let [gauge_area, next_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+        self.render_compact_progress(frame, gauge_area);
+        let next = Paragraph::new(Line::from(vec![
+            Span::styled(" Up next: ", self.theme.muted_style()),
+            Span::styled(up_next.clone(), self.theme.normal_style()),
+        ]));
+        frame.render_widget(next, next_area);
     }
 
     /// Renders the chat input area and returns the inner area for cursor positioning.
     fn render_chat_input(&self, frame: &mut Frame, area: Rect) -> Rect {
-        let title = if self.is_running {
+        let locked = self.input_locked();
+        let title = if locked {
             " Task (Readonly) "
+        } else if matches!(self.flow.phase, FlowPhase::Paused { .. }) && self.is_running {
+            " Command (flow paused) "
+        } else if self.is_running {
+            " Command (flow running) "
         } else {
             " Task Text "
         };
@@ -137,12 +168,16 @@ impl App {
                 Span::styled(" \\", self.theme.highlight_style()),
                 Span::styled("+Enter for newline ", self.theme.muted_style()),
             ]))
-            .border_style(self.theme.border_style());
+            .border_style(if self.focus == Focus::Input {
+                self.theme.focused_border_style()
+            } else {
+                self.theme.border_style()
+            });
 
         let inner = block.inner(area);
 
         // Create a clone of the textarea widget with the styled block
-        let mut textarea = if self.is_running {
+        let mut textarea = if locked {
             let lines: Vec<String> = self.flow.input_text.split('\n').map(String::from).collect();
             TextArea::new(lines)
         } else {
@@ -162,11 +197,27 @@ impl App {
 
     /// Renders the chat footer with key hints (single line).
     fn render_chat_footer(&self, frame: &mut Frame, area: Rect) {
-        let footer_content = if self.is_running {
+        let footer_content = if self.input_locked() {
             vec![
-                Span::styled(" [Esc] ", self.theme.highlight_style()),
+                Span::styled(" [/] ", self.theme.highlight_style()),
+                Span::styled("Command  ", self.theme.muted_style()),
+                Span::styled("[Esc] ", self.theme.highlight_style()),
                 Span::styled("Cancel", self.theme.muted_style()),
             ]
+        } else if self.is_running {
+            vec![
+                Span::styled(" [Enter] ", self.theme.highlight_style()),
+                Span::styled("Run command  ", self.theme.muted_style()),
+                Span::styled("[Esc] ", self.theme.highlight_style()),
+                Span::styled(
+                    if self.typing_command() {
+                        "Discard command"
+                    } else {
+                        "Cancel flow"
+                    },
+                    self.theme.muted_style(),
+                ),
+            ]
         } else if self.should_show_file_popup() {
             vec![
                 Span::styled(" [↑/↓] ", self.theme.highlight_style()),
@@ -179,13 +230,27 @@ impl App {
         } else {
             vec![
                 Span::styled(" [Enter] ", self.theme.highlight_style()),
-                Span::styled("Submit  ", self.theme.muted_style()),
+                Span::styled(
+                    format!("{}  ", self.settings.enter_behavior.name()),
+                    self.theme.muted_style(),
+                ),
                 Span::styled("[Ctrl+S] ", self.theme.highlight_style()),
-                Span::styled("Settings", self.theme.muted_style()),
+                Span::styled("Settings  ", self.theme.muted_style()),
+                Span::styled("[Ctrl+T] ", self.theme.highlight_style()),
+                Span::styled("Toggle Enter", self.theme.muted_style()),
             ]
         };
 
-        let footer = Paragraph::new(Line::from(footer_content));
+        // The compact layout has no header row, so the header leads the footer
+        let spans = if self.layout.chat.compact {
+            let mut spans = self.chat_header_spans();
+            spans.extend(footer_content);
+            spans
+        } else {
+            footer_content
+        };
+
+        let footer = Paragraph::new(Line::from(spans));
         frame.render_widget(footer, area);
     }
 
@@ -243,24 +308,37 @@ impl App {
     }
 
     /// Renders a compact single-line progress bar.
+    ///
+    /// Once execution starts, shows how many todos of the cycle's batch have
+    /// finished; before that, the file position from the current phase.
     #[allow(clippy::cast_precision_loss)] // Precision loss acceptable for progress ratio
     fn render_compact_progress(&self, frame: &mut Frame, area: Rect) {
-        let (current, total) = match &self.flow.phase {
-            FlowPhase::ProcessingTodos { current, total } => (*current, *total),
-            FlowPhase::RunningExecution { file_index, .. } => {
-                (*file_index, self.flow.todo_files.len())
-            }
-            _ => (0, self.flow.todo_files.len().max(1)),
-        };
-
-        let ratio = if total > 0 {
-            current as f64 / total as f64
+        let (ratio, label) = if let Some(progress) = self.flow_ui.todo_progress {
+            (
+                progress.ratio(),
+                format!(
+                    " {}/{} todos ({}%) ",
+                    progress.completed,
+                    progress.total,
+                    progress.percent()
+                ),
+            )
         } else {
-            0.0
+            let (current, total) = match &self.flow.phase {
+                FlowPhase::ProcessingTodos { current, total } => (*current, *total),
+                FlowPhase::RunningExecution { file_index, .. } => {
+                    (*file_index, self.flow.todo_files.len())
+                }
+                _ => (0, self.flow.todo_files.len().max(1)),
+            };
+            let ratio = if total > 0 {
+                current as f64 / total as f64
+            } else {
+                0.0
+            };
+            (ratio, format!(" {current}/{total} files "))
         };
 
-        let label = format!(" {current}/{total} files ");
-
         let gauge = LineGauge::default()
             .ratio(ratio)
             .label(label)
@@ -324,10 +402,11 @@ impl App {
             screen.width.saturating_sub(popup_width)
         };
 
-        // Clamp height to available space
+        // Clamp size to available space
+        let actual_width = popup_width.min(screen.width.saturating_sub(x));
         let actual_height = popup_height.min(screen.height.saturating_sub(y));
 
-        Rect::new(x, y, popup_width, actual_height)
+        Rect::new(x, y, actual_width, actual_height)
     }
 
     /// Renders the file suggestion popup.
@@ -384,14 +463,16 @@ impl App {
 /// The popup is positioned above the input area, aligned to the left edge.
 /// Width and height are provided by the widget's `preferred_size()` method.
 fn calculate_command_popup_area(popup_width: u16, popup_height: u16, input_area: Rect) -> Rect {
-    // Clamp width to input area width
+    // Clamp width to input area width, and height to the rows above the
+    // input's bottom edge (on short terminals the popup covers the input)
     let width = popup_width.min(input_area.width);
+    let height = popup_height.min(input_area.bottom());
 
     // Position: above input area, aligned to left
     Rect {
         x: input_area.x,
-        y: input_area.y.saturating_sub(popup_height),
+        y: input_area.y.saturating_sub(height),
         width,
-        height: popup_height,
+        height,
     }
 }
@@ -0,0 +1,60 @@
+//! Unlimited-iterations confirmation dialog rendering.
+//!
+//! This module contains the rendering logic for the modal shown before a flow
+//! without an iteration cap is started.
+
+use ratatui::{
+    Frame,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_popup;
+use crate::app::App;
+
+impl App {
+    /// Renders the start confirmation dialog as a centered overlay.
+    pub(crate) fn render_confirm_start_dialog(&self, frame: &mut Frame) {
+        let area = frame.area();
+
+        // Calculate centered popup dimensions
+        let popup_width = 56u16;
+        let popup_height = 10u16;
+        let popup_area = centered_popup(area, popup_width, popup_height);
+
+        // Clear background
+        frame.render_widget(Clear, popup_area);
+
+        let content_lines = vec![
+            Line::from(Span::styled(
+                "Unlimited Iterations",
+                self.theme.header_style(),
+            )),
+            Line::from(Span::styled(
+                "This will run until no todos remain.",
+                self.theme.muted_style(),
+            )),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("[Enter] ", self.theme.highlight_style()),
+                Span::styled("Continue", self.theme.normal_style()),
+            ]),
+            Line::from(vec![
+                Span::styled("[Esc] ", self.theme.highlight_style()),
+                Span::styled("Cancel", self.theme.normal_style()),
+            ]),
+        ];
+
+        let block = Block::default()
+            .title(" Start Flow? ")
+            .title_style(self.theme.header_style())
+            .borders(Borders::ALL)
+            .border_style(self.theme.border_style());
+
+        let paragraph = Paragraph::new(content_lines)
+            .block(block)
+            .alignment(ratatui::layout::Alignment::Center);
+
+        frame.render_widget(paragraph, popup_area);
+    }
+}
@@ -4,48 +4,83 @@
 
 use ratatui::{
     Frame,
-    layout::Rect,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use super::centered_popup;
 use crate::app::App;
 
+/// Maximum width of the error line in the finished dialog.
+const FINISHED_ERROR_WIDTH: usize = 48;
+
+/// Shortens a single-line view of `error` to `max_chars`, marking the cut with `…`.
+fn truncate_error(error: &str, max_chars: usize) -> String {
+    let first_line = error.lines().next().unwrap_or_default();
+    if first_line.chars().count() <= max_chars && !error.contains('\n') {
+        return first_line.to_string();
+    }
+    let mut shortened: String = first_line
+        .chars()
+        .take(max_chars.saturating_sub(1))
+        .collect();
+    shortened.push('…');
+    shortened
+}
+
 impl App {
     /// Renders the finished dialog as a centered overlay.
     pub(crate) fn render_finished_dialog(&self, frame: &mut Frame) {
         let area = frame.area();
 
-        // Calculate centered popup dimensions
-        let popup_width = 52u16;
-        let popup_height = 10u16;
-        let x = area.width.saturating_sub(popup_width) / 2;
-        let y = area.height.saturating_sub(popup_height) / 2;
-        let popup_area = Rect::new(x, y, popup_width, popup_height);
-
-        // Clear background
-        frame.render_widget(Clear, popup_area);
-
-        let content_lines = vec![
+        let mut content_lines = vec![
             Line::from(Span::styled("Flow Complete", self.theme.header_style())),
             Line::from(Span::styled(
                 "No more tasks to process.",
                 self.theme.muted_style(),
             )),
             Line::from(""),
-            Line::from(vec![
-                Span::styled("[Enter] ", self.theme.highlight_style()),
-                Span::styled(
-                    "Start new session (clears task history)",
-                    self.theme.normal_style(),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled("[q] ", self.theme.highlight_style()),
-                Span::styled("Quit", self.theme.normal_style()),
-            ]),
         ];
 
+        // Keep an error from the run visible, since it has likely scrolled away
+        if let Some(error) = &self.flow_ui.last_error {
+            content_lines.push(Line::from(Span::styled(
+                "Last error:",
+                self.theme.error_style(),
+            )));
+            content_lines.push(Line::from(Span::styled(
+                truncate_error(error, FINISHED_ERROR_WIDTH),
+                self.theme.normal_style(),
+            )));
+            content_lines.push(Line::from(""));
+        }
+
+        content_lines.push(Line::from(vec![
+            Span::styled("[Enter] ", self.theme.highlight_style()),
+            Span::styled(
+                "Start new session (clears task history)",
+                self.theme.normal_style(),
+            ),
+        ]));
+        if self.flow_ui.last_error.is_some() {
+            content_lines.push(Line::from(vec![
+                Span::styled("[c] ", self.theme.highlight_style()),
+                Span::styled("Copy error", self.theme.normal_style()),
+            ]));
+        }
+        content_lines.push(Line::from(vec![
+            Span::styled("[q] ", self.theme.highlight_style()),
+            Span::styled("Quit", self.theme.normal_style()),
+        ]));
+
+        // Calculate centered popup dimensions (content plus borders and padding)
+        let popup_width = 52u16;
+        let popup_height = u16::try_from(content_lines.len() + 4).unwrap_or(u16::MAX);
+        let popup_area = centered_popup(area, popup_width, popup_height);
+
+        // Clear background
+        frame.render_widget(Clear, popup_area);
+
         let block = Block::default()
             .title(" Finished ")
             .title_style(self.theme.header_style())
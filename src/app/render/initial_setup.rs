@@ -6,11 +6,11 @@
 
 use ratatui::{
     Frame,
-    layout::Rect,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use super::centered_popup;
 use crate::app::{App, InitialSetupField};
 use crate::core::Model;
 
@@ -27,9 +27,7 @@ impl App {
         // Taller than settings to fit welcome message and error lines
         let popup_width = 56u16;
         let popup_height = 18u16;
-        let x = area.width.saturating_sub(popup_width) / 2;
-        let y = area.height.saturating_sub(popup_height) / 2;
-        let popup_area = Rect::new(x, y, popup_width, popup_height);
+        let popup_area = centered_popup(area, popup_width, popup_height);
 
         // Clear background (required for popups per ratatui best practices)
         frame.render_widget(Clear, popup_area);
@@ -5,25 +5,61 @@
 //! - **Settings panel**: Modal overlay for model configuration
 //! - **Finished dialog**: Modal overlay after flow completion
 //! - **Initial setup**: First-run modal for model selection
+//! - **Confirm start**: Modal asking before an unlimited-iterations flow starts
+//! - **Text modals**: Scrollable modals showing a wrapped prompt (`/preview`) or the task text (`/task`)
+//! - **Model picker**: Modal for choosing planning and execution models (`/models`)
 
 mod chat;
+mod confirm_start;
 mod finished;
 mod initial_setup;
+mod model_picker;
 mod settings;
+mod text_modal;
 
-use ratatui::Frame;
+use ratatui::{
+    Frame,
+    layout::Rect,
+    text::Line,
+    widgets::{Paragraph, Wrap},
+};
 
+use super::layout::{MIN_TERMINAL_HEIGHT, MIN_TERMINAL_WIDTH};
 use super::{App, AppMode};
 
+/// Returns a `width` x `height` popup centered in `area`, shrunk to fit.
+pub(super) fn centered_popup(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
 impl App {
     /// Renders the application UI.
     ///
-    /// The application has four modes:
+    /// The application has seven modes:
     /// - **Chat**: Main unified interface with input, output, and status
     /// - **Settings**: Modal overlay for model configuration
     /// - **Finished**: Modal overlay prompting for next action
     /// - **`InitialSetup`**: First-run modal for selecting default models
+    /// - **`ConfirmStart`**: Modal confirming an unlimited-iterations flow
+    /// - **`PromptPreview`**: Scrollable modal showing a wrapped prompt
+    /// - **`TaskView`**: Scrollable modal showing the live task text
+    ///
+    /// Below [`MIN_TERMINAL_WIDTH`] x [`MIN_TERMINAL_HEIGHT`] only a
+    /// "terminal too small" message is shown.
     pub fn render(&self, frame: &mut Frame) {
+        let area = frame.area();
+        if area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT {
+            self.render_too_small(frame, area);
+            return;
+        }
+
         match self.mode {
             AppMode::Chat => self.render_chat(frame),
             AppMode::Settings => {
@@ -41,6 +77,38 @@ impl App {
                 self.render_chat(frame);
                 self.render_initial_setup(frame);
             }
+            AppMode::ConfirmStart => {
+                // Render chat as background, then overlay confirmation modal
+                self.render_chat(frame);
+                self.render_confirm_start_dialog(frame);
+            }
+            AppMode::PromptPreview => {
+                // Render chat as background, then overlay the prompt
+                self.render_chat(frame);
+                self.render_prompt_preview(frame);
+            }
+            AppMode::TaskView => {
+                // Render chat as background, then overlay the task text
+                self.render_chat(frame);
+                self.render_task_view(frame);
+            }
+            AppMode::ModelPicker => {
+                // Render chat as background, then overlay the model picker
+                self.render_chat(frame);
+                self.render_model_picker(frame);
+            }
         }
     }
+    /// Renders the message shown when the terminal is below the minimum size.
+    fn render_too_small(&self, frame: &mut Frame, area: Rect) {
+        let message = Paragraph::new(vec![
+            Line::styled("Terminal too small", self.theme.warning_style()),
+            Line::styled(
+                format!("Need at least {MIN_TERMINAL_WIDTH}x{MIN_TERMINAL_HEIGHT}"),
+                self.theme.muted_style(),
+            ),
+        ])
+        .wrap(Wrap { trim: true });
+        frame.render_widget(message, area);
+    }
 }
@@ -0,0 +1,111 @@
+//! Model picker modal rendering.
+//!
+//! This module contains the rendering logic for the modal opened by
+//! `/models`, which lists every model with its description, availability,
+//! and whether it is used for planning or execution.
+
+use ratatui::{
+    Frame,
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use super::centered_popup;
+use crate::app::App;
+use crate::app::state::ModelPickerEntry;
+
+impl App {
+    /// Renders the model picker as a centered overlay.
+    pub(crate) fn render_model_picker(&self, frame: &mut Frame) {
+        let entries = self.settings.model_picker_entries();
+
+        // Header (3) + column titles (1) + one line per model + footer (3) + borders (2)
+        let popup_width = 64u16;
+        let entry_count = u16::try_from(entries.len()).unwrap_or(u16::MAX);
+        let popup_height = 9u16.saturating_add(entry_count);
+        let popup_area = centered_popup(frame.area(), popup_width, popup_height);
+
+        // Clear background
+        frame.render_widget(Clear, popup_area);
+
+        let mut content_lines = vec![
+            Line::from(Span::styled("Models", self.theme.header_style())),
+            Line::from(Span::styled(
+                "Pick the AI CLIs used for planning and execution.",
+                self.theme.muted_style(),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                format!("  {:<13}{:<23}{}", "Model", "Description", "Used for"),
+                self.theme.muted_style().add_modifier(Modifier::BOLD),
+            )),
+        ];
+
+        for (i, &entry) in entries.iter().enumerate() {
+            content_lines.push(self.render_model_picker_entry(entry, i == self.model_picker_index));
+        }
+
+        content_lines.push(Line::from(""));
+        content_lines.push(Line::from(vec![
+            Span::styled("[↑/↓] ", self.theme.highlight_style()),
+            Span::styled("Move  ", self.theme.muted_style()),
+            Span::styled("[P] ", self.theme.highlight_style()),
+            Span::styled("Plan  ", self.theme.muted_style()),
+            Span::styled("[E] ", self.theme.highlight_style()),
+            Span::styled("Exec  ", self.theme.muted_style()),
+            Span::styled("[Enter] ", self.theme.highlight_style()),
+            Span::styled("Both  ", self.theme.muted_style()),
+            Span::styled("[Esc] ", self.theme.highlight_style()),
+            Span::styled("Close", self.theme.muted_style()),
+        ]));
+
+        let block = Block::default()
+            .title(" Models ")
+            .title_style(self.theme.header_style())
+            .borders(Borders::ALL)
+            .border_style(self.theme.border_style());
+
+        let paragraph = Paragraph::new(content_lines).block(block);
+        frame.render_widget(paragraph, popup_area);
+    }
+
+    /// Renders one model row. Unavailable models are dimmed and marked as
+    /// not installed instead of showing their role.
+    fn render_model_picker_entry(
+        &self,
+        entry: ModelPickerEntry,
+        is_selected: bool,
+    ) -> Line<'static> {
+        let prefix = if is_selected { "› " } else { "  " };
+        let text_style = if !entry.available {
+            self.theme.muted_style()
+        } else if is_selected {
+            self.theme.highlight_style()
+        } else {
+            self.theme.normal_style()
+        };
+
+        let (role, role_style) = if entry.available {
+            let role = match (entry.planning, entry.execution) {
+                (true, true) => "Both",
+                (true, false) => "Planning",
+                (false, true) => "Execution",
+                (false, false) => "",
+            };
+            (role.to_string(), self.theme.success_style())
+        } else {
+            (
+                format!("✗ `{}` not installed", entry.model.command()),
+                self.theme.error_style(),
+            )
+        };
+
+        Line::from(vec![
+            Span::styled(prefix, self.theme.highlight_style()),
+            Span::styled(format!("{:<13}", entry.model.name()), text_style),
+            Span::styled(format!("{:<23}", entry.model.description()), text_style),
+            Span::styled(role, role_style),
+        ])
+    }
+}
@@ -4,15 +4,26 @@
 
 use ratatui::{
     Frame,
-    layout::Rect,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use super::centered_popup;
 use crate::app::{App, SettingsItem};
+use crate::tui::widgets::OutputLine;
+
+/// Width of the settings popup, including its borders.
+const SETTINGS_POPUP_WIDTH: u16 = 52;
+
+/// Blank lines between the settings and the footer hints, when they fit.
+const FOOTER_SPACING: usize = 2;
 
 impl App {
     /// Renders the settings panel as a centered overlay.
+    ///
+    /// The popup is as tall as its content, leaving a row free above and
+    /// below it; when the terminal is shorter, the spacing above the footer
+    /// hints is dropped first.
     pub(crate) fn render_settings(&self, frame: &mut Frame) {
         let area = frame.area();
 
@@ -23,21 +34,11 @@ impl App {
         let execution_unavailable = !self
             .settings
             .is_model_available(self.settings.execution_model);
-        let error_line_count = u16::from(planning_unavailable) + u16::from(execution_unavailable);
-
-        // Calculate centered popup dimensions
-        // Base height: 15 lines + error lines as needed (5 settings + header/footer)
-        let popup_width = 52u16;
-        let popup_height = 15u16 + error_line_count;
-        let x = area.width.saturating_sub(popup_width) / 2;
-        let y = area.height.saturating_sub(popup_height) / 2;
-        let popup_area = Rect::new(x, y, popup_width, popup_height);
-
-        // Clear background
-        frame.render_widget(Clear, popup_area);
 
         // Build settings content
         let items = SettingsItem::all();
+        let theme_selected = items.get(self.settings.selected_index) == Some(&SettingsItem::Theme);
+
         let mut content_lines = Vec::new();
 
         // Header
@@ -62,6 +63,10 @@ impl App {
                 SettingsItem::EnterBehavior => self.settings.enter_behavior.name(),
                 SettingsItem::MaxIterations => self.settings.max_iterations.name(),
                 SettingsItem::SummaryGeneration => self.settings.summary_generation.name(),
+                SettingsItem::ArchiveMode => self.settings.archive_mode.name(),
+                SettingsItem::ResetClearsTask => self.settings.reset_clears_task_name(),
+                SettingsItem::FlowMode => self.settings.flow_mode.name(),
+                SettingsItem::Theme => self.settings.color_theme.name(),
             };
 
             let line = if is_selected {
@@ -95,9 +100,18 @@ impl App {
             }
         }
 
-        // Spacing before footer
-        content_lines.push(Line::from(""));
-        content_lines.push(Line::from(""));
+        // Sample lines in the highlighted theme, which only applies on close
+        if theme_selected {
+            content_lines.push(Line::from(""));
+            content_lines.extend(self.theme_preview_lines());
+        }
+
+        // Spacing before footer, as far as it fits with the footer and borders
+        let max_height = usize::from(area.height.saturating_sub(2));
+        let spacing = max_height
+            .saturating_sub(content_lines.len() + 3)
+            .min(FOOTER_SPACING);
+        content_lines.extend(std::iter::repeat_n(Line::from(""), spacing));
 
         // Footer hints
         content_lines.push(Line::from(vec![
@@ -109,6 +123,17 @@ impl App {
             Span::styled("Close", self.theme.muted_style()),
         ]));
 
+        // Size the popup to its content and borders
+        let popup_height = u16::try_from(content_lines.len() + 2).unwrap_or(u16::MAX);
+        let popup_area = centered_popup(
+            area,
+            SETTINGS_POPUP_WIDTH,
+            popup_height.min(area.height.saturating_sub(2)),
+        );
+
+        // Clear background
+        frame.render_widget(Clear, popup_area);
+
         // Render the popup
         let block = Block::default()
             .title(" Settings ")
@@ -122,4 +147,22 @@ impl App {
 
         frame.render_widget(paragraph, popup_area);
     }
+
+    /// Returns one sample output line of each message type, styled with the
+    /// highlighted (not yet applied) theme.
+    fn theme_preview_lines(&self) -> Vec<Line<'static>> {
+        let preview_theme = self.settings.color_theme.theme();
+        [
+            OutputLine::error("Build failed"),
+            OutputLine::success("Todo completed"),
+            OutputLine::warning("Retrying in 5s"),
+            OutputLine::info("Planning next cycle"),
+        ]
+        .into_iter()
+        .map(|sample| {
+            let style = sample.line_type.style(&preview_theme);
+            Line::from(Span::styled(sample.text, style))
+        })
+        .collect()
+    }
 }
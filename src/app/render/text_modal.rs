@@ -0,0 +1,78 @@
+//! Scrollable text modal rendering.
+//!
+//! This module contains the rendering logic for the large scrollable modals
+//! that show a block of text: the wrapped prompt opened by `/preview` and the
+//! live task text (including the `<COMPLETED_TASKS>` block) opened by `/task`.
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use super::centered_popup;
+use crate::app::App;
+
+impl App {
+    /// Renders the prompt preview as a large centered overlay.
+    pub(crate) fn render_prompt_preview(&self, frame: &mut Frame) {
+        let Some(preview) = &self.prompt_preview else {
+            return;
+        };
+        let title = format!(" {} Prompt ", preview.kind.name());
+        self.render_text_modal(frame, &title, &preview.text, preview.scroll);
+    }
+
+    /// Renders the task text as a large centered overlay.
+    ///
+    /// Reads `flow.input_text`, which `TaskTextUpdated` events keep in sync
+    /// with task.md while the flow runs.
+    pub(crate) fn render_task_view(&self, frame: &mut Frame) {
+        let title = if self.is_running {
+            " Task Text (live) "
+        } else {
+            " Task Text "
+        };
+        self.render_text_modal(frame, title, &self.flow.input_text, self.task_view_scroll);
+    }
+
+    /// Renders `text` scrolled down by `scroll` lines in a large centered
+    /// overlay titled `title`, with a hint line for the scroll and close keys.
+    fn render_text_modal(&self, frame: &mut Frame, title: &str, text: &str, scroll: u16) {
+        let area = frame.area();
+        let popup_area = centered_popup(
+            area,
+            area.width.saturating_sub(4),
+            area.height.saturating_sub(2),
+        );
+
+        // Clear background
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .title(title)
+            .title_style(self.theme.header_style())
+            .borders(Borders::ALL)
+            .border_style(self.theme.border_style());
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let [text_area, hint_area] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(inner);
+
+        let text = Paragraph::new(text)
+            .style(self.theme.normal_style())
+            .wrap(Wrap { trim: false })
+            .scroll((scroll, 0));
+        frame.render_widget(text, text_area);
+
+        let hint = Line::from(vec![
+            Span::styled("[↑/↓ PgUp/PgDn] ", self.theme.highlight_style()),
+            Span::styled("Scroll  ", self.theme.muted_style()),
+            Span::styled("[Esc] ", self.theme.highlight_style()),
+            Span::styled("Close", self.theme.muted_style()),
+        ]);
+        frame.render_widget(Paragraph::new(hint), hint_area);
+    }
+}
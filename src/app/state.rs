@@ -24,16 +24,27 @@
 //! - It cannot be dismissed with Esc (user must select models)
 //! - It includes a welcome/introduction message
 
-use std::path::PathBuf;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
 
 use tokio::sync::mpsc;
 use tui_textarea::TextArea;
 
+use crate::app::history::InputHistory;
 use crate::app::input::RapidInputDetector;
 use crate::app::slash_commands::SlashToken;
-use crate::core::{FlowPhase, Model, ModelAvailability};
-use crate::file_search::SearchResult;
+use crate::core::channels::EXECUTOR_OUTPUT_CHANNEL_SIZE;
+use crate::core::doctor::DoctorCheck;
+use crate::core::{
+    AiCliExecutor, CommandResolution, FlowOptions, FlowPhase, Model, ModelAvailability, PromptKind,
+    PromptSize, PromptStats, StderrFilter, TodoCounts, TodoProgress, resolve_cli_command,
+};
+use crate::file_search::{FileMatch, SearchOptions, SearchResult};
+use crate::fs::McgravityPaths;
+use crate::tui::Theme;
+use crate::tui::markdown::MarkdownRenderer;
 use crate::tui::widgets::{CommandPopupState, OutputLine, PopupState};
 
 /// Behavior of the Enter key in the text input area.
@@ -158,6 +169,202 @@ impl SummaryGeneration {
     }
 }
 
+/// What happens to a todo file after it has been executed successfully.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ArchiveMode {
+    /// Move completed todos to `.mcgravity/todo/done/` (default).
+    #[default]
+    MoveToDone,
+    /// Delete completed todos once their summary is recorded in task.md.
+    Delete,
+    /// Move completed todos to a custom directory.
+    ///
+    /// Relative paths are resolved against the project base directory.
+    MoveTo(PathBuf),
+}
+
+impl ArchiveMode {
+    /// Cycles to the next option.
+    ///
+    /// A custom directory can only be configured in `settings.json`, so
+    /// "Move To" is only offered when `archive_dir` is set.
+    #[must_use]
+    pub fn next(&self, archive_dir: Option<&Path>) -> Self {
+        match (self, archive_dir) {
+            (Self::MoveToDone, _) => Self::Delete,
+            (Self::Delete, Some(dir)) => Self::MoveTo(dir.to_path_buf()),
+            (Self::Delete, None) | (Self::MoveTo(_), _) => Self::MoveToDone,
+        }
+    }
+
+    /// Returns the display name for this option.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::MoveToDone => "Move to Done",
+            Self::Delete => "Delete",
+            Self::MoveTo(_) => "Move To",
+        }
+    }
+
+    /// Returns the directory completed todos are moved to, or `None` when
+    /// they are deleted.
+    #[must_use]
+    pub fn target_dir(&self, paths: &McgravityPaths) -> Option<PathBuf> {
+        match self {
+            Self::MoveToDone => Some(paths.done_dir()),
+            Self::Delete => None,
+            Self::MoveTo(dir) => Some(paths.base().join(dir)),
+        }
+    }
+}
+
+/// Which phases a flow runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlowMode {
+    /// Plan, then execute the resulting todos (default).
+    #[default]
+    Full,
+    /// Run planning once and stop without executing todos.
+    PlanOnly,
+    /// Skip planning and execute the existing todo files.
+    ExecuteOnly,
+}
+
+impl FlowMode {
+    /// Cycles to the next option.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Full => Self::PlanOnly,
+            Self::PlanOnly => Self::ExecuteOnly,
+            Self::ExecuteOnly => Self::Full,
+        }
+    }
+
+    /// Returns the display name for this option.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Full => "Full",
+            Self::PlanOnly => "Plan Only",
+            Self::ExecuteOnly => "Execute Only",
+        }
+    }
+
+    /// Returns whether the planning phase runs.
+    #[must_use]
+    pub const fn runs_planning(self) -> bool {
+        !matches!(self, Self::ExecuteOnly)
+    }
+
+    /// Returns whether todos are executed.
+    #[must_use]
+    pub const fn runs_execution(self) -> bool {
+        !matches!(self, Self::PlanOnly)
+    }
+}
+
+/// Color theme of the interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorTheme {
+    /// Colors for dark terminal backgrounds (default).
+    #[default]
+    Dark,
+    /// Colors for light terminal backgrounds.
+    Light,
+    /// Bright colors only.
+    HighContrast,
+}
+
+impl ColorTheme {
+    /// Cycles to the next option.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Dark => Self::Light,
+            Self::Light => Self::HighContrast,
+            Self::HighContrast => Self::Dark,
+        }
+    }
+
+    /// Returns the display name for this option.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Dark => "Dark",
+            Self::Light => "Light",
+            Self::HighContrast => "High Contrast",
+        }
+    }
+
+    /// Returns the theme's colors.
+    #[must_use]
+    pub fn theme(self) -> Theme {
+        match self {
+            Self::Dark => Theme::default(),
+            Self::Light => Theme::light(),
+            Self::HighContrast => Theme::high_contrast(),
+        }
+    }
+}
+
+/// How the planner names todo files, which also decides execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TodoNaming {
+    /// Zero-padded numbers (`task-001.md`), executed in numeric order (default).
+    #[default]
+    Sequential,
+    /// Timestamp and topic (`20260117-143000-add-login.md`), executed in
+    /// timestamp order.
+    Timestamp,
+}
+
+impl TodoNaming {
+    /// Returns the name stored in settings.json.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Sequential => "Sequential",
+            Self::Timestamp => "Timestamp",
+        }
+    }
+}
+
+/// How much executor output the panel shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Verbosity {
+    /// Only phase changes, warnings, errors and important executor lines.
+    Quiet,
+    /// All executor output, with stderr subject to `suppress_stderr` (default).
+    #[default]
+    Normal,
+    /// All executor output, including stderr hidden by `suppress_stderr`.
+    Verbose,
+}
+
+impl Verbosity {
+    /// Cycles to the next option.
+    #[must_use]
+    pub const fn next(self) -> Self {
+        match self {
+            Self::Quiet => Self::Normal,
+            Self::Normal => Self::Verbose,
+            Self::Verbose => Self::Quiet,
+        }
+    }
+
+    /// Returns the display name for this option, also stored in settings.json.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Quiet => "Quiet",
+            Self::Normal => "Normal",
+            Self::Verbose => "Verbose",
+        }
+    }
+}
+
 /// Identifiers for settings items.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SettingsItem {
@@ -171,6 +378,14 @@ pub enum SettingsItem {
     MaxIterations,
     /// Summary generation strategy.
     SummaryGeneration,
+    /// What happens to completed todo files.
+    ArchiveMode,
+    /// Whether starting a new session clears the task text.
+    ResetClearsTask,
+    /// Which phases a flow runs.
+    FlowMode,
+    /// Color theme of the interface.
+    Theme,
 }
 
 impl SettingsItem {
@@ -183,6 +398,10 @@ impl SettingsItem {
             SettingsItem::EnterBehavior,
             SettingsItem::MaxIterations,
             SettingsItem::SummaryGeneration,
+            SettingsItem::ArchiveMode,
+            SettingsItem::ResetClearsTask,
+            SettingsItem::FlowMode,
+            SettingsItem::Theme,
         ]
     }
 
@@ -195,6 +414,10 @@ impl SettingsItem {
             Self::EnterBehavior => "Enter Key",
             Self::MaxIterations => "Max Iterations",
             Self::SummaryGeneration => "Summary Mode",
+            Self::ArchiveMode => "Completed Todos",
+            Self::ResetClearsTask => "New Session",
+            Self::FlowMode => "Flow Mode",
+            Self::Theme => "Theme",
         }
     }
 
@@ -207,6 +430,10 @@ impl SettingsItem {
             Self::EnterBehavior => "Behavior of the Enter key (Submit vs Newline)",
             Self::MaxIterations => "Maximum cycles before stopping",
             Self::SummaryGeneration => "How task summaries are generated (Inline vs Model)",
+            Self::ArchiveMode => "Move completed todo files to the done folder or delete them",
+            Self::ResetClearsTask => "Whether a new session clears or keeps the task text",
+            Self::FlowMode => "Run planning and execution, or only one of them",
+            Self::Theme => "Colors used by the interface",
         }
     }
 }
@@ -221,16 +448,48 @@ pub enum FlowEvent {
     PhaseChanged(FlowPhase),
     /// Output line added (includes both CLI output and system messages).
     Output(OutputLine),
+    /// CLI output line still waiting for its newline, added like `Output`.
+    PartialOutput(OutputLine),
+    /// Replaces the most recent `PartialOutput` line, even when other lines
+    /// were added after it.
+    ///
+    /// Used for in-place updates of partial CLI output lines, such as
+    /// progress indicators that redraw themselves with `\r`.
+    ReplaceLastOutput(OutputLine),
+    /// A new orchestration cycle started (1-based cycle number).
+    CycleStarted(u32),
+    /// A wrapped prompt of the given size is about to be sent to a model.
+    PromptSent(PromptKind, PromptSize),
     /// Todo files list updated.
     TodoFilesUpdated(Vec<PathBuf>),
+    /// The planner created a new todo file (reported while planning runs).
+    TodoFileCreated(PathBuf),
+    /// Todos of the current batch finished so far, sent when the batch starts
+    /// and after each todo.
+    Progress(TodoProgress),
+    /// A todo of the current batch was completed (pinned todos excluded).
+    TodoCompleted,
     /// Current file being processed.
     CurrentFile(Option<String>),
+    /// Summary of the todo that runs after the current one (`None` when the
+    /// current todo is the last of the batch).
+    UpNext(Option<String>),
     /// Retry wait countdown.
     RetryWait(Option<u64>),
     /// Clear output buffer.
     ClearOutput,
     /// Flow completed.
     Done,
+    /// Matches found so far by a file search that is still running.
+    ///
+    /// Batches are unsorted and arrive before the final
+    /// [`FlowEvent::SearchResult`], which replaces them.
+    SearchPartial {
+        /// The generation of the search request (for cancellation).
+        generation: u64,
+        /// Matches found since the previous batch.
+        matches: Vec<FileMatch>,
+    },
     /// File search result received from background task.
     SearchResult {
         /// The generation of the search request (for cancellation).
@@ -243,6 +502,42 @@ pub enum FlowEvent {
     /// Emitted after successful `task.md` persistence so the read-only Task Text
     /// panel stays synchronized with the on-disk state.
     TaskTextUpdated(String),
+    /// A completed todo was summarized into `<COMPLETED_TASKS>` and archived.
+    TaskSummarized(SummarizedTask),
+    /// A `/resummarize` run finished (successfully or not).
+    ResummarizeDone,
+    /// The commit checked out when the flow started, looked up in the
+    /// background and kept as the base for `/diff`.
+    FlowStartCommit(Option<String>),
+    /// Output of a `/diff` run on a background thread, shown as is.
+    DiffOutput(Vec<OutputLine>),
+    /// A `/preview` prompt built on a background thread, or `None` when
+    /// there was no task to preview.
+    PromptPreview(PromptKind, Option<String>),
+    /// The environment checks of a `/doctor` run on a background thread.
+    DoctorChecks(Vec<DoctorCheck>),
+    /// Where a model's CLI resolves, looked up on a background thread for
+    /// `/which`.
+    CliResolution(Model, CommandResolution),
+}
+
+/// The most recently summarized todo, kept so `/resummarize` can regenerate
+/// its `<COMPLETED_TASKS>` entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SummarizedTask {
+    /// Archived location of the completed todo file.
+    pub file: PathBuf,
+    /// The summary entry recorded for it (e.g. `"- Added login form"`).
+    pub entry: String,
+}
+
+/// A flow phase boundary in the output panel, used by `/goto`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseMarker {
+    /// Index into the output buffer of the phase's separator line.
+    pub line: usize,
+    /// Description of the phase that started here.
+    pub label: String,
 }
 
 /// Query sent to the background file search task.
@@ -254,6 +549,10 @@ pub struct SearchQuery {
     pub working_dir: PathBuf,
     /// Generation counter for debouncing/cancellation.
     pub generation: u64,
+    /// Set once the query is superseded, to cut its walk short.
+    pub cancelled: Arc<AtomicBool>,
+    /// Ignore globs and hidden-file handling for the walk.
+    pub options: SearchOptions,
 }
 
 /// Application mode.
@@ -280,6 +579,40 @@ pub enum AppMode {
     /// Initial setup modal for first-run model selection.
     /// Displayed when no `.mcgravity/settings.json` exists.
     InitialSetup,
+    /// Confirmation modal shown before starting a flow with unlimited
+    /// iterations.
+    ConfirmStart,
+    /// Scrollable modal showing a wrapped prompt (`/preview`).
+    PromptPreview,
+    /// Scrollable modal showing the live task text (`/task`).
+    TaskView,
+    /// Modal for picking the planning and execution models (`/models`).
+    ModelPicker,
+}
+
+/// Region of the chat view that receives unmodified keys.
+///
+/// Tab toggles between the two. With the input focused, typing edits the
+/// task and scrolling needs Ctrl; with the output focused, arrows and
+/// Home/End scroll the output and typing is ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Focus {
+    /// The text input area.
+    #[default]
+    Input,
+    /// The output panel.
+    Output,
+}
+
+impl Focus {
+    /// Returns the other region.
+    #[must_use]
+    pub const fn toggle(self) -> Self {
+        match self {
+            Self::Input => Self::Output,
+            Self::Output => Self::Input,
+        }
+    }
 }
 
 /// Information about an `@` token being typed.
@@ -328,9 +661,14 @@ pub struct TextInputState {
     pub(crate) search_tx: mpsc::Sender<SearchQuery>,
     /// Current search generation (incremented for each new search).
     pub(crate) search_generation: u64,
+    /// Cancel flag of the most recent search query.
+    pub(crate) search_cancel: Arc<AtomicBool>,
 
     /// Rapid input detector for paste fallback when bracketed paste mode is unavailable.
     pub(crate) rapid_input: RapidInputDetector,
+    /// Whether bracketed paste mode is enabled. Without it, rapid key
+    /// sequences are treated as pastes so their Enter keys insert newlines.
+    pub(crate) bracketed_paste: bool,
 
     // === Autosave State ===
     /// Timestamp of the last text edit (for autosave debouncing).
@@ -343,6 +681,13 @@ pub struct TextInputState {
     pub command_popup_state: CommandPopupState,
     /// Current slash token being typed (if any).
     pub slash_token: Option<SlashToken>,
+
+    // === History State ===
+    /// Previously submitted inputs, recalled with Up/Down.
+    pub history: InputHistory,
+    /// Files inserted from the `@` popup since the input was last cleared,
+    /// which lose their `@` on insertion but are still attachable.
+    pub selected_files: Vec<PathBuf>,
 }
 
 impl TextInputState {
@@ -359,13 +704,18 @@ impl TextInputState {
             last_search_time: None,
             search_tx,
             search_generation: 0,
+            search_cancel: Arc::default(),
             rapid_input: RapidInputDetector::new(),
+            bracketed_paste: false,
             // Autosave state
             last_edit_time: None,
             is_dirty: false,
             // Slash command state
             command_popup_state: CommandPopupState::default(),
             slash_token: None,
+            // History state
+            history: InputHistory::default(),
+            selected_files: Vec::new(),
         }
     }
 
@@ -389,6 +739,7 @@ impl TextInputState {
         self.is_dirty = false;
         self.command_popup_state = CommandPopupState::default();
         self.slash_token = None;
+        self.selected_files.clear();
     }
 
     /// Returns the lines of text from the textarea.
@@ -410,6 +761,29 @@ impl TextInputState {
         self.textarea.lines().join("\n")
     }
 
+    /// Replaces the textarea content with `text`, placing the cursor at the end.
+    pub fn set_text(&mut self, text: &str) {
+        let mut textarea = TextArea::new(text.split('\n').map(str::to_string).collect());
+        textarea.set_placeholder_text("Type / for commands or describe a task...");
+        textarea.move_cursor(tui_textarea::CursorMove::Bottom);
+        textarea.move_cursor(tui_textarea::CursorMove::End);
+        self.textarea = textarea;
+    }
+
+    /// Returns true if Up should recall history instead of moving the cursor:
+    /// the input is empty or the cursor is on the first line.
+    #[must_use]
+    pub fn at_history_top(&self) -> bool {
+        self.cursor().0 == 0
+    }
+
+    /// Returns true if Down should recall history instead of moving the cursor:
+    /// the cursor is on the last line.
+    #[must_use]
+    pub fn at_history_bottom(&self) -> bool {
+        self.cursor().0 + 1 >= self.lines().len()
+    }
+
     /// Sets the textarea content from a list of lines (for testing).
     ///
     /// Creates a new `TextArea` with the given lines and replaces the current one.
@@ -422,12 +796,38 @@ impl TextInputState {
     }
 }
 
+/// Default autosave debounce time in milliseconds (saves after 1 second of inactivity).
+pub const DEFAULT_AUTOSAVE_DEBOUNCE_MS: u64 = 1000;
+
+/// Minimum allowed autosave debounce, to avoid thrashing the disk while typing.
+pub const MIN_AUTOSAVE_DEBOUNCE_MS: u64 = 200;
+
+/// Default byte budget for task.md before old completed-task summaries are trimmed.
+pub const DEFAULT_MAX_TASK_BYTES: usize = 64 * 1024;
+
+/// Default maximum characters of a `<COMPLETED_TASKS>` summary entry.
+pub const DEFAULT_SUMMARY_MAX_LEN: usize = 500;
+
+/// Smallest allowed summary entry cap; shorter entries lose the task's gist.
+pub const MIN_SUMMARY_MAX_LEN: usize = 40;
+
+/// Largest allowed summary entry cap, so a single entry cannot crowd out the
+/// rest of the task.md budget.
+pub const MAX_SUMMARY_MAX_LEN: usize = 4000;
+
+/// Default seconds without executor output before a "still working" notice.
+pub const DEFAULT_IDLE_NOTICE_SECS: u64 = 30;
+
+/// Minimum allowed task.md byte budget.
+pub const MIN_MAX_TASK_BYTES: usize = 1024;
+
 /// State for the settings panel.
 ///
 /// Contains fields for navigating and selecting models and other settings.
 /// Following the `OpenAI` Codex pattern, settings are displayed as a list
 /// of items with checkboxes/toggles.
 #[derive(Debug, Clone)]
+#[allow(clippy::struct_excessive_bools)] // Independent on/off settings, not a state machine
 pub struct SettingsState {
     /// Currently selected index in the settings list.
     pub selected_index: usize,
@@ -441,6 +841,108 @@ pub struct SettingsState {
     pub max_iterations: MaxIterations,
     /// Summary generation strategy.
     pub summary_generation: SummaryGeneration,
+    /// Milliseconds of inactivity before task text is autosaved.
+    pub autosave_debounce_ms: u64,
+    /// Byte budget for task.md; the oldest completed-task summaries are dropped
+    /// when it is exceeded.
+    pub max_task_bytes: usize,
+    /// What happens to todo files after successful execution.
+    pub archive_mode: ArchiveMode,
+    /// Custom directory configured in `settings.json` for "Move To", kept
+    /// while another archive mode is selected.
+    pub archive_dir: Option<PathBuf>,
+    /// Which phases a flow runs.
+    pub flow_mode: FlowMode,
+    /// Color theme of the interface.
+    pub color_theme: ColorTheme,
+    /// Shell command run at the start of each cycle, before planning.
+    pub pre_cycle_hook: Option<String>,
+    /// Shell command run once when the flow completes successfully.
+    pub post_run_hook: Option<String>,
+    /// Whether to show a desktop notification when a flow completes or fails.
+    pub desktop_notifications: bool,
+    /// Whether to ring the terminal bell when a flow completes or fails.
+    pub bell_on_complete: bool,
+    /// Whether starting a flow with unlimited iterations asks for confirmation.
+    pub confirm_unlimited: bool,
+    /// Whether output lines are prefixed with an `HH:MM:SS` timestamp.
+    pub show_timestamps: bool,
+    /// Whether executor stderr lines are hidden from the output panel.
+    pub suppress_stderr: bool,
+    /// Substrings that keep a stderr line visible while `suppress_stderr` is on.
+    pub stderr_keep_patterns: Vec<String>,
+    /// How much executor output the panel shows (toggled live with Ctrl+O).
+    pub verbosity: Verbosity,
+    /// Seconds a model call may go without output before a "still working"
+    /// notice is shown (0 disables the notice).
+    pub idle_notice_secs: u64,
+    /// Extra globs excluded from `@` file search.
+    pub ignore_globs: Vec<String>,
+    /// Whether `@` file search includes hidden files and directories.
+    pub search_hidden: bool,
+    /// How many directory levels `@` file search descends (`None` for no limit).
+    pub search_max_depth: Option<usize>,
+    /// Whether starting a new session after completion clears the task text.
+    ///
+    /// When `false`, the original task (without its `<COMPLETED_TASKS>` block)
+    /// is kept in task.md and restored into the input for refinement.
+    pub reset_clears_task: bool,
+    /// Whether new output only scrolls the output panel while it is pinned to
+    /// the bottom. When `false`, new output always jumps to the bottom.
+    pub sticky_scroll: bool,
+    /// Whether the first cycle migrates legacy done files into the
+    /// `<COMPLETED_TASKS>` block.
+    pub migrate_done_files: bool,
+    /// Whether the flow pauses after each cycle until `/next` or `/stop`.
+    pub step_mode: bool,
+    /// Maximum characters of each `<COMPLETED_TASKS>` summary entry.
+    pub summary_max_len: usize,
+    /// Whether consecutive identical output lines collapse into one line
+    /// with a `(×N)` count.
+    pub collapse_repeated_lines: bool,
+    /// How the planner names todo files and the order they run in.
+    pub todo_naming: TodoNaming,
+    /// Model used for task summaries (`None` uses the execution model).
+    pub summary_model: Option<Model>,
+    /// Wall-clock seconds after which a flow stops, regardless of cycles
+    /// (`None` for no limit).
+    pub max_runtime_secs: Option<u64>,
+    /// Wall-clock seconds a single todo may take, across retries and its
+    /// summary, before it is skipped (`None` for no limit).
+    pub per_todo_timeout_secs: Option<u64>,
+    /// Whether the output captured for each completed todo's summary is saved
+    /// to `.mcgravity/logs/<todo>.out`.
+    pub save_captured_output: bool,
+    /// Whether model output is styled as markdown in the output panel.
+    pub render_markdown: bool,
+    /// Directories searched by `@` file search and scanned for guideline
+    /// files in addition to the project directory.
+    pub extra_roots: Vec<PathBuf>,
+    /// Binary run for the Codex model instead of `codex` from `PATH`.
+    pub codex_path: Option<PathBuf>,
+    /// Binary run for the Claude model instead of `claude` from `PATH`.
+    pub claude_path: Option<PathBuf>,
+    /// Binary run for the Gemini model instead of `gemini` from `PATH`.
+    pub gemini_path: Option<PathBuf>,
+    /// Whether each cycle's CLI output is also saved to
+    /// `.mcgravity/logs/cycle-NNN.log`.
+    pub log_cycles: bool,
+    /// Whether the first todo that fails after retries stops the whole flow.
+    pub fail_fast: bool,
+    /// Whether the flow pauses for `/next` before executing todos that look
+    /// like prompt-injection attempts.
+    pub confirm_suspicious_todos: bool,
+    /// Whether the planning prompt includes recent commits and the
+    /// uncommitted diff stat.
+    pub include_git_context: bool,
+    /// Whether files mentioned with `@` are attached to a submitted task.
+    pub attach_mentioned_files: bool,
+    /// Whether startup opens the settings panel when a configured model's
+    /// CLI is not installed.
+    pub open_settings_on_missing_cli: bool,
+    /// Capacity of the channel carrying executor output to the output panel
+    /// (see [`crate::core::channels`] for the tradeoffs).
+    pub output_buffer_size: usize,
     /// Previous mode to return to when closing settings.
     pub previous_mode: Option<AppMode>,
     /// Cached availability status for all AI CLI tools.
@@ -457,6 +959,48 @@ impl Default for SettingsState {
             enter_behavior: EnterBehavior::default(),
             max_iterations: MaxIterations::default(),
             summary_generation: SummaryGeneration::default(),
+            autosave_debounce_ms: DEFAULT_AUTOSAVE_DEBOUNCE_MS,
+            max_task_bytes: DEFAULT_MAX_TASK_BYTES,
+            archive_mode: ArchiveMode::default(),
+            archive_dir: None,
+            flow_mode: FlowMode::default(),
+            color_theme: ColorTheme::default(),
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: false,
+            bell_on_complete: false,
+            confirm_unlimited: true,
+            show_timestamps: false,
+            suppress_stderr: false,
+            stderr_keep_patterns: Vec::new(),
+            verbosity: Verbosity::Normal,
+            idle_notice_secs: DEFAULT_IDLE_NOTICE_SECS,
+            ignore_globs: Vec::new(),
+            search_hidden: true,
+            search_max_depth: None,
+            reset_clears_task: true,
+            sticky_scroll: true,
+            migrate_done_files: true,
+            step_mode: false,
+            summary_max_len: DEFAULT_SUMMARY_MAX_LEN,
+            collapse_repeated_lines: false,
+            todo_naming: TodoNaming::Sequential,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: false,
+            render_markdown: false,
+            extra_roots: Vec::new(),
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: false,
+            fail_fast: false,
+            confirm_suspicious_todos: false,
+            include_git_context: false,
+            attach_mentioned_files: false,
+            open_settings_on_missing_cli: true,
+            output_buffer_size: EXECUTOR_OUTPUT_CHANNEL_SIZE,
             previous_mode: None,
             model_availability: ModelAvailability::check_all(),
         }
@@ -464,6 +1008,56 @@ impl Default for SettingsState {
 }
 
 impl SettingsState {
+    /// Returns the model that generates task summaries: the summary model
+    /// override if set, otherwise the execution model.
+    #[must_use]
+    pub fn effective_summary_model(&self) -> Model {
+        self.summary_model.unwrap_or(self.execution_model)
+    }
+
+    /// Returns the settings a flow started now runs with.
+    #[must_use]
+    pub fn flow_options(&self) -> FlowOptions {
+        FlowOptions {
+            use_model_summary: self.summary_generation.uses_model_fallback(),
+            max_task_bytes: self.max_task_bytes,
+            archive_mode: self.archive_mode.clone(),
+            pre_cycle_hook: self.pre_cycle_hook.clone(),
+            post_run_hook: self.post_run_hook.clone(),
+            stderr_filter: StderrFilter::new(
+                self.suppress_stderr,
+                self.stderr_keep_patterns.clone(),
+            ),
+            idle_notice: (self.idle_notice_secs > 0)
+                .then(|| Duration::from_secs(self.idle_notice_secs)),
+            output_buffer: self.output_buffer_size,
+            migrate_done_files: self.migrate_done_files,
+            flow_mode: self.flow_mode,
+            step_mode: self.step_mode,
+            summary_max_len: self.summary_max_len,
+            todo_naming: self.todo_naming,
+            max_runtime: self.max_runtime_secs.map(Duration::from_secs),
+            log_cycles: self.log_cycles,
+            fail_fast: self.fail_fast,
+            confirm_suspicious_todos: self.confirm_suspicious_todos,
+            include_git_context: self.include_git_context,
+            todo_budget: self.per_todo_timeout_secs.map(Duration::from_secs),
+            save_captured_output: self.save_captured_output,
+            extra_roots: self.extra_roots.clone(),
+            attached_files: Vec::new(),
+        }
+    }
+
+    /// Returns the display name for the new-session behavior.
+    #[must_use]
+    pub const fn reset_clears_task_name(&self) -> &'static str {
+        if self.reset_clears_task {
+            "Clear Task"
+        } else {
+            "Keep Task"
+        }
+    }
+
     /// Returns whether the given model's CLI tool is available on the system.
     #[must_use]
     pub fn is_model_available(&self, model: Model) -> bool {
@@ -473,6 +1067,85 @@ impl SettingsState {
             Model::Gemini => self.model_availability.gemini,
         }
     }
+
+    /// Returns the binary path override configured for `model`'s CLI.
+    #[must_use]
+    pub fn cli_path(&self, model: Model) -> Option<&PathBuf> {
+        match model {
+            Model::Codex => self.codex_path.as_ref(),
+            Model::Claude => self.claude_path.as_ref(),
+            Model::Gemini => self.gemini_path.as_ref(),
+        }
+    }
+
+    /// Resolves `model`'s CLI command, honoring its binary path override.
+    #[must_use]
+    pub fn resolve_cli(&self, model: Model) -> CommandResolution {
+        resolve_cli_command(model.command(), self.cli_path(model).map(PathBuf::as_path))
+    }
+
+    /// Creates an executor for `model` that runs its binary path override.
+    #[must_use]
+    pub fn executor(&self, model: Model) -> Box<dyn AiCliExecutor> {
+        model.executor(self.cli_path(model).cloned())
+    }
+
+    /// Re-checks every model's CLI with the current binary path overrides,
+    /// so setting or clearing an override updates its availability.
+    pub fn refresh_model_availability(&mut self) {
+        self.model_availability = ModelAvailability {
+            codex: self.resolve_cli(Model::Codex).is_available(),
+            claude: self.resolve_cli(Model::Claude).is_available(),
+            gemini: self.resolve_cli(Model::Gemini).is_available(),
+        };
+    }
+
+    /// Returns the configured models (planning, execution, and summary
+    /// override) whose CLI tool is not available, without duplicates.
+    #[must_use]
+    pub fn unavailable_models(&self) -> Vec<Model> {
+        let configured = [
+            Some(self.planning_model),
+            Some(self.execution_model),
+            self.summary_model,
+        ];
+        let mut unavailable = Vec::new();
+        for model in configured.into_iter().flatten() {
+            if !self.is_model_available(model) && !unavailable.contains(&model) {
+                unavailable.push(model);
+            }
+        }
+        unavailable
+    }
+}
+
+/// A row of the model picker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelPickerEntry {
+    /// The model shown in this row.
+    pub model: Model,
+    /// Whether the model's CLI tool is available (and so can be picked).
+    pub available: bool,
+    /// Whether the model is the configured planning model.
+    pub planning: bool,
+    /// Whether the model is the configured execution model.
+    pub execution: bool,
+}
+
+impl SettingsState {
+    /// Returns one model picker row per model, in [`Model::all`] order.
+    #[must_use]
+    pub fn model_picker_entries(&self) -> Vec<ModelPickerEntry> {
+        Model::all()
+            .iter()
+            .map(|&model| ModelPickerEntry {
+                model,
+                available: self.is_model_available(model),
+                planning: self.planning_model == model,
+                execution: self.execution_model == model,
+            })
+            .collect()
+    }
 }
 
 /// Fields available for selection in the initial setup modal.
@@ -545,6 +1218,57 @@ pub struct InitialSetupState {
     pub execution_model: Model,
 }
 
+/// State of the `/preview` prompt modal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptPreview {
+    /// Phase whose prompt is shown.
+    pub kind: PromptKind,
+    /// The fully wrapped prompt.
+    pub text: String,
+    /// First visible line.
+    pub scroll: u16,
+}
+
+impl PromptPreview {
+    /// Creates a preview scrolled to the top.
+    #[must_use]
+    pub const fn new(kind: PromptKind, text: String) -> Self {
+        Self {
+            kind,
+            text,
+            scroll: 0,
+        }
+    }
+
+    /// Returns the largest scroll offset, which keeps the last line visible.
+    #[must_use]
+    pub fn max_scroll(&self) -> u16 {
+        max_text_scroll(&self.text)
+    }
+
+    /// Scrolls by `delta` lines, clamped to the prompt.
+    pub fn scroll_by(&mut self, delta: i32) {
+        self.scroll = scroll_text(self.scroll, delta, &self.text);
+    }
+}
+
+/// Returns the largest scroll offset of a text modal showing `text`, which
+/// keeps its last line visible.
+#[must_use]
+pub fn max_text_scroll(text: &str) -> u16 {
+    u16::try_from(text.lines().count().saturating_sub(1)).unwrap_or(u16::MAX)
+}
+
+/// Returns the scroll offset `scroll` moved by `delta` lines, clamped to a
+/// text modal showing `text`.
+#[must_use]
+pub fn scroll_text(scroll: u16, delta: i32, text: &str) -> u16 {
+    let scroll = i32::from(scroll)
+        .saturating_add(delta)
+        .clamp(0, i32::from(max_text_scroll(text)));
+    u16::try_from(scroll).unwrap_or(0)
+}
+
 /// State for flow execution UI.
 ///
 /// Contains fields for output display, scrolling, and flow progress.
@@ -559,8 +1283,43 @@ pub struct FlowUiState {
     pub output_truncated: bool,
     /// Current file being processed.
     pub current_file: Option<String>,
+    /// Finished todos of the current cycle's batch (`None` before execution).
+    pub todo_progress: Option<TodoProgress>,
+    /// Todos done this session and still pending (`None` before the first
+    /// todo scan).
+    pub todo_counts: Option<TodoCounts>,
+    /// "Up next" text shown beside the progress bar while a todo runs.
+    pub up_next: Option<String>,
     /// Retry wait countdown in seconds.
     pub(crate) retry_wait: Option<u64>,
+    /// Sizes of the prompts sent during the current (or last) flow.
+    pub prompt_stats: PromptStats,
+    /// The last todo summarized into `<COMPLETED_TASKS>`, if any.
+    pub last_summary: Option<SummarizedTask>,
+    /// Phase boundaries in `output`, oldest first.
+    pub phase_markers: Vec<PhaseMarker>,
+    /// Current frame of the running spinner, advanced on each tick.
+    pub spinner_frame: usize,
+    /// The most recent error of the current (or last) flow, for `/copyerror`.
+    pub last_error: Option<String>,
+    /// Indices of error and warning lines in `output`, oldest first.
+    pub error_lines: Vec<usize>,
+    /// Position in `error_lines` of the last line jumped to with `n`/`N`.
+    pub error_cursor: Option<usize>,
+    /// Original text and count of the last output line while identical lines
+    /// are being collapsed into it.
+    pub(crate) last_line_repeat: Option<(String, u32)>,
+    /// Index in `output` of the line selected for copying while the output
+    /// is focused.
+    pub selected_line: Option<usize>,
+    /// Markdown state for model output when `render_markdown` is on.
+    pub(crate) markdown: MarkdownRenderer,
+    /// `markdown` before the last output line, for re-rendering that line
+    /// when a partial line is replaced.
+    pub(crate) markdown_before_last: MarkdownRenderer,
+    /// Index in `output` of the most recent partial CLI line, which
+    /// `ReplaceLastOutput` updates in place.
+    pub(crate) partial_line: Option<usize>,
 }
 
 impl Default for FlowUiState {
@@ -570,11 +1329,43 @@ impl Default for FlowUiState {
             output_scroll: ScrollState::new(),
             output_truncated: false,
             current_file: None,
+            todo_progress: None,
+            todo_counts: None,
+            up_next: None,
             retry_wait: None,
+            prompt_stats: PromptStats::default(),
+            last_summary: None,
+            phase_markers: Vec::new(),
+            spinner_frame: 0,
+            last_error: None,
+            error_lines: Vec::new(),
+            error_cursor: None,
+            last_line_repeat: None,
+            selected_line: None,
+            markdown: MarkdownRenderer::default(),
+            markdown_before_last: MarkdownRenderer::default(),
+            partial_line: None,
         }
     }
 }
 
+impl FlowUiState {
+    /// Clears the output buffer along with its scroll state, phase markers,
+    /// error index, line selection and markdown state.
+    pub fn clear_output(&mut self) {
+        self.output.clear();
+        self.output_scroll.reset();
+        self.output_truncated = false;
+        self.phase_markers.clear();
+        self.error_lines.clear();
+        self.error_cursor = None;
+        self.selected_line = None;
+        self.partial_line = None;
+        self.markdown = MarkdownRenderer::default();
+        self.markdown_before_last = MarkdownRenderer::default();
+    }
+}
+
 /// Dynamic layout tracking state.
 ///
 /// Stores the full [`ChatLayout`] calculated once per frame.
@@ -619,8 +1410,9 @@ impl LayoutState {
 pub struct ScrollState {
     /// Current scroll offset (number of lines/visual lines from top).
     pub offset: usize,
-    /// Whether to auto-scroll to bottom when new content is added.
-    /// Set to false when user manually scrolls up, true when they scroll to bottom.
+    /// Whether the view is pinned to the bottom, so new content scrolls it.
+    /// Set to false when user manually scrolls up, true when they scroll back
+    /// to the bottom.
     pub auto_scroll: bool,
 }
 
@@ -678,6 +1470,13 @@ impl ScrollState {
         self.auto_scroll = true;
     }
 
+    /// Re-pins the view if `offset` is at (or past) the bottom.
+    pub fn repin_if_at_bottom(&mut self, content_len: usize, visible_height: usize) {
+        if self.offset >= content_len.saturating_sub(visible_height) {
+            self.auto_scroll = true;
+        }
+    }
+
     /// Auto-scrolls to the bottom if auto-scroll is enabled.
     pub fn auto_scroll_if_enabled(&mut self, content_len: usize, visible_height: usize) {
         if self.auto_scroll {
@@ -703,12 +1502,16 @@ mod settings_state_tests {
     #[test]
     fn settings_item_all_returns_expected_items() {
         let items = SettingsItem::all();
-        assert_eq!(items.len(), 5);
+        assert_eq!(items.len(), 9);
         assert_eq!(items[0], SettingsItem::PlanningModel);
         assert_eq!(items[1], SettingsItem::ExecutionModel);
         assert_eq!(items[2], SettingsItem::EnterBehavior);
         assert_eq!(items[3], SettingsItem::MaxIterations);
         assert_eq!(items[4], SettingsItem::SummaryGeneration);
+        assert_eq!(items[5], SettingsItem::ArchiveMode);
+        assert_eq!(items[6], SettingsItem::ResetClearsTask);
+        assert_eq!(items[7], SettingsItem::FlowMode);
+        assert_eq!(items[8], SettingsItem::Theme);
     }
 
     #[test]
@@ -888,6 +1691,58 @@ mod settings_state_tests {
         assert!(settings.is_model_available(Model::Claude));
         assert!(settings.is_model_available(Model::Gemini));
     }
+
+    #[test]
+    fn unavailable_models_lists_configured_models_once() {
+        let mut settings = SettingsState {
+            planning_model: Model::Claude,
+            execution_model: Model::Claude,
+            summary_model: Some(Model::Gemini),
+            model_availability: ModelAvailability {
+                codex: false,
+                claude: false,
+                gemini: true,
+            },
+            ..SettingsState::default()
+        };
+        assert_eq!(settings.unavailable_models(), vec![Model::Claude]);
+
+        settings.model_availability.gemini = false;
+        assert_eq!(
+            settings.unavailable_models(),
+            vec![Model::Claude, Model::Gemini]
+        );
+
+        settings.model_availability.claude = true;
+        settings.summary_model = None;
+        assert!(settings.unavailable_models().is_empty());
+    }
+
+    /// Tests that setting and clearing a binary path override both update
+    /// the model's availability.
+    #[cfg(unix)]
+    #[test]
+    fn refresh_model_availability_follows_path_overrides() -> anyhow::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::TempDir::new()?;
+        let stub = dir.path().join("gemini-stub");
+        std::fs::write(&stub, "#!/bin/sh\n")?;
+        std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))?;
+        let on_path = crate::core::check_cli_in_path("gemini");
+        let mut settings = SettingsState {
+            gemini_path: Some(stub),
+            ..SettingsState::default()
+        };
+
+        settings.refresh_model_availability();
+        assert!(settings.is_model_available(Model::Gemini));
+
+        settings.gemini_path = None;
+        settings.refresh_model_availability();
+        assert_eq!(settings.is_model_available(Model::Gemini), on_path);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -10,7 +10,7 @@
 use super::helpers::*;
 use crate::app::FILE_SEARCH_DEBOUNCE_MS;
 use crate::app::state::AtToken;
-use crate::file_search::FileMatch;
+use crate::file_search::{FileMatch, search_files_with_options};
 use crate::tui::widgets::PopupState;
 use anyhow::Result;
 use serial_test::serial;
@@ -58,6 +58,33 @@ mod file_search_integration_tests {
         Ok(())
     }
 
+    /// Test that the search runs in the configured project directory, not
+    /// the process working directory.
+    #[test]
+    fn test_search_uses_paths_base() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut app = create_test_app_with_paths(
+            &["@src"],
+            0,
+            4,
+            crate::fs::McgravityPaths::new(temp_dir.path()),
+        );
+        let (search_tx, mut search_rx) = tokio::sync::mpsc::channel(16);
+        app.text_input.search_tx = search_tx;
+
+        app.perform_file_search("src");
+
+        let query = search_rx.try_recv()?;
+        assert_eq!(query.working_dir, temp_dir.path());
+        assert_eq!(query.query, "src");
+        assert_eq!(query.options.max_depth, None);
+
+        app.settings.search_max_depth = Some(2);
+        app.perform_file_search("sr");
+        assert_eq!(search_rx.try_recv()?.options.max_depth, Some(2));
+        Ok(())
+    }
+
     #[test]
     fn test_popup_hides_when_at_removed() {
         let mut app = create_test_app_with_lines(&["hello"], 0, 5);
@@ -289,6 +316,103 @@ mod file_search_integration_tests {
         assert!(is_visible);
         Ok(())
     }
+
+    fn scored_match(path: &str, score: u32) -> FileMatch {
+        FileMatch {
+            path: PathBuf::from(path),
+            score,
+            is_dir: false,
+        }
+    }
+
+    /// Test that partial batches fill the popup before the search finishes.
+    #[tokio::test]
+    async fn test_partial_search_batches_accumulate_in_popup() -> Result<()> {
+        use crate::app::state::FlowEvent;
+
+        let mut app = create_test_app_with_lines(&["@mod"], 0, 4);
+        let (tx, rx) = tokio::sync::mpsc::channel(8);
+        app.event_rx = rx;
+        app.text_input.search_generation = 2;
+        app.text_input.file_popup_state = PopupState::Loading;
+
+        let batch = |range: std::ops::Range<u32>| -> Vec<FileMatch> {
+            range
+                .map(|i| scored_match(&format!("mod_{i:02}.rs"), i))
+                .collect()
+        };
+        tx.send(FlowEvent::SearchPartial {
+            generation: 2,
+            matches: batch(0..5),
+        })
+        .await?;
+        app.process_events();
+        let PopupState::Showing { matches, .. } = &app.text_input.file_popup_state else {
+            anyhow::bail!("first batch should show the popup");
+        };
+        assert_eq!(matches.len(), 5);
+
+        // A later batch merges in, best first and capped at 8
+        tx.send(FlowEvent::SearchPartial {
+            generation: 2,
+            matches: batch(5..20),
+        })
+        .await?;
+        // Batches of an older search are ignored
+        tx.send(FlowEvent::SearchPartial {
+            generation: 1,
+            matches: vec![scored_match("stale.rs", 999)],
+        })
+        .await?;
+        app.process_events();
+
+        let PopupState::Showing { matches, selected } = &app.text_input.file_popup_state else {
+            anyhow::bail!("popup should still show matches");
+        };
+        let scores: Vec<u32> = matches.iter().map(|m| m.score).collect();
+        assert_eq!(scores, vec![19, 18, 17, 16, 15, 14, 13, 12]);
+        assert_eq!(*selected, 0);
+        Ok(())
+    }
+
+    /// Test that clearing the `@` token cancels the in-flight search and
+    /// ignores whatever it still sends.
+    #[tokio::test]
+    async fn test_dismissing_popup_cancels_in_flight_search() -> Result<()> {
+        use crate::app::state::FlowEvent;
+        use std::sync::atomic::Ordering;
+
+        let mut app = create_test_app_with_lines(&["@mod"], 0, 4);
+        let (search_tx, mut search_rx) = tokio::sync::mpsc::channel(16);
+        app.text_input.search_tx = search_tx;
+        let (tx, rx) = tokio::sync::mpsc::channel(8);
+        app.event_rx = rx;
+
+        app.perform_file_search("mod");
+        let first = search_rx.try_recv()?;
+        // A newer query supersedes the first one
+        app.perform_file_search("mod_");
+        let second = search_rx.try_recv()?;
+        assert!(first.cancelled.load(Ordering::Relaxed));
+        assert!(!second.cancelled.load(Ordering::Relaxed));
+
+        app.dismiss_file_popup();
+        assert!(second.cancelled.load(Ordering::Relaxed));
+        assert_ne!(app.text_input.search_generation, second.generation);
+
+        // Results the cancelled search already sent are ignored
+        tx.send(FlowEvent::SearchPartial {
+            generation: second.generation,
+            matches: vec![scored_match("mod_01.rs", 10)],
+        })
+        .await?;
+        app.process_events();
+        assert!(matches!(
+            app.text_input.file_popup_state,
+            PopupState::Hidden
+        ));
+        Ok(())
+    }
 }
 
 mod file_selection_tests {
@@ -795,10 +919,85 @@ mod file_selection_tests {
 
         app.select_file_from_popup();
 
-        // Verify directory path has trailing slash
-        assert_eq!(app.text_input.lines()[0], "src/ ");
-        // Verify cursor is at end of inserted text (including slash and trailing space)
+        // The directory stays an @ token with a trailing slash, ready to drill into
+        assert_eq!(app.text_input.lines()[0], "@src/");
         assert_eq!(app.text_input.cursor().1, 5);
+        assert_eq!(app.current_at_query(), "src/");
+        assert!(matches!(
+            app.text_input.file_popup_state,
+            PopupState::Loading
+        ));
+    }
+
+    #[test]
+    fn test_directory_selection_searches_inside_it() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(
+            temp_dir.path(),
+            &["src/main.rs", "src/app/mod.rs", "README.md"],
+        )?;
+        let mut app = create_test_app_with_paths(
+            &["see @sr"],
+            0,
+            7,
+            crate::fs::McgravityPaths::new(temp_dir.path()),
+        );
+        let (search_tx, mut search_rx) = tokio::sync::mpsc::channel(16);
+        app.text_input.search_tx = search_tx;
+        app.text_input.at_token = Some(AtToken {
+            query: "sr".to_string(),
+            start_byte: 4,
+            end_byte: 7,
+            row: 0,
+        });
+        app.text_input.file_popup_state = PopupState::Showing {
+            matches: vec![FileMatch {
+                path: PathBuf::from("src"),
+                score: 100,
+                is_dir: true,
+            }],
+            selected: 0,
+        };
+
+        app.select_file_from_popup();
+
+        assert_eq!(app.text_input.lines()[0], "see @src/");
+        let query = search_rx.try_recv()?;
+        assert_eq!(query.query, "");
+        assert_eq!(query.options.scope, Some(PathBuf::from("src")));
+
+        // The follow-up search lists the directory's immediate children
+        let result = search_files_with_options(&query.query, &query.working_dir, &query.options);
+        let paths: Vec<_> = result.matches.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(
+            paths,
+            [PathBuf::from("src/app"), PathBuf::from("src/main.rs")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_directory_needing_quotes_is_inserted_like_a_file() {
+        let mut app = create_test_app_with_lines(&["@my"], 0, 3);
+        app.text_input.at_token = Some(AtToken {
+            query: "my".to_string(),
+            start_byte: 0,
+            end_byte: 3,
+            row: 0,
+        });
+        app.text_input.file_popup_state = PopupState::Showing {
+            matches: vec![FileMatch {
+                path: PathBuf::from("my docs"),
+                score: 100,
+                is_dir: true,
+            }],
+            selected: 0,
+        };
+
+        app.select_file_from_popup();
+
+        assert_eq!(app.text_input.lines()[0], "'my docs/' ");
+        assert!(!app.text_input.file_popup_state.is_visible());
     }
 
     #[test]
@@ -1231,3 +1430,177 @@ mod bounds_check_tests {
         assert_eq!(app.text_input.lines()[0], "test.rs ");
     }
 }
+
+mod attachment_tests {
+    use super::*;
+    use crate::app::state::{AppMode, MaxIterations};
+    use crate::core::{PromptKind, preview_prompt};
+    use crate::fs::McgravityPaths;
+
+    /// Creates an app in `dir` with attachments on, whose submissions stop
+    /// at the unlimited-run confirmation instead of starting a flow.
+    fn attaching_app(dir: &TempDir, line: &str) -> crate::app::App {
+        let mut app = create_test_app_with_paths(
+            &[line],
+            0,
+            line.chars().count(),
+            McgravityPaths::new(dir.path()),
+        );
+        app.settings.attach_mentioned_files = true;
+        app.settings.max_iterations = MaxIterations::Unlimited;
+        app.settings.confirm_unlimited = true;
+        app
+    }
+
+    #[tokio::test]
+    async fn submitted_mention_is_attached_to_the_planning_prompt() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n",
+        )?;
+        let mut app = attaching_app(&temp_dir, "Bump the version in @Cargo.toml");
+
+        app.submit_text_input();
+
+        assert_eq!(app.mode, AppMode::ConfirmStart);
+        assert_eq!(app.flow.attached_files, vec![PathBuf::from("Cargo.toml")]);
+        // The task text, which the flow persists, holds no file contents
+        assert_eq!(app.flow.input_text, "Bump the version in @Cargo.toml");
+        let prompt = planning_prompt(&app);
+        assert!(prompt.contains("<FILE path=\"Cargo.toml\">\n[package]\nname = \"demo\"\n</FILE>"));
+        // Each planning prompt reads the file again
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nversion = \"2\"\n",
+        )?;
+        assert!(planning_prompt(&app).contains("[package]\nversion = \"2\"\n</FILE>"));
+        assert!(
+            app.flow_ui
+                .output
+                .iter()
+                .any(|line| line.text.contains("Attached 1 file to the task"))
+        );
+        // task.md keeps the text as typed
+        assert_eq!(
+            std::fs::read_to_string(app.paths.task_file())?,
+            "Bump the version in @Cargo.toml"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn file_picked_from_the_popup_is_attached() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(temp_dir.path(), &["src/lib.rs"])?;
+        std::fs::write(temp_dir.path().join("src/lib.rs"), "pub fn demo() {}\n")?;
+        let mut app = attaching_app(&temp_dir, "Refactor @li");
+        app.text_input.at_token = Some(AtToken {
+            query: "li".to_string(),
+            start_byte: 9,
+            end_byte: 12,
+            row: 0,
+        });
+        app.text_input.file_popup_state = PopupState::Showing {
+            matches: vec![FileMatch {
+                path: PathBuf::from("src/lib.rs"),
+                score: 100,
+                is_dir: false,
+            }],
+            selected: 0,
+        };
+        app.select_file_from_popup();
+        assert_eq!(app.text_input.lines()[0], "Refactor src/lib.rs ");
+
+        app.submit_text_input();
+
+        assert_eq!(app.flow.attached_files, vec![PathBuf::from("src/lib.rs")]);
+        assert!(
+            planning_prompt(&app).contains("<FILE path=\"src/lib.rs\">\npub fn demo() {}\n</FILE>")
+        );
+        assert!(app.text_input.selected_files.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn file_deleted_after_selection_warns_and_still_submits() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(temp_dir.path(), &["src/old.rs"])?;
+        let mut app = attaching_app(&temp_dir, "Port @ol");
+        app.text_input.at_token = Some(AtToken {
+            query: "ol".to_string(),
+            start_byte: 5,
+            end_byte: 8,
+            row: 0,
+        });
+        app.text_input.file_popup_state = PopupState::Showing {
+            matches: vec![FileMatch {
+                path: PathBuf::from("src/old.rs"),
+                score: 100,
+                is_dir: false,
+            }],
+            selected: 0,
+        };
+        app.select_file_from_popup();
+        std::fs::remove_file(temp_dir.path().join("src/old.rs"))?;
+
+        app.submit_text_input();
+
+        assert_eq!(app.mode, AppMode::ConfirmStart);
+        assert_eq!(app.flow.input_text, "Port src/old.rs ");
+        assert!(app.flow_ui.output.iter().any(|line| {
+            line.line_type == crate::tui::widgets::OutputLineType::SystemWarning
+                && line
+                    .text
+                    .contains("src/old.rs no longer exists; keeping its path as text")
+        }));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mentions_are_not_attached_when_the_setting_is_off() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        std::fs::write(temp_dir.path().join("Cargo.toml"), "[package]\n")?;
+        let mut app = attaching_app(&temp_dir, "Bump @Cargo.toml");
+        app.settings.attach_mentioned_files = false;
+
+        app.submit_text_input();
+
+        assert_eq!(app.flow.input_text, "Bump @Cargo.toml");
+        assert!(app.flow.attached_files.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn mentions_outside_the_workspace_are_not_attached() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let project = temp_dir.path().join("app");
+        std::fs::create_dir(&project)?;
+        std::fs::write(temp_dir.path().join("secret.txt"), "token")?;
+        let line = "Use @../secret.txt";
+        let mut app =
+            create_test_app_with_paths(&[line], 0, line.len(), McgravityPaths::new(&project));
+        app.settings.attach_mentioned_files = true;
+        app.settings.max_iterations = MaxIterations::Unlimited;
+
+        app.submit_text_input();
+
+        assert!(app.flow.attached_files.is_empty());
+        assert!(!planning_prompt(&app).contains("token"));
+        assert!(app.flow_ui.output.iter().any(|line| {
+            line.text
+                .contains("../secret.txt is outside the workspace; keeping its path as text")
+        }));
+        Ok(())
+    }
+
+    /// Returns the planning prompt for the submitted task.
+    fn planning_prompt(app: &crate::app::App) -> String {
+        preview_prompt(
+            PromptKind::Planning,
+            &app.flow.input_text,
+            app.paths.base(),
+            &app.flow_options(),
+        )
+    }
+}
@@ -79,3 +79,229 @@ fn shutdown_resets_after_cancel_without_receivers() {
         "shutdown flag should be false after reset, even when reset had no receivers"
     );
 }
+
+#[test]
+fn tick_advances_spinner_only_while_running() {
+    use crate::tui::widgets::SPINNER_FRAMES;
+
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.tick();
+    assert_eq!(app.flow_ui.spinner_frame, 0);
+
+    app.is_running = true;
+    app.tick();
+    assert_eq!(app.flow_ui.spinner_frame, 1);
+    app.tick();
+    assert_eq!(app.flow_ui.spinner_frame, 2);
+
+    // Wraps back to the first frame after the last one
+    for _ in 2..SPINNER_FRAMES.len() {
+        app.tick();
+    }
+    assert_eq!(app.flow_ui.spinner_frame, 0);
+}
+
+#[test]
+fn failed_flow_records_last_error() -> anyhow::Result<()> {
+    use crate::app::FlowEvent;
+    use crate::core::FlowPhase;
+    use crate::tui::widgets::OutputLine;
+
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.is_running = true;
+    let reason = "Codex failed after 3 attempts: exit code 1".to_string();
+
+    app.event_tx
+        .try_send(FlowEvent::Output(OutputLine::error("earlier error")))?;
+    app.event_tx
+        .try_send(FlowEvent::Output(OutputLine::stdout("more output")))?;
+    app.event_tx
+        .try_send(FlowEvent::PhaseChanged(FlowPhase::Failed {
+            reason: reason.clone(),
+        }))?;
+    app.event_tx.try_send(FlowEvent::Done)?;
+    app.process_events();
+
+    assert_eq!(app.flow_ui.last_error.as_deref(), Some(reason.as_str()));
+    Ok(())
+}
+
+#[test]
+fn error_output_line_becomes_last_error() {
+    use crate::tui::widgets::OutputLine;
+
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.push_output(OutputLine::error("Failed to archive todo"));
+    app.push_output(OutputLine::warning("not an error"));
+
+    assert_eq!(
+        app.flow_ui.last_error.as_deref(),
+        Some("Failed to archive todo")
+    );
+}
+
+#[test]
+fn todo_counts_track_done_and_pending_across_cycles() -> anyhow::Result<()> {
+    use crate::app::FlowEvent;
+    use std::path::PathBuf;
+
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    assert_eq!(app.flow_ui.todo_counts, None);
+
+    let todos = |count: usize| {
+        (1..=count)
+            .map(|i| PathBuf::from(format!("task-{i:03}.md")))
+            .collect::<Vec<_>>()
+    };
+    let label = |app: &crate::app::App| app.flow_ui.todo_counts.map(crate::core::TodoCounts::label);
+
+    app.event_tx.try_send(FlowEvent::CycleStarted(1))?;
+    app.event_tx
+        .try_send(FlowEvent::TodoFilesUpdated(todos(3)))?;
+    app.event_tx.try_send(FlowEvent::TodoCompleted)?;
+    app.process_events();
+    assert_eq!(label(&app).as_deref(), Some("1 done / 2 pending"));
+
+    app.event_tx.try_send(FlowEvent::TodoCompleted)?;
+    app.event_tx.try_send(FlowEvent::TodoCompleted)?;
+    app.process_events();
+    assert_eq!(label(&app).as_deref(), Some("3 done / 0 pending"));
+
+    // The next cycle's scan refreshes the pending count; done keeps counting
+    app.event_tx.try_send(FlowEvent::CycleStarted(2))?;
+    app.event_tx
+        .try_send(FlowEvent::TodoFilesUpdated(todos(2)))?;
+    app.process_events();
+    assert_eq!(label(&app).as_deref(), Some("3 done / 2 pending"));
+    Ok(())
+}
+
+#[test]
+fn model_output_is_styled_as_markdown_when_enabled() -> anyhow::Result<()> {
+    use crate::app::FlowEvent;
+    use crate::tui::widgets::OutputLine;
+
+    for render_markdown in [false, true] {
+        let mut app = create_test_app_with_lines(&[""], 0, 0);
+        (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+        app.settings.render_markdown = render_markdown;
+        for text in ["## Plan", "```", "- kept"] {
+            app.event_tx.try_send(FlowEvent::Output(
+                OutputLine::stdout(text).with_source("Codex"),
+            ))?;
+        }
+        app.event_tx.try_send(FlowEvent::PartialOutput(
+            OutputLine::stdout("`").with_source("Codex"),
+        ))?;
+        // Progress redraws of the open fence line do not close the fence
+        app.event_tx.try_send(FlowEvent::ReplaceLastOutput(
+            OutputLine::stdout("``` ").with_source("Codex"),
+        ))?;
+        app.event_tx.try_send(FlowEvent::Output(
+            OutputLine::stdout("- **item**").with_source("Codex"),
+        ))?;
+        // System lines are never styled
+        app.event_tx
+            .try_send(FlowEvent::Output(OutputLine::stdout("# note")))?;
+        app.process_events();
+
+        let texts: Vec<&str> = app.flow_ui.output.iter().map(|l| l.text.as_str()).collect();
+        if render_markdown {
+            assert_eq!(
+                texts,
+                vec!["Plan", "```", "- kept", "``` ", "• item", "# note"]
+            );
+            assert!(!app.flow_ui.output[0].styles.is_empty());
+        } else {
+            assert_eq!(
+                texts,
+                vec!["## Plan", "```", "- kept", "``` ", "- **item**", "# note"]
+            );
+            assert!(app.flow_ui.output.iter().all(|l| l.styles.is_empty()));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn partial_line_update_skips_lines_added_after_it() -> anyhow::Result<()> {
+    use crate::app::FlowEvent;
+    use crate::tui::widgets::OutputLine;
+
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.event_tx
+        .try_send(FlowEvent::PartialOutput(OutputLine::stdout("progress 10%")))?;
+    app.event_tx
+        .try_send(FlowEvent::Output(OutputLine::warning("Still working...")))?;
+    app.event_tx
+        .try_send(FlowEvent::ReplaceLastOutput(OutputLine::stderr(
+            "failed at 20%",
+        )))?;
+    app.process_events();
+
+    let texts: Vec<&str> = app.flow_ui.output.iter().map(|l| l.text.as_str()).collect();
+    assert_eq!(texts, vec!["failed at 20%", "! Still working..."]);
+    assert_eq!(app.flow_ui.error_lines, vec![1]);
+    Ok(())
+}
+
+#[test]
+fn partial_line_is_not_collapsed_into_previous_line() -> anyhow::Result<()> {
+    use crate::app::FlowEvent;
+    use crate::tui::widgets::OutputLine;
+
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.settings.collapse_repeated_lines = true;
+    app.event_tx
+        .try_send(FlowEvent::Output(OutputLine::stdout("progress 0%")))?;
+    app.event_tx
+        .try_send(FlowEvent::PartialOutput(OutputLine::stdout("progress 0%")))?;
+    app.event_tx
+        .try_send(FlowEvent::ReplaceLastOutput(OutputLine::stdout(
+            "progress 50%",
+        )))?;
+    app.event_tx
+        .try_send(FlowEvent::Output(OutputLine::stdout("progress 50%")))?;
+    app.process_events();
+
+    let texts: Vec<&str> = app.flow_ui.output.iter().map(|l| l.text.as_str()).collect();
+    assert_eq!(texts, vec!["progress 0%", "progress 50%", "progress 50%"]);
+    Ok(())
+}
+
+#[test]
+fn repeated_output_lines_collapse_with_count() {
+    use crate::tui::widgets::OutputLine;
+
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.settings.collapse_repeated_lines = true;
+    app.push_output(OutputLine::stdout("start"));
+    for _ in 0..5 {
+        app.push_output(OutputLine::stderr("⠋ Thinking..."));
+    }
+    // Same text with a different line type is not a repeat
+    app.push_output(OutputLine::stdout("⠋ Thinking..."));
+    app.push_output(OutputLine::stdout("done"));
+    app.push_output(OutputLine::stdout("done"));
+
+    let texts: Vec<&str> = app.flow_ui.output.iter().map(|l| l.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        ["start", "⠋ Thinking... (×5)", "⠋ Thinking...", "done (×2)"]
+    );
+}
+
+#[test]
+fn repeated_output_lines_kept_when_collapsing_is_off() {
+    use crate::tui::widgets::OutputLine;
+
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    for _ in 0..3 {
+        app.push_output(OutputLine::stdout("tick"));
+    }
+    assert_eq!(app.flow_ui.output.len(), 3);
+}
@@ -8,7 +8,7 @@
 //! - Key event helpers (`char_key`, `enter_key`)
 
 use crate::app::input::RapidInputDetector;
-use crate::app::{App, AppMode, FlowUiState, LayoutState, SettingsState, TextInputState};
+use crate::app::{App, AppMode, FlowUiState, Focus, LayoutState, SettingsState, TextInputState};
 use crate::fs::McgravityPaths;
 use crate::tui::widgets::PopupState;
 use anyhow::Result;
@@ -129,11 +129,15 @@ pub fn create_test_app_with_paths(
         flow: crate::core::FlowState::new_without_file(),
         theme: crate::tui::Theme::default(),
         mode: AppMode::Chat,
+        focus: Focus::Input,
         should_quit: false,
         is_running: false,
         event_rx: tokio::sync::mpsc::channel(1).1,
         event_tx: tokio::sync::mpsc::channel(1).0,
         shutdown_tx: tokio::sync::watch::channel(false).0,
+        max_iterations_tx: tokio::sync::watch::channel(None).0,
+        verbosity_tx: tokio::sync::watch::channel(crate::app::state::Verbosity::default()).0,
+        step_tx: None,
         text_input: TextInputState {
             textarea,
             at_token: None,
@@ -142,19 +146,30 @@ pub fn create_test_app_with_paths(
             last_search_time: None,
             search_tx,
             search_generation: 0,
+            search_cancel: std::sync::Arc::default(),
             rapid_input: RapidInputDetector::new(),
+            bracketed_paste: false,
             // Autosave state
             last_edit_time: None,
             is_dirty: false,
             // Slash command state
             command_popup_state: crate::tui::widgets::CommandPopupState::default(),
             slash_token: None,
+            // History state
+            history: crate::app::history::InputHistory::default(),
+            selected_files: Vec::new(),
         },
         settings: SettingsState::default(),
         flow_ui: FlowUiState::default(),
         layout: LayoutState::default(),
         initial_setup: None,
         command_registry: crate::core::CommandRegistry::with_builtins(),
+        pending_edit: None,
+        flow_start_commit: None,
+        prompt_preview: None,
+        task_view_scroll: 0,
+        model_picker_index: 0,
+        log_tail: None,
     };
 
     app.settings.model_availability = crate::core::ModelAvailability {
@@ -182,6 +197,23 @@ pub fn create_test_files(dir: &std::path::Path, files: &[&str]) -> Result<()> {
     Ok(())
 }
 
+/// Waits for the next event sent from background work and processes it.
+///
+/// The app must have a connected event channel, e.g. one created with
+/// `tokio::sync::mpsc::channel` in the test.
+///
+/// # Errors
+///
+/// Returns an error if no event arrives within five seconds.
+pub async fn process_next_event(app: &mut App) -> Result<()> {
+    let event = tokio::time::timeout(std::time::Duration::from_secs(5), app.event_rx.recv())
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("event channel closed"))?;
+    app.event_tx.send(event).await?;
+    app.process_events();
+    Ok(())
+}
+
 /// Renders the app to a `TestBackend` terminal for `assert_buffer_lines` assertions.
 ///
 /// This function mimics the main loop behavior by calling `update_layout()`
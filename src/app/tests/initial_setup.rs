@@ -9,7 +9,7 @@
 //! - Subsequent runs loading saved settings correctly
 
 use super::helpers::*;
-use crate::app::state::{AppMode, InitialSetupField};
+use crate::app::state::{AppMode, InitialSetupField, MaxIterations};
 use crate::core::Model;
 use crate::fs::{McgravityPaths, PersistedSettings};
 use anyhow::Result;
@@ -68,7 +68,7 @@ mod first_run_detection_tests {
         std::env::set_current_dir(temp_dir.path())?;
 
         // No settings file - should trigger first run
-        let app = crate::app::App::new(None)?;
+        let app = crate::app::App::new(Vec::new())?;
 
         assert_eq!(
             app.mode,
@@ -98,11 +98,61 @@ mod first_run_detection_tests {
             enter_behavior: "Submit".to_string(),
             max_iterations: "5".to_string(),
             summary_generation: String::new(),
+            autosave_debounce_ms: None,
+            max_task_bytes: None,
+            archive_mode: None,
+            archive_dir: None,
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: None,
+            bell_on_complete: None,
+            confirm_unlimited: None,
+            show_timestamps: None,
+            suppress_stderr: None,
+            stderr_keep_patterns: None,
+            idle_notice_secs: None,
+            ignore_globs: None,
+            search_hidden: None,
+            search_max_depth: None,
+            reset_clears_task: None,
+            sticky_scroll: None,
+            migrate_done_files: None,
+            flow_mode: None,
+            step_mode: None,
+            summary_max_len: None,
+            collapse_repeated_lines: None,
+            todo_naming: None,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: None,
+            render_markdown: None,
+            extra_roots: None,
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: None,
+            fail_fast: None,
+            confirm_suspicious_todos: None,
+            include_git_context: None,
+            attach_mentioned_files: None,
+            verbosity: None,
+            output_buffer_size: None,
+            open_settings_on_missing_cli: None,
+            theme: None,
         };
         paths.save_settings(&settings)?;
 
         // Create app - should start in Chat mode
-        let app = crate::app::App::new(None)?;
+        let app = crate::app::App::new_with_availability(
+            Vec::new(),
+            McgravityPaths::from_cwd(),
+            crate::core::ModelAvailability {
+                codex: true,
+                claude: true,
+                gemini: true,
+            },
+        )?;
 
         assert_eq!(
             app.mode,
@@ -141,11 +191,53 @@ mod subsequent_run_tests {
             enter_behavior: "Newline".to_string(),
             max_iterations: "10".to_string(),
             summary_generation: String::new(),
+            autosave_debounce_ms: None,
+            max_task_bytes: None,
+            archive_mode: None,
+            archive_dir: None,
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: None,
+            bell_on_complete: None,
+            confirm_unlimited: None,
+            show_timestamps: None,
+            suppress_stderr: None,
+            stderr_keep_patterns: None,
+            idle_notice_secs: None,
+            ignore_globs: None,
+            search_hidden: None,
+            search_max_depth: None,
+            reset_clears_task: None,
+            sticky_scroll: None,
+            migrate_done_files: None,
+            flow_mode: None,
+            step_mode: None,
+            summary_max_len: None,
+            collapse_repeated_lines: None,
+            todo_naming: None,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: None,
+            render_markdown: None,
+            extra_roots: None,
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: None,
+            fail_fast: None,
+            confirm_suspicious_todos: None,
+            include_git_context: None,
+            attach_mentioned_files: None,
+            verbosity: None,
+            output_buffer_size: None,
+            open_settings_on_missing_cli: None,
+            theme: None,
         };
         paths.save_settings(&settings)?;
 
         // Create app - should load saved settings
-        let app = crate::app::App::new(None)?;
+        let app = crate::app::App::new(Vec::new())?;
 
         assert_eq!(
             app.settings.planning_model,
@@ -181,11 +273,53 @@ mod subsequent_run_tests {
             enter_behavior: "unknown".to_string(),
             max_iterations: "999".to_string(),
             summary_generation: String::new(),
+            autosave_debounce_ms: None,
+            max_task_bytes: None,
+            archive_mode: None,
+            archive_dir: None,
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: None,
+            bell_on_complete: None,
+            confirm_unlimited: None,
+            show_timestamps: None,
+            suppress_stderr: None,
+            stderr_keep_patterns: None,
+            idle_notice_secs: None,
+            ignore_globs: None,
+            search_hidden: None,
+            search_max_depth: None,
+            reset_clears_task: None,
+            sticky_scroll: None,
+            migrate_done_files: None,
+            flow_mode: None,
+            step_mode: None,
+            summary_max_len: None,
+            collapse_repeated_lines: None,
+            todo_naming: None,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: None,
+            render_markdown: None,
+            extra_roots: None,
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: None,
+            fail_fast: None,
+            confirm_suspicious_todos: None,
+            include_git_context: None,
+            attach_mentioned_files: None,
+            verbosity: None,
+            output_buffer_size: None,
+            open_settings_on_missing_cli: None,
+            theme: None,
         };
         paths.save_settings(&settings)?;
 
         // Create app - should fall back to defaults for invalid values
-        let app = crate::app::App::new(None)?;
+        let app = crate::app::App::new(Vec::new())?;
 
         // Invalid values should fall back to defaults
         assert_eq!(
@@ -205,6 +339,70 @@ mod subsequent_run_tests {
         );
         Ok(())
     }
+
+    /// Tests that a settings file with invalid JSON is backed up and the app
+    /// falls back to defaults in the initial setup modal.
+    #[tokio::test]
+    #[serial]
+    async fn corrupt_settings_are_backed_up_and_setup_reopens() -> Result<()> {
+        let _guard = CwdGuard::new()?;
+        let temp_dir = TempDir::new()?;
+        std::env::set_current_dir(temp_dir.path())?;
+
+        let paths = McgravityPaths::new(temp_dir.path());
+        paths.ensure_mcgravity_dir()?;
+        std::fs::write(
+            paths.settings_file(),
+            "{ \"planning_model\": \"Claude Code\",",
+        )?;
+
+        let app = crate::app::App::new(Vec::new())?;
+
+        assert!(!paths.settings_file().exists());
+        assert_eq!(
+            std::fs::read_to_string(paths.settings_backup_file())?,
+            "{ \"planning_model\": \"Claude Code\","
+        );
+        assert_eq!(app.mode, AppMode::InitialSetup);
+        assert!(app.initial_setup.is_some());
+        assert_eq!(app.settings.planning_model, Model::Codex);
+        assert!(app.flow_ui.output.iter().any(|line| {
+            line.text.contains("could not be parsed") && line.text.contains("settings.json.bak")
+        }));
+        Ok(())
+    }
+
+    /// Tests that an input file does not start a flow over the setup modal,
+    /// including after corrupt settings reopened it, and that the flow is
+    /// requested once the models are confirmed.
+    #[tokio::test]
+    #[serial]
+    async fn input_file_flow_waits_for_initial_setup() -> Result<()> {
+        for corrupt_settings in [false, true] {
+            let _guard = CwdGuard::new()?;
+            let temp_dir = TempDir::new()?;
+            std::env::set_current_dir(temp_dir.path())?;
+            let paths = McgravityPaths::new(temp_dir.path());
+            if corrupt_settings {
+                paths.ensure_mcgravity_dir()?;
+                std::fs::write(paths.settings_file(), "{")?;
+            }
+            let task = temp_dir.path().join("task.md");
+            std::fs::write(&task, "Build the thing")?;
+
+            let mut app = crate::app::App::new(vec![task])?;
+
+            assert_eq!(app.mode, AppMode::InitialSetup, "{corrupt_settings}");
+            assert!(!app.is_running);
+
+            app.settings.open_settings_on_missing_cli = false;
+            app.settings.max_iterations = MaxIterations::Unlimited;
+            app.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE));
+
+            assert_eq!(app.mode, AppMode::ConfirmStart, "{corrupt_settings}");
+        }
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -222,7 +420,7 @@ mod initial_setup_navigation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -263,7 +461,7 @@ mod initial_setup_navigation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -307,7 +505,7 @@ mod initial_setup_navigation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -337,7 +535,7 @@ mod initial_setup_navigation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -372,7 +570,7 @@ mod initial_setup_navigation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -424,7 +622,7 @@ mod model_cycling_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -477,7 +675,7 @@ mod model_cycling_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -504,7 +702,7 @@ mod model_cycling_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -549,7 +747,7 @@ mod model_cycling_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -605,7 +803,7 @@ mod confirmation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -633,7 +831,7 @@ mod confirmation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         assert_eq!(app.mode, AppMode::InitialSetup);
 
         // Press lowercase 'c' to confirm
@@ -652,7 +850,7 @@ mod confirmation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         assert_eq!(app.mode, AppMode::InitialSetup);
 
         // Press Ctrl+C
@@ -677,7 +875,7 @@ mod confirmation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         assert_eq!(app.mode, AppMode::InitialSetup);
 
         // Press Esc
@@ -705,7 +903,7 @@ mod confirmation_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         assert_eq!(app.mode, AppMode::InitialSetup);
 
         // Change planning model to Claude
@@ -763,7 +961,7 @@ mod confirmation_tests {
 
         // First app instance: configure and confirm
         {
-            let mut app = crate::app::App::new(None)?;
+            let mut app = crate::app::App::new(Vec::new())?;
             assert_eq!(app.mode, AppMode::InitialSetup);
 
             // Change to Claude/Gemini
@@ -782,7 +980,15 @@ mod confirmation_tests {
 
         // Second app instance: should start in Chat mode with saved settings
         {
-            let app = crate::app::App::new(None)?;
+            let app = crate::app::App::new_with_availability(
+                Vec::new(),
+                McgravityPaths::from_cwd(),
+                crate::core::ModelAvailability {
+                    codex: true,
+                    claude: true,
+                    gemini: true,
+                },
+            )?;
             assert_eq!(
                 app.mode,
                 AppMode::Chat,
@@ -818,7 +1024,7 @@ mod render_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -856,7 +1062,7 @@ mod render_tests {
                     "│                                                                    │",
                     "│                                                                    │",
                     "└ \\+Enter for newline ───────────────────────────────────────────────┘",
-                    " [Enter] Submit  [Ctrl+S] Settings",
+                    " [Enter] Submit  [Ctrl+S] Settings  [Ctrl+T] Toggle Enter",
                 ],
             ));
         Ok(())
@@ -870,7 +1076,7 @@ mod render_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -911,7 +1117,7 @@ mod render_tests {
                     "│                                                                    │",
                     "│                                                                    │",
                     "└ \\+Enter for newline ───────────────────────────────────────────────┘",
-                    " [Enter] Submit  [Ctrl+S] Settings",
+                    " [Enter] Submit  [Ctrl+S] Settings  [Ctrl+T] Toggle Enter",
                 ],
             ));
         Ok(())
@@ -925,7 +1131,7 @@ mod render_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -972,7 +1178,7 @@ mod render_tests {
                     "│                                                                    │",
                     "│                                                                    │",
                     "└ \\+Enter for newline ───────────────────────────────────────────────┘",
-                    " [Enter] Submit  [Ctrl+S] Settings",
+                    " [Enter] Submit  [Ctrl+S] Settings  [Ctrl+T] Toggle Enter",
                 ],
             ));
         Ok(())
@@ -986,7 +1192,7 @@ mod render_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
 
         // Override model availability to simulate unavailable models
         // This tests that error messages appear for unavailable CLIs
@@ -1026,7 +1232,7 @@ mod render_tests {
                     "│                                                                    │",
                     "│                                                                    │",
                     "└ \\+Enter for newline ───────────────────────────────────────────────┘",
-                    " [Enter] Submit  [Ctrl+S] Settings",
+                    " [Enter] Submit  [Ctrl+S] Settings  [Ctrl+T] Toggle Enter",
                 ],
             ));
         Ok(())
@@ -1040,7 +1246,7 @@ mod render_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = crate::app::App::new(None)?;
+        let mut app = crate::app::App::new(Vec::new())?;
         app.settings.model_availability = crate::core::ModelAvailability {
             codex: true,
             claude: true,
@@ -1053,8 +1259,8 @@ mod render_tests {
             .assert_buffer_lines(styled_lines_from_buffer(
                 &terminal,
                 &[
-                    " McGravity [Codex/Codex]",
-                    "┌O┌ Initial Setup ───────────────────────────────────────┐─┐",
+                    "┌Output (waiting for input)────────────────────────────────┐",
+                    "│ ┌ Initial Setup ───────────────────────────────────────┐ │",
                     "│ │Welcome to McGravity                                  │ │",
                     "│ │Select your default AI CLI tools.                     │ │",
                     "│ │                                                      │ │",
@@ -1063,16 +1269,16 @@ mod render_tests {
                     "│ │  Execution Model   [Codex]                           │ │",
                     "│ │                                                      │ │",
                     "│ │                                                      │ │",
+                    "│ │                                                      │ │",
+                    "│ │[↑/↓] Navigate  [Enter] Change  [C] Confirm           │ │",
+                    "│ │                                                      │ │",
                     "└─│                                                      │─┘",
-                    " ·│[↑/↓] Navigate  [Enter] Change  [C] Confirm           │",
-                    "  │                                                      │",
+                    " ·│                                                      │",
                     "  │                                                      │",
                     "┌ │                                                      │─┐",
                     "│ │                                                      │ │",
-                    "│ │                                                      │ │",
-                    "│ │                                                      │ │",
                     "└ └──────────────────────────────────────────────────────┘─┘",
-                    " [Enter] Submit  [Ctrl+S] Settings",
+                    " McGravity [Codex/Codex] [Enter] Submit  [Ctrl+S] Settings",
                 ],
             ));
         Ok(())
@@ -7,7 +7,7 @@
 //! - Text input state management
 
 use super::helpers::*;
-use crate::app::state::AppMode;
+use crate::app::state::{AppMode, EnterBehavior, Verbosity};
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 
 // =============================================================================
@@ -865,6 +865,104 @@ mod key_binding_tests {
         assert_eq!(app.text_input.lines(), vec![""]);
     }
 
+    // =========================================================================
+    // Enter Behavior Tests
+    // Plain Enter follows the Enter behavior setting; modifiers override it
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_plain_enter_in_submit_mode_submits() {
+        let mut app = create_test_app_with_lines(&["task"], 0, 4);
+        app.settings.enter_behavior = EnterBehavior::Submit;
+
+        app.handle_key(enter_key(KeyModifiers::NONE));
+
+        assert_eq!(app.text_input.lines(), vec![""]);
+    }
+
+    #[test]
+    fn test_plain_enter_in_newline_mode_inserts_newline() {
+        let mut app = create_test_app_with_lines(&["task"], 0, 4);
+        app.settings.enter_behavior = EnterBehavior::Newline;
+
+        app.handle_key(enter_key(KeyModifiers::NONE));
+
+        assert_eq!(app.text_input.lines(), vec!["task", ""]);
+    }
+
+    #[test]
+    fn test_alt_enter_inserts_newline_in_both_modes() {
+        for behavior in [EnterBehavior::Submit, EnterBehavior::Newline] {
+            let mut app = create_test_app_with_lines(&["task"], 0, 4);
+            app.settings.enter_behavior = behavior;
+
+            app.handle_key(enter_key(KeyModifiers::ALT));
+
+            assert_eq!(app.text_input.lines(), vec!["task", ""], "{behavior:?}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ctrl_enter_submits_in_newline_mode() {
+        let mut app = create_test_app_with_lines(&["task"], 0, 4);
+        app.settings.enter_behavior = EnterBehavior::Newline;
+
+        app.handle_key(enter_key(KeyModifiers::CONTROL));
+
+        assert_eq!(app.text_input.lines(), vec![""]);
+    }
+
+    #[test]
+    fn test_alt_enter_with_command_popup_inserts_newline() {
+        let mut app = create_test_app_with_lines(&["/cl"], 0, 3);
+        app.update_slash_command_popup();
+
+        app.handle_key(enter_key(KeyModifiers::ALT));
+
+        assert_eq!(app.text_input.lines(), vec!["/cl", ""]);
+    }
+
+    #[test]
+    fn test_ctrl_t_toggles_enter_behavior_and_persists() -> anyhow::Result<()> {
+        let temp = tempfile::TempDir::new()?;
+        let paths = crate::fs::McgravityPaths::new(temp.path());
+        let mut app = create_test_app_with_paths(&["task"], 0, 4, paths.clone());
+        assert_eq!(app.settings.enter_behavior, EnterBehavior::Submit);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+        assert_eq!(app.settings.enter_behavior, EnterBehavior::Newline);
+        assert_eq!(paths.load_settings()?.enter_behavior, "Newline");
+        // The input text is left untouched
+        assert_eq!(app.text_input.lines(), vec!["task"]);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+        assert_eq!(app.settings.enter_behavior, EnterBehavior::Submit);
+        assert_eq!(paths.load_settings()?.enter_behavior, "Submit");
+        Ok(())
+    }
+
+    #[test]
+    fn test_ctrl_o_cycles_verbosity_live_and_persists() -> anyhow::Result<()> {
+        let temp = tempfile::TempDir::new()?;
+        let paths = crate::fs::McgravityPaths::new(temp.path());
+        let mut app = create_test_app_with_paths(&["task"], 0, 4, paths.clone());
+        let verbosity = app.verbosity_receiver();
+        assert_eq!(*verbosity.borrow(), Verbosity::Normal);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+        assert_eq!(app.settings.verbosity, Verbosity::Verbose);
+        // A running flow sees the change right away
+        assert_eq!(*verbosity.borrow(), Verbosity::Verbose);
+        assert_eq!(paths.load_settings()?.verbosity.as_deref(), Some("Verbose"));
+        assert_eq!(app.text_input.lines(), vec!["task"]);
+
+        app.handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+        assert_eq!(*verbosity.borrow(), Verbosity::Quiet);
+        let last = app.flow_ui.output.last().map(|line| line.text.as_str());
+        assert_eq!(last, Some("  Verbosity: Quiet"));
+        Ok(())
+    }
+
     // =========================================================================
     // Multi-line Task Tests
     // =========================================================================
@@ -1273,4 +1371,228 @@ mod rapid_input_detection_tests {
         // But a newline should be inserted
         assert_eq!(app.text_input.lines().len(), 2);
     }
+
+    /// Without bracketed paste, keys typed in a rapid burst assemble a
+    /// multi-line paste instead of submitting at the embedded Enter.
+    #[tokio::test]
+    async fn rapid_keys_assemble_paste_without_bracketed_paste() {
+        let mut app = create_test_app_with_lines(&[""], 0, 0);
+        app.set_terminal_modes(false, true);
+
+        for key in [char_key('a'), char_key('b'), char_key('c')] {
+            app.handle_key(key);
+        }
+        app.handle_key(enter_key(KeyModifiers::NONE));
+        for key in [char_key('d'), char_key('e')] {
+            app.handle_key(key);
+        }
+
+        assert_eq!(app.text_input.lines(), vec!["abc", "de"]);
+        assert!(!app.is_running);
+    }
+
+    /// With bracketed paste, pastes arrive as paste events, so Enter submits
+    /// even right after fast typing, with or without keyboard enhancement.
+    #[tokio::test]
+    async fn rapid_keys_submit_with_bracketed_paste() {
+        let mut app = create_test_app_with_lines(&[""], 0, 0);
+        app.set_terminal_modes(true, false);
+
+        for key in [char_key('a'), char_key('b'), char_key('c')] {
+            app.handle_key(key);
+        }
+        app.handle_key(enter_key(KeyModifiers::NONE));
+
+        assert_eq!(app.text_input.lines(), vec![""]);
+        assert!(app.is_running);
+    }
+
+    /// Clearing the session keeps the detected bracketed paste support, so
+    /// Enter still submits after `/clear`.
+    #[tokio::test]
+    async fn clear_keeps_bracketed_paste() -> anyhow::Result<()> {
+        let temp = tempfile::TempDir::new()?;
+        let paths = crate::fs::McgravityPaths::new(temp.path());
+        let mut app = create_test_app_with_paths(&["/clear"], 0, 6, paths);
+        app.set_terminal_modes(true, true);
+
+        assert!(app.try_execute_slash_command());
+        for key in [char_key('a'), char_key('b'), char_key('c')] {
+            app.handle_key(key);
+        }
+        app.handle_key(enter_key(KeyModifiers::NONE));
+
+        assert_eq!(app.text_input.lines(), vec![""]);
+        assert!(app.is_running);
+        Ok(())
+    }
+
+    /// Only missing terminal modes are reported at startup.
+    #[test]
+    fn only_missing_terminal_modes_are_reported() {
+        let mut app = create_test_app_with_lines(&[""], 0, 0);
+        app.set_terminal_modes(true, true);
+        assert!(app.flow_ui.output.is_empty());
+
+        app.set_terminal_modes(true, false);
+        assert_eq!(app.flow_ui.output.len(), 1);
+        assert!(app.flow_ui.output[0].text.contains("Ctrl+J"));
+
+        app.set_terminal_modes(false, true);
+        assert_eq!(app.flow_ui.output.len(), 2);
+        assert!(app.flow_ui.output[1].text.contains("rapid typing"));
+    }
+}
+
+// =============================================================================
+// Input History Tests - Up/Down recall of previous submissions
+// =============================================================================
+
+mod history_recall_tests {
+    use super::*;
+    use crate::app::history::InputHistory;
+    use crate::fs::McgravityPaths;
+    use tempfile::TempDir;
+
+    fn arrow_key(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn app_with_history(lines: &[&str], row: usize, col: usize) -> crate::app::App {
+        let mut app = create_test_app_with_lines(lines, row, col);
+        app.text_input.history =
+            InputHistory::with_entries(["first task".to_string(), "second task".to_string()], 10);
+        app
+    }
+
+    #[test]
+    fn up_on_empty_input_recalls_newest_then_older() {
+        let mut app = app_with_history(&[""], 0, 0);
+
+        app.handle_key(arrow_key(KeyCode::Up));
+        assert_eq!(app.text_input.collect_text(), "second task");
+
+        app.handle_key(arrow_key(KeyCode::Up));
+        assert_eq!(app.text_input.collect_text(), "first task");
+
+        // Already at the oldest entry: the input stays as is
+        app.handle_key(arrow_key(KeyCode::Up));
+        assert_eq!(app.text_input.collect_text(), "first task");
+    }
+
+    #[test]
+    fn down_past_newest_restores_draft() {
+        let mut app = app_with_history(&["draft"], 0, 5);
+
+        app.handle_key(arrow_key(KeyCode::Up));
+        assert_eq!(app.text_input.collect_text(), "second task");
+
+        app.handle_key(arrow_key(KeyCode::Down));
+        assert_eq!(app.text_input.collect_text(), "draft");
+        assert!(!app.text_input.history.is_browsing());
+    }
+
+    #[test]
+    fn up_below_first_line_moves_cursor_instead_of_recalling() {
+        let mut app = app_with_history(&["line one", "line two"], 1, 3);
+
+        app.handle_key(arrow_key(KeyCode::Up));
+
+        assert_eq!(app.text_input.collect_text(), "line one\nline two");
+        assert_eq!(app.text_input.cursor().0, 0);
+        assert!(!app.text_input.history.is_browsing());
+    }
+
+    #[test]
+    fn down_above_last_line_moves_cursor_instead_of_recalling() {
+        let mut app = app_with_history(&["line one", "line two"], 0, 3);
+        app.text_input.history.recall_prev("");
+
+        app.handle_key(arrow_key(KeyCode::Down));
+
+        assert_eq!(app.text_input.collect_text(), "line one\nline two");
+        assert_eq!(app.text_input.cursor().0, 1);
+    }
+
+    #[test]
+    fn up_on_first_line_of_multiline_input_recalls() {
+        let mut app = app_with_history(&["line one", "line two"], 0, 2);
+
+        app.handle_key(arrow_key(KeyCode::Up));
+
+        assert_eq!(app.text_input.collect_text(), "second task");
+    }
+
+    #[test]
+    fn recalled_multiline_entry_is_navigable_before_recalling_further() {
+        let mut app = create_test_app_with_lines(&[""], 0, 0);
+        app.text_input.history =
+            InputHistory::with_entries(["older".to_string(), "multi\nline".to_string()], 10);
+
+        app.handle_key(arrow_key(KeyCode::Up));
+        assert_eq!(app.text_input.collect_text(), "multi\nline");
+        assert_eq!(app.text_input.cursor().0, 1);
+
+        // Cursor is on the last line of the recalled entry, so Up moves it
+        app.handle_key(arrow_key(KeyCode::Up));
+        assert_eq!(app.text_input.collect_text(), "multi\nline");
+        assert_eq!(app.text_input.cursor().0, 0);
+
+        app.handle_key(arrow_key(KeyCode::Up));
+        assert_eq!(app.text_input.collect_text(), "older");
+    }
+
+    #[test]
+    fn up_without_history_leaves_input_unchanged() {
+        let mut app = create_test_app_with_lines(&["text"], 0, 4);
+
+        app.handle_key(arrow_key(KeyCode::Up));
+
+        assert_eq!(app.text_input.collect_text(), "text");
+    }
+
+    #[test]
+    fn submitted_command_is_recorded_and_persisted() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let paths = McgravityPaths::new(temp.path());
+        let mut app = create_test_app_with_paths(&["/stats"], 0, 6, paths.clone());
+
+        app.handle_key(enter_key(KeyModifiers::NONE));
+
+        assert_eq!(
+            app.text_input.history.entries().collect::<Vec<_>>(),
+            ["/stats"]
+        );
+        assert_eq!(
+            crate::fs::history::read_history(&paths.history_file()),
+            ["/stats"]
+        );
+
+        app.handle_key(arrow_key(KeyCode::Up));
+        assert_eq!(app.text_input.collect_text(), "/stats");
+        Ok(())
+    }
+    #[test]
+    fn history_survives_clear() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let paths = McgravityPaths::new(temp.path());
+        let mut app = create_test_app_with_paths(&["/clear"], 0, 6, paths);
+        app.text_input.history = InputHistory::with_entries(["first task".to_string()], 10);
+
+        app.handle_key(enter_key(KeyModifiers::NONE));
+        assert_eq!(app.text_input.collect_text(), "");
+
+        assert_eq!(
+            app.text_input.history.entries().collect::<Vec<_>>(),
+            ["first task", "/clear"]
+        );
+        app.handle_key(arrow_key(KeyCode::Up));
+        assert_eq!(app.text_input.collect_text(), "/clear");
+        Ok(())
+    }
 }
@@ -5,11 +5,13 @@
 //! - Multi-step user workflow tests (typing, pasting, submitting)
 
 use super::helpers::*;
-use crate::app::state::{AppMode, AtToken};
+use crate::app::App;
+use crate::app::state::{AppMode, AtToken, FlowEvent, MaxIterations};
+use crate::core::{FileRef, Model};
 use crate::file_search::FileMatch;
 use crate::fs::TASK_FILE;
-use crate::tui::widgets::PopupState;
-use anyhow::Result;
+use crate::tui::widgets::{OutputLine, OutputLineType, PopupState};
+use anyhow::{Context, Result};
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyEventState, KeyModifiers};
 use serial_test::serial;
 use std::fs;
@@ -38,7 +40,7 @@ async fn test_finished_mode_on_no_todo_files() -> Result<()> {
     let temp_dir = TempDir::new()?;
     std::env::set_current_dir(temp_dir.path())?;
 
-    let mut app = crate::app::App::new(None)?;
+    let mut app = crate::app::App::new(Vec::new())?;
 
     // Set the phase to NoTodoFiles (as if planning completed with no new tasks)
     app.flow.phase = crate::core::FlowPhase::NoTodoFiles;
@@ -70,7 +72,7 @@ async fn test_finished_mode_on_completed_phase() -> Result<()> {
     let temp_dir = TempDir::new()?;
     std::env::set_current_dir(temp_dir.path())?;
 
-    let mut app = crate::app::App::new(None)?;
+    let mut app = crate::app::App::new(Vec::new())?;
 
     // Set the phase to Completed
     app.flow.phase = crate::core::FlowPhase::Completed;
@@ -121,7 +123,7 @@ async fn test_finished_mode_enter_starts_new_session() -> Result<()> {
     std::env::set_current_dir(temp_dir.path())?;
 
     // Create app with some state
-    let mut app = crate::app::App::new(None)?;
+    let mut app = crate::app::App::new(Vec::new())?;
 
     // Set up task.md
     app.text_input
@@ -194,7 +196,7 @@ async fn test_reset_session_clears_all_state() -> Result<()> {
     let temp_dir = TempDir::new()?;
     std::env::set_current_dir(temp_dir.path())?;
 
-    let mut app = crate::app::App::new(None)?;
+    let mut app = crate::app::App::new(Vec::new())?;
 
     // Set up various state
     app.text_input
@@ -263,7 +265,7 @@ async fn test_complete_flow_without_verification_integration() -> Result<()> {
     std::env::set_current_dir(temp_dir.path())?;
 
     // Step 1: Create app and set task text
-    let mut app = crate::app::App::new(None)?;
+    let mut app = crate::app::App::new(Vec::new())?;
     app.text_input
         .set_lines(vec!["Implement feature X".to_string()]);
     app.save_current_task()?;
@@ -775,7 +777,7 @@ async fn test_process_events_task_text_updated_only_updates_flow_input() -> Resu
     let temp_dir = TempDir::new()?;
     std::env::set_current_dir(temp_dir.path())?;
 
-    let mut app = crate::app::App::new(None)?;
+    let mut app = crate::app::App::new(Vec::new())?;
 
     // Set up editable textarea with user content
     app.text_input
@@ -812,6 +814,32 @@ async fn test_process_events_task_text_updated_only_updates_flow_input() -> Resu
     Ok(())
 }
 
+/// Test that `TodoFileCreated` events show the new todo file in the output.
+#[tokio::test]
+#[serial]
+async fn test_process_events_todo_file_created_reports_file_name() -> Result<()> {
+    let _guard = CwdGuard::new()?;
+    let temp_dir = TempDir::new()?;
+    std::env::set_current_dir(temp_dir.path())?;
+
+    let mut app = crate::app::App::new(Vec::new())?;
+    app.event_tx
+        .send(crate::app::state::FlowEvent::TodoFileCreated(
+            std::path::PathBuf::from(".mcgravity/todo/task-003.md"),
+        ))
+        .await?;
+
+    app.process_events();
+
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.trim() == "Created task-003.md")
+    );
+    Ok(())
+}
+
 /// Test that multiple `TaskTextUpdated` events accumulate correctly in `flow.input_text`.
 ///
 /// Each update should replace the previous value (last-writer-wins), never affecting
@@ -823,7 +851,7 @@ async fn test_process_events_multiple_task_text_updates() -> Result<()> {
     let temp_dir = TempDir::new()?;
     std::env::set_current_dir(temp_dir.path())?;
 
-    let mut app = crate::app::App::new(None)?;
+    let mut app = crate::app::App::new(Vec::new())?;
 
     // Set up editable textarea
     app.text_input
@@ -872,7 +900,7 @@ async fn test_process_events_task_text_updated_with_output_events() -> Result<()
     let temp_dir = TempDir::new()?;
     std::env::set_current_dir(temp_dir.path())?;
 
-    let mut app = crate::app::App::new(None)?;
+    let mut app = crate::app::App::new(Vec::new())?;
 
     app.text_input.set_lines(vec!["User editing".to_string()]);
     app.is_running = true;
@@ -935,7 +963,7 @@ async fn test_process_events_task_text_updated_preserves_long_completed_summary(
     let temp_dir = TempDir::new()?;
     std::env::set_current_dir(temp_dir.path())?;
 
-    let mut app = crate::app::App::new(None)?;
+    let mut app = crate::app::App::new(Vec::new())?;
 
     // Set up editable textarea with user content
     app.text_input
@@ -1001,3 +1029,717 @@ async fn integration_paste_then_manual_enter_submits() {
     assert_eq!(app.text_input.lines(), vec![""]);
     assert!(app.is_running);
 }
+
+/// A configured model whose CLI is missing opens Settings at startup instead
+/// of auto-starting the flow for the input file.
+#[tokio::test]
+async fn missing_model_cli_opens_settings_instead_of_starting() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let paths = crate::fs::McgravityPaths::new(temp_dir.path());
+    paths.save_settings(&crate::fs::PersistedSettings {
+        planning_model: Model::Claude.name().to_string(),
+        ..crate::fs::PersistedSettings::defaults()
+    })?;
+    let input = temp_dir.path().join("input.md");
+    fs::write(&input, "Build the thing")?;
+
+    let availability = crate::core::ModelAvailability {
+        codex: true,
+        claude: false,
+        gemini: true,
+    };
+    let app = App::new_with_availability(vec![input], paths, availability)?;
+
+    assert_eq!(app.mode, AppMode::Settings);
+    assert!(!app.is_running());
+    assert!(
+        app.flow_ui.output.iter().any(|line| {
+            line.line_type == OutputLineType::SystemWarning
+                && line.text.contains("Claude Code (claude)")
+        }),
+        "startup should name the missing CLI"
+    );
+    Ok(())
+}
+
+// =============================================================================
+// Orphaned Process Detection Tests
+// =============================================================================
+
+/// A still-running PID recorded by a previous session is reported at startup.
+#[cfg(unix)]
+#[tokio::test]
+async fn startup_warns_about_orphaned_processes() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let paths = crate::fs::McgravityPaths::new(temp_dir.path());
+    paths.ensure_mcgravity_dir()?;
+
+    // Owned by an instance that has since exited
+    let mut owner = std::process::Command::new("true").spawn()?;
+    owner.wait()?;
+    let mut orphan = std::process::Command::new("sleep").arg("30").spawn()?;
+    crate::fs::pids::register_pid(&paths.pids_file(), orphan.id(), owner.id())?;
+
+    let app = crate::app::App::new_with_paths(Vec::new(), paths);
+
+    let _ = orphan.kill();
+    let _ = orphan.wait();
+
+    let app = app?;
+    let warning = format!("PIDs: {}", orphan.id());
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.contains("orphaned") && line.text.contains(&warning)),
+        "startup output should warn about the orphaned PID"
+    );
+    Ok(())
+}
+
+/// `/reap` reports when there is nothing to terminate.
+#[tokio::test]
+async fn reap_command_without_orphans_reports_none() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut app = create_test_app_with_paths(
+        &["/reap"],
+        0,
+        5,
+        crate::fs::McgravityPaths::new(temp_dir.path()),
+    );
+
+    assert!(app.try_execute_slash_command());
+
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.contains("No orphaned CLI processes found"))
+    );
+    Ok(())
+}
+
+/// Creates an isolated app whose todo folder holds two pending files and one
+/// completed file.
+fn create_app_with_todo_files(temp_dir: &TempDir, input: &str) -> Result<App> {
+    let paths = crate::fs::McgravityPaths::new(temp_dir.path());
+    paths.ensure_todo_dirs()?;
+    std::fs::write(paths.todo_dir().join("task-001.md"), "first")?;
+    std::fs::write(paths.todo_dir().join("task-002.md"), "second")?;
+    std::fs::write(paths.done_dir().join("task-000.md"), "done")?;
+    let cursor_col = input.chars().count();
+    Ok(create_test_app_with_paths(&[input], 0, cursor_col, paths))
+}
+
+/// `/edit` without arguments lists numbered todo files without opening one.
+#[tokio::test]
+async fn edit_command_lists_todo_files() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut app = create_app_with_todo_files(&temp_dir, "/edit")?;
+
+    assert!(app.try_execute_slash_command());
+
+    let texts: Vec<&str> = app.flow_ui.output.iter().map(|l| l.text.as_str()).collect();
+    assert!(
+        texts
+            .iter()
+            .any(|t| t.contains("1.") && t.ends_with("task-001.md"))
+    );
+    assert!(
+        texts
+            .iter()
+            .any(|t| t.contains("2.") && t.ends_with("task-002.md"))
+    );
+    assert!(
+        texts
+            .iter()
+            .any(|t| t.contains("3.") && t.ends_with("task-000.md"))
+    );
+    assert!(app.take_pending_edit().is_none());
+    Ok(())
+}
+
+/// `/edit <n>` queues the numbered file for the main loop to open.
+#[tokio::test]
+async fn edit_command_with_number_queues_file() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut app = create_app_with_todo_files(&temp_dir, "/edit 2")?;
+
+    assert!(app.try_execute_slash_command());
+
+    assert_eq!(
+        app.take_pending_edit(),
+        Some(FileRef::new(app.paths.todo_dir().join("task-002.md")))
+    );
+    // The request is consumed once taken
+    assert!(app.take_pending_edit().is_none());
+    Ok(())
+}
+
+/// `/edit` with an out-of-range number warns instead of queueing a file.
+#[tokio::test]
+async fn edit_command_out_of_range_warns() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut app = create_app_with_todo_files(&temp_dir, "/edit 9")?;
+
+    assert!(app.try_execute_slash_command());
+
+    assert!(app.take_pending_edit().is_none());
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.contains("No todo file numbered 9"))
+    );
+    Ok(())
+}
+
+/// `/edit` with an argument that is not a file number warns.
+#[tokio::test]
+async fn edit_command_invalid_argument_warns() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut app = create_app_with_todo_files(&temp_dir, "/edit abc")?;
+
+    assert!(app.try_execute_slash_command());
+
+    assert!(app.take_pending_edit().is_none());
+    let line = app
+        .flow_ui
+        .output
+        .iter()
+        .find(|line| line.text.contains("Invalid file number"))
+        .context("no invalid argument message")?;
+    assert_eq!(line.line_type, OutputLineType::SystemWarning);
+    Ok(())
+}
+
+/// `/open` queues the last existing file mentioned in the output, with its line.
+#[tokio::test]
+async fn open_command_queues_last_mentioned_file() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    std::fs::create_dir_all(temp_dir.path().join("src/core"))?;
+    std::fs::write(temp_dir.path().join("src/core/flow.rs"), "fn main() {}")?;
+    std::fs::write(temp_dir.path().join("README.md"), "readme")?;
+    let mut app = create_app_with_todo_files(&temp_dir, "/open")?;
+    app.flow_ui
+        .output
+        .push(OutputLine::stdout("Updated README.md"));
+    app.flow_ui.output.push(OutputLine::stdout(
+        "Changed `src/core/flow.rs:42` and src/missing.rs",
+    ));
+    app.flow_ui.output.push(OutputLine::stdout("All done."));
+
+    assert!(app.try_execute_slash_command());
+
+    assert_eq!(
+        app.take_pending_edit(),
+        Some(FileRef {
+            path: temp_dir.path().join("src/core/flow.rs"),
+            line: Some(42),
+        })
+    );
+    Ok(())
+}
+
+/// `/open` with a path that does not exist warns instead of queueing it.
+#[tokio::test]
+async fn open_command_with_missing_path_warns() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut app = create_app_with_todo_files(&temp_dir, "/open src/missing.rs:3")?;
+
+    assert!(app.try_execute_slash_command());
+
+    assert!(app.take_pending_edit().is_none());
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.contains("No such file"))
+    );
+    Ok(())
+}
+
+/// `/edit` is rejected while the flow is running.
+#[tokio::test]
+async fn edit_command_blocked_while_running() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut app = create_app_with_todo_files(&temp_dir, "/edit 1")?;
+    app.is_running = true;
+
+    assert!(app.try_execute_slash_command());
+
+    assert!(app.take_pending_edit().is_none());
+    assert!(app.flow_ui.output.iter().any(|line| {
+        line.text
+            .contains("Cannot execute /edit while flow is running")
+    }));
+    Ok(())
+}
+
+/// `/cycles <n>` changes the cap seen by the running flow and the status line.
+#[tokio::test]
+async fn cycles_command_updates_running_cap() -> Result<()> {
+    let mut app = create_test_app_with_lines(&["/cycles 2"], 0, 9);
+    app.is_running = true;
+    let rx = app.max_iterations_receiver(Some(5));
+
+    assert!(app.try_execute_slash_command());
+
+    assert_eq!(*rx.borrow(), Some(2));
+    assert_eq!(app.effective_max_iterations(), Some(2));
+    Ok(())
+}
+
+/// `/cycles <n>` is rejected with a hint when no flow is running.
+#[tokio::test]
+async fn cycles_command_without_running_flow_warns() -> Result<()> {
+    let mut app = create_test_app_with_lines(&["/cycles 2"], 0, 9);
+
+    assert!(app.try_execute_slash_command());
+
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.contains("No flow is running"))
+    );
+    assert_eq!(
+        app.effective_max_iterations(),
+        app.settings.max_iterations.value()
+    );
+    Ok(())
+}
+
+/// `/next` releases a paused step-mode flow and warns when nothing is paused.
+#[tokio::test]
+async fn next_command_releases_paused_flow() -> Result<()> {
+    let mut app = create_test_app_with_lines(&["/next"], 0, 5);
+    app.is_running = true;
+    let (step_tx, mut step_rx) = tokio::sync::mpsc::channel(1);
+    app.step_tx = Some(step_tx);
+    app.flow.phase = crate::core::FlowPhase::Paused { iteration: 1 };
+
+    assert!(app.try_execute_slash_command());
+    assert_eq!(step_rx.try_recv().ok(), Some(crate::core::StepSignal::Next));
+
+    app.flow.phase = crate::core::FlowPhase::ReadingInput;
+    app.text_input.textarea.insert_str("/next");
+    assert!(app.try_execute_slash_command());
+    assert!(step_rx.try_recv().is_err());
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.contains("No flow is paused"))
+    );
+    Ok(())
+}
+
+/// A paused flow unlocks the input so `/next` can be typed, but not a new task.
+#[tokio::test]
+async fn paused_flow_accepts_typed_commands_only() -> Result<()> {
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.is_running = true;
+    let (step_tx, mut step_rx) = tokio::sync::mpsc::channel(1);
+    app.step_tx = Some(step_tx);
+    app.flow.phase = crate::core::FlowPhase::RunningPlanning {
+        model_name: std::borrow::Cow::Borrowed("Codex"),
+        attempt: 1,
+    };
+    assert!(app.input_locked());
+    app.flow.phase = crate::core::FlowPhase::Paused { iteration: 1 };
+    assert!(!app.input_locked());
+
+    for c in "another task".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    app.submit_text_input();
+    assert!(step_rx.try_recv().is_err());
+    assert_eq!(app.text_input.lines(), vec!["another task"]);
+
+    app.text_input.clear();
+    for c in "/next".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(step_rx.try_recv().ok(), Some(crate::core::StepSignal::Next));
+    Ok(())
+}
+
+/// `/cancel` typed during a run signals shutdown and shows the flow as cancelled.
+#[test]
+fn cancel_command_stops_running_flow() -> Result<()> {
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.is_running = true;
+    app.flow.phase = crate::core::FlowPhase::RunningPlanning {
+        model_name: std::borrow::Cow::Borrowed("Codex"),
+        attempt: 1,
+    };
+    assert!(app.input_locked());
+
+    for c in "/cancel".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    assert!(!app.input_locked());
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    assert!(*app.shutdown_tx.borrow());
+    assert_eq!(app.flow.phase, crate::core::FlowPhase::Cancelled);
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.contains("Cancelling the flow"))
+    );
+
+    // Phase updates from the winding-down flow do not replace the cancelled state
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.event_tx.try_send(FlowEvent::PhaseChanged(
+        crate::core::FlowPhase::ReadingInput,
+    ))?;
+    app.event_tx.try_send(FlowEvent::Done)?;
+    app.process_events();
+    assert_eq!(app.flow.phase, crate::core::FlowPhase::Cancelled);
+    assert!(!app.is_running);
+    assert_eq!(app.mode, AppMode::Chat);
+    Ok(())
+}
+
+/// `/cancel` is rejected when no flow is running.
+#[test]
+fn cancel_command_rejected_without_running_flow() {
+    let mut app = create_test_app_with_lines(&["/cancel"], 0, 7);
+
+    assert!(app.try_execute_slash_command());
+    assert!(!*app.shutdown_tx.borrow());
+    assert_eq!(app.flow.phase, crate::core::FlowPhase::Idle);
+    assert!(app.flow_ui.output.iter().any(|line| {
+        line.text
+            .contains("Cannot execute /cancel when no flow is running")
+    }));
+}
+
+/// Esc discards a command typed mid-run before it cancels the flow.
+#[test]
+fn esc_discards_typed_command_then_cancels() {
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.is_running = true;
+    app.flow.phase = crate::core::FlowPhase::ReadingInput;
+
+    for c in "/cancel ".chars() {
+        app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(!*app.shutdown_tx.borrow());
+
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(*app.shutdown_tx.borrow());
+    assert_eq!(app.flow.phase, crate::core::FlowPhase::Cancelled);
+}
+
+// =============================================================================
+// /diff Command Tests
+// =============================================================================
+
+/// Git runner that records the diff base it was asked for.
+#[derive(Default)]
+struct RecordingGit {
+    calls: std::sync::Mutex<Vec<Vec<String>>>,
+}
+
+impl crate::core::git::GitRunner for RecordingGit {
+    fn run(&self, _dir: &std::path::Path, args: &[String]) -> std::io::Result<String> {
+        if let Ok(mut calls) = self.calls.lock() {
+            calls.push(args.to_vec());
+        }
+        Ok(format!("{}\n", args.join(" ")))
+    }
+}
+
+/// `/diff` outside a git repository warns instead of running git.
+#[tokio::test]
+async fn diff_command_outside_git_repo_warns() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let mut app = create_app_with_todo_files(&temp_dir, "/diff")?;
+
+    assert!(app.try_execute_slash_command());
+
+    let last = app
+        .flow_ui
+        .output
+        .last()
+        .map(|l| (l.line_type, l.text.as_str()));
+    assert!(matches!(
+        last,
+        Some((OutputLineType::SystemWarning, text)) if text.contains("requires a git repository")
+    ));
+    Ok(())
+}
+
+/// The starting commit looked up by the flow task becomes the `/diff` base.
+#[tokio::test]
+async fn flow_start_commit_event_sets_diff_base() -> Result<()> {
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.event_tx
+        .send(FlowEvent::FlowStartCommit(Some("abc123".to_string())))
+        .await?;
+
+    app.process_events();
+
+    assert_eq!(app.flow_start_commit.as_deref(), Some("abc123"));
+    Ok(())
+}
+
+/// `/diff` diffs against the commit recorded when the flow started.
+#[tokio::test]
+async fn diff_uses_flow_start_commit_as_base() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    fs::create_dir(temp_dir.path().join(".git"))?;
+    let mut app = create_app_with_todo_files(&temp_dir, "")?;
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.flow_start_commit = Some("0123456789abcdef".to_string());
+    let git = std::sync::Arc::new(RecordingGit::default());
+
+    app.show_diff(git.clone());
+
+    // The diff arrives from a blocking thread as one event
+    let event = tokio::time::timeout(std::time::Duration::from_secs(5), app.event_rx.recv())
+        .await?
+        .context("event channel closed")?;
+    app.event_tx.send(event).await?;
+    app.process_events();
+    assert_eq!(
+        git.calls
+            .lock()
+            .ok()
+            .and_then(|calls| calls.last().and_then(|args| args.last().cloned())),
+        Some("0123456789abcdef".to_string())
+    );
+    let texts: Vec<&str> = app.flow_ui.output.iter().map(|l| l.text.as_str()).collect();
+    assert!(texts.contains(&"  Changes since flow start (0123456789ab):"));
+    assert!(texts.contains(&"status --porcelain"));
+    Ok(())
+}
+
+// =============================================================================
+// /stats Command Tests
+// =============================================================================
+
+/// Prompt sizes reported by the flow accumulate per phase and are shown by
+/// `/stats`.
+#[tokio::test]
+async fn stats_command_reports_accumulated_prompt_sizes() -> Result<()> {
+    use crate::core::{PromptKind, PromptSize};
+
+    let mut app = create_test_app_with_lines(&["/stats"], 0, 6);
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    for (kind, prompt) in [
+        (PromptKind::Planning, "a".repeat(400)),
+        (PromptKind::Execution, "b".repeat(100)),
+        (PromptKind::Execution, "c".repeat(100)),
+    ] {
+        app.event_tx
+            .send(FlowEvent::PromptSent(kind, PromptSize::of(&prompt)))
+            .await?;
+    }
+    app.process_events();
+
+    assert!(app.try_execute_slash_command());
+
+    let texts: Vec<&str> = app.flow_ui.output.iter().map(|l| l.text.trim()).collect();
+    assert!(texts.contains(&"Planning: 1 prompt(s), 400 bytes (~100 tokens)"));
+    assert!(texts.contains(&"Execution: 2 prompt(s), 200 bytes (~50 tokens)"));
+    assert!(texts.contains(&"Summary: 0 prompt(s), 0 bytes (~0 tokens)"));
+    assert!(texts.contains(&"Total: 3 prompt(s), 600 bytes (~150 tokens)"));
+    Ok(())
+}
+
+/// `/config` shows the active models and iteration setting, even while the
+/// flow is running.
+#[test]
+fn config_command_shows_models_and_iterations() {
+    let mut app = create_test_app_with_lines(&["/config"], 0, 7);
+    app.settings.planning_model = Model::Claude;
+    app.settings.execution_model = Model::Gemini;
+    app.settings.max_iterations = MaxIterations::Ten;
+    app.is_running = true;
+
+    assert!(app.try_execute_slash_command());
+
+    let texts: Vec<&str> = app.flow_ui.output.iter().map(|l| l.text.trim()).collect();
+    assert!(texts.contains(&"Planning model: Claude Code"));
+    assert!(texts.contains(&"Execution model: Gemini"));
+    assert!(texts.contains(&"Summary model: Gemini"));
+    assert!(texts.contains(&"Max iterations: 10"));
+    assert!(
+        texts
+            .iter()
+            .any(|t| t.starts_with("Retries: up to 100 attempts"))
+    );
+}
+
+// =============================================================================
+// Unlimited Iterations Confirmation Tests
+// =============================================================================
+
+/// Submitting with unlimited iterations asks for confirmation before starting;
+/// cancelling keeps the task text for editing.
+#[tokio::test]
+async fn unlimited_run_enters_confirm_state() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let paths = crate::fs::McgravityPaths::new(temp_dir.path());
+    let mut app = create_test_app_with_paths(&["Build the thing"], 0, 15, paths);
+    app.settings.max_iterations = MaxIterations::Unlimited;
+
+    app.submit_text_input();
+
+    assert_eq!(app.mode, AppMode::ConfirmStart);
+    assert!(!app.is_running());
+
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+    assert_eq!(app.mode, AppMode::Chat);
+    assert!(!app.is_running());
+    assert_eq!(app.text_input.collect_text(), "Build the thing");
+    Ok(())
+}
+
+/// Task text piped through stdin is saved and submitted as the flow input.
+#[tokio::test]
+async fn stdin_text_is_submitted_as_flow_input() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let paths = crate::fs::McgravityPaths::new(temp_dir.path());
+    let mut app = create_test_app_with_paths(&[""], 0, 0, paths.clone());
+    // The confirmation modal holds the flow before any model is spawned
+    app.settings.max_iterations = MaxIterations::Unlimited;
+    let piped = crate::cli::read_stdin_input(
+        &mut vec![std::path::PathBuf::from(crate::cli::STDIN_INPUT)],
+        false,
+        &mut "# Spec\nBuild the thing\n".as_bytes(),
+    )?;
+
+    app.start_with_input_text(piped.as_deref().unwrap_or_default());
+
+    assert_eq!(app.mode, AppMode::ConfirmStart);
+    assert_eq!(app.flow.input_text, "# Spec\nBuild the thing\n");
+    assert_eq!(
+        std::fs::read_to_string(paths.task_file())?,
+        "# Spec\nBuild the thing\n"
+    );
+    assert!(app.text_input.collect_text().is_empty());
+    Ok(())
+}
+
+/// Confirming the modal starts the unlimited flow.
+#[tokio::test]
+async fn confirming_unlimited_run_starts_flow() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let paths = crate::fs::McgravityPaths::new(temp_dir.path());
+    let mut app = create_test_app_with_paths(&["Build the thing"], 0, 15, paths);
+    app.settings.max_iterations = MaxIterations::Unlimited;
+
+    app.submit_text_input();
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    assert_eq!(app.mode, AppMode::Chat);
+    assert!(app.is_running());
+    app.trigger_shutdown();
+    Ok(())
+}
+
+/// A capped run, or an unlimited one with confirmation disabled, starts
+/// directly.
+#[tokio::test]
+async fn capped_or_unconfirmed_run_starts_directly() -> Result<()> {
+    for (max_iterations, confirm_unlimited) in [
+        (MaxIterations::Five, true),
+        (MaxIterations::Unlimited, false),
+    ] {
+        let temp_dir = TempDir::new()?;
+        let paths = crate::fs::McgravityPaths::new(temp_dir.path());
+        let mut app = create_test_app_with_paths(&["Build the thing"], 0, 15, paths);
+        app.settings.max_iterations = max_iterations;
+        app.settings.confirm_unlimited = confirm_unlimited;
+
+        app.submit_text_input();
+
+        assert_eq!(app.mode, AppMode::Chat);
+        assert!(app.is_running());
+        app.trigger_shutdown();
+    }
+    Ok(())
+}
+
+// =============================================================================
+// Unwritable State Directory Tests
+// =============================================================================
+
+/// A read-only `.mcgravity/` is reported at startup with its path and a fix,
+/// and submitting a task does not start a flow.
+#[cfg(unix)]
+#[tokio::test]
+async fn read_only_state_dir_blocks_flow_start() -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp_dir = TempDir::new()?;
+    let paths = crate::fs::McgravityPaths::new(temp_dir.path());
+    paths.ensure_todo_dirs()?;
+    let mcgravity_dir = paths.mcgravity_dir();
+    fs::set_permissions(&mcgravity_dir, fs::Permissions::from_mode(0o555))?;
+
+    // Privileged users (e.g. root in containers) bypass directory permissions
+    let probe = mcgravity_dir.join("probe");
+    if fs::write(&probe, "").is_ok() {
+        fs::remove_file(&probe)?;
+        fs::set_permissions(&mcgravity_dir, fs::Permissions::from_mode(0o755))?;
+        return Ok(());
+    }
+
+    let mut app = crate::app::App::new_with_paths(Vec::new(), paths)?;
+    let expected = format!("Cannot write to {}", mcgravity_dir.display());
+    let error = app
+        .flow_ui
+        .output
+        .iter()
+        .find(|line| line.line_type == OutputLineType::SystemError)
+        .map(|line| line.text.clone())
+        .unwrap_or_default();
+    assert!(error.contains(&expected), "unexpected error: {error}");
+    assert!(error.contains("read-only filesystem"));
+
+    app.text_input.textarea.insert_str("Build the thing");
+    app.submit_text_input();
+    assert!(!app.is_running());
+    assert_eq!(app.text_input.collect_text(), "Build the thing");
+
+    fs::set_permissions(&mcgravity_dir, fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
+
+/// A state directory that cannot be created blocks submission and keeps the
+/// task text in the input.
+#[tokio::test]
+async fn unwritable_todo_dir_blocks_submission() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let paths = crate::fs::McgravityPaths::new(temp_dir.path());
+    paths.ensure_mcgravity_dir()?;
+    // A file where the todo directory should be makes it impossible to create
+    fs::write(paths.todo_dir(), "not a directory")?;
+    let todo_dir = paths.todo_dir();
+    let mut app = create_test_app_with_paths(&["Build the thing"], 0, 15, paths);
+
+    app.submit_text_input();
+
+    assert!(!app.is_running());
+    assert_eq!(app.text_input.collect_text(), "Build the thing");
+    let expected = format!("Cannot write to {}", todo_dir.display());
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.line_type == OutputLineType::SystemError
+                && line.text.contains(&expected))
+    );
+    Ok(())
+}
@@ -163,7 +163,7 @@ mod task_persistence_integration_tests {
         fs::write(TASK_FILE, content)?;
 
         // Initialize App - it should load task.md automatically
-        let app = App::new(None)?;
+        let app = App::new(Vec::new())?;
 
         // Verify the content was loaded
         assert_eq!(app.text_input.lines(), vec!["Line 1", "Line 2", "Line 3"]);
@@ -185,7 +185,7 @@ mod task_persistence_integration_tests {
         let content = "Line 1\nLine 2\n";
         fs::write(TASK_FILE, content)?;
 
-        let app = App::new(None)?;
+        let app = App::new(Vec::new())?;
 
         // Trailing newline should result in an empty last line
         assert_eq!(app.text_input.lines(), vec!["Line 1", "Line 2", ""]);
@@ -205,7 +205,7 @@ mod task_persistence_integration_tests {
         fs::create_dir_all(MCGRAVITY_DIR)?;
         fs::write(TASK_FILE, "")?;
 
-        let app = App::new(None)?;
+        let app = App::new(Vec::new())?;
 
         // Empty file should result in default state (single empty line)
         assert_eq!(app.text_input.lines(), vec![""]);
@@ -223,7 +223,7 @@ mod task_persistence_integration_tests {
 
         // Don't create task.md
 
-        let app = App::new(None)?;
+        let app = App::new(Vec::new())?;
 
         // No file should result in default state
         assert_eq!(app.text_input.lines(), vec![""]);
@@ -240,7 +240,7 @@ mod task_persistence_integration_tests {
         std::env::set_current_dir(temp_dir.path())?;
 
         // Create app with no task.md
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
 
         // Set some content
         app.text_input
@@ -262,7 +262,7 @@ mod task_persistence_integration_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
 
         // Set content with empty lines
         let expected_text = "Line 1\n\nLine 3";
@@ -295,7 +295,7 @@ mod task_persistence_integration_tests {
         std::env::set_current_dir(temp_dir.path())?;
 
         // Create first app and set content
-        let mut app1 = App::new(None)?;
+        let mut app1 = App::new(Vec::new())?;
         app1.text_input.set_lines(vec![
             "First line".to_string(),
             "Second line".to_string(),
@@ -304,7 +304,7 @@ mod task_persistence_integration_tests {
         app1.save_current_task()?;
 
         // Create second app - it should load the saved content
-        let app2 = App::new(None)?;
+        let app2 = App::new(Vec::new())?;
         assert_eq!(app2.text_input.lines(), app1.text_input.lines());
         Ok(())
     }
@@ -316,13 +316,13 @@ mod task_persistence_integration_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app1 = App::new(None)?;
+        let mut app1 = App::new(Vec::new())?;
         // Simulate pressing Enter at the end
         app1.text_input
             .set_lines(vec!["Some text".to_string(), String::new()]);
         app1.save_current_task()?;
 
-        let app2 = App::new(None)?;
+        let app2 = App::new(Vec::new())?;
         assert_eq!(app2.text_input.lines(), app1.text_input.lines());
         Ok(())
     }
@@ -334,7 +334,7 @@ mod task_persistence_integration_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app1 = App::new(None)?;
+        let mut app1 = App::new(Vec::new())?;
         app1.text_input.set_lines(vec![
             "Hello 世界".to_string(),
             "日本語テスト".to_string(),
@@ -342,7 +342,7 @@ mod task_persistence_integration_tests {
         ]);
         app1.save_current_task()?;
 
-        let app2 = App::new(None)?;
+        let app2 = App::new(Vec::new())?;
         assert_eq!(app2.text_input.lines(), app1.text_input.lines());
         Ok(())
     }
@@ -363,7 +363,7 @@ mod task_persistence_integration_tests {
 
         // Create app with input file - should NOT load task.md
         let input_path = temp_dir.path().join("input.txt");
-        let app = App::new(Some(input_path))?;
+        let app = App::new(vec![input_path])?;
 
         // When an input file is provided, task.md should not be loaded
         // (the input comes from the specified file instead)
@@ -387,7 +387,7 @@ mod task_persistence_integration_tests {
         std::env::set_current_dir(temp_dir.path())?;
 
         // Create an app and simulate a task submission
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
 
         // Set task text and save it (simulating submission)
         let original_task = "Fix the bug in module X";
@@ -432,7 +432,7 @@ mod task_persistence_integration_tests {
         std::env::set_current_dir(temp_dir.path())?;
 
         // Create an app with task text
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
         app.text_input
             .set_lines(vec!["My task description".to_string()]);
         app.save_current_task()?;
@@ -487,7 +487,7 @@ mod task_persistence_integration_tests {
         assert!(done_file2.exists(), "done file 2 should exist before reset");
 
         // Create app and call reset_session
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
         app.reset_session();
 
         // Verify done folder exists but is empty
@@ -519,7 +519,7 @@ mod task_persistence_integration_tests {
         std::env::set_current_dir(temp_dir.path())?;
 
         // Create an app with task text and a file in the done folder
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
         app.text_input
             .set_lines(vec!["My completed task".to_string()]);
         app.save_current_task()?;
@@ -586,7 +586,7 @@ mod task_persistence_integration_tests {
         std::env::set_current_dir(temp_dir.path())?;
 
         // Create an app with task text
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
         let task_content = "Important task to complete";
         app.text_input.set_lines(vec![task_content.to_string()]);
         app.save_current_task()?;
@@ -625,7 +625,7 @@ mod task_persistence_integration_tests {
         std::env::set_current_dir(temp_dir.path())?;
 
         // Create an app with task text
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
         app.text_input.set_lines(vec!["Original task".to_string()]);
         app.save_current_task()?;
 
@@ -661,7 +661,7 @@ mod task_persistence_integration_tests {
         let temp_dir = TempDir::new()?;
         std::env::set_current_dir(temp_dir.path())?;
 
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
         app.text_input
             .set_lines(vec!["Task with no todos".to_string()]);
         app.save_current_task()?;
@@ -696,7 +696,7 @@ mod task_persistence_integration_tests {
         std::env::set_current_dir(temp_dir.path())?;
 
         // Step 1: Create app and submit initial task
-        let mut app = App::new(None)?;
+        let mut app = App::new(Vec::new())?;
         let initial_task = "Implement feature X";
         app.text_input.set_lines(vec![initial_task.to_string()]);
         app.save_current_task()?;
@@ -743,3 +743,352 @@ mod task_persistence_integration_tests {
         Ok(())
     }
 }
+
+mod autosave_tests {
+    use super::*;
+    use crate::fs::McgravityPaths;
+    use std::time::{Duration, Instant};
+
+    /// Creates a test app with unsaved edits made `elapsed` ago.
+    fn dirty_app(paths: McgravityPaths, elapsed: Duration) -> App {
+        let mut app = create_test_app_with_paths(&["draft task"], 0, 10, paths);
+        app.text_input.is_dirty = true;
+        app.text_input.last_edit_time = Instant::now().checked_sub(elapsed);
+        app
+    }
+
+    #[tokio::test]
+    async fn test_tick_respects_custom_debounce() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        let task_file = paths.task_file();
+
+        let mut app = dirty_app(paths, Duration::from_secs(2));
+        app.settings.autosave_debounce_ms = 5000;
+
+        // 2s of inactivity is below the 5s debounce - nothing is saved
+        app.tick();
+        assert!(!task_file.exists());
+        assert!(app.text_input.is_dirty);
+
+        // Past the debounce - the draft is saved
+        app.text_input.last_edit_time = Instant::now().checked_sub(Duration::from_secs(6));
+        app.tick();
+        assert_eq!(fs::read_to_string(&task_file)?, "draft task");
+        assert!(!app.text_input.is_dirty);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_save_command_writes_task_file_when_not_dirty() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        let task_file = paths.task_file();
+
+        let mut app = create_test_app_with_paths(&["/save Refactor the parser"], 0, 25, paths);
+        assert!(!app.text_input.is_dirty);
+
+        assert!(app.try_execute_slash_command());
+
+        assert!(task_file.exists(), "/save should write task.md");
+        assert_eq!(
+            fs::read_to_string(&task_file)?,
+            "Refactor the parser",
+            "the task text is persisted without the /save command itself"
+        );
+        assert_eq!(app.text_input.lines(), vec!["Refactor the parser"]);
+        assert!(
+            app.flow_ui
+                .output
+                .iter()
+                .any(|line| line.text.contains("Saved task text to"))
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_bare_save_command_writes_current_task() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        let task_file = paths.task_file();
+
+        let mut app = create_test_app_with_paths(&["/save"], 0, 5, paths);
+        app.flow.input_text = "Build a REST API".to_string();
+
+        assert!(app.try_execute_slash_command());
+
+        assert_eq!(fs::read_to_string(&task_file)?, "Build a REST API");
+        assert_eq!(app.text_input.lines(), vec!["Build a REST API"]);
+        assert!(
+            app.flow_ui
+                .output
+                .iter()
+                .any(|line| line.text.contains("Saved task text to"))
+        );
+        Ok(())
+    }
+}
+
+mod reset_keeps_task_tests {
+    use super::*;
+    use crate::fs::McgravityPaths;
+
+    const TASK_WITH_SUMMARY: &str =
+        "Build a REST API\n\n<COMPLETED_TASKS>\n- Set up routing\n</COMPLETED_TASKS>\n";
+
+    #[tokio::test]
+    async fn test_reset_clears_task_by_default() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        paths.ensure_mcgravity_dir()?;
+        fs::write(paths.task_file(), TASK_WITH_SUMMARY)?;
+
+        let mut app = create_test_app_with_paths(&[""], 0, 0, paths.clone());
+        assert!(app.settings.reset_clears_task);
+
+        app.reset_session();
+
+        assert!(!paths.task_file().exists(), "task.md should be deleted");
+        assert_eq!(app.text_input.lines(), vec![""]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reset_keeps_original_task_when_disabled() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        paths.ensure_mcgravity_dir()?;
+        fs::write(paths.task_file(), TASK_WITH_SUMMARY)?;
+
+        let mut app = create_test_app_with_paths(&[""], 0, 0, paths.clone());
+        app.settings.reset_clears_task = false;
+        app.mode = AppMode::Finished;
+
+        app.reset_session();
+
+        assert_eq!(
+            fs::read_to_string(paths.task_file())?,
+            "Build a REST API",
+            "task.md should keep the task without COMPLETED_TASKS"
+        );
+        assert_eq!(app.text_input.lines(), vec!["Build a REST API"]);
+        assert_eq!(app.mode, AppMode::Chat);
+        Ok(())
+    }
+}
+
+mod load_task_from_path_tests {
+    use super::*;
+    use crate::fs::McgravityPaths;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[test]
+    fn test_load_existing_file_replaces_input() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        fs::create_dir_all(temp_dir.path().join("docs"))?;
+        fs::write(
+            temp_dir.path().join("docs/plan.md"),
+            "Add caching\nto the API",
+        )?;
+
+        let mut app = create_test_app_with_paths(&["old text"], 0, 8, paths.clone());
+        app.load_task_from_path(std::path::Path::new("docs/plan.md"))?;
+        assert_eq!(app.text_input.lines(), vec!["Add caching", "to the API"]);
+
+        // The slash command accepts @-mention paths
+        let mut app = create_test_app_with_paths(&["/load @docs/plan.md"], 0, 19, paths);
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.text_input.lines(), vec!["Add caching", "to the API"]);
+        assert!(
+            app.flow_ui
+                .output
+                .iter()
+                .any(|line| line.text.contains("Loaded task text from @docs/plan.md"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_missing_file_reports_warning() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+
+        let mut app = create_test_app_with_paths(&["keep me"], 0, 7, paths.clone());
+        assert!(
+            app.load_task_from_path(std::path::Path::new("missing.md"))
+                .is_err()
+        );
+        assert_eq!(app.text_input.lines(), vec!["keep me"]);
+
+        let mut app = create_test_app_with_paths(&["/load missing.md"], 0, 16, paths);
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.text_input.lines(), vec!["/load missing.md"]);
+        assert!(
+            app.flow_ui
+                .output
+                .iter()
+                .any(|line| line.text.starts_with("! Failed to load missing.md"))
+        );
+        Ok(())
+    }
+}
+
+mod tail_command_tests {
+    use super::*;
+    use crate::fs::McgravityPaths;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use std::io::Write;
+    use std::time::Duration;
+
+    fn output_contains(app: &App, text: &str) -> bool {
+        app.flow_ui.output.iter().any(|line| line.text == text)
+    }
+
+    #[tokio::test]
+    async fn test_tail_forwards_appended_lines_until_untail() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        let log = temp_dir.path().join("build.log");
+        fs::write(&log, "before tail\n")?;
+
+        let mut app = create_test_app_with_paths(&["/tail @build.log"], 0, 16, paths);
+        let (tx, rx) = tokio::sync::mpsc::channel(16);
+        app.event_tx = tx;
+        app.event_rx = rx;
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.log_tail.is_some());
+        assert!(output_contains(
+            &app,
+            "+ Tailing build.log. Run /untail to stop."
+        ));
+
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&log)?
+            .write_all(b"Compiling app\n")?;
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(2);
+        while !output_contains(&app, "Compiling app") {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "appended line was not forwarded"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            app.process_events();
+        }
+        assert!(!output_contains(&app, "before tail"));
+
+        app.text_input.set_lines(vec!["/untail".to_string()]);
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(app.log_tail.is_none());
+        assert!(
+            app.flow_ui
+                .output
+                .iter()
+                .any(|line| line.text.starts_with("  Stopped tailing"))
+        );
+        Ok(())
+    }
+}
+
+mod doctor_command_tests {
+    use super::*;
+    use crate::fs::McgravityPaths;
+    use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    #[tokio::test]
+    async fn test_doctor_reports_checks_and_summary() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        let mut app = create_test_app_with_paths(&["/doctor"], 0, 7, paths);
+        (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        // The checks run on a blocking thread
+        assert!(app.flow_ui.output.is_empty());
+        process_next_event(&mut app).await?;
+
+        let texts: Vec<&str> = app
+            .flow_ui
+            .output
+            .iter()
+            .map(|line| line.text.as_str())
+            .collect();
+        assert!(texts.iter().any(|t| t.starts_with("+ State directory: ")));
+        assert!(texts.iter().any(|t| t.starts_with("! Git repository: ")));
+        let summary = texts.last().copied().unwrap_or_default();
+        assert!(summary.contains("critical check"), "{summary}");
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_which_reports_path_override() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new()?;
+        let cli = temp_dir.path().join("claude-wrapper");
+        fs::write(&cli, "#!/bin/sh\n")?;
+        fs::set_permissions(&cli, fs::Permissions::from_mode(0o755))?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        let mut app = create_test_app_with_paths(&["/which claude"], 0, 13, paths);
+        (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+        app.settings.claude_path = Some(cli.clone());
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        // The CLI is resolved on a blocking thread
+        assert!(app.flow_ui.output.is_empty());
+        process_next_event(&mut app).await?;
+
+        let texts: Vec<&str> = app.flow_ui.output.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![format!(
+                "  `claude` resolves to {} (via path override)",
+                cli.display()
+            )]
+        );
+        Ok(())
+    }
+}
+
+mod cls_command_tests {
+    use super::*;
+    use crate::core::CommandResult;
+    use crate::fs::McgravityPaths;
+    use crate::tui::widgets::OutputLine;
+
+    #[test]
+    fn test_cls_clears_output_but_keeps_task_todos_and_input() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        paths.ensure_todo_dirs()?;
+        fs::write(paths.task_file(), "Saved task")?;
+        let todo = paths.todo_dir().join("task-001.md");
+        fs::write(&todo, "# Task 001")?;
+        let mut app = create_test_app_with_paths(&["My draft task"], 0, 13, paths);
+        app.flow_ui.output.push(OutputLine::info("old output"));
+        app.flow_ui.output_truncated = true;
+
+        app.handle_command_result(CommandResult::ClearOutput);
+
+        assert!(app.flow_ui.output.is_empty());
+        assert!(!app.flow_ui.output_truncated);
+        assert_eq!(fs::read_to_string(app.paths.task_file())?, "Saved task");
+        assert!(todo.exists());
+        assert_eq!(app.text_input.lines(), vec!["My draft task"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_cls_runs_while_a_flow_is_running() {
+        let mut app = create_test_app_with_lines(&["/cls"], 0, 4);
+        app.is_running = true;
+        app.flow_ui.output.push(OutputLine::info("old output"));
+
+        app.submit_text_input();
+
+        assert!(app.flow_ui.output.is_empty());
+        assert!(app.text_input.collect_text().is_empty());
+    }
+}
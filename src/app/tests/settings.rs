@@ -340,6 +340,141 @@ mod settings_integration_tests {
     }
 }
 
+mod reset_settings_tests {
+    use super::*;
+    use crate::app::state::{MaxIterations, SettingsState};
+    use crate::fs::{McgravityPaths, PersistedSettings};
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    /// Creates an app with non-default settings and `input` in the text box.
+    fn create_customized_app(input: &str, paths: McgravityPaths) -> crate::app::App {
+        let mut app = create_test_app_with_paths(&[input], 0, input.len(), paths);
+        app.settings.planning_model = Model::Claude;
+        app.settings.max_iterations = MaxIterations::Ten;
+        app.settings.show_timestamps = true;
+        app.settings.sticky_scroll = false;
+        app
+    }
+
+    #[test]
+    fn reset_settings_confirm_restores_defaults_in_memory_and_on_disk() -> Result<()> {
+        let temp = TempDir::new()?;
+        let paths = McgravityPaths::new(temp.path());
+        let mut app = create_customized_app("/reset-settings confirm", paths.clone());
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            PersistedSettings::from(&app.settings),
+            PersistedSettings::from(&SettingsState::default())
+        );
+        assert_eq!(paths.load_settings()?, PersistedSettings::defaults());
+        assert!(
+            app.flow_ui
+                .output
+                .iter()
+                .any(|line| line.text.contains("Settings restored to defaults"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reset_settings_without_confirm_changes_nothing() -> Result<()> {
+        let temp = TempDir::new()?;
+        let paths = McgravityPaths::new(temp.path());
+        let mut app = create_customized_app("/reset-settings", paths.clone());
+
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(app.settings.planning_model, Model::Claude);
+        assert!(app.settings.show_timestamps);
+        assert!(!paths.settings_file().exists());
+        Ok(())
+    }
+}
+
+mod model_picker_tests {
+    use super::*;
+    use crate::core::ModelAvailability;
+    use crate::fs::McgravityPaths;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    /// Creates an app where only Codex and Gemini are installed.
+    fn app_without_claude(paths: McgravityPaths) -> crate::app::App {
+        let mut app = create_test_app_with_paths(&["/models"], 0, 7, paths);
+        app.settings.model_availability = ModelAvailability {
+            codex: true,
+            claude: false,
+            gemini: true,
+        };
+        app
+    }
+
+    #[test]
+    fn picker_lists_all_models_with_availability() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let mut app = app_without_claude(McgravityPaths::new(temp_dir.path()));
+        app.settings.execution_model = Model::Gemini;
+
+        assert!(app.try_execute_slash_command());
+        assert_eq!(app.mode, AppMode::ModelPicker);
+
+        let entries = app.settings.model_picker_entries();
+        let models: Vec<Model> = entries.iter().map(|entry| entry.model).collect();
+        assert_eq!(models, Model::all());
+        let available: Vec<bool> = entries.iter().map(|entry| entry.available).collect();
+        assert_eq!(available, vec![true, false, true]);
+        assert!(entries[0].planning && !entries[0].execution);
+        assert!(entries[2].execution && !entries[2].planning);
+
+        let terminal = render_app_to_terminal(&mut app, 80, 24)?;
+        let screen: Vec<String> = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(80)
+            .map(|row| row.iter().map(ratatui::buffer::Cell::symbol).collect())
+            .collect();
+        for entry in &entries {
+            let row = screen
+                .iter()
+                .find(|row| row.contains(entry.model.description()))
+                .ok_or_else(|| anyhow::anyhow!("{} is not listed", entry.model.name()))?;
+            assert_eq!(row.contains("not installed"), !entry.available, "{row}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unavailable_model_cannot_be_picked() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let paths = McgravityPaths::new(temp_dir.path());
+        paths.ensure_mcgravity_dir()?;
+        let mut app = app_without_claude(paths.clone());
+        app.open_model_picker();
+        assert_eq!(app.model_picker_index, 0);
+
+        // Claude is not installed: picking it changes nothing
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.settings.planning_model, Model::Codex);
+        assert_eq!(app.settings.execution_model, Model::Codex);
+
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        app.handle_key(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+        assert_eq!(app.settings.planning_model, Model::Codex);
+        assert_eq!(app.settings.execution_model, Model::Gemini);
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(app.mode, AppMode::Chat);
+        assert_eq!(paths.load_settings()?.execution_model, Model::Gemini.name());
+        Ok(())
+    }
+}
+
 mod settings_render_tests {
     use super::*;
     use anyhow::Result;
@@ -355,7 +490,6 @@ mod settings_render_tests {
             .assert_buffer_lines(styled_lines_from_buffer(
                 &terminal,
                 &[
-                    " McGravity [Codex/Codex]",
                     "┌Output (waiting for input)────────────────────────────────┐",
                     "│   ┌ Settings ────────────────────────────────────────┐   │",
                     "│   │McGravity Settings                                │   │",
@@ -365,16 +499,17 @@ mod settings_render_tests {
                     "│   │  Execution Model   [Codex]                       │   │",
                     "│   │  Enter Key         [Submit]                      │   │",
                     "│   │  Max Iterations    [5]                           │   │",
-                    "└───│  Summary Mode      [Inline Only]                 │───┘",
+                    "│   │  Summary Mode      [Inline Only]                 │   │",
+                    "│   │  Completed Todos   [Move to Done]                │   │",
+                    "│   │  New Session       [Clear Task]                  │   │",
+                    "│   │  Flow Mode         [Full]                        │   │",
+                    "└───│  Theme             [Dark]                        │───┘",
                     " · W│                                                  │",
                     "   R│                                                  │",
-                    "    │[↑/↓] Navigate  [Enter] Change  [Esc] Close       │",
-                    "┌ Ta│                                                  │───┐",
-                    "│hel│                                                  │   │",
-                    "│   └──────────────────────────────────────────────────┘   │",
-                    "│                                                          │",
+                    "┌ Ta│[↑/↓] Navigate  [Enter] Change  [Esc] Close       │───┐",
+                    "│hel└──────────────────────────────────────────────────┘   │",
                     "└ \\+Enter for newline ─────────────────────────────────────┘",
-                    " [Enter] Submit  [Ctrl+S] Settings",
+                    " McGravity [Codex/Codex] [Enter] Submit  [Ctrl+S] Settings",
                 ],
             ));
         Ok(())
@@ -392,7 +527,6 @@ mod settings_render_tests {
             .assert_buffer_lines(styled_lines_from_buffer(
                 &terminal,
                 &[
-                    " McGravity [Codex/Codex]",
                     "┌Output (waiting for input)────────────────────────────────┐",
                     "│   ┌ Settings ────────────────────────────────────────┐   │",
                     "│   │McGravity Settings                                │   │",
@@ -402,16 +536,17 @@ mod settings_render_tests {
                     "│   │› Execution Model   [Codex]                       │   │",
                     "│   │  Enter Key         [Submit]                      │   │",
                     "│   │  Max Iterations    [5]                           │   │",
-                    "└───│  Summary Mode      [Inline Only]                 │───┘",
+                    "│   │  Summary Mode      [Inline Only]                 │   │",
+                    "│   │  Completed Todos   [Move to Done]                │   │",
+                    "│   │  New Session       [Clear Task]                  │   │",
+                    "│   │  Flow Mode         [Full]                        │   │",
+                    "└───│  Theme             [Dark]                        │───┘",
                     " · W│                                                  │",
                     "   R│                                                  │",
-                    "    │[↑/↓] Navigate  [Enter] Change  [Esc] Close       │",
-                    "┌ Ta│                                                  │───┐",
-                    "│hel│                                                  │   │",
-                    "│   └──────────────────────────────────────────────────┘   │",
-                    "│                                                          │",
+                    "┌ Ta│[↑/↓] Navigate  [Enter] Change  [Esc] Close       │───┐",
+                    "│hel└──────────────────────────────────────────────────┘   │",
                     "└ \\+Enter for newline ─────────────────────────────────────┘",
-                    " [Enter] Submit  [Ctrl+S] Settings",
+                    " McGravity [Codex/Codex] [Enter] Submit  [Ctrl+S] Settings",
                 ],
             ));
         Ok(())
@@ -430,7 +565,6 @@ mod settings_render_tests {
             .assert_buffer_lines(styled_lines_from_buffer(
                 &terminal,
                 &[
-                    " McGravity [Claude Code/Gemini]",
                     "┌Output (waiting for input)────────────────────────────────┐",
                     "│   ┌ Settings ────────────────────────────────────────┐   │",
                     "│   │McGravity Settings                                │   │",
@@ -440,16 +574,17 @@ mod settings_render_tests {
                     "│   │  Execution Model   [Gemini]                      │   │",
                     "│   │  Enter Key         [Submit]                      │   │",
                     "│   │  Max Iterations    [5]                           │   │",
-                    "└───│  Summary Mode      [Inline Only]                 │───┘",
+                    "│   │  Summary Mode      [Inline Only]                 │   │",
+                    "│   │  Completed Todos   [Move to Done]                │   │",
+                    "│   │  New Session       [Clear Task]                  │   │",
+                    "│   │  Flow Mode         [Full]                        │   │",
+                    "└───│  Theme             [Dark]                        │───┘",
                     " · W│                                                  │",
                     "   R│                                                  │",
-                    "    │[↑/↓] Navigate  [Enter] Change  [Esc] Close       │",
-                    "┌ Ta│                                                  │───┐",
-                    "│hel│                                                  │   │",
-                    "│   └──────────────────────────────────────────────────┘   │",
-                    "│                                                          │",
+                    "┌ Ta│[↑/↓] Navigate  [Enter] Change  [Esc] Close       │───┐",
+                    "│hel└──────────────────────────────────────────────────┘   │",
                     "└ \\+Enter for newline ─────────────────────────────────────┘",
-                    " [Enter] Submit  [Ctrl+S] Settings",
+                    " McGravity [Claude Code/Gemini] [Enter] Submit  [Ctrl+S] Set",
                 ],
             ));
         Ok(())
@@ -469,7 +604,6 @@ mod settings_render_tests {
             .assert_buffer_lines(styled_lines_from_buffer(
                 &terminal,
                 &[
-                    " McGravity [Gemini/Gemini]",
                     "┌Output (waiting for input)────────────────────────────────┐",
                     "│   ┌ Settings ────────────────────────────────────────┐   │",
                     "│   │McGravity Settings                                │   │",
@@ -479,16 +613,17 @@ mod settings_render_tests {
                     "│   │› Execution Model   [Gemini]                      │   │",
                     "│   │  Enter Key         [Submit]                      │   │",
                     "│   │  Max Iterations    [5]                           │   │",
-                    "└───│  Summary Mode      [Inline Only]                 │───┘",
+                    "│   │  Summary Mode      [Inline Only]                 │   │",
+                    "│   │  Completed Todos   [Move to Done]                │   │",
+                    "│   │  New Session       [Clear Task]                  │   │",
+                    "│   │  Flow Mode         [Full]                        │   │",
+                    "└───│  Theme             [Dark]                        │───┘",
                     " · W│                                                  │",
                     "   R│                                                  │",
-                    "    │[↑/↓] Navigate  [Enter] Change  [Esc] Close       │",
-                    "┌ Ta│                                                  │───┐",
-                    "│hel│                                                  │   │",
-                    "│   └──────────────────────────────────────────────────┘   │",
-                    "│                                                          │",
+                    "┌ Ta│[↑/↓] Navigate  [Enter] Change  [Esc] Close       │───┐",
+                    "│hel└──────────────────────────────────────────────────┘   │",
                     "└ \\+Enter for newline ─────────────────────────────────────┘",
-                    " [Enter] Submit  [Ctrl+S] Settings",
+                    " McGravity [Gemini/Gemini] [Enter] Submit  [Ctrl+S] Settings",
                 ],
             ));
         Ok(())
@@ -506,21 +641,21 @@ mod settings_render_tests {
             .assert_buffer_lines(styled_lines_from_buffer(
                 &terminal,
                 &[
-                    " ┌ Settings ────────────────────────────────────────┐",
-                    "┌│McGravity Settings                                │─┐",
+                    "┌Output (waiting for input)───────────────────────────┐",
+                    "│┌ Settings ────────────────────────────────────────┐ │",
+                    "││McGravity Settings                                │ │",
                     "││Configure AI model preferences.                   │ │",
                     "││                                                  │ │",
                     "││› Planning Model    [Codex]                       │ │",
-                    "└│  Execution Model   [Codex]                       │─┘",
-                    " │  Enter Key         [Submit]                      │",
-                    " │  Max Iterations    [5]                           │",
+                    "││  Execution Model   [Codex]                       │ │",
+                    "││  Enter Key         [Submit]                      │ │",
+                    "└│  Max Iterations    [5]                           │─┘",
                     " │  Summary Mode      [Inline Only]                 │",
-                    "┌│                                                  │─┐",
-                    "││                                                  │ │",
-                    "││[↑/↓] Navigate  [Enter] Change  [Esc] Close       │ │",
-                    "││                                                  │ │",
-                    "└│                                                  │─┘",
-                    " └──────────────────────────────────────────────────┘",
+                    " │  Completed Todos   [Move to Done]                │",
+                    "┌│  New Session       [Clear Task]                  │─┐",
+                    "││  Flow Mode         [Full]                        │ │",
+                    "└└──────────────────────────────────────────────────┘─┘",
+                    " McGravity [Codex/Codex] [Enter] Submit  [Ctrl+S] Setti",
                 ],
             ));
         Ok(())
@@ -538,7 +673,6 @@ mod settings_render_tests {
             "┌Output (waiting for input)────────────────────────────────────────────────────┐",
             "│                                                                              │",
             "│                                                                              │",
-            "│                                                                              │",
             "│             ┌ Settings ────────────────────────────────────────┐             │",
             "│             │McGravity Settings                                │             │",
             "│             │Configure AI model preferences.                   │             │",
@@ -548,18 +682,73 @@ mod settings_render_tests {
             "│             │  Enter Key         [Submit]                      │             │",
             "│             │  Max Iterations    [5]                           │             │",
             "│             │  Summary Mode      [Inline Only]                 │             │",
-            "│             │                                                  │             │",
-            "└─────────────│                                                  │─────────────┘",
-            " · Waiting for│[↑/↓] Navigate  [Enter] Change  [Esc] Close       │",
+            "│             │  Completed Todos   [Move to Done]                │             │",
+            "│             │  New Session       [Clear Task]                  │             │",
+            "└─────────────│  Flow Mode         [Full]                        │─────────────┘",
+            " · Waiting for│  Theme             [Dark]                        │",
             "   Ready to pr│                                                  │",
             "              │                                                  │",
-            "┌ Task Text ──└──────────────────────────────────────────────────┘─────────────┐",
-            "│hello                                                                         │",
+            "┌ Task Text ──│[↑/↓] Navigate  [Enter] Change  [Esc] Close       │─────────────┐",
+            "│hello        └──────────────────────────────────────────────────┘             │",
             "│                                                                              │",
             "│                                                                              │",
             "└ \\+Enter for newline ─────────────────────────────────────────────────────────┘",
-            " [Enter] Submit  [Ctrl+S] Settings",
+            " [Enter] Submit  [Ctrl+S] Settings  [Ctrl+T] Toggle Enter",
         ]));
         Ok(())
     }
+
+    /// Returns the foreground color of the first cell of `text` in the buffer.
+    fn fg_of_text(
+        terminal: &ratatui::Terminal<ratatui::backend::TestBackend>,
+        text: &str,
+    ) -> Option<ratatui::style::Color> {
+        let buffer = terminal.backend().buffer();
+        let area = *buffer.area();
+        (area.top()..area.bottom()).find_map(|y| {
+            let row: String = (area.left()..area.right())
+                .map(|x| buffer[(x, y)].symbol())
+                .collect();
+            let column = row.find(text)?;
+            let x = u16::try_from(row[..column].chars().count()).ok()?;
+            Some(buffer[(area.left() + x, y)].fg)
+        })
+    }
+
+    #[test]
+    fn theme_preview_uses_highlighted_theme_colors() -> Result<()> {
+        use crate::app::state::ColorTheme;
+
+        let mut app = create_test_app_with_lines(&["hello"], 0, 5);
+        app.open_settings();
+        let theme_index = SettingsItem::all()
+            .iter()
+            .position(|item| *item == SettingsItem::Theme)
+            .ok_or_else(|| anyhow::anyhow!("Theme setting missing"))?;
+        app.settings.selected_index = theme_index;
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(app.settings.color_theme, ColorTheme::Light);
+
+        let terminal = render_app_to_terminal(&mut app, 80, 30)?;
+        let light = ColorTheme::Light.theme();
+        assert_eq!(fg_of_text(&terminal, "✗ Build failed"), Some(light.error));
+        assert_eq!(
+            fg_of_text(&terminal, "+ Todo completed"),
+            Some(light.success)
+        );
+        assert_eq!(
+            fg_of_text(&terminal, "! Retrying in 5s"),
+            Some(light.warning)
+        );
+        assert_eq!(
+            fg_of_text(&terminal, "Planning next cycle"),
+            Some(light.muted)
+        );
+
+        // The active theme only changes once the panel is closed
+        assert_eq!(app.theme.error, crate::tui::Theme::default().error);
+        app.close_settings();
+        assert_eq!(app.theme.warning, light.warning);
+        Ok(())
+    }
 }
@@ -10,6 +10,7 @@ use crate::app::*;
 use crate::tui::widgets::{MAX_OUTPUT_LINES, OutputLine, calculate_visual_line_count};
 use anyhow::Result;
 use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{Terminal, backend::TestBackend};
 
 // =============================================================================
 // Output Truncation Tests
@@ -380,6 +381,314 @@ fn scrolling_up_from_bottom_disables_auto_scroll() {
     );
 }
 
+// =============================================================================
+// Sticky Bottom Tests
+// =============================================================================
+
+/// Sends `count` stdout lines through the event channel and processes them.
+fn send_output_lines(app: &mut App, count: usize) -> Result<()> {
+    for i in 0..count {
+        app.event_tx
+            .try_send(FlowEvent::Output(OutputLine::stdout(format!("New {i}"))))?;
+    }
+    app.process_events();
+    Ok(())
+}
+
+#[test]
+fn output_keeps_view_when_scrolled_up() -> Result<()> {
+    let mut app = create_scrollable_app();
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.flow_ui.output_scroll.offset = 80;
+    app.handle_key(KeyEvent::new(KeyCode::PageUp, KeyModifiers::NONE));
+    assert_eq!(app.flow_ui.output_scroll.offset, 70);
+
+    send_output_lines(&mut app, 5)?;
+
+    assert_eq!(
+        app.flow_ui.output_scroll.offset, 70,
+        "Output must not move the view while scrolled up"
+    );
+    assert!(!app.flow_ui.output_scroll.auto_scroll);
+    Ok(())
+}
+
+#[test]
+fn output_follows_when_pinned_to_bottom() -> Result<()> {
+    let mut app = create_scrollable_app();
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.flow_ui.output_scroll.offset = 70;
+    app.flow_ui.output_scroll.auto_scroll = false;
+    // Scrolling back to the bottom re-pins the view
+    app.handle_key(KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
+    assert!(app.flow_ui.output_scroll.auto_scroll);
+
+    send_output_lines(&mut app, 4)?;
+    app.event_tx
+        .try_send(FlowEvent::PartialOutput(OutputLine::stdout("New 4")))?;
+    app.process_events();
+
+    assert_eq!(app.flow_ui.output_scroll.offset, 105 - 20);
+    app.event_tx
+        .try_send(FlowEvent::ReplaceLastOutput(OutputLine::stdout(
+            "x".repeat(200),
+        )))?;
+    app.process_events();
+    assert_eq!(
+        app.flow_ui.output_scroll.offset,
+        107 - 20,
+        "A replaced line that wraps further is followed too"
+    );
+    Ok(())
+}
+
+#[test]
+fn output_always_follows_without_sticky_scroll() -> Result<()> {
+    let mut app = create_scrollable_app();
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.settings.sticky_scroll = false;
+    app.flow_ui.output_scroll.offset = 10;
+    app.flow_ui.output_scroll.auto_scroll = false;
+
+    send_output_lines(&mut app, 1)?;
+
+    assert_eq!(app.flow_ui.output_scroll.offset, 101 - 20);
+    assert!(app.flow_ui.output_scroll.auto_scroll);
+    Ok(())
+}
+
+// =============================================================================
+// Phase Boundary Tests
+// =============================================================================
+
+/// Sends a planning phase, 30 output lines, an execution phase, 50 output
+/// lines and a cycle-complete phase, then processes the events.
+fn create_app_with_phase_boundaries() -> Result<App> {
+    use crate::core::FlowPhase;
+    use std::borrow::Cow;
+
+    let mut app = create_scrollable_app();
+    app.flow_ui.output.clear();
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(128);
+    let phases = [
+        FlowPhase::RunningPlanning {
+            model_name: Cow::Borrowed("Codex"),
+            attempt: 1,
+        },
+        FlowPhase::RunningExecution {
+            model_name: Cow::Borrowed("Codex"),
+            file_index: 1,
+            attempt: 1,
+        },
+    ];
+    for (phase, lines) in phases.into_iter().zip([30, 50]) {
+        app.event_tx
+            .try_send(FlowEvent::PhaseChanged(phase.clone()))?;
+        // Announcing the same phase again does not add a second boundary
+        app.event_tx.try_send(FlowEvent::PhaseChanged(phase))?;
+        for i in 0..lines {
+            app.event_tx
+                .try_send(FlowEvent::Output(OutputLine::stdout(format!("Line {i}"))))?;
+        }
+    }
+    app.event_tx
+        .try_send(FlowEvent::PhaseChanged(FlowPhase::CycleComplete {
+            iteration: 1,
+        }))?;
+    app.process_events();
+    Ok(app)
+}
+
+#[test]
+fn phase_changes_record_boundaries_with_separator_lines() -> Result<()> {
+    let app = create_app_with_phase_boundaries()?;
+
+    let lines: Vec<usize> = app
+        .flow_ui
+        .phase_markers
+        .iter()
+        .map(|marker| marker.line)
+        .collect();
+    assert_eq!(lines, [0, 31, 82]);
+    assert_eq!(app.flow_ui.output.len(), 83);
+    assert_eq!(
+        app.flow_ui.output[31].text,
+        "── Running Codex on file 1 (attempt 1) ──"
+    );
+    Ok(())
+}
+
+#[test]
+fn goto_scrolls_to_phase_boundary() -> Result<()> {
+    let mut app = create_app_with_phase_boundaries()?;
+    assert!(app.flow_ui.output_scroll.auto_scroll);
+
+    assert!(app.goto_phase_marker(2));
+    assert_eq!(app.flow_ui.output_scroll.offset, 31);
+    assert!(!app.flow_ui.output_scroll.auto_scroll);
+
+    assert!(app.goto_phase_marker(1));
+    assert_eq!(app.flow_ui.output_scroll.offset, 0);
+
+    // The last boundary is too close to the end to reach the top of the panel
+    assert!(app.goto_phase_marker(3));
+    assert_eq!(app.flow_ui.output_scroll.offset, 83 - 20);
+    assert!(
+        app.flow_ui.output_scroll.auto_scroll,
+        "Landing on the bottom re-pins the view"
+    );
+
+    assert!(!app.goto_phase_marker(4));
+    assert!(!app.goto_phase_marker(0));
+    assert_eq!(app.flow_ui.output_scroll.offset, 83 - 20);
+    Ok(())
+}
+
+#[test]
+fn goto_command_jumps_to_boundary() -> Result<()> {
+    let mut app = create_app_with_phase_boundaries()?;
+    app.is_running = false;
+    app.text_input.textarea = tui_textarea::TextArea::new(vec!["/goto 2".to_string()]);
+
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    assert_eq!(app.flow_ui.output_scroll.offset, 31);
+    Ok(())
+}
+
+#[test]
+fn alt_page_keys_step_between_phase_boundaries() -> Result<()> {
+    let mut app = create_app_with_phase_boundaries()?;
+    app.flow_ui.output_scroll.offset = 40;
+
+    app.handle_key(KeyEvent::new(KeyCode::PageUp, KeyModifiers::ALT));
+    assert_eq!(app.flow_ui.output_scroll.offset, 31);
+    app.handle_key(KeyEvent::new(KeyCode::PageUp, KeyModifiers::ALT));
+    assert_eq!(app.flow_ui.output_scroll.offset, 0);
+    app.handle_key(KeyEvent::new(KeyCode::PageUp, KeyModifiers::ALT));
+    assert_eq!(app.flow_ui.output_scroll.offset, 0);
+
+    app.handle_key(KeyEvent::new(KeyCode::PageDown, KeyModifiers::ALT));
+    assert_eq!(app.flow_ui.output_scroll.offset, 31);
+    assert!(!app.flow_ui.output_scroll.auto_scroll);
+    Ok(())
+}
+
+#[test]
+fn clearing_output_drops_phase_boundaries() -> Result<()> {
+    let mut app = create_app_with_phase_boundaries()?;
+    app.event_tx.try_send(FlowEvent::ClearOutput)?;
+    app.process_events();
+
+    assert!(app.flow_ui.phase_markers.is_empty());
+    assert!(!app.goto_phase_marker(1));
+    Ok(())
+}
+
+// =============================================================================
+// Error Navigation Tests
+// =============================================================================
+
+/// Sends 100 output lines with an error at 10, a warning at 40 and an error
+/// at 70 (info lines elsewhere), then processes the events.
+fn create_app_with_errors() -> Result<App> {
+    let mut app = create_scrollable_app();
+    app.flow_ui.output.clear();
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(128);
+    for i in 0..100 {
+        let line = match i {
+            10 | 70 => OutputLine::error(format!("Error {i}")),
+            40 => OutputLine::warning(format!("Warning {i}")),
+            _ => OutputLine::info(format!("Line {i}")),
+        };
+        app.event_tx.try_send(FlowEvent::Output(line))?;
+    }
+    app.process_events();
+    Ok(app)
+}
+
+#[test]
+fn error_lines_are_indexed_as_they_arrive() -> Result<()> {
+    let app = create_app_with_errors()?;
+    assert_eq!(app.flow_ui.error_lines, [10, 40, 70]);
+    Ok(())
+}
+
+#[test]
+fn n_jumps_to_next_error_and_wraps() -> Result<()> {
+    let mut app = create_app_with_errors()?;
+    app.is_running = true;
+    app.flow_ui.output_scroll.offset = 20;
+    app.flow_ui.output_scroll.auto_scroll = false;
+
+    let n = KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE);
+    app.handle_key(n);
+    assert_eq!(app.flow_ui.output_scroll.offset, 40);
+    app.handle_key(n);
+    assert_eq!(app.flow_ui.output_scroll.offset, 70);
+    app.handle_key(n);
+    assert_eq!(app.flow_ui.output_scroll.offset, 10, "Should wrap to first");
+    assert!(!app.flow_ui.output_scroll.auto_scroll);
+    Ok(())
+}
+
+#[test]
+fn shift_n_jumps_to_previous_error_and_wraps() -> Result<()> {
+    let mut app = create_app_with_errors()?;
+    app.is_running = true;
+    app.flow_ui.output_scroll.offset = 50;
+    app.flow_ui.output_scroll.auto_scroll = false;
+
+    let shift_n = KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT);
+    app.handle_key(shift_n);
+    assert_eq!(app.flow_ui.output_scroll.offset, 40);
+    app.handle_key(shift_n);
+    assert_eq!(app.flow_ui.output_scroll.offset, 10);
+    app.handle_key(shift_n);
+    assert_eq!(app.flow_ui.output_scroll.offset, 70, "Should wrap to last");
+    Ok(())
+}
+
+#[test]
+fn n_types_into_input_when_idle() -> Result<()> {
+    let mut app = create_app_with_errors()?;
+    app.flow_ui.output_scroll.offset = 0;
+
+    app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::NONE));
+
+    assert_eq!(app.flow_ui.output_scroll.offset, 0);
+    assert!(app.text_input.collect_text().contains('n'));
+    Ok(())
+}
+
+#[test]
+fn n_navigates_errors_in_finished_dialog() -> Result<()> {
+    let mut app = create_app_with_errors()?;
+    app.mode = AppMode::Finished;
+    app.flow_ui.output_scroll.offset = 80;
+
+    app.handle_key(KeyEvent::new(KeyCode::Char('N'), KeyModifiers::SHIFT));
+    assert_eq!(app.flow_ui.output_scroll.offset, 70);
+    assert_eq!(app.mode, AppMode::Finished);
+    Ok(())
+}
+
+#[test]
+fn truncation_shifts_error_index() {
+    let mut app = create_test_app_with_lines(&["test"], 0, 0);
+    app.push_output(OutputLine::error("first"));
+    for i in 0..MAX_OUTPUT_LINES {
+        if i == 5 {
+            app.push_output(OutputLine::warning("second"));
+        } else {
+            app.push_output(OutputLine::stdout(format!("Line {i}")));
+        }
+    }
+
+    assert_eq!(app.flow_ui.error_lines, [5]);
+    assert!(app.flow_ui.output[5].text.ends_with("second"));
+}
+
 // =============================================================================
 // Visual Line Counting Tests
 // =============================================================================
@@ -454,6 +763,13 @@ fn wrapping_exactly_at_boundary() {
     assert_eq!(count, 1, "Line exactly at width should not wrap");
 }
 
+#[test]
+fn one_column_width_wraps_every_character() {
+    let lines = vec![OutputLine::stdout("abc"), OutputLine::stdout("中中")];
+    // Wide characters exceed the row but still take one row each, with no empty rows
+    assert_eq!(calculate_visual_line_count(&lines, 1), 5);
+}
+
 #[test]
 fn wrapping_one_over_boundary() {
     let line_21 = "a".repeat(21);
@@ -484,7 +800,7 @@ fn render_chat_input_running_uses_flow_input_text() -> Result<()> {
     app.flow.input_text = "Flow task text\nWith completed tasks".to_string();
     app.is_running = true;
 
-    let terminal = render_app_to_terminal(&mut app, 60, 20)?;
+    let terminal = render_app_to_terminal(&mut app, 60, 25)?;
     terminal
         .backend()
         .assert_buffer_lines(styled_lines_from_buffer(
@@ -498,6 +814,8 @@ fn render_chat_input_running_uses_flow_input_text() -> Result<()> {
                 "│                                                          │",
                 "│                                                          │",
                 "│                                                          │",
+                "│                                                          │",
+                "│                                                          │",
                 "└──────────────────────────────────────────────────────────┘",
                 "┌ Task (Readonly) ─────────────────────────────────────────┐",
                 "│Flow task text                                            │",
@@ -505,11 +823,14 @@ fn render_chat_input_running_uses_flow_input_text() -> Result<()> {
                 "│                                                          │",
                 "│                                                          │",
                 "│                                                          │",
+                "│                                                          │",
+                "│                                                          │",
+                "│                                                          │",
                 "└ \\+Enter for newline ─────────────────────────────────────┘",
-                " · Waiting for input",
+                " · ⠋ Waiting for input",
                 "   Ready to process tasks",
                 " 0/1 files  ────────────────────────────────────────────────",
-                " [Esc] Cancel",
+                " [/] Command  [Esc] Cancel",
             ],
         ));
 
@@ -528,7 +849,7 @@ fn render_chat_input_idle_uses_textarea() -> Result<()> {
     app.flow.input_text = "Stale flow text from previous run".to_string();
     app.is_running = false;
 
-    let terminal = render_app_to_terminal(&mut app, 60, 20)?;
+    let terminal = render_app_to_terminal(&mut app, 60, 25)?;
     terminal
         .backend()
         .assert_buffer_lines(styled_lines_from_buffer(
@@ -544,6 +865,11 @@ fn render_chat_input_idle_uses_textarea() -> Result<()> {
                 "│                                                          │",
                 "│                                                          │",
                 "│                                                          │",
+                "│                                                          │",
+                "│                                                          │",
+                "│                                                          │",
+                "│                                                          │",
+                "│                                                          │",
                 "└──────────────────────────────────────────────────────────┘",
                 " · Waiting for input",
                 "   Ready to process tasks",
@@ -553,7 +879,7 @@ fn render_chat_input_idle_uses_textarea() -> Result<()> {
                 "│                                                          │",
                 "│                                                          │",
                 "└ \\+Enter for newline ─────────────────────────────────────┘",
-                " [Enter] Submit  [Ctrl+S] Settings",
+                " [Enter] Submit  [Ctrl+S] Settings  [Ctrl+T] Toggle Enter",
             ],
         ));
 
@@ -626,3 +952,431 @@ fn render_chat_idle_does_not_mutate_state() -> Result<()> {
 
     Ok(())
 }
+
+// =============================================================================
+// Finished Dialog Tests
+// =============================================================================
+
+/// Test that the finished dialog shows the last error with a copy hint.
+#[test]
+fn finished_dialog_shows_last_error() -> Result<()> {
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.mode = AppMode::Finished;
+    app.flow_ui.last_error =
+        Some("Failed to archive task-002.md: permission denied (os error 13)".to_string());
+
+    let terminal = render_app_to_terminal(&mut app, 80, 30)?;
+    let buffer = terminal.backend().buffer();
+    let rows: Vec<String> = (0..buffer.area.height)
+        .map(|y| {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect()
+        })
+        .collect();
+
+    assert!(rows.iter().any(|row| row.contains("Last error:")));
+    assert!(
+        rows.iter()
+            .any(|row| row.contains("Failed to archive task-002.md: permission denie…"))
+    );
+    assert!(rows.iter().any(|row| row.contains("[c] Copy error")));
+    Ok(())
+}
+
+// =============================================================================
+// Tiny Terminal Tests
+// =============================================================================
+
+/// Test that every mode renders at tiny and degenerate sizes without panicking.
+#[test]
+fn tiny_terminals_render_without_panicking() -> Result<()> {
+    let modes = [
+        AppMode::Chat,
+        AppMode::Settings,
+        AppMode::Finished,
+        AppMode::InitialSetup,
+        AppMode::ConfirmStart,
+        AppMode::PromptPreview,
+        AppMode::TaskView,
+    ];
+    let sizes = [
+        (0, 0),
+        (1, 1),
+        (2, 2),
+        (1, 30),
+        (80, 1),
+        (10, 4),
+        (20, 8),
+        (25, 12),
+    ];
+    for mode in modes {
+        for is_running in [false, true] {
+            for (width, height) in sizes {
+                let mut app =
+                    create_test_app_with_lines(&["a task that is longer than the screen"], 0, 5);
+                app.mode = mode;
+                app.is_running = is_running;
+                app.prompt_preview = Some(PromptPreview::new(
+                    crate::core::PromptKind::Planning,
+                    "line\n".repeat(50),
+                ));
+                for i in 0..20 {
+                    app.push_output(OutputLine::info(format!("output line {i} that wraps")));
+                }
+                render_app_to_terminal(&mut app, width, height)?;
+                app.handle_key(KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Test that the layout stays inside tiny areas.
+#[test]
+fn update_layout_with_tiny_areas_stays_in_bounds() {
+    let mut app = create_test_app_with_lines(&["task"], 0, 4);
+    for (width, height) in [(0, 0), (1, 1), (2, 3), (3, 2), (1, 40), (120, 1)] {
+        let area = ratatui::layout::Rect::new(0, 0, width, height);
+        app.update_layout(area);
+        let layout = app.layout.chat;
+        for region in [
+            layout.header,
+            layout.output,
+            layout.status,
+            layout.progress,
+            layout.input,
+            layout.footer,
+        ] {
+            assert_eq!(region.intersection(area), region, "{width}x{height}");
+        }
+        assert!(layout.output_visible_height <= usize::from(height));
+        assert!(layout.output_content_width <= usize::from(width));
+        assert!(layout.input_inner_width <= usize::from(width));
+    }
+}
+
+/// Test that a terminal below the minimum size shows a message instead of the UI.
+#[test]
+fn terminal_below_minimum_size_shows_message() -> Result<()> {
+    let mut app = create_test_app_with_lines(&["task"], 0, 4);
+    let terminal = render_app_to_terminal(&mut app, 19, 8)?;
+    terminal
+        .backend()
+        .assert_buffer_lines(styled_lines_from_buffer(
+            &terminal,
+            &[
+                "Terminal too small",
+                "Need at least 20x8",
+                "",
+                "",
+                "",
+                "",
+                "",
+                "",
+            ],
+        ));
+    Ok(())
+}
+
+// =============================================================================
+// Prompt Preview Tests
+// =============================================================================
+
+/// Test that `/preview` opens the wrapped prompt in a modal that scrolls and closes.
+#[tokio::test]
+async fn preview_command_opens_scrollable_prompt_modal() -> Result<()> {
+    let mut app = create_test_app_with_lines(&["/preview execution"], 0, 18);
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+    app.flow.input_text = "Refactor the parser".to_string();
+
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    // The prompt is built on a blocking thread
+    assert_eq!(app.mode, AppMode::Chat);
+    process_next_event(&mut app).await?;
+
+    assert_eq!(app.mode, AppMode::PromptPreview);
+    let Some(preview) = app.prompt_preview.clone() else {
+        anyhow::bail!("preview not opened");
+    };
+    assert_eq!(preview.kind, crate::core::PromptKind::Execution);
+    assert!(preview.text.contains("Refactor the parser"));
+    assert!(
+        preview
+            .text
+            .ends_with(crate::core::prompts::EXECUTION_POSTFIX_TEMPLATE)
+    );
+
+    let terminal = render_app_to_terminal(&mut app, 80, 30)?;
+    let buffer = terminal.backend().buffer();
+    let screen: String = (0..buffer.area.height)
+        .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+        .map(|pos| buffer[pos].symbol())
+        .collect();
+    assert!(screen.contains(" Execution Prompt "));
+    assert!(screen.contains("[Esc] Close"));
+
+    let max_scroll = preview.max_scroll();
+    app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    assert_eq!(app.prompt_preview.as_ref().map(|p| p.scroll), Some(1));
+    app.handle_key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
+    assert_eq!(
+        app.prompt_preview.as_ref().map(|p| p.scroll),
+        Some(max_scroll)
+    );
+    app.handle_key(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+    app.handle_key(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    assert_eq!(app.prompt_preview.as_ref().map(|p| p.scroll), Some(0));
+
+    app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert_eq!(app.mode, AppMode::Chat);
+    assert!(app.prompt_preview.is_none());
+    Ok(())
+}
+
+/// Test that `/preview` without a task reports it instead of opening the modal.
+#[tokio::test]
+async fn preview_command_without_task_shows_message() -> Result<()> {
+    let temp = tempfile::TempDir::new()?;
+    let mut app = create_test_app_with_paths(
+        &["/preview"],
+        0,
+        8,
+        crate::fs::McgravityPaths::new(temp.path()),
+    );
+    (app.event_tx, app.event_rx) = tokio::sync::mpsc::channel(16);
+
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    process_next_event(&mut app).await?;
+
+    assert_eq!(app.mode, AppMode::Chat);
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.contains("No task to preview"))
+    );
+    Ok(())
+}
+
+// =============================================================================
+// Task View Tests
+// =============================================================================
+
+/// Collects every cell of the rendered screen into one string.
+fn screen_text(terminal: &Terminal<TestBackend>) -> String {
+    let buffer = terminal.backend().buffer();
+    (0..buffer.area.height)
+        .flat_map(|y| (0..buffer.area.width).map(move |x| (x, y)))
+        .map(|pos| buffer[pos].symbol())
+        .collect()
+}
+
+/// Test that `t` during a run shows the live task text, which follows
+/// `TaskTextUpdated`.
+#[tokio::test]
+async fn task_view_follows_task_text_updates() -> Result<()> {
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.is_running = true;
+    app.flow.input_text = "Refactor the parser".to_string();
+
+    app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+    assert_eq!(app.mode, AppMode::TaskView);
+    let screen = screen_text(&render_app_to_terminal(&mut app, 80, 30)?);
+    assert!(screen.contains(" Task Text (live) "));
+    assert!(screen.contains("Refactor the parser"));
+
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    app.event_rx = rx;
+    tx.send(crate::app::state::FlowEvent::TaskTextUpdated(
+        "Refactor the parser\n<COMPLETED_TASKS>\n- Split the lexer\n</COMPLETED_TASKS>".to_string(),
+    ))
+    .await?;
+    app.process_events();
+
+    assert_eq!(app.mode, AppMode::TaskView);
+    let screen = screen_text(&render_app_to_terminal(&mut app, 80, 30)?);
+    assert!(screen.contains("- Split the lexer"));
+
+    app.handle_key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+    assert_eq!(app.task_view_scroll, 3);
+    app.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE));
+    assert_eq!(app.mode, AppMode::Chat);
+    Ok(())
+}
+
+/// Test that `/task` opens the modal when idle and reports a missing task.
+#[test]
+fn task_command_opens_task_view() {
+    let mut app = create_test_app_with_lines(&["/task"], 0, 5);
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.mode, AppMode::Chat);
+    assert!(
+        app.flow_ui
+            .output
+            .iter()
+            .any(|line| line.text.contains("No task text yet"))
+    );
+
+    app.flow.input_text = "Refactor the parser".to_string();
+    app.text_input.set_lines(vec!["/task".to_string()]);
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.mode, AppMode::TaskView);
+}
+
+// =============================================================================
+// Focus Tests
+// =============================================================================
+
+#[test]
+fn tab_toggles_focus_between_input_and_output() {
+    let mut app = create_scrollable_app();
+    assert_eq!(app.focus, Focus::Input);
+
+    app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+    assert_eq!(app.focus, Focus::Output);
+
+    app.handle_key(KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT));
+    assert_eq!(app.focus, Focus::Input);
+}
+
+#[test]
+fn output_focus_moves_line_selection_and_ignores_typing() {
+    let mut app = create_scrollable_app();
+    app.focus = Focus::Output;
+
+    // Enter copies only once a line is selected
+    app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(app.flow_ui.selected_line, None);
+    assert!(!app.is_running);
+    app.flow_ui.output.pop();
+    app.flow_ui.output_scroll.offset = 10;
+
+    // The first press selects the last visible line without scrolling
+    app.handle_key(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    assert_eq!(app.flow_ui.selected_line, Some(29));
+    assert_eq!(app.flow_ui.output_scroll.offset, 10);
+    // Moving past the bottom edge scrolls the selection into view
+    app.handle_key(char_key('j'));
+    assert_eq!(app.flow_ui.selected_line, Some(30));
+    assert_eq!(app.flow_ui.output_scroll.offset, 11);
+
+    // The selection is clamped to the buffer
+    app.handle_key(KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+    app.handle_key(char_key('k'));
+    assert_eq!(app.flow_ui.selected_line, Some(0));
+    assert_eq!(app.flow_ui.output_scroll.offset, 0);
+    app.handle_key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+    app.handle_key(char_key('j'));
+    assert_eq!(app.flow_ui.selected_line, Some(99));
+    assert_eq!(app.flow_ui.output_scroll.offset, 80);
+    assert!(app.flow_ui.output_scroll.auto_scroll);
+
+    app.handle_key(char_key('x'));
+    assert_eq!(app.text_input.lines(), ["test"]);
+    assert!(!app.is_running);
+}
+
+#[test]
+fn selected_line_text_includes_wrapped_continuation() {
+    let mut app = create_scrollable_app();
+    app.layout.chat.output_content_width = 10;
+    app.flow_ui.output.truncate(3);
+    app.flow_ui
+        .output
+        .push(OutputLine::stdout("error: a message long enough to wrap"));
+    app.focus = Focus::Output;
+
+    app.handle_key(KeyEvent::new(KeyCode::End, KeyModifiers::NONE));
+    assert_eq!(
+        app.selected_line_text(),
+        Some("error: a message long enough to wrap")
+    );
+    app.handle_key(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    assert_eq!(app.selected_line_text(), Some("Line 2"));
+}
+
+#[test]
+fn input_focus_needs_modifier_to_scroll() {
+    let mut app = create_scrollable_app();
+    app.flow_ui.output_scroll.offset = 10;
+
+    app.handle_key(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    app.handle_key(char_key('j'));
+    assert_eq!(app.flow_ui.output_scroll.offset, 10);
+    assert_eq!(app.text_input.lines(), ["jtest"]);
+}
+
+#[test]
+fn focused_region_border_is_highlighted() -> Result<()> {
+    let mut app = create_test_app_with_lines(&["test"], 0, 0);
+    let accent = app.theme.accent;
+    let backend = TestBackend::new(60, 20);
+    let mut terminal = Terminal::new(backend)?;
+    app.update_layout(ratatui::layout::Rect::new(0, 0, 60, 20));
+
+    // The output panel starts below the one-line header
+    terminal.draw(|frame| app.render(frame))?;
+    assert_ne!(terminal.backend().buffer()[(0, 1)].fg, accent);
+
+    app.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::NONE));
+    terminal.draw(|frame| app.render(frame))?;
+    assert_eq!(terminal.backend().buffer()[(0, 1)].fg, accent);
+    Ok(())
+}
+
+// =============================================================================
+// Progress Gauge Tests
+// =============================================================================
+
+/// Test that batch progress events drive the gauge label.
+#[tokio::test]
+async fn progress_gauge_shows_finished_todos() -> Result<()> {
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.is_running = true;
+
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    app.event_rx = rx;
+    tx.send(crate::app::state::FlowEvent::Progress(
+        crate::core::TodoProgress::new(1, 4),
+    ))
+    .await?;
+    app.process_events();
+
+    let screen = screen_text(&render_app_to_terminal(&mut app, 80, 30)?);
+    assert!(screen.contains(" 1/4 todos (25%) "));
+    Ok(())
+}
+
+/// Tests that the progress line previews the next todo, and the last one.
+#[tokio::test]
+async fn progress_line_shows_up_next() -> Result<()> {
+    let mut app = create_test_app_with_lines(&[""], 0, 0);
+    app.is_running = true;
+
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    app.event_rx = rx;
+    tx.send(crate::app::state::FlowEvent::UpNext(Some(
+        "Task 002: Add login".to_string(),
+    )))
+    .await?;
+    app.process_events();
+    let screen = screen_text(&render_app_to_terminal(&mut app, 80, 30)?);
+    assert!(screen.contains("Up next: Task 002: Add login"));
+
+    tx.send(crate::app::state::FlowEvent::UpNext(None)).await?;
+    app.process_events();
+    let screen = screen_text(&render_app_to_terminal(&mut app, 80, 30)?);
+    assert!(screen.contains("Up next: last task"));
+
+    // A finished batch has nothing up next
+    tx.send(crate::app::state::FlowEvent::Progress(
+        crate::core::TodoProgress::new(2, 2),
+    ))
+    .await?;
+    app.process_events();
+    let screen = screen_text(&render_app_to_terminal(&mut app, 80, 30)?);
+    assert!(!screen.contains("Up next"));
+    Ok(())
+}
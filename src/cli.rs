@@ -1,15 +1,673 @@
 //! CLI argument parsing using clap.
 
-use clap::Parser;
-use std::path::PathBuf;
+use anyhow::Context;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use tokio::sync::mpsc;
+
+use crate::app::{FlowEvent, SettingsState};
+use crate::core::channels::EVENT_CHANNEL_SIZE;
+use crate::core::doctor::{DoctorCheck, doctor_summary, run_doctor};
+use crate::core::runner::combine_input_files;
+use crate::core::{Model, PromptKind, TodoSpec, describe_resolution, plan_todos, preview_prompt};
+use crate::fs::McgravityPaths;
+
+/// Version string with build provenance, e.g. `0.1.8 (abc1234, 2026-01-01)`.
+///
+/// The git hash and build date are embedded by `build.rs`.
+pub const VERSION: &str = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("MCGRAVITY_GIT_HASH"),
+    ", ",
+    env!("MCGRAVITY_BUILD_DATE"),
+    ")"
+);
+
+/// Returns the program name and [`VERSION`], as printed by `--version`.
+#[must_use]
+pub fn build_info() -> String {
+    format!("mcgravity {VERSION}")
+}
 
 /// `McGravity` - AI CLI Orchestrator
 ///
-/// Orchestrates Codex CLI and Claude CLI to process tasks from a plan file.
+/// Orchestrates Codex CLI and Claude CLI to process tasks from plan files.
 /// If no input file is provided, opens an interactive text input screen.
 #[derive(Parser, Debug)]
-#[command(name = "mcgravity", version, about, long_about = None)]
+#[command(
+    name = "mcgravity",
+    version = VERSION,
+    about,
+    long_about = None
+)]
 pub struct Args {
-    /// Path to the input text file (optional - if omitted, shows text input screen)
-    pub input_file: Option<PathBuf>,
+    /// Utility subcommand to run instead of the TUI.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Print the fully wrapped prompt for a phase of the input files and exit
+    #[arg(long, value_name = "PHASE", requires = "input_files")]
+    pub print_prompt: Option<PromptPhase>,
+    /// Project directory to run in instead of the current directory
+    #[arg(long, short = 'C', value_name = "DIR", global = true)]
+    pub working_dir: Option<PathBuf>,
+    /// Print where a model's CLI command resolves and exit, non-zero if it
+    /// is not found
+    #[arg(long, value_name = "MODEL", value_parser = parse_model)]
+    pub which: Option<Model>,
+    /// Paths to input text files, planned together as one combined plan
+    /// (optional - if omitted, shows text input screen)
+    pub input_files: Vec<PathBuf>,
+}
+
+/// Utility subcommands that exit without starting the TUI.
+#[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Print a shell completion script to stdout
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate completions for
+        shell: Shell,
+    },
+    /// Run a single planning pass and print the pending todo files
+    Plan {
+        /// Print the todos as a JSON array of
+        /// `{ name, objective, steps, acceptance_criteria }`
+        #[arg(long)]
+        json: bool,
+        /// Paths to input text files, planned together as one combined plan
+        #[arg(required = true)]
+        input_files: Vec<PathBuf>,
+    },
+    /// Check the AI CLIs, the .mcgravity directory and git, exiting non-zero
+    /// if a critical check fails
+    Doctor,
+}
+
+/// Flow phase whose prompt `--print-prompt` prints.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptPhase {
+    /// The planning prompt that creates todo files.
+    Planning,
+    /// The execution prompt for a single todo.
+    Execution,
+}
+
+impl From<PromptPhase> for PromptKind {
+    fn from(phase: PromptPhase) -> Self {
+        match phase {
+            PromptPhase::Planning => Self::Planning,
+            PromptPhase::Execution => Self::Execution,
+        }
+    }
+}
+
+/// Parses a `--which` model from its display or command name.
+fn parse_model(name: &str) -> Result<Model, String> {
+    Model::from_name(name)
+        .ok_or_else(|| format!("unknown model '{name}' (expected codex, claude or gemini)"))
+}
+
+/// Resolves `model`'s CLI command for `--which`, honoring the binary path
+/// overrides in the settings at `paths`.
+///
+/// Returns the description to print and whether the command was found.
+#[must_use]
+pub fn which_model(model: Model, paths: &McgravityPaths) -> (String, bool) {
+    let settings = load_cli_settings(paths, "ignoring binary path overrides");
+    let resolution = settings.resolve_cli(model);
+    (
+        describe_resolution(model.command(), &resolution),
+        resolution.is_available(),
+    )
+}
+
+/// Reads the input files and returns the prompt `phase` would send for them
+/// with the settings at `paths`.
+///
+/// Several files are combined the same way a flow combines them.
+///
+/// # Errors
+///
+/// Returns an error if an input file cannot be read.
+pub fn render_prompt(
+    phase: PromptPhase,
+    input_files: &[PathBuf],
+    paths: &McgravityPaths,
+) -> anyhow::Result<String> {
+    Ok(render_prompt_for_text(
+        phase,
+        &read_input_files(input_files)?,
+        paths,
+    ))
+}
+
+/// Returns the prompt `phase` would send for `text` with the settings at
+/// `paths`.
+#[must_use]
+pub fn render_prompt_for_text(phase: PromptPhase, text: &str, paths: &McgravityPaths) -> String {
+    let settings = load_cli_settings(paths, "using the default settings");
+    preview_prompt(phase.into(), text, paths.base(), &settings.flow_options())
+}
+
+/// Reads and combines the input files the same way a flow combines them.
+fn read_input_files(input_files: &[PathBuf]) -> anyhow::Result<String> {
+    let mut inputs: Vec<(&Path, String)> = Vec::with_capacity(input_files.len());
+    for path in input_files {
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read input file {}", path.display()))?;
+        inputs.push((path.as_path(), text));
+    }
+    Ok(combine_input_files(&inputs))
+}
+
+/// Runs `mcgravity plan`: one planning pass with the configured planning
+/// model, then formats the pending todos.
+///
+/// Planner output is forwarded to stderr so stdout only carries the result.
+///
+/// # Errors
+///
+/// Returns an error if an input file cannot be read or planning fails.
+pub async fn run_plan(
+    input_files: &[PathBuf],
+    json: bool,
+    paths: &McgravityPaths,
+) -> anyhow::Result<String> {
+    let input_text = read_input_files(input_files)?;
+
+    let settings = load_cli_settings(paths, "using the default settings");
+    let planner = settings.executor(settings.planning_model);
+
+    let (tx, mut rx) = mpsc::channel(EVENT_CHANNEL_SIZE);
+    let forwarder = tokio::spawn(async move {
+        while let Some(event) = rx.recv().await {
+            match event {
+                FlowEvent::Output(line) | FlowEvent::PartialOutput(line) => {
+                    eprintln!("{}", line.text);
+                }
+                FlowEvent::TodoFileCreated(path) => eprintln!("Created {}", path.display()),
+                _ => {}
+            }
+        }
+    });
+    let todos = plan_todos(
+        &input_text,
+        planner.as_ref(),
+        paths,
+        &settings.flow_options(),
+        &tx,
+    )
+    .await;
+    drop(tx);
+    forwarder.await.ok();
+
+    format_plan(&todos?, json)
+}
+
+/// Formats planned todos as a JSON array, or as one `name: objective` line
+/// per todo.
+///
+/// # Errors
+///
+/// Returns an error if JSON serialization fails.
+pub fn format_plan(todos: &[TodoSpec], json: bool) -> anyhow::Result<String> {
+    if json {
+        return Ok(serde_json::to_string_pretty(todos)? + "\n");
+    }
+    let mut out = String::new();
+    for todo in todos {
+        writeln!(out, "{}: {}", todo.name, todo.objective)?;
+    }
+    Ok(out)
+}
+
+/// Runs the environment checks for `mcgravity doctor`.
+///
+/// The models selected in settings.json are the required ones; if the
+/// settings cannot be read, the default models are checked instead.
+#[must_use]
+pub fn run_doctor_command(paths: &McgravityPaths) -> Vec<DoctorCheck> {
+    let settings = load_cli_settings(paths, "checking the default models");
+    run_doctor(paths, &settings)
+}
+
+/// Loads the settings for a command-line utility, reporting problems on
+/// stderr.
+///
+/// If the settings cannot be read, the defaults are used and `fallback`
+/// explains what happens instead.
+fn load_cli_settings(paths: &McgravityPaths, fallback: &str) -> SettingsState {
+    let mut settings = SettingsState::default();
+    match paths.load_settings() {
+        Ok(persisted) => {
+            for warning in persisted.apply_to(&mut settings) {
+                eprintln!("Warning: {warning}");
+            }
+        }
+        Err(e) => eprintln!("Warning: {e:#}; {fallback}"),
+    }
+    settings
+}
+
+/// Formats a doctor report, one `✓`/`✗`/`!` line per check and a summary.
+///
+/// Passed checks are green, failed critical checks red and failed optional
+/// checks yellow when `color` is set.
+#[must_use]
+pub fn format_doctor_report(checks: &[DoctorCheck], color: bool) -> String {
+    const GREEN: &str = "\x1b[32m";
+    const RED: &str = "\x1b[31m";
+    const YELLOW: &str = "\x1b[33m";
+    const RESET: &str = "\x1b[0m";
+    let paint = |code: &str, text: &str| {
+        if color {
+            format!("{code}{text}{RESET}")
+        } else {
+            text.to_string()
+        }
+    };
+    let mut out = String::new();
+    for check in checks {
+        let mark = match (check.passed, check.critical) {
+            (true, _) => paint(GREEN, "✓"),
+            (false, true) => paint(RED, "✗"),
+            (false, false) => paint(YELLOW, "!"),
+        };
+        let _ = writeln!(out, "{mark} {}: {}", check.label, check.detail);
+    }
+    let summary = doctor_summary(checks);
+    let summary = if checks.iter().any(DoctorCheck::is_blocking) {
+        paint(RED, &summary)
+    } else {
+        paint(GREEN, &summary)
+    };
+    let _ = writeln!(out, "\n{summary}");
+    out
+}
+
+/// Resolves `--working-dir` to an absolute directory path.
+///
+/// # Errors
+///
+/// Returns an error if the path does not exist or is not a directory.
+pub fn resolve_working_dir(dir: &Path) -> anyhow::Result<PathBuf> {
+    let resolved = dir
+        .canonicalize()
+        .with_context(|| format!("Working directory {} does not exist", dir.display()))?;
+    anyhow::ensure!(
+        resolved.is_dir(),
+        "Working directory {} is not a directory",
+        dir.display()
+    );
+    Ok(resolved)
+}
+
+/// Makes relative input file paths absolute against `launch_dir`.
+///
+/// Input files are given relative to where `mcgravity` was launched, so they
+/// must be resolved before switching to `--working-dir`. A `-` (read the task
+/// from stdin) is kept as is.
+#[must_use]
+pub fn absolutize_inputs(input_files: Vec<PathBuf>, launch_dir: &Path) -> Vec<PathBuf> {
+    input_files
+        .into_iter()
+        .map(|path| {
+            if path.is_absolute() || path.as_os_str() == STDIN_INPUT {
+                path
+            } else {
+                launch_dir.join(path)
+            }
+        })
+        .collect()
+}
+
+/// Warns that the project is nested inside `ancestor`'s `.mcgravity` and asks
+/// whether to continue.
+///
+/// Only an answer starting with `y` continues; end of input declines.
+///
+/// # Errors
+///
+/// Returns an error if the prompt cannot be written or the answer read.
+pub fn confirm_nested_project(
+    ancestor: &Path,
+    input: &mut dyn std::io::BufRead,
+    output: &mut dyn std::io::Write,
+) -> std::io::Result<bool> {
+    write!(
+        output,
+        "{} already has a .mcgravity directory, so this project may be nested inside \
+         another flow.\nContinue anyway? [y/N] ",
+        ancestor.display()
+    )?;
+    output.flush()?;
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(answer.trim_start().to_lowercase().starts_with('y'))
+}
+
+/// Input file argument that reads the task text from stdin, as in
+/// `cat spec.md | mcgravity -`.
+pub const STDIN_INPUT: &str = "-";
+
+/// Reads the task text from stdin when it is the input.
+///
+/// Stdin is read when `input_files` is just `-` (which is removed), or when
+/// no input files are given and stdin is not a terminal. Returns `None` when
+/// stdin is not the input or holds only whitespace. This must run before the
+/// TUI starts, since the TUI reads keys from the terminal instead.
+///
+/// # Errors
+///
+/// Returns an error if `-` is combined with input files or stdin cannot be
+/// read.
+pub fn read_stdin_input(
+    input_files: &mut Vec<PathBuf>,
+    stdin_is_terminal: bool,
+    stdin: &mut dyn std::io::Read,
+) -> anyhow::Result<Option<String>> {
+    let requested = input_files
+        .iter()
+        .any(|path| path.as_os_str() == STDIN_INPUT);
+    if requested {
+        anyhow::ensure!(
+            input_files.len() == 1,
+            "`{STDIN_INPUT}` (read the task from stdin) cannot be combined with input files"
+        );
+        input_files.clear();
+    } else if !input_files.is_empty() || stdin_is_terminal {
+        return Ok(None);
+    }
+    let mut text = String::new();
+    stdin
+        .read_to_string(&mut text)
+        .context("Failed to read the task from stdin")?;
+    Ok((!text.trim().is_empty()).then_some(text))
+}
+
+/// Writes the completion script for `shell` to `out`.
+pub fn write_completions(shell: Shell, out: &mut dyn std::io::Write) {
+    clap_complete::generate(shell, &mut Args::command(), "mcgravity", out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_info_contains_crate_version() {
+        let info = build_info();
+        assert!(info.starts_with("mcgravity "));
+        assert!(info.contains(env!("CARGO_PKG_VERSION")));
+        assert!(info.ends_with(')'));
+    }
+
+    #[test]
+    fn doctor_report_marks_each_check() {
+        use crate::core::Model;
+        use crate::core::doctor::model_cli_check;
+
+        let checks = [
+            model_cli_check(Model::Codex, true, true),
+            model_cli_check(Model::Claude, false, true),
+            model_cli_check(Model::Gemini, false, false),
+        ];
+        let report = format_doctor_report(&checks, false);
+        let lines: Vec<&str> = report.lines().collect();
+        assert!(lines[0].starts_with("✓ Codex CLI: "));
+        assert!(lines[1].starts_with("✗ Claude Code CLI: "));
+        assert!(lines[2].starts_with("! Gemini CLI: "));
+        assert_eq!(lines.last(), Some(&"1 critical check failed."));
+        assert!(!report.contains('\x1b'));
+
+        let colored = format_doctor_report(&checks, true);
+        assert!(colored.contains("\x1b[31m✗\x1b[0m"));
+    }
+
+    #[test]
+    fn doctor_subcommand_parses() -> anyhow::Result<()> {
+        let args = Args::try_parse_from(["mcgravity", "doctor"])?;
+        assert_eq!(args.command, Some(Command::Doctor));
+        Ok(())
+    }
+
+    #[test]
+    fn version_flag_prints_build_info() {
+        let version = Args::command().render_version();
+        assert_eq!(version.trim_end(), build_info());
+    }
+
+    #[test]
+    fn plan_subcommand_requires_input_files() -> anyhow::Result<()> {
+        let args = Args::try_parse_from(["mcgravity", "plan", "--json", "task.md"])?;
+        assert_eq!(
+            args.command,
+            Some(Command::Plan {
+                json: true,
+                input_files: vec![PathBuf::from("task.md")],
+            })
+        );
+        assert!(Args::try_parse_from(["mcgravity", "plan", "--json"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn dash_reads_task_from_stdin() -> anyhow::Result<()> {
+        let mut input_files = vec![PathBuf::from(STDIN_INPUT)];
+        let text = read_stdin_input(&mut input_files, true, &mut "Build the thing\n".as_bytes())?;
+        assert_eq!(text.as_deref(), Some("Build the thing\n"));
+        assert!(input_files.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn piped_stdin_is_read_without_input_files() -> anyhow::Result<()> {
+        let mut input_files = Vec::new();
+        let text = read_stdin_input(&mut input_files, false, &mut "Piped spec".as_bytes())?;
+        assert_eq!(text.as_deref(), Some("Piped spec"));
+
+        // A terminal stdin, input files, or blank input leave stdin unused
+        assert_eq!(
+            read_stdin_input(&mut input_files, true, &mut "x".as_bytes())?,
+            None
+        );
+        assert_eq!(
+            read_stdin_input(&mut input_files, false, &mut " \n".as_bytes())?,
+            None
+        );
+        let mut input_files = vec![PathBuf::from("task.md")];
+        assert_eq!(
+            read_stdin_input(&mut input_files, false, &mut "x".as_bytes())?,
+            None
+        );
+        assert_eq!(input_files, [PathBuf::from("task.md")]);
+        Ok(())
+    }
+
+    #[test]
+    fn dash_cannot_be_combined_with_input_files() {
+        let mut input_files = vec![PathBuf::from("task.md"), PathBuf::from(STDIN_INPUT)];
+        assert!(read_stdin_input(&mut input_files, false, &mut "x".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn plan_text_format_lists_objectives() -> anyhow::Result<()> {
+        let todos = [TodoSpec {
+            name: "task-001.md".to_string(),
+            objective: "Add a login form.".to_string(),
+            steps: Vec::new(),
+            acceptance_criteria: Vec::new(),
+        }];
+        assert_eq!(
+            format_plan(&todos, false)?,
+            "task-001.md: Add a login form.\n"
+        );
+        assert!(format_plan(&todos, true)?.contains("\"acceptance_criteria\": []"));
+        Ok(())
+    }
+
+    #[test]
+    fn completions_are_generated_for_each_shell() -> anyhow::Result<()> {
+        for shell in ["bash", "zsh", "fish"] {
+            let args = Args::try_parse_from(["mcgravity", "completions", shell])?;
+            let Some(Command::Completions { shell }) = args.command else {
+                anyhow::bail!("expected completions subcommand for {shell}");
+            };
+            let mut out = Vec::new();
+            write_completions(shell, &mut out);
+            let script = String::from_utf8(out)?;
+            assert!(script.contains("mcgravity"), "{shell} script: {script}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn input_files_still_parse_without_subcommand() -> anyhow::Result<()> {
+        let args = Args::try_parse_from(["mcgravity", "plan.md", "extra.md"])?;
+        assert!(args.command.is_none());
+        assert_eq!(
+            args.input_files,
+            [PathBuf::from("plan.md"), PathBuf::from("extra.md")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn working_dir_flag_applies_to_doctor() -> anyhow::Result<()> {
+        for argv in [
+            ["mcgravity", "-C", "proj", "doctor"],
+            ["mcgravity", "doctor", "-C", "proj"],
+        ] {
+            let args = Args::try_parse_from(argv)?;
+            assert_eq!(args.command, Some(Command::Doctor));
+            assert_eq!(args.working_dir, Some(PathBuf::from("proj")));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn working_dir_flag_applies_to_plan() -> anyhow::Result<()> {
+        for argv in [
+            ["mcgravity", "-C", "proj", "plan", "plan.md"],
+            ["mcgravity", "plan", "-C", "proj", "plan.md"],
+        ] {
+            let args = Args::try_parse_from(argv)?;
+            assert!(matches!(args.command, Some(Command::Plan { .. })));
+            assert_eq!(args.working_dir, Some(PathBuf::from("proj")));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn which_flag_parses_model_names() -> anyhow::Result<()> {
+        let args = Args::try_parse_from(["mcgravity", "--which", "Claude"])?;
+        assert_eq!(args.which, Some(Model::Claude));
+        assert!(Args::try_parse_from(["mcgravity", "--which", "gpt"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn print_prompt_requires_input_files() {
+        assert!(Args::try_parse_from(["mcgravity", "--print-prompt", "planning"]).is_err());
+    }
+
+    #[test]
+    fn printed_prompt_wraps_input_with_guidelines_and_postfix() -> anyhow::Result<()> {
+        use crate::app::state::TodoNaming;
+        use crate::core::prompts::{EXECUTION_POSTFIX_TEMPLATE, planning_postfix};
+
+        let dir = tempfile::TempDir::new()?;
+        let plan = dir.path().join("plan.md");
+        std::fs::write(&plan, "Add a dark mode toggle")?;
+        let args = Args::try_parse_from([
+            std::ffi::OsStr::new("mcgravity"),
+            std::ffi::OsStr::new("--print-prompt"),
+            std::ffi::OsStr::new("execution"),
+            plan.as_os_str(),
+        ])?;
+        assert_eq!(args.print_prompt, Some(PromptPhase::Execution));
+
+        let planning = planning_postfix(TodoNaming::Sequential);
+        for (phase, postfix) in [
+            (PromptPhase::Planning, planning.as_str()),
+            (PromptPhase::Execution, EXECUTION_POSTFIX_TEMPLATE),
+        ] {
+            let prompt = render_prompt(phase, &args.input_files, &McgravityPaths::new(dir.path()))?;
+            assert!(prompt.contains("Add a dark mode toggle"), "{phase:?}");
+            assert!(
+                prompt.contains("read the project guideline files"),
+                "{phase:?}"
+            );
+            assert!(prompt.contains("<COMPLETED_TASKS>\n\n</COMPLETED_TASKS>"));
+            assert!(prompt.ends_with(postfix), "{phase:?}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn working_dir_flag_resolves_existing_directories() -> anyhow::Result<()> {
+        let dir = tempfile::TempDir::new()?;
+        let args = Args::try_parse_from([
+            std::ffi::OsStr::new("mcgravity"),
+            std::ffi::OsStr::new("--working-dir"),
+            dir.path().as_os_str(),
+            std::ffi::OsStr::new("plan.md"),
+        ])?;
+        let working_dir = args
+            .working_dir
+            .ok_or_else(|| anyhow::anyhow!("--working-dir not parsed"))?;
+        assert_eq!(
+            resolve_working_dir(&working_dir)?,
+            dir.path().canonicalize()?
+        );
+
+        let file = dir.path().join("plan.md");
+        std::fs::write(&file, "plan")?;
+        assert!(resolve_working_dir(&file).is_err());
+        assert!(resolve_working_dir(&dir.path().join("missing")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn input_files_resolve_against_launch_dir() {
+        let resolved = absolutize_inputs(
+            vec![PathBuf::from("plan.md"), PathBuf::from("/abs/extra.md")],
+            Path::new("/launch"),
+        );
+        assert_eq!(
+            resolved,
+            [
+                PathBuf::from("/launch/plan.md"),
+                PathBuf::from("/abs/extra.md")
+            ]
+        );
+    }
+
+    #[test]
+    fn stdin_input_is_not_resolved_against_launch_dir() {
+        let resolved = absolutize_inputs(vec![PathBuf::from(STDIN_INPUT)], Path::new("/launch"));
+        assert_eq!(resolved, [PathBuf::from(STDIN_INPUT)]);
+    }
+
+    #[test]
+    fn nested_project_prompt_requires_yes() -> anyhow::Result<()> {
+        for (answer, expected) in [("y\n", true), ("Yes\n", true), ("\n", false), ("", false)] {
+            let mut output = Vec::new();
+            let confirmed =
+                confirm_nested_project(Path::new("/work"), &mut answer.as_bytes(), &mut output)?;
+            assert_eq!(confirmed, expected, "{answer:?}");
+            assert!(String::from_utf8(output)?.starts_with("/work already has a .mcgravity"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn completions_subcommand_is_hidden_from_help() {
+        let help = Args::command().render_help().to_string();
+        assert!(!help.contains("completions"));
+    }
 }
@@ -0,0 +1,307 @@
+//! Attaching `@`-mentioned files to the planning prompt.
+//!
+//! With the `attach_mentioned_files` setting on, files referenced with `@`
+//! (typed like `@src/lib.rs`, or picked from the file popup) are recorded
+//! when the task is submitted. Every planning prompt then reads them again
+//! and adds their current contents in an `<ATTACHED_FILES>` block, so the
+//! planner sees their contents and not just their paths. The task text
+//! itself, and so `task.md`, never holds the contents. Each file and the
+//! block as a whole are size-capped.
+//!
+//! Only files inside the project directory or one of the extra roots are
+//! attached. A file outside them, or one that cannot be read, is reported
+//! as a warning and its path stays in the text as written.
+
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+/// Largest part of a single file that is attached.
+pub const MAX_ATTACHED_FILE_BYTES: usize = 8_000;
+
+/// Largest total size of attached file contents.
+pub const MAX_ATTACHED_TOTAL_BYTES: usize = 32_000;
+
+/// Opening tag of the attachments block.
+pub const ATTACHED_FILES_OPEN: &str = "<ATTACHED_FILES>";
+
+/// Punctuation that ends a sentence after a mention, like `@Cargo.toml.`
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '`', '"', '\''];
+
+/// The contents of attached files, rendered as a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AttachedFiles {
+    /// The `<ATTACHED_FILES>` block, or empty if nothing was attached.
+    pub block: String,
+    /// The files whose contents were attached, in order.
+    pub attached: Vec<PathBuf>,
+    /// One message per file that could not be attached.
+    pub warnings: Vec<String>,
+}
+
+/// Returns the paths mentioned with `@` in `text`, in order, without duplicates.
+#[must_use]
+pub fn mentioned_paths(text: &str) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for token in text.split_whitespace() {
+        let Some(mention) = token.strip_prefix('@') else {
+            continue;
+        };
+        let mention = mention.trim_end_matches(TRAILING_PUNCTUATION);
+        if mention.is_empty() {
+            continue;
+        }
+        let path = PathBuf::from(mention);
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Returns the files to attach for `text`: the `selected` files (picked
+/// from the popup) whose path is still in the text, then the `@` mentions
+/// naming existing files under `base_dir`.
+///
+/// Mentions that are not files, such as directories or words that merely
+/// start with `@`, are left out. Selected files are kept even if they are
+/// gone, so [`attach_files`] can report them.
+#[must_use]
+pub fn attachment_candidates(text: &str, base_dir: &Path, selected: &[PathBuf]) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = selected
+        .iter()
+        .filter(|path| text.contains(path.to_string_lossy().as_ref()))
+        .cloned()
+        .collect();
+    for path in mentioned_paths(text) {
+        if !paths.contains(&path) && base_dir.join(&path).is_file() {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Returns true if `path` (relative to `base_dir`) resolves to a location
+/// inside `base_dir` or one of the `extra_roots`, after following `..` and
+/// symlinks. Paths that do not exist are not inside.
+#[must_use]
+pub fn is_within_workspace(path: &Path, base_dir: &Path, extra_roots: &[PathBuf]) -> bool {
+    let Ok(resolved) = base_dir.join(path).canonicalize() else {
+        return false;
+    };
+    std::iter::once(base_dir.to_path_buf())
+        .chain(extra_roots.iter().map(|root| base_dir.join(root)))
+        .filter_map(|root| root.canonicalize().ok())
+        .any(|root| resolved.starts_with(root))
+}
+
+/// Reads the files at `paths` (relative to `base_dir`) into an
+/// `<ATTACHED_FILES>` block.
+///
+/// A file outside the workspace (see [`is_within_workspace`]) or one that
+/// cannot be read as text is skipped with a warning.
+#[must_use]
+pub fn attach_files(base_dir: &Path, extra_roots: &[PathBuf], paths: &[PathBuf]) -> AttachedFiles {
+    let mut result = AttachedFiles::default();
+    let mut block = String::new();
+    let mut remaining = MAX_ATTACHED_TOTAL_BYTES;
+    for path in paths {
+        let display = path.display();
+        let full_path = base_dir.join(path);
+        if full_path.exists() && !is_within_workspace(path, base_dir, extra_roots) {
+            result.warnings.push(format!(
+                "{display} is outside the workspace; keeping its path as text"
+            ));
+            continue;
+        }
+        let content = match std::fs::read_to_string(full_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                result.warnings.push(format!(
+                    "{display} no longer exists; keeping its path as text"
+                ));
+                continue;
+            }
+            Err(e) => {
+                result.warnings.push(format!(
+                    "Could not attach {display} ({e}); keeping its path as text"
+                ));
+                continue;
+            }
+        };
+        if remaining == 0 {
+            let _ = writeln!(
+                block,
+                "<FILE path=\"{display}\">\n(omitted: attachment limit reached)\n</FILE>"
+            );
+            continue;
+        }
+        let limit = MAX_ATTACHED_FILE_BYTES.min(remaining);
+        let (content, truncated) = truncate_at_char_boundary(&content, limit);
+        remaining -= content.len();
+        let _ = writeln!(block, "<FILE path=\"{display}\">\n{}", content.trim_end());
+        if truncated {
+            block.push_str("... (truncated)\n");
+        }
+        block.push_str("</FILE>\n");
+        result.attached.push(path.clone());
+    }
+
+    if !block.is_empty() {
+        result.block = format!("{ATTACHED_FILES_OPEN}\n{block}</ATTACHED_FILES>\n");
+    }
+    result
+}
+
+/// Cuts `text` to at most `max_bytes`, backing off to a char boundary.
+///
+/// Returns the kept part and whether anything was cut.
+fn truncate_at_char_boundary(text: &str, max_bytes: usize) -> (&str, bool) {
+    if text.len() <= max_bytes {
+        return (text, false);
+    }
+    let mut end = max_bytes;
+    while end > 0 && !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    (&text[..end], true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn mentions_are_found_in_order_without_duplicates() {
+        assert_eq!(
+            mentioned_paths("Update @Cargo.toml, then @src/lib.rs and @Cargo.toml. Ask @ me"),
+            vec![PathBuf::from("Cargo.toml"), PathBuf::from("src/lib.rs")]
+        );
+    }
+
+    #[test]
+    fn mentioned_files_are_attached() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n",
+        )?;
+        std::fs::create_dir(dir.path().join("src"))?;
+
+        let text = "Bump @Cargo.toml and check @src and @alice";
+        let paths = attachment_candidates(text, dir.path(), &[]);
+        let result = attach_files(dir.path(), &[], &paths);
+
+        assert_eq!(result.attached, vec![PathBuf::from("Cargo.toml")]);
+        assert!(result.warnings.is_empty());
+        assert_eq!(
+            result.block,
+            "<ATTACHED_FILES>\n\
+             <FILE path=\"Cargo.toml\">\n[package]\nname = \"demo\"\n</FILE>\n\
+             </ATTACHED_FILES>\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn large_files_are_truncated_and_the_total_is_capped() -> Result<()> {
+        let dir = TempDir::new()?;
+        let paths: Vec<PathBuf> = (0..6)
+            .map(|i| PathBuf::from(format!("big{i}.txt")))
+            .collect();
+        for path in &paths {
+            std::fs::write(dir.path().join(path), "é".repeat(MAX_ATTACHED_FILE_BYTES))?;
+        }
+
+        let result = attach_files(dir.path(), &[], &paths);
+
+        assert_eq!(result.attached.len(), 4);
+        assert_eq!(result.block.matches("... (truncated)").count(), 4);
+        assert_eq!(
+            result
+                .block
+                .matches("(omitted: attachment limit reached)")
+                .count(),
+            2
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn no_files_give_no_block() -> Result<()> {
+        let dir = TempDir::new()?;
+        assert_eq!(attach_files(dir.path(), &[], &[]), AttachedFiles::default());
+        Ok(())
+    }
+
+    #[test]
+    fn selected_files_still_in_the_text_are_candidates() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(dir.path().join("a.rs"), "")?;
+        let selected = [PathBuf::from("gone.rs"), PathBuf::from("removed.rs")];
+
+        assert_eq!(
+            attachment_candidates("Fix gone.rs and @a.rs", dir.path(), &selected),
+            vec![PathBuf::from("gone.rs"), PathBuf::from("a.rs")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unreadable_files_are_skipped_with_a_warning() -> Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(dir.path().join("logo.png"), [0xff, 0xfe, 0x00])?;
+        std::fs::write(dir.path().join("a.rs"), "fn a() {}")?;
+        let paths = [
+            PathBuf::from("gone.rs"),
+            PathBuf::from("logo.png"),
+            PathBuf::from("a.rs"),
+        ];
+
+        let result = attach_files(dir.path(), &[], &paths);
+
+        assert_eq!(result.attached, vec![PathBuf::from("a.rs")]);
+        assert_eq!(result.warnings.len(), 2);
+        assert_eq!(
+            result.warnings[0],
+            "gone.rs no longer exists; keeping its path as text"
+        );
+        assert!(result.warnings[1].starts_with("Could not attach logo.png"));
+        assert!(
+            result
+                .block
+                .starts_with("<ATTACHED_FILES>\n<FILE path=\"a.rs\">")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn files_outside_the_workspace_are_not_attached() -> Result<()> {
+        let dir = TempDir::new()?;
+        let project = dir.path().join("app");
+        let api = dir.path().join("api");
+        std::fs::create_dir_all(&project)?;
+        std::fs::create_dir_all(&api)?;
+        std::fs::write(dir.path().join("secret.txt"), "token")?;
+        std::fs::write(api.join("schema.sql"), "create table t;")?;
+        let paths = [
+            PathBuf::from("../secret.txt"),
+            dir.path().join("secret.txt"),
+            PathBuf::from("../api/schema.sql"),
+        ];
+
+        let result = attach_files(&project, &[PathBuf::from("../api")], &paths);
+
+        assert_eq!(result.attached, vec![PathBuf::from("../api/schema.sql")]);
+        assert_eq!(result.warnings.len(), 2);
+        assert!(
+            result
+                .warnings
+                .iter()
+                .all(|w| w.ends_with("is outside the workspace; keeping its path as text"))
+        );
+        assert!(!result.block.contains("token"));
+        Ok(())
+    }
+}
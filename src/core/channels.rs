@@ -0,0 +1,37 @@
+//! Capacities of the bounded channels between the flow, the executors and the UI.
+//!
+//! Every channel is bounded, so a sender waits once its buffer is full. The
+//! capacity trades memory for slack:
+//!
+//! - A small buffer keeps memory low, but a chatty executor stalls on every
+//!   send until the receiver catches up. Receivers always drain concurrently
+//!   with the sender, so a full buffer slows a run down but never deadlocks it.
+//! - A large buffer absorbs bursts (a compiler dumping thousands of lines at
+//!   once) without slowing the executor, at the cost of holding that many
+//!   queued messages in memory while the UI falls behind.
+//!
+//! Only the executor output buffer can be changed, through the
+//! `output_buffer_size` entry in settings.json, since it carries by far the
+//! most traffic.
+
+/// Capacity of the flow event channel feeding the UI.
+pub const EVENT_CHANNEL_SIZE: usize = 1000;
+
+/// Capacity of the channel carrying `@` file search queries. Queries are
+/// debounced and stale ones are ignored, so only a few are ever queued.
+pub const SEARCH_CHANNEL_SIZE: usize = 16;
+
+/// Default capacity of the channel carrying an executor attempt's output to
+/// the forwarder; overridden by the `output_buffer_size` setting.
+pub const EXECUTOR_OUTPUT_CHANNEL_SIZE: usize = 1000;
+
+/// Capacity of the channel capturing summary generation output, which is
+/// short and collected in one place.
+pub const SUMMARY_OUTPUT_CHANNEL_SIZE: usize = 100;
+
+/// Capacity of the channel carrying hook command output.
+pub const HOOK_OUTPUT_CHANNEL_SIZE: usize = 1000;
+
+/// Capacity of the channel feeding the per-cycle logger, which forwards
+/// every flow event.
+pub const CYCLE_LOG_CHANNEL_SIZE: usize = EVENT_CHANNEL_SIZE;
@@ -12,6 +12,8 @@
 //!
 //! The resolution algorithm follows this priority:
 //!
+//! 0. A binary path override from settings, passed in by the caller, if it is
+//!    an executable file
 //! 1. Direct PATH scan via `which` (Unix) or `where` (Windows)
 //! 2. If not found, shell-based resolution via the user's login shell
 //! 3. Executability verification for PATH-resolved commands
@@ -24,6 +26,7 @@
 //! # Classification
 //!
 //! Commands are classified into categories for appropriate execution strategy:
+//! - [`CommandResolution::PathOverride`]: Configured binary, can use `Command::new(path)`
 //! - [`CommandResolution::PathExecutable`]: Direct executable, can use `Command::new(path)`
 //! - [`CommandResolution::ShellAlias`]: Requires shell wrapper for execution
 //! - [`CommandResolution::ShellFunction`]: Requires shell wrapper for execution
@@ -32,6 +35,7 @@
 //!
 //! See `docs/adding-executors.md` for the complete resolution strategy documentation.
 
+use std::ffi::OsStr;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
@@ -41,6 +45,10 @@ use std::process::Command;
 /// See `docs/adding-executors.md` for detailed classification rules.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CommandResolution {
+    /// Command configured with a binary path override in settings.
+    ///
+    /// Can be executed directly via `Command::new(path)`.
+    PathOverride(PathBuf),
     /// Command found as executable in PATH.
     ///
     /// Can be executed directly via `Command::new(path)`.
@@ -68,11 +76,11 @@ impl CommandResolution {
         !matches!(self, Self::NotFound)
     }
 
-    /// Returns the resolved path if this is a `PathExecutable`.
+    /// Returns the resolved path if this is a `PathOverride` or `PathExecutable`.
     #[must_use]
     pub fn path(&self) -> Option<&PathBuf> {
         match self {
-            Self::PathExecutable(path) => Some(path),
+            Self::PathOverride(path) | Self::PathExecutable(path) => Some(path),
             _ => None,
         }
     }
@@ -138,6 +146,8 @@ pub fn is_safe_command_name(command: &str) -> bool {
 ///
 /// # Arguments
 /// * `command` - The command name to resolve (e.g., "claude", "codex")
+/// * `path_override` - A configured binary for the command, used when it is
+///   an executable file
 ///
 /// # Returns
 /// A [`CommandResolution`] indicating how the command can be executed.
@@ -146,20 +156,38 @@ pub fn is_safe_command_name(command: &str) -> bool {
 /// ```no_run
 /// use mcgravity::core::cli_check::resolve_cli_command;
 ///
-/// let resolution = resolve_cli_command("claude");
+/// let resolution = resolve_cli_command("claude", None);
 /// if resolution.is_available() {
 ///     println!("Claude CLI is available");
 /// }
 /// ```
 #[must_use]
-pub fn resolve_cli_command(command: &str) -> CommandResolution {
+pub fn resolve_cli_command(command: &str, path_override: Option<&Path>) -> CommandResolution {
+    resolve_cli_command_with_path(command, path_override, None)
+}
+
+/// Resolves a CLI command like [`resolve_cli_command`], looking executables
+/// up in `path_var` instead of the `PATH` environment variable when given.
+///
+/// The shell-based fallback still uses the user's login shell profile.
+#[must_use]
+pub fn resolve_cli_command_with_path(
+    command: &str,
+    path_override: Option<&Path>,
+    path_var: Option<&OsStr>,
+) -> CommandResolution {
     // Security: validate command name first
     if !is_safe_command_name(command) {
         return CommandResolution::NotFound;
     }
 
+    // An unusable override falls back to the normal lookup
+    if let Some(path) = path_override.filter(|path| is_executable(path)) {
+        return CommandResolution::PathOverride(path.to_path_buf());
+    }
+
     // Step 1: Fast PATH lookup
-    if let Some(resolution) = try_path_lookup(command) {
+    if let Some(resolution) = try_path_lookup(command, path_var) {
         return resolution;
     }
 
@@ -172,17 +200,40 @@ pub fn resolve_cli_command(command: &str) -> CommandResolution {
     CommandResolution::NotFound
 }
 
+/// Checks that a binary path override from the `setting` can be used.
+///
+/// Returns a warning when `path` does not exist or is not an executable
+/// file, in which case the command is looked up on `PATH` instead.
+#[must_use]
+pub fn check_cli_path_override(setting: &str, path: &Path) -> Option<String> {
+    let problem = if !path.exists() {
+        "does not exist"
+    } else if !is_executable(path) {
+        "is not an executable file"
+    } else {
+        return None;
+    };
+    Some(format!(
+        "{setting} {} {problem}; falling back to PATH lookup",
+        path.display()
+    ))
+}
+
 /// Attempts to find a command via direct PATH lookup using `which`/`where`.
 ///
 /// Returns `Some(CommandResolution::PathExecutable)` if found and executable,
-/// `None` otherwise.
-fn try_path_lookup(command: &str) -> Option<CommandResolution> {
+/// `None` otherwise. `path_var` replaces the `PATH` searched when given.
+fn try_path_lookup(command: &str, path_var: Option<&OsStr>) -> Option<CommandResolution> {
     #[cfg(windows)]
     let check_cmd = "where";
     #[cfg(not(windows))]
     let check_cmd = "which";
 
-    let output = Command::new(check_cmd)
+    let mut lookup = Command::new(check_cmd);
+    if let Some(path_var) = path_var {
+        lookup.env("PATH", path_var);
+    }
+    let output = lookup
         .arg(command)
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::null())
@@ -316,6 +367,33 @@ fn classify_command_v_output(output: &str, command: &str) -> CommandResolution {
     CommandResolution::ShellAlias(output.to_string())
 }
 
+/// Describes how `command` resolved, for `/which` and `--which`.
+///
+/// Names the resolved executable and whether it came from a path override,
+/// `PATH` or only the login shell, or reports that the command was not found.
+#[must_use]
+pub fn describe_resolution(command: &str, resolution: &CommandResolution) -> String {
+    match resolution {
+        CommandResolution::PathOverride(path) => {
+            format!(
+                "`{command}` resolves to {} (via path override)",
+                path.display()
+            )
+        }
+        CommandResolution::PathExecutable(path) => {
+            format!("`{command}` resolves to {} (via PATH)", path.display())
+        }
+        CommandResolution::ShellAlias(definition) => {
+            format!("`{command}` is a shell alias (via login shell): {definition}")
+        }
+        CommandResolution::ShellFunction(_) => {
+            format!("`{command}` is a shell function (via login shell)")
+        }
+        CommandResolution::ShellBuiltin => format!("`{command}` is a shell builtin"),
+        CommandResolution::NotFound => format!("`{command}` not found"),
+    }
+}
+
 /// Checks if a CLI command is available and executable.
 ///
 /// Uses the shell-aware resolution strategy documented in `docs/adding-executors.md`:
@@ -338,7 +416,7 @@ fn classify_command_v_output(output: &str, command: &str) -> CommandResolution {
 /// The command name is validated before any shell invocation. See [`is_safe_command_name`].
 #[must_use]
 pub fn check_cli_in_path(command: &str) -> bool {
-    resolve_cli_command(command).is_available()
+    resolve_cli_command(command, None).is_available()
 }
 
 /// Stores CLI availability status for all supported AI models.
@@ -538,7 +616,7 @@ mod tests {
             // 'sh' should exist on all Unix systems as an executable in PATH
             #[cfg(not(windows))]
             {
-                let resolution = resolve_cli_command("sh");
+                let resolution = resolve_cli_command("sh", None);
                 assert!(resolution.is_available(), "sh should be available via PATH");
 
                 // Should be resolved as PathExecutable with a valid path
@@ -557,7 +635,7 @@ mod tests {
             // 'cmd' should exist on Windows
             #[cfg(windows)]
             {
-                let resolution = resolve_cli_command("cmd");
+                let resolution = resolve_cli_command("cmd", None);
                 assert!(
                     resolution.is_available(),
                     "cmd should be available on Windows"
@@ -568,7 +646,8 @@ mod tests {
         /// Tests that a nonexistent command returns `NotFound`.
         #[test]
         fn nonexistent_command_returns_not_found() {
-            let resolution = resolve_cli_command("this_command_definitely_does_not_exist_xyz789");
+            let resolution =
+                resolve_cli_command("this_command_definitely_does_not_exist_xyz789", None);
             assert_eq!(resolution, CommandResolution::NotFound);
             assert!(!resolution.is_available());
         }
@@ -577,17 +656,17 @@ mod tests {
         #[test]
         fn invalid_command_names_return_not_found() {
             // Empty
-            assert_eq!(resolve_cli_command(""), CommandResolution::NotFound);
+            assert_eq!(resolve_cli_command("", None), CommandResolution::NotFound);
 
             // With path separators
             assert_eq!(
-                resolve_cli_command("not/a/valid/command"),
+                resolve_cli_command("not/a/valid/command", None),
                 CommandResolution::NotFound
             );
 
             // Injection attempt
             assert_eq!(
-                resolve_cli_command("cmd; rm -rf"),
+                resolve_cli_command("cmd; rm -rf", None),
                 CommandResolution::NotFound
             );
         }
@@ -600,7 +679,7 @@ mod tests {
             let dangerous_inputs = ["$(whoami)", "`id`", "x; y", "x | y", "x && y", "x || y"];
 
             for input in dangerous_inputs {
-                let resolution = resolve_cli_command(input);
+                let resolution = resolve_cli_command(input, None);
                 assert_eq!(
                     resolution,
                     CommandResolution::NotFound,
@@ -608,6 +687,91 @@ mod tests {
                 );
             }
         }
+
+        /// Tests that a usable path override wins over `PATH`, and an unusable
+        /// one is reported and ignored.
+        #[cfg(unix)]
+        #[test]
+        fn path_override_is_used_when_executable() -> anyhow::Result<()> {
+            use std::fs::{self, File};
+            use std::os::unix::fs::PermissionsExt;
+
+            let dir = tempfile::TempDir::new()?;
+            let stub = dir.path().join("custom-cli");
+            File::create(&stub)?;
+            fs::set_permissions(&stub, fs::Permissions::from_mode(0o755))?;
+
+            let resolution = resolve_cli_command("mcgravity-override-cli", Some(&stub));
+            assert_eq!(resolution, CommandResolution::PathOverride(stub.clone()));
+            assert_eq!(
+                describe_resolution("mcgravity-override-cli", &resolution),
+                format!(
+                    "`mcgravity-override-cli` resolves to {} (via path override)",
+                    stub.display()
+                )
+            );
+            assert_eq!(check_cli_path_override("codex_path", &stub), None);
+
+            let missing = dir.path().join("missing-cli");
+            assert_eq!(
+                resolve_cli_command("mcgravity-override-cli", Some(&missing)),
+                CommandResolution::NotFound
+            );
+            assert_eq!(
+                check_cli_path_override("codex_path", &missing),
+                Some(format!(
+                    "codex_path {} does not exist; falling back to PATH lookup",
+                    missing.display()
+                ))
+            );
+            Ok(())
+        }
+
+        /// Tests that a stub binary on a custom PATH is reported with its
+        /// path, and a missing command as not found.
+        #[cfg(unix)]
+        #[test]
+        fn resolution_with_custom_path_reports_stub_binary() -> anyhow::Result<()> {
+            use std::fs::{self, File};
+            use std::os::unix::fs::PermissionsExt;
+
+            let dir = tempfile::TempDir::new()?;
+            let stub = dir.path().join("mcgravity-stub-cli");
+            File::create(&stub)?;
+            fs::set_permissions(&stub, fs::Permissions::from_mode(0o755))?;
+            // Keep the system PATH after the stub so `which` itself is found
+            let system_path = std::env::var_os("PATH").unwrap_or_default();
+            let path_var = std::env::join_paths(
+                std::iter::once(dir.path().to_path_buf())
+                    .chain(std::env::split_paths(&system_path)),
+            )?;
+
+            let resolution = resolve_cli_command_with_path(
+                "mcgravity-stub-cli",
+                None,
+                Some(path_var.as_os_str()),
+            );
+            assert_eq!(resolution, CommandResolution::PathExecutable(stub.clone()));
+            assert_eq!(
+                describe_resolution("mcgravity-stub-cli", &resolution),
+                format!(
+                    "`mcgravity-stub-cli` resolves to {} (via PATH)",
+                    stub.display()
+                )
+            );
+
+            let missing = resolve_cli_command_with_path(
+                "mcgravity-missing-cli",
+                None,
+                Some(path_var.as_os_str()),
+            );
+            assert_eq!(missing, CommandResolution::NotFound);
+            assert_eq!(
+                describe_resolution("mcgravity-missing-cli", &missing),
+                "`mcgravity-missing-cli` not found"
+            );
+            Ok(())
+        }
     }
 
     // =========================================================================
@@ -721,7 +885,7 @@ mod tests {
             // `echo` is a builtin in most shells, should be resolvable
             // Note: We test with 'ls' which is typically a PATH executable
             // to ensure the shell can resolve commands
-            let resolution = resolve_cli_command("ls");
+            let resolution = resolve_cli_command("ls", None);
             assert!(
                 resolution.is_available(),
                 "ls should be resolvable via shell or PATH"
@@ -29,7 +29,10 @@
 //! }
 //! ```
 
+use std::path::PathBuf;
+
 use crate::app::state::AppMode;
+use crate::core::{FileRef, Model, PromptKind, StepSignal, parse_file_ref};
 
 /// Result of executing a slash command.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -42,8 +45,80 @@ pub enum CommandResult {
     OpenSettings,
     /// Command requests clearing task, output, and todo files.
     Clear,
+    /// Command requests clearing only the output panel.
+    ClearOutput,
     /// Command executed with a message to display.
     Message(String),
+    /// Command rejected its arguments, with a warning to display.
+    Warning(String),
+    /// Command requests terminating orphaned CLI processes from a previous session.
+    ReapOrphans,
+    /// Command requests an environment check report.
+    Doctor,
+    /// Command requests saving the task text to disk immediately.
+    Save,
+    /// Command requests editing a todo file in `$EDITOR`.
+    ///
+    /// `None` lists the editable files; `Some(n)` opens the file numbered `n`.
+    EditTodoFile(Option<usize>),
+    /// Command requests showing the current cycle and iteration cap.
+    ShowCycles,
+    /// Command requests changing the running flow's iteration cap
+    /// (`None` = unlimited).
+    SetMaxIterations(Option<u32>),
+    /// Command requests showing the git changes made since the flow started.
+    ShowDiff,
+    /// Command requests showing prompt size totals for the flow.
+    ShowStats,
+    /// Command requests regenerating the last completed-task summary.
+    Resummarize,
+    /// Command requests scrolling the output to a phase boundary.
+    ///
+    /// `None` lists the boundaries; `Some(n)` jumps to the boundary numbered `n`.
+    GotoPhase(Option<usize>),
+    /// Command requests copying the most recent flow error to the clipboard.
+    CopyLastError,
+    /// Command requests restoring all settings to their defaults (confirmed).
+    ResetSettings,
+    /// Command requests showing the wrapped prompt of a phase for the current task.
+    PreviewPrompt(PromptKind),
+    /// Command requests loading a file's content into the text input.
+    LoadTask(PathBuf),
+    /// Command requests releasing a step-mode flow paused after a cycle.
+    Step(StepSignal),
+    /// Command requests cancelling the running flow, like ESC.
+    CancelFlow,
+    /// Command requests showing the live task text in a modal.
+    ShowTaskText,
+    /// Command requests showing the project directory flows run in.
+    ShowWorkingDir,
+    /// Command requests snapshotting task.md and the todo files.
+    Snapshot,
+    /// Command requests restoring a snapshot.
+    ///
+    /// `None` lists the snapshots; `Some(name)` restores the snapshot `name`
+    /// (confirmed).
+    RestoreSnapshot(Option<String>),
+    /// Command requests opening a file mentioned in the output in `$EDITOR`.
+    ///
+    /// `None` opens the most recent existing file mentioned in the output;
+    /// `Some(file)` opens the given path.
+    OpenFile(Option<FileRef>),
+    /// Command requests listing the numbered pending todo files.
+    ListTodos,
+    /// Command requests deleting the pending todo file with the given 1-based
+    /// number (confirmed).
+    RemoveTodo(usize),
+    /// Command requests showing the active models and flow settings.
+    ShowConfig,
+    /// Command requests opening the model picker.
+    OpenModelPicker,
+    /// Command requests following a log file in the output panel.
+    TailFile(PathBuf),
+    /// Command requests stopping the log file started with `/tail`.
+    StopTail,
+    /// Command requests showing where a model's CLI command resolves.
+    WhichCli(Model),
 }
 
 /// Context provided to commands during execution.
@@ -53,6 +128,8 @@ pub struct CommandContext<'a> {
     pub is_running: bool,
     /// Current application mode.
     pub mode: &'a AppMode,
+    /// Arguments following the command name, if any.
+    pub args: Option<&'a str>,
 }
 
 /// Trait for implementing slash commands.
@@ -131,104 +208,839 @@ impl CommandRegistry {
             .map(AsRef::as_ref)
     }
 
-    /// Returns all registered commands.
-    #[must_use]
-    pub fn all(&self) -> &[Box<dyn SlashCommand>] {
-        &self.commands
+    /// Returns all registered commands.
+    #[must_use]
+    pub fn all(&self) -> &[Box<dyn SlashCommand>] {
+        &self.commands
+    }
+
+    /// Returns all commands whose names start with the given prefix.
+    ///
+    /// Useful for autocomplete functionality.
+    #[must_use]
+    pub fn matching(&self, prefix: &str) -> Vec<&dyn SlashCommand> {
+        self.commands
+            .iter()
+            .filter(|cmd| cmd.name().starts_with(prefix))
+            .map(AsRef::as_ref)
+            .collect()
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommandRegistry {
+    /// Creates a registry with all built-in commands pre-registered.
+    #[must_use]
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(ExitCommand));
+        registry.register(Box::new(SettingsCommand));
+        registry.register(Box::new(ClearCommand));
+        registry.register(Box::new(ClsCommand));
+        registry.register(Box::new(ReapCommand));
+        registry.register(Box::new(SaveCommand));
+        registry.register(Box::new(EditCommand));
+        registry.register(Box::new(CyclesCommand));
+        registry.register(Box::new(DiffCommand));
+        registry.register(Box::new(StatsCommand));
+        registry.register(Box::new(ResummarizeCommand));
+        registry.register(Box::new(GotoCommand));
+        registry.register(Box::new(CopyErrorCommand));
+        registry.register(Box::new(VersionCommand));
+        registry.register(Box::new(DoctorCommand));
+        registry.register(Box::new(ResetSettingsCommand));
+        registry.register(Box::new(PreviewCommand));
+        registry.register(Box::new(LoadCommand));
+        registry.register(Box::new(NextCommand));
+        registry.register(Box::new(StopCommand));
+        registry.register(Box::new(CancelCommand));
+        registry.register(Box::new(TaskCommand));
+        registry.register(Box::new(WorkingDirCommand));
+        registry.register(Box::new(SnapshotCommand));
+        registry.register(Box::new(RestoreCommand));
+        registry.register(Box::new(OpenCommand));
+        registry.register(Box::new(TodosCommand));
+        registry.register(Box::new(RmTodoCommand));
+        registry.register(Box::new(ConfigCommand));
+        registry.register(Box::new(ModelsCommand));
+        registry.register(Box::new(TailCommand));
+        registry.register(Box::new(UntailCommand));
+        registry.register(Box::new(WhichCommand));
+        registry
+    }
+}
+
+// =============================================================================
+// Built-in Commands
+// =============================================================================
+
+/// Command to exit the application gracefully.
+pub struct ExitCommand;
+
+impl SlashCommand for ExitCommand {
+    fn name(&self) -> &'static str {
+        "exit"
+    }
+
+    fn description(&self) -> &'static str {
+        "Exit the application"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::Exit
+    }
+
+    /// Exit can execute even while the flow is running (user wants to quit).
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to open the settings panel.
+pub struct SettingsCommand;
+
+impl SlashCommand for SettingsCommand {
+    fn name(&self) -> &'static str {
+        "settings"
+    }
+
+    fn description(&self) -> &'static str {
+        "Open settings panel (Ctrl+S)"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::OpenSettings
+    }
+}
+
+/// Command to clear task, output, and todo files.
+///
+/// Note: Does NOT reset settings.
+pub struct ClearCommand;
+
+impl SlashCommand for ClearCommand {
+    fn name(&self) -> &'static str {
+        "clear"
+    }
+
+    fn description(&self) -> &'static str {
+        "Clear task, output, and todo files"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        // The actual clearing logic will be handled by App
+        // This just signals the intent
+        CommandResult::Clear
+    }
+}
+
+/// Command to clear the output panel, leaving task.md, todos and the input
+/// alone.
+pub struct ClsCommand;
+
+impl SlashCommand for ClsCommand {
+    fn name(&self) -> &'static str {
+        "cls"
+    }
+
+    fn description(&self) -> &'static str {
+        "Clear the output panel only"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::ClearOutput
+    }
+
+    /// Clearing the screen is harmless, even while a flow runs.
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to terminate CLI processes orphaned by a previous crashed session.
+pub struct ReapCommand;
+
+impl SlashCommand for ReapCommand {
+    fn name(&self) -> &'static str {
+        "reap"
+    }
+
+    fn description(&self) -> &'static str {
+        "Terminate orphaned CLI processes from a previous session"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::ReapOrphans
+    }
+}
+
+/// Command to save the task text to `.mcgravity/task.md` without waiting for autosave.
+pub struct SaveCommand;
+
+impl SlashCommand for SaveCommand {
+    fn name(&self) -> &'static str {
+        "save"
+    }
+
+    fn description(&self) -> &'static str {
+        "Save task text to .mcgravity/task.md now"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::Save
+    }
+}
+
+/// Command to open a todo file in the user's editor.
+///
+/// Without arguments it lists the numbered todo files; `/edit <n>` opens
+/// file `n`. Blocked while the flow is running, since execution reads and
+/// moves these files.
+pub struct EditCommand;
+
+impl SlashCommand for EditCommand {
+    fn name(&self) -> &'static str {
+        "edit"
+    }
+
+    fn description(&self) -> &'static str {
+        "List todo files, or open one in $EDITOR (/edit <n>)"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        match ctx.args {
+            None => CommandResult::EditTodoFile(None),
+            Some(arg) => match arg.parse::<usize>() {
+                Ok(n) if n > 0 => CommandResult::EditTodoFile(Some(n)),
+                _ => CommandResult::Warning(format!(
+                    "Invalid file number \"{arg}\". Usage: /edit [n]"
+                )),
+            },
+        }
+    }
+}
+
+/// Command to show or change the iteration cap of the running flow.
+///
+/// `/cycles` shows the current cycle and cap; `/cycles <n>` or
+/// `/cycles unlimited` changes the cap, taking effect at the next cycle
+/// boundary.
+pub struct CyclesCommand;
+
+impl SlashCommand for CyclesCommand {
+    fn name(&self) -> &'static str {
+        "cycles"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show or change max iterations for the running flow (/cycles <n>)"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        let Some(arg) = ctx.args else {
+            return CommandResult::ShowCycles;
+        };
+        if arg.eq_ignore_ascii_case("unlimited") {
+            return CommandResult::SetMaxIterations(None);
+        }
+        match arg.parse::<u32>() {
+            Ok(n) if n > 0 => CommandResult::SetMaxIterations(Some(n)),
+            _ => CommandResult::Warning(format!(
+                "Invalid cycle count \"{arg}\". Usage: /cycles [n|unlimited]"
+            )),
+        }
+    }
+
+    /// Allowed at any time; without a running flow `/cycles` shows the
+    /// default cap, and changing it points to the settings instead.
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to review what the flow changed via `git status` and `git diff`.
+///
+/// Diffs against the commit checked out when the flow started, if known.
+pub struct DiffCommand;
+
+impl SlashCommand for DiffCommand {
+    fn name(&self) -> &'static str {
+        "diff"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show git changes since the flow started"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::ShowDiff
+    }
+}
+
+/// Command to show the size of prompts sent to each model.
+///
+/// Available while the flow runs, so totals can be checked mid-run.
+pub struct StatsCommand;
+
+impl SlashCommand for StatsCommand {
+    fn name(&self) -> &'static str {
+        "stats"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show prompt sizes sent to each model"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::ShowStats
+    }
+
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to regenerate the summary of the last completed todo.
+///
+/// Re-runs the summary model on the last archived todo file and replaces the
+/// newest `<COMPLETED_TASKS>` entry. Unavailable while the flow runs, since
+/// the flow owns `task.md` then.
+pub struct ResummarizeCommand;
+
+impl SlashCommand for ResummarizeCommand {
+    fn name(&self) -> &'static str {
+        "resummarize"
+    }
+
+    fn description(&self) -> &'static str {
+        "Regenerate the last completed task summary"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::Resummarize
+    }
+}
+
+/// Command to scroll the output to a phase boundary.
+///
+/// `/goto` lists the phase boundaries in the output; `/goto <n>` scrolls so
+/// the `n`th boundary is at the top of the output panel.
+pub struct GotoCommand;
+
+impl SlashCommand for GotoCommand {
+    fn name(&self) -> &'static str {
+        "goto"
+    }
+
+    fn description(&self) -> &'static str {
+        "List phase boundaries, or jump the output to one (/goto <n>)"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        match ctx.args {
+            None => CommandResult::GotoPhase(None),
+            Some(arg) => match arg.parse::<usize>() {
+                Ok(n) if n > 0 => CommandResult::GotoPhase(Some(n)),
+                _ => CommandResult::Warning(format!(
+                    "Invalid boundary number \"{arg}\". Usage: /goto [n]"
+                )),
+            },
+        }
+    }
+
+    /// Scrolling only reads the output, so it is safe while the flow runs.
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to copy the most recent flow error to the clipboard.
+pub struct CopyErrorCommand;
+
+impl SlashCommand for CopyErrorCommand {
+    fn name(&self) -> &'static str {
+        "copyerror"
+    }
+
+    fn description(&self) -> &'static str {
+        "Copy the last error message to the clipboard"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::CopyLastError
+    }
+
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to show the version and build provenance.
+pub struct VersionCommand;
+
+impl SlashCommand for VersionCommand {
+    fn name(&self) -> &'static str {
+        "version"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the version, git commit and build date"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::Message(crate::cli::build_info())
+    }
+
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to check the AI CLIs, the state directory and git.
+pub struct DoctorCommand;
+
+impl SlashCommand for DoctorCommand {
+    fn name(&self) -> &'static str {
+        "doctor"
+    }
+
+    fn description(&self) -> &'static str {
+        "Check the AI CLIs, .mcgravity/ and git"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::Doctor
+    }
+
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to restore all settings to their defaults.
+///
+/// Resetting cannot be undone, so `/reset-settings` alone only explains what
+/// will happen; `/reset-settings confirm` performs the reset.
+pub struct ResetSettingsCommand;
+
+impl SlashCommand for ResetSettingsCommand {
+    fn name(&self) -> &'static str {
+        "reset-settings"
+    }
+
+    fn description(&self) -> &'static str {
+        "Restore all settings to their defaults"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        match ctx.args {
+            Some("confirm") => CommandResult::ResetSettings,
+            _ => CommandResult::Message(
+                "This replaces .mcgravity/settings.json with the defaults. \
+                 Run /reset-settings confirm to proceed."
+                    .to_string(),
+            ),
+        }
+    }
+}
+
+/// Command to preview the fully wrapped prompt for the current task.
+///
+/// `/preview` shows the planning prompt; `/preview execution` shows the
+/// execution prompt.
+pub struct PreviewCommand;
+
+impl SlashCommand for PreviewCommand {
+    fn name(&self) -> &'static str {
+        "preview"
+    }
+
+    fn description(&self) -> &'static str {
+        "Preview the planning or execution prompt"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        match ctx.args {
+            None | Some("planning") => CommandResult::PreviewPrompt(PromptKind::Planning),
+            Some("execution") => CommandResult::PreviewPrompt(PromptKind::Execution),
+            Some(other) => CommandResult::Warning(format!(
+                "Unknown phase '{other}'. Usage: /preview [planning|execution]"
+            )),
+        }
+    }
+}
+
+/// Command to load a file into the text input.
+///
+/// `/load <path>` replaces the input with the file's content. The path may
+/// be written as an `@` mention (`/load @docs/plan.md`).
+pub struct LoadCommand;
+
+impl SlashCommand for LoadCommand {
+    fn name(&self) -> &'static str {
+        "load"
+    }
+
+    fn description(&self) -> &'static str {
+        "Load a file into the input (/load <path>)"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        match ctx.args {
+            Some(path) => CommandResult::LoadTask(PathBuf::from(path)),
+            None => CommandResult::Warning("Usage: /load <path>".to_string()),
+        }
+    }
+}
+
+/// Command to run the next cycle of a flow paused in step mode.
+pub struct NextCommand;
+
+impl SlashCommand for NextCommand {
+    fn name(&self) -> &'static str {
+        "next"
+    }
+
+    fn description(&self) -> &'static str {
+        "Run the next cycle of a paused step-mode flow"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::Step(StepSignal::Next)
+    }
+
+    /// Always allowed; whether a flow is paused is checked when the signal
+    /// is sent (`App::send_step_signal`), which warns otherwise.
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to end a flow paused in step mode.
+pub struct StopCommand;
+
+impl SlashCommand for StopCommand {
+    fn name(&self) -> &'static str {
+        "stop"
+    }
+
+    fn description(&self) -> &'static str {
+        "End a paused step-mode flow"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::Step(StepSignal::Stop)
+    }
+
+    /// Always allowed; whether a flow is paused is checked when the signal
+    /// is sent (`App::send_step_signal`), which warns otherwise.
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to cancel the running flow, the typed equivalent of ESC.
+pub struct CancelCommand;
+
+impl SlashCommand for CancelCommand {
+    fn name(&self) -> &'static str {
+        "cancel"
+    }
+
+    fn description(&self) -> &'static str {
+        "Cancel the running flow (same as Esc)"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::CancelFlow
+    }
+
+    /// There is nothing to cancel without a running flow.
+    fn can_execute(&self, ctx: &CommandContext) -> bool {
+        ctx.is_running
+    }
+}
+
+/// Command to show the live task text, including `<COMPLETED_TASKS>`.
+pub struct TaskCommand;
+
+impl SlashCommand for TaskCommand {
+    fn name(&self) -> &'static str {
+        "task"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the live task text with completed tasks"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::ShowTaskText
+    }
+
+    /// Most useful while the flow runs and records completed tasks.
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to show the project directory (set with `--working-dir`).
+pub struct WorkingDirCommand;
+
+impl SlashCommand for WorkingDirCommand {
+    fn name(&self) -> &'static str {
+        "working-dir"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show the project directory flows run in"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::ShowWorkingDir
+    }
+
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
+    }
+}
+
+/// Command to snapshot task.md and the todo files before a risky run.
+pub struct SnapshotCommand;
+
+impl SlashCommand for SnapshotCommand {
+    fn name(&self) -> &'static str {
+        "snapshot"
+    }
+
+    fn description(&self) -> &'static str {
+        "Snapshot task.md and the todo files"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::Snapshot
+    }
+}
+
+/// Command to restore a snapshot taken with `/snapshot`.
+///
+/// `/restore` lists the snapshots; `/restore <name> confirm` restores one.
+pub struct RestoreCommand;
+
+impl SlashCommand for RestoreCommand {
+    fn name(&self) -> &'static str {
+        "restore"
+    }
+
+    fn description(&self) -> &'static str {
+        "Restore a snapshot (/restore <name> confirm)"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        let mut args = ctx.args.unwrap_or_default().split_whitespace();
+        match (args.next(), args.next(), args.next()) {
+            (None, _, _) => CommandResult::RestoreSnapshot(None),
+            (Some(name), Some("confirm"), None) => {
+                CommandResult::RestoreSnapshot(Some(name.to_string()))
+            }
+            (Some(name), _, _) => CommandResult::Message(format!(
+                "This replaces .mcgravity/task.md and the todo files with snapshot {name}. \
+                 Run /restore {name} confirm to proceed."
+            )),
+        }
+    }
+}
+
+/// Command to open a file mentioned in the output in the user's editor.
+///
+/// `/open` picks the most recent output line that mentions an existing file;
+/// `/open <path[:line]>` opens the given path. Blocked while the flow is
+/// running, since the editor takes over the terminal.
+pub struct OpenCommand;
+
+impl SlashCommand for OpenCommand {
+    fn name(&self) -> &'static str {
+        "open"
+    }
+
+    fn description(&self) -> &'static str {
+        "Open the last file mentioned in the output in $EDITOR (/open [path[:line]])"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        CommandResult::OpenFile(
+            ctx.args
+                .map(|arg| parse_file_ref(arg).unwrap_or_else(|| FileRef::new(arg))),
+        )
+    }
+}
+
+/// Command to list the pending todo files with their numbers.
+///
+/// Listing is read-only, so it is allowed while the flow is running.
+pub struct TodosCommand;
+
+impl SlashCommand for TodosCommand {
+    fn name(&self) -> &'static str {
+        "todos"
+    }
+
+    fn description(&self) -> &'static str {
+        "List pending todo files with their numbers"
+    }
+
+    fn execute(&self, _ctx: &CommandContext) -> CommandResult {
+        CommandResult::ListTodos
     }
 
-    /// Returns all commands whose names start with the given prefix.
-    ///
-    /// Useful for autocomplete functionality.
-    #[must_use]
-    pub fn matching(&self, prefix: &str) -> Vec<&dyn SlashCommand> {
-        self.commands
-            .iter()
-            .filter(|cmd| cmd.name().starts_with(prefix))
-            .map(AsRef::as_ref)
-            .collect()
+    /// Listing never touches the files, so it is safe at any time.
+    fn can_execute(&self, _ctx: &CommandContext) -> bool {
+        true
     }
 }
 
-impl Default for CommandRegistry {
-    fn default() -> Self {
-        Self::new()
+/// Command to delete a single pending todo file.
+///
+/// `/rm-todo <n> confirm` deletes file `n` as numbered by `/todos`. Blocked
+/// while the flow is running, since execution reads and moves these files.
+pub struct RmTodoCommand;
+
+impl SlashCommand for RmTodoCommand {
+    fn name(&self) -> &'static str {
+        "rm-todo"
     }
-}
 
-impl CommandRegistry {
-    /// Creates a registry with all built-in commands pre-registered.
-    #[must_use]
-    pub fn with_builtins() -> Self {
-        let mut registry = Self::new();
-        registry.register(Box::new(ExitCommand));
-        registry.register(Box::new(SettingsCommand));
-        registry.register(Box::new(ClearCommand));
-        registry
+    fn description(&self) -> &'static str {
+        "Delete a pending todo file (/rm-todo <n> confirm)"
     }
-}
 
-// =============================================================================
-// Built-in Commands
-// =============================================================================
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        let mut args = ctx.args.unwrap_or_default().split_whitespace();
+        let (arg, confirm, extra) = (args.next(), args.next(), args.next());
+        let Some(n) = arg.and_then(|a| a.parse::<usize>().ok()).filter(|&n| n > 0) else {
+            return CommandResult::Warning(
+                "Usage: /rm-todo <n> confirm (see /todos for numbers)".to_string(),
+            );
+        };
+        match (confirm, extra) {
+            (Some("confirm"), None) => CommandResult::RemoveTodo(n),
+            _ => CommandResult::Message(format!(
+                "This deletes todo file {n}. Run /rm-todo {n} confirm to proceed."
+            )),
+        }
+    }
+}
 
-/// Command to exit the application gracefully.
-pub struct ExitCommand;
+/// Command to show the active models and flow settings.
+pub struct ConfigCommand;
 
-impl SlashCommand for ExitCommand {
+impl SlashCommand for ConfigCommand {
     fn name(&self) -> &'static str {
-        "exit"
+        "config"
     }
 
     fn description(&self) -> &'static str {
-        "Exit the application"
+        "Show the active models and flow settings"
     }
 
     fn execute(&self, _ctx: &CommandContext) -> CommandResult {
-        CommandResult::Exit
+        CommandResult::ShowConfig
     }
 
-    /// Exit can execute even while the flow is running (user wants to quit).
+    /// Showing the configuration is read-only, so it is safe at any time.
     fn can_execute(&self, _ctx: &CommandContext) -> bool {
         true
     }
 }
 
-/// Command to open the settings panel.
-pub struct SettingsCommand;
+/// Command to open the model picker.
+pub struct ModelsCommand;
 
-impl SlashCommand for SettingsCommand {
+impl SlashCommand for ModelsCommand {
     fn name(&self) -> &'static str {
-        "settings"
+        "models"
     }
 
     fn description(&self) -> &'static str {
-        "Open settings panel (Ctrl+S)"
+        "Pick the planning and execution models"
     }
 
     fn execute(&self, _ctx: &CommandContext) -> CommandResult {
-        CommandResult::OpenSettings
+        CommandResult::OpenModelPicker
     }
 }
 
-/// Command to clear task, output, and todo files.
+/// Command to follow a log file in the output panel.
 ///
-/// Note: Does NOT reset settings.
-pub struct ClearCommand;
+/// `/tail <path>` forwards lines appended to the file until `/untail` or the
+/// next flow start. The path may be written as an `@` mention.
+pub struct TailCommand;
 
-impl SlashCommand for ClearCommand {
+impl SlashCommand for TailCommand {
     fn name(&self) -> &'static str {
-        "clear"
+        "tail"
     }
 
     fn description(&self) -> &'static str {
-        "Clear task, output, and todo files"
+        "Follow a log file in the output (/tail <path>)"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        match ctx.args {
+            Some(path) => CommandResult::TailFile(PathBuf::from(path)),
+            None => CommandResult::Warning("Usage: /tail <path>".to_string()),
+        }
+    }
+}
+
+/// Command to stop following the log file started with `/tail`.
+pub struct UntailCommand;
+
+impl SlashCommand for UntailCommand {
+    fn name(&self) -> &'static str {
+        "untail"
+    }
+
+    fn description(&self) -> &'static str {
+        "Stop following the log file started with /tail"
     }
 
     fn execute(&self, _ctx: &CommandContext) -> CommandResult {
-        // The actual clearing logic will be handled by App
-        // This just signals the intent
-        CommandResult::Clear
+        CommandResult::StopTail
+    }
+}
+
+/// Command to show where a model's CLI command resolves.
+///
+/// `/which <model>` reports the executable the model runs, or that it was
+/// not found, to debug a wrong binary being picked up.
+pub struct WhichCommand;
+
+impl SlashCommand for WhichCommand {
+    fn name(&self) -> &'static str {
+        "which"
+    }
+
+    fn description(&self) -> &'static str {
+        "Show where a model's CLI resolves (/which <model>)"
+    }
+
+    fn execute(&self, ctx: &CommandContext) -> CommandResult {
+        let Some(name) = ctx.args else {
+            return CommandResult::Warning("Usage: /which <model>".to_string());
+        };
+        Model::from_name(name).map_or_else(
+            || {
+                CommandResult::Warning(format!(
+                    "Unknown model '{name}': expected codex, claude or gemini"
+                ))
+            },
+            CommandResult::WhichCli,
+        )
     }
 }
 
@@ -283,6 +1095,7 @@ mod tests {
         CommandContext {
             is_running,
             mode: &CHAT_MODE,
+            args: None,
         }
     }
 
@@ -735,6 +1548,432 @@ mod tests {
         assert!(cmd.can_execute(&ctx));
     }
 
+    #[test]
+    fn cls_command_clears_output_even_while_running() {
+        let cmd = ClsCommand;
+        assert_eq!(cmd.name(), "cls");
+        assert_eq!(
+            cmd.execute(&make_context(false)),
+            CommandResult::ClearOutput
+        );
+        assert!(cmd.can_execute(&make_context(false)));
+        assert!(cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn reap_command_returns_reap_orphans() {
+        let cmd = ReapCommand;
+        let ctx = make_context(false);
+        assert_eq!(cmd.name(), "reap");
+        assert_eq!(cmd.execute(&ctx), CommandResult::ReapOrphans);
+    }
+
+    #[test]
+    fn doctor_command_returns_doctor_and_runs_anytime() {
+        let cmd = DoctorCommand;
+        assert_eq!(cmd.name(), "doctor");
+        assert_eq!(cmd.execute(&make_context(false)), CommandResult::Doctor);
+        assert!(cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn reap_command_cannot_execute_while_running() {
+        let cmd = ReapCommand;
+        let ctx = make_context(true);
+        assert!(!cmd.can_execute(&ctx));
+    }
+
+    #[test]
+    fn save_command_returns_save() {
+        let cmd = SaveCommand;
+        let ctx = make_context(false);
+        assert_eq!(cmd.name(), "save");
+        assert_eq!(cmd.execute(&ctx), CommandResult::Save);
+    }
+
+    #[test]
+    fn save_command_blocked_while_running() {
+        let cmd = SaveCommand;
+        let ctx = make_context(true);
+        assert!(!cmd.can_execute(&ctx));
+    }
+
+    #[test]
+    fn edit_command_without_args_lists_files() {
+        let cmd = EditCommand;
+        let ctx = make_context(false);
+        assert_eq!(cmd.name(), "edit");
+        assert_eq!(cmd.execute(&ctx), CommandResult::EditTodoFile(None));
+    }
+
+    #[test]
+    fn edit_command_with_number_selects_file() {
+        let cmd = EditCommand;
+        let ctx = CommandContext {
+            args: Some("2"),
+            ..make_context(false)
+        };
+        assert_eq!(cmd.execute(&ctx), CommandResult::EditTodoFile(Some(2)));
+    }
+
+    #[test]
+    fn edit_command_rejects_invalid_numbers() {
+        let cmd = EditCommand;
+        for arg in ["0", "abc", "-1"] {
+            let ctx = CommandContext {
+                args: Some(arg),
+                ..make_context(false)
+            };
+            assert!(matches!(cmd.execute(&ctx), CommandResult::Warning(_)));
+        }
+    }
+
+    #[test]
+    fn edit_command_cannot_execute_while_running() {
+        let cmd = EditCommand;
+        let ctx = make_context(true);
+        assert!(!cmd.can_execute(&ctx));
+    }
+
+    #[test]
+    fn goto_command_parses_boundary_number() {
+        let cmd = GotoCommand;
+        assert_eq!(cmd.name(), "goto");
+        assert_eq!(
+            cmd.execute(&make_context(true)),
+            CommandResult::GotoPhase(None)
+        );
+        let ctx = CommandContext {
+            args: Some("3"),
+            ..make_context(true)
+        };
+        assert!(cmd.can_execute(&ctx));
+        assert_eq!(cmd.execute(&ctx), CommandResult::GotoPhase(Some(3)));
+        let ctx = CommandContext {
+            args: Some("0"),
+            ..make_context(false)
+        };
+        assert!(matches!(cmd.execute(&ctx), CommandResult::Warning(_)));
+    }
+
+    #[test]
+    fn copyerror_command_works_while_running() {
+        let cmd = CopyErrorCommand;
+        let ctx = make_context(true);
+        assert_eq!(cmd.name(), "copyerror");
+        assert!(cmd.can_execute(&ctx));
+        assert_eq!(cmd.execute(&ctx), CommandResult::CopyLastError);
+    }
+
+    #[test]
+    fn version_command_shows_build_info() {
+        let cmd = VersionCommand;
+        let ctx = make_context(true);
+        assert_eq!(cmd.name(), "version");
+        assert!(cmd.can_execute(&ctx));
+        assert_eq!(
+            cmd.execute(&ctx),
+            CommandResult::Message(crate::cli::build_info())
+        );
+    }
+
+    #[test]
+    fn reset_settings_command_requires_confirmation() {
+        let cmd = ResetSettingsCommand;
+        let ctx = make_context(false);
+        assert_eq!(cmd.name(), "reset-settings");
+        assert!(matches!(cmd.execute(&ctx), CommandResult::Message(_)));
+        let ctx = CommandContext {
+            args: Some("yes"),
+            ..make_context(false)
+        };
+        assert!(matches!(cmd.execute(&ctx), CommandResult::Message(_)));
+        let ctx = CommandContext {
+            args: Some("confirm"),
+            ..make_context(false)
+        };
+        assert_eq!(cmd.execute(&ctx), CommandResult::ResetSettings);
+        assert!(!cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn restore_command_lists_or_requires_confirmation() {
+        let cmd = RestoreCommand;
+        assert_eq!(cmd.name(), "restore");
+        assert_eq!(
+            cmd.execute(&make_context(false)),
+            CommandResult::RestoreSnapshot(None)
+        );
+        for args in ["20260101-120000", "20260101-120000 yes", "a confirm extra"] {
+            let ctx = CommandContext {
+                args: Some(args),
+                ..make_context(false)
+            };
+            assert!(
+                matches!(cmd.execute(&ctx), CommandResult::Message(_)),
+                "{args}"
+            );
+        }
+        let ctx = CommandContext {
+            args: Some("20260101-120000 confirm"),
+            ..make_context(false)
+        };
+        assert_eq!(
+            cmd.execute(&ctx),
+            CommandResult::RestoreSnapshot(Some("20260101-120000".to_string()))
+        );
+        assert!(!cmd.can_execute(&make_context(true)));
+        assert!(!SnapshotCommand.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn todos_command_lists_even_while_running() {
+        let cmd = TodosCommand;
+        assert_eq!(cmd.name(), "todos");
+        assert_eq!(cmd.execute(&make_context(false)), CommandResult::ListTodos);
+        assert!(cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn rm_todo_command_requires_number_and_confirmation() {
+        let cmd = RmTodoCommand;
+        assert_eq!(cmd.name(), "rm-todo");
+        for args in [None, Some("abc"), Some("0")] {
+            let ctx = CommandContext {
+                args,
+                ..make_context(false)
+            };
+            assert!(
+                matches!(cmd.execute(&ctx), CommandResult::Warning(_)),
+                "{args:?}"
+            );
+        }
+        for args in [Some("2"), Some("2 yes"), Some("2 confirm x")] {
+            let ctx = CommandContext {
+                args,
+                ..make_context(false)
+            };
+            assert!(
+                matches!(cmd.execute(&ctx), CommandResult::Message(_)),
+                "{args:?}"
+            );
+        }
+        let ctx = CommandContext {
+            args: Some("2 confirm"),
+            ..make_context(false)
+        };
+        assert_eq!(cmd.execute(&ctx), CommandResult::RemoveTodo(2));
+        assert!(!cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn config_command_is_always_available() {
+        let cmd = ConfigCommand;
+        assert_eq!(cmd.name(), "config");
+        assert_eq!(cmd.execute(&make_context(false)), CommandResult::ShowConfig);
+        assert!(cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn models_command_opens_picker_when_idle() {
+        let cmd = ModelsCommand;
+        assert_eq!(cmd.name(), "models");
+        assert_eq!(
+            cmd.execute(&make_context(false)),
+            CommandResult::OpenModelPicker
+        );
+        assert!(!cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn tail_command_requires_a_path() {
+        let cmd = TailCommand;
+        assert_eq!(cmd.name(), "tail");
+        assert!(matches!(
+            cmd.execute(&make_context(false)),
+            CommandResult::Warning(_)
+        ));
+        let ctx = CommandContext {
+            args: Some("build.log"),
+            ..make_context(false)
+        };
+        assert_eq!(
+            cmd.execute(&ctx),
+            CommandResult::TailFile(PathBuf::from("build.log"))
+        );
+        assert_eq!(UntailCommand.execute(&ctx), CommandResult::StopTail);
+    }
+
+    #[test]
+    fn which_command_parses_model_name() {
+        let cmd = WhichCommand;
+        assert_eq!(cmd.name(), "which");
+        assert_eq!(
+            cmd.execute(&make_context(false)),
+            CommandResult::Warning("Usage: /which <model>".to_string())
+        );
+        let with_args = |args| CommandContext {
+            args: Some(args),
+            ..make_context(false)
+        };
+        assert_eq!(
+            cmd.execute(&with_args("Claude")),
+            CommandResult::WhichCli(Model::Claude)
+        );
+        assert!(matches!(
+            cmd.execute(&with_args("gpt")),
+            CommandResult::Warning(message) if message.starts_with("Unknown model 'gpt'")
+        ));
+    }
+
+    #[test]
+    fn open_command_parses_path_and_line() {
+        let cmd = OpenCommand;
+        assert_eq!(cmd.name(), "open");
+        assert_eq!(
+            cmd.execute(&make_context(false)),
+            CommandResult::OpenFile(None)
+        );
+        let ctx = CommandContext {
+            args: Some("src/core/flow.rs:42"),
+            ..make_context(false)
+        };
+        assert_eq!(
+            cmd.execute(&ctx),
+            CommandResult::OpenFile(Some(FileRef {
+                path: PathBuf::from("src/core/flow.rs"),
+                line: Some(42),
+            }))
+        );
+        assert!(!cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn preview_command_parses_phase() {
+        let cmd = PreviewCommand;
+        assert_eq!(cmd.name(), "preview");
+        assert_eq!(
+            cmd.execute(&make_context(false)),
+            CommandResult::PreviewPrompt(PromptKind::Planning)
+        );
+        let ctx = CommandContext {
+            args: Some("execution"),
+            ..make_context(false)
+        };
+        assert_eq!(
+            cmd.execute(&ctx),
+            CommandResult::PreviewPrompt(PromptKind::Execution)
+        );
+        let ctx = CommandContext {
+            args: Some("summary"),
+            ..make_context(false)
+        };
+        assert!(matches!(cmd.execute(&ctx), CommandResult::Warning(_)));
+        assert!(!cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn load_command_requires_path() {
+        let cmd = LoadCommand;
+        assert_eq!(cmd.name(), "load");
+        assert!(matches!(
+            cmd.execute(&make_context(false)),
+            CommandResult::Warning(_)
+        ));
+        let ctx = CommandContext {
+            args: Some("@docs/plan.md"),
+            ..make_context(false)
+        };
+        assert_eq!(
+            cmd.execute(&ctx),
+            CommandResult::LoadTask(PathBuf::from("@docs/plan.md"))
+        );
+        assert!(!cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn step_commands_signal_paused_flow() {
+        assert_eq!(
+            NextCommand.execute(&make_context(true)),
+            CommandResult::Step(StepSignal::Next)
+        );
+        assert_eq!(
+            StopCommand.execute(&make_context(true)),
+            CommandResult::Step(StepSignal::Stop)
+        );
+        assert!(NextCommand.can_execute(&make_context(true)));
+        assert!(StopCommand.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn cancel_command_needs_a_running_flow() {
+        let cmd = CancelCommand;
+        assert_eq!(cmd.name(), "cancel");
+        assert_eq!(cmd.execute(&make_context(true)), CommandResult::CancelFlow);
+        assert!(cmd.can_execute(&make_context(true)));
+        assert!(!cmd.can_execute(&make_context(false)));
+    }
+
+    #[test]
+    fn cycles_command_without_args_shows_cycles() {
+        let cmd = CyclesCommand;
+        let ctx = make_context(true);
+        assert_eq!(cmd.name(), "cycles");
+        assert_eq!(cmd.execute(&ctx), CommandResult::ShowCycles);
+    }
+
+    #[test]
+    fn cycles_command_parses_cap() {
+        let cmd = CyclesCommand;
+        for (arg, expected) in [
+            ("3", CommandResult::SetMaxIterations(Some(3))),
+            ("unlimited", CommandResult::SetMaxIterations(None)),
+            ("Unlimited", CommandResult::SetMaxIterations(None)),
+        ] {
+            let ctx = CommandContext {
+                args: Some(arg),
+                ..make_context(true)
+            };
+            assert_eq!(cmd.execute(&ctx), expected);
+        }
+    }
+
+    #[test]
+    fn cycles_command_rejects_invalid_cap() {
+        let cmd = CyclesCommand;
+        for arg in ["0", "-2", "many"] {
+            let ctx = CommandContext {
+                args: Some(arg),
+                ..make_context(true)
+            };
+            assert!(matches!(cmd.execute(&ctx), CommandResult::Warning(_)));
+        }
+    }
+
+    #[test]
+    fn cycles_command_can_execute_while_running() {
+        let cmd = CyclesCommand;
+        assert!(cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn diff_command_shows_diff() {
+        let cmd = DiffCommand;
+        let ctx = make_context(false);
+        assert_eq!(cmd.name(), "diff");
+        assert_eq!(cmd.execute(&ctx), CommandResult::ShowDiff);
+        assert!(!cmd.can_execute(&make_context(true)));
+    }
+
+    #[test]
+    fn stats_command_shows_stats_while_running() {
+        let cmd = StatsCommand;
+        let ctx = make_context(true);
+        assert_eq!(cmd.name(), "stats");
+        assert!(cmd.can_execute(&ctx));
+        assert_eq!(cmd.execute(&ctx), CommandResult::ShowStats);
+    }
+
     // =========================================================================
     // CommandRegistry::with_builtins() Tests
     // =========================================================================
@@ -770,9 +2009,9 @@ mod tests {
     }
 
     #[test]
-    fn registry_with_builtins_has_three_commands() {
+    fn registry_with_builtins_has_thirty_three_commands() {
         let registry = CommandRegistry::with_builtins();
-        assert_eq!(registry.all().len(), 3);
+        assert_eq!(registry.all().len(), 33);
     }
 
     #[test]
@@ -806,7 +2045,7 @@ mod tests {
     fn registry_with_builtins_matching_empty_returns_all() {
         let registry = CommandRegistry::with_builtins();
         let matches = registry.matching("");
-        assert_eq!(matches.len(), 3);
+        assert_eq!(matches.len(), 33);
     }
 
     // =========================================================================
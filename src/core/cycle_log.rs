@@ -0,0 +1,284 @@
+//! Per-cycle logs of the CLI output forwarded to the UI.
+//!
+//! With the `log_cycles` setting on, the runner sends its events through
+//! [`with_cycle_logs`], which writes every forwarded stdout and stderr line
+//! to the log file of the current cycle before passing the event on. The
+//! logger runs on a blocking thread, so file writes never stall the runtime.
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use tokio::sync::mpsc;
+
+use crate::app::FlowEvent;
+use crate::core::channels::CYCLE_LOG_CHANNEL_SIZE;
+use crate::fs::logs::{MAX_CYCLE_LOGS, cycle_log_file, new_run_id, prune_cycle_logs};
+use crate::tui::widgets::{OutputLine, OutputLineType};
+
+/// Returns a sender that logs CLI output per cycle and forwards every event
+/// to `tx`.
+///
+/// A new `cycle-<run>-NNN.log` in `logs_dir` is started at each
+/// [`FlowEvent::CycleStarted`], where `<run>` is fixed when this is called so
+/// each flow's logs are kept apart. The oldest logs beyond
+/// [`MAX_CYCLE_LOGS`] are pruned. In-place line updates
+/// ([`FlowEvent::ReplaceLastOutput`]) rewrite the line last started with
+/// [`FlowEvent::PartialOutput`], as the output panel does, so the file holds
+/// each line's final text.
+/// A log that cannot be written is reported once and logging stops until the
+/// next cycle; the events are forwarded either way.
+#[must_use]
+pub fn with_cycle_logs(logs_dir: PathBuf, tx: mpsc::Sender<FlowEvent>) -> mpsc::Sender<FlowEvent> {
+    let (log_tx, mut log_rx) = mpsc::channel(CYCLE_LOG_CHANNEL_SIZE);
+    let run_id = new_run_id();
+    tokio::task::spawn_blocking(move || {
+        let mut log: Option<CycleLog> = None;
+        while let Some(event) = log_rx.blocking_recv() {
+            let result = match &event {
+                FlowEvent::CycleStarted(cycle) => match CycleLog::start(&logs_dir, &run_id, *cycle)
+                {
+                    Ok(started) => {
+                        log = Some(started);
+                        Ok(())
+                    }
+                    Err(e) => {
+                        log = None;
+                        Err(e)
+                    }
+                },
+                FlowEvent::Output(line) => log.as_mut().map_or(Ok(()), |log| log.push(line)),
+                FlowEvent::PartialOutput(line) => {
+                    log.as_mut().map_or(Ok(()), |log| log.push_partial(line))
+                }
+                FlowEvent::ReplaceLastOutput(line) => {
+                    log.as_mut().map_or(Ok(()), |log| log.replace_last(line))
+                }
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                log = None;
+                tx.blocking_send(FlowEvent::Output(OutputLine::warning(format!(
+                    "Failed to write cycle log in {}: {e}",
+                    logs_dir.display()
+                ))))
+                .ok();
+            }
+            if tx.blocking_send(event).is_err() {
+                break;
+            }
+        }
+    });
+    log_tx
+}
+
+/// The open log file of the current cycle.
+struct CycleLog {
+    file: File,
+    /// Length of the file in bytes.
+    len: u64,
+    /// Offset and length (newline included) of the partial line that
+    /// in-place updates rewrite, like `FlowUiState::partial_line`.
+    partial_line: Option<(u64, u64)>,
+}
+
+impl CycleLog {
+    /// Creates (or truncates) the log of `cycle` in run `run_id` and prunes
+    /// old logs.
+    fn start(logs_dir: &Path, run_id: &str, cycle: u32) -> std::io::Result<Self> {
+        std::fs::create_dir_all(logs_dir)?;
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(cycle_log_file(logs_dir, run_id, cycle))?;
+        prune_cycle_logs(logs_dir, MAX_CYCLE_LOGS)?;
+        Ok(Self {
+            file,
+            len: 0,
+            partial_line: None,
+        })
+    }
+
+    /// Appends a CLI output line. System messages are not logged.
+    fn push(&mut self, line: &OutputLine) -> std::io::Result<()> {
+        if !is_cli_output(line) {
+            return Ok(());
+        }
+        self.write_line(&line.text)
+    }
+
+    /// Appends a partial CLI output line and remembers where it starts, so
+    /// later updates rewrite it.
+    fn push_partial(&mut self, line: &OutputLine) -> std::io::Result<()> {
+        if !is_cli_output(line) {
+            return Ok(());
+        }
+        let start = self.len;
+        self.write_line(&line.text)?;
+        self.partial_line = Some((start, self.len - start));
+        Ok(())
+    }
+
+    /// Rewrites the partial line, keeping the lines logged after it, or
+    /// appends when there is none.
+    fn replace_last(&mut self, line: &OutputLine) -> std::io::Result<()> {
+        let Some((start, old_len)) = self.partial_line else {
+            return self.push(line);
+        };
+        if !is_cli_output(line) {
+            return Ok(());
+        }
+        let mut tail = Vec::new();
+        self.file.seek(SeekFrom::Start(start + old_len))?;
+        self.file.read_to_end(&mut tail)?;
+        self.file.set_len(start)?;
+        self.file.seek(SeekFrom::Start(start))?;
+        self.len = start;
+        self.write_line(&line.text)?;
+        self.partial_line = Some((start, self.len - start));
+        self.file.write_all(&tail)?;
+        self.len += tail.len() as u64;
+        Ok(())
+    }
+
+    fn write_line(&mut self, text: &str) -> std::io::Result<()> {
+        self.file.write_all(text.as_bytes())?;
+        self.file.write_all(b"\n")?;
+        self.len += text.len() as u64 + 1;
+        Ok(())
+    }
+}
+
+/// Returns whether `line` is output of a CLI rather than a system message.
+const fn is_cli_output(line: &OutputLine) -> bool {
+    matches!(
+        line.line_type,
+        OutputLineType::Stdout | OutputLineType::Stderr
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    /// Sends `events` through the logger and waits until all are forwarded.
+    async fn log_events(logs_dir: &Path, events: Vec<FlowEvent>) -> Vec<FlowEvent> {
+        let (tx, mut rx) = mpsc::channel(100);
+        let log_tx = with_cycle_logs(logs_dir.to_path_buf(), tx);
+        for event in events {
+            log_tx.send(event).await.ok();
+        }
+        drop(log_tx);
+        let mut forwarded = Vec::new();
+        while let Some(event) = rx.recv().await {
+            forwarded.push(event);
+        }
+        forwarded
+    }
+
+    /// Returns the contents of the cycle logs in `logs_dir`, ordered by name.
+    fn read_cycle_logs(logs_dir: &Path) -> Result<Vec<String>> {
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(logs_dir)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<_>>()?;
+        paths.sort();
+        paths
+            .iter()
+            .map(|path| Ok(std::fs::read_to_string(path)?))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn writes_cli_lines_to_the_current_cycle_log() -> Result<()> {
+        let dir = TempDir::new()?;
+        let forwarded = log_events(
+            dir.path(),
+            vec![
+                FlowEvent::Output(OutputLine::stdout("before any cycle")),
+                FlowEvent::CycleStarted(1),
+                FlowEvent::Output(OutputLine::info("system message")),
+                FlowEvent::Output(OutputLine::stdout("planning")),
+                FlowEvent::Output(OutputLine::stderr("warn: slow")),
+                FlowEvent::CycleStarted(2),
+                FlowEvent::Output(OutputLine::stdout("executing")),
+            ],
+        )
+        .await;
+
+        assert_eq!(forwarded.len(), 7);
+        assert_eq!(
+            read_cycle_logs(dir.path())?,
+            vec!["planning\nwarn: slow\n", "executing\n"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn later_flows_keep_earlier_logs() -> Result<()> {
+        let dir = TempDir::new()?;
+        for text in ["first flow", "second flow"] {
+            log_events(
+                dir.path(),
+                vec![
+                    FlowEvent::CycleStarted(1),
+                    FlowEvent::Output(OutputLine::stdout(text)),
+                ],
+            )
+            .await;
+            // Run ids have millisecond resolution
+            tokio::time::sleep(std::time::Duration::from_millis(2)).await;
+        }
+
+        assert_eq!(
+            read_cycle_logs(dir.path())?,
+            vec!["first flow\n", "second flow\n"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn in_place_updates_rewrite_the_partial_line() -> Result<()> {
+        let dir = TempDir::new()?;
+        log_events(
+            dir.path(),
+            vec![
+                FlowEvent::CycleStarted(1),
+                FlowEvent::Output(OutputLine::stdout("first")),
+                FlowEvent::PartialOutput(OutputLine::stdout("progress 10%")),
+                FlowEvent::Output(OutputLine::info("Still working...")),
+                FlowEvent::Output(OutputLine::stderr("warn: slow")),
+                FlowEvent::ReplaceLastOutput(OutputLine::stdout("progress 50%")),
+                FlowEvent::ReplaceLastOutput(OutputLine::stdout("progress 100%")),
+                FlowEvent::Output(OutputLine::stdout("done")),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            read_cycle_logs(dir.path())?,
+            vec!["first\nprogress 100%\nwarn: slow\ndone\n"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn in_place_update_without_partial_line_is_appended() -> Result<()> {
+        let dir = TempDir::new()?;
+        log_events(
+            dir.path(),
+            vec![
+                FlowEvent::CycleStarted(1),
+                FlowEvent::Output(OutputLine::stdout("first")),
+                FlowEvent::ReplaceLastOutput(OutputLine::stdout("second")),
+            ],
+        )
+        .await;
+
+        assert_eq!(read_cycle_logs(dir.path())?, vec!["first\nsecond\n"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,215 @@
+//! Environment checks behind `mcgravity doctor` and `/doctor`.
+//!
+//! Each check looks at one thing `McGravity` relies on: the AI CLIs, a writable
+//! `.mcgravity/` directory, and a git repository. Critical checks are the
+//! ones a flow cannot run without; `mcgravity doctor` exits non-zero when
+//! any of them fails.
+
+use std::path::Path;
+
+use crate::app::state::SettingsState;
+use crate::core::Model;
+use crate::core::git::is_git_repo;
+use crate::fs::{McgravityPaths, unwritable_state_message};
+
+/// The outcome of one environment check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DoctorCheck {
+    /// What was checked, e.g. "Codex CLI".
+    pub label: String,
+    /// Whether the check passed.
+    pub passed: bool,
+    /// Whether a failure keeps flows from running.
+    pub critical: bool,
+    /// What was found, or how to fix a failure.
+    pub detail: String,
+}
+
+impl DoctorCheck {
+    /// Returns true if this is a failed critical check.
+    #[must_use]
+    pub const fn is_blocking(&self) -> bool {
+        self.critical && !self.passed
+    }
+}
+
+/// Runs every check for the project at `paths`.
+///
+/// The CLIs of the planning and execution models in `settings` are critical;
+/// the other models' CLIs are reported but optional. Each CLI is resolved
+/// with its binary path override.
+#[must_use]
+pub fn run_doctor(paths: &McgravityPaths, settings: &SettingsState) -> Vec<DoctorCheck> {
+    let mut checks: Vec<DoctorCheck> = Model::all()
+        .iter()
+        .map(|&model| {
+            let required = model == settings.planning_model || model == settings.execution_model;
+            model_cli_check(model, settings.resolve_cli(model).is_available(), required)
+        })
+        .collect();
+    checks.push(check_state_dir(paths));
+    checks.push(check_git_repo(paths.base()));
+    checks
+}
+
+/// Summarizes a report in one line: whether every critical check passed.
+#[must_use]
+pub fn doctor_summary(checks: &[DoctorCheck]) -> String {
+    match checks.iter().filter(|check| check.is_blocking()).count() {
+        0 => "All critical checks passed.".to_string(),
+        1 => "1 critical check failed.".to_string(),
+        n => format!("{n} critical checks failed."),
+    }
+}
+
+/// Builds the check for a model's CLI, given whether it was found on `PATH`.
+#[must_use]
+pub fn model_cli_check(model: Model, found: bool, required: bool) -> DoctorCheck {
+    let command = model.command();
+    let detail = match (found, required) {
+        (true, _) => format!("`{command}` found"),
+        (false, true) => format!(
+            "`{command}` not found on PATH, but it is a selected model; \
+             install it or pick another model with /models"
+        ),
+        (false, false) => format!("`{command}` not found on PATH (not a selected model)"),
+    };
+    DoctorCheck {
+        label: format!("{} CLI", model.name()),
+        passed: found,
+        critical: required,
+        detail,
+    }
+}
+
+/// Checks that `.mcgravity/` and its todo folders can be written.
+#[must_use]
+pub fn check_state_dir(paths: &McgravityPaths) -> DoctorCheck {
+    let (passed, detail) = match paths.find_unwritable_dir() {
+        None => (
+            true,
+            format!("{} is writable", paths.mcgravity_dir().display()),
+        ),
+        Some((dir, e)) => (false, unwritable_state_message(&dir, &e)),
+    };
+    DoctorCheck {
+        label: "State directory".to_string(),
+        passed,
+        critical: true,
+        detail,
+    }
+}
+
+/// Checks whether `dir` is inside a git repository.
+///
+/// Flows run without git, so this check is optional, but `/diff` needs it.
+#[must_use]
+pub fn check_git_repo(dir: &Path) -> DoctorCheck {
+    let passed = is_git_repo(dir);
+    let detail = if passed {
+        format!("{} is in a git repository", dir.display())
+    } else {
+        format!(
+            "{} is not in a git repository; /diff is unavailable",
+            dir.display()
+        )
+    };
+    DoctorCheck {
+        label: "Git repository".to_string(),
+        passed,
+        critical: false,
+        detail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    #[test]
+    fn selected_model_cli_is_critical() {
+        let missing = model_cli_check(Model::Codex, false, true);
+        assert!(missing.is_blocking());
+        assert!(missing.detail.contains("/models"));
+
+        let found = model_cli_check(Model::Codex, true, true);
+        assert!(found.passed);
+        assert!(!found.is_blocking());
+        assert_eq!(found.label, "Codex CLI");
+    }
+
+    #[test]
+    fn unselected_model_cli_is_optional() {
+        let check = model_cli_check(Model::Gemini, false, false);
+        assert!(!check.passed);
+        assert!(!check.is_blocking());
+    }
+
+    #[test]
+    fn writable_state_dir_passes() -> Result<()> {
+        let temp = TempDir::new()?;
+        let check = check_state_dir(&McgravityPaths::new(temp.path()));
+        assert!(check.passed, "{check:?}");
+        assert!(check.critical);
+        Ok(())
+    }
+
+    #[test]
+    fn state_dir_blocked_by_a_file_fails() -> Result<()> {
+        let temp = TempDir::new()?;
+        std::fs::write(temp.path().join(".mcgravity"), "not a directory")?;
+        let check = check_state_dir(&McgravityPaths::new(temp.path()));
+        assert!(check.is_blocking());
+        assert!(check.detail.contains(".mcgravity"), "{}", check.detail);
+        Ok(())
+    }
+
+    #[test]
+    fn git_repo_check_follows_dot_git() -> Result<()> {
+        let temp = TempDir::new()?;
+        let project = temp.path().join("project");
+        std::fs::create_dir_all(project.join("src"))?;
+        let outside = check_git_repo(&project);
+        assert!(!outside.passed);
+        assert!(!outside.is_blocking());
+
+        std::fs::create_dir(project.join(".git"))?;
+        assert!(check_git_repo(&project.join("src")).passed);
+        Ok(())
+    }
+
+    #[test]
+    fn run_doctor_reports_every_model_and_the_project() -> Result<()> {
+        let temp = TempDir::new()?;
+        let settings = SettingsState {
+            planning_model: Model::Claude,
+            execution_model: Model::Claude,
+            ..SettingsState::default()
+        };
+        let checks = run_doctor(&McgravityPaths::new(temp.path()), &settings);
+        assert_eq!(checks.len(), Model::all().len() + 2);
+        let critical: Vec<&str> = checks
+            .iter()
+            .filter(|check| check.critical)
+            .map(|check| check.label.as_str())
+            .collect();
+        assert_eq!(critical, vec!["Claude Code CLI", "State directory"]);
+        Ok(())
+    }
+
+    #[test]
+    fn summary_counts_blocking_failures() {
+        let optional = model_cli_check(Model::Gemini, false, false);
+        assert_eq!(
+            doctor_summary(std::slice::from_ref(&optional)),
+            "All critical checks passed."
+        );
+        let blocking = model_cli_check(Model::Codex, false, true);
+        assert_eq!(
+            doctor_summary(&[optional, blocking.clone(), blocking]),
+            "2 critical checks failed."
+        );
+    }
+}
@@ -6,6 +6,7 @@
 //! # Command Resolution
 //!
 //! This module uses the shell-aware resolution strategy from [`crate::core::cli_check`]:
+//! 0. The executor's binary path override from settings, if it is executable
 //! 1. Fast PATH lookup via `which`/`where` with executability verification
 //! 2. Shell-based resolution via `$SHELL -l -i -c "command -v <cmd>"` (Unix only)
 //!
@@ -17,17 +18,27 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::process::{ExitStatus, Stdio};
-use tokio::io::{AsyncBufReadExt, BufReader};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, BufReader};
 use tokio::process::{Child, ChildStderr, ChildStdout, Command};
 use tokio::sync::{mpsc, watch};
 
-/// Output line from CLI execution.
+/// Output chunk from CLI execution.
+///
+/// Chunks carry raw text including any `\n` and `\r` characters and do not
+/// necessarily end on a line boundary. Text after the last newline is treated
+/// as a partial line that later chunks continue.
+///
+/// CLI output is decoded lossily: invalid UTF-8 is replaced with U+FFFD, and
+/// multi-byte characters split across reads are reassembled first.
 #[derive(Debug, Clone)]
 pub enum CliOutput {
-    /// Line from stdout.
+    /// Text from stdout.
     Stdout(String),
-    /// Line from stderr.
+    /// Text from stderr.
     Stderr(String),
 }
 
@@ -134,20 +145,40 @@ pub trait AiCliExecutor: Send + Sync {
     /// Returns the CLI command name used by this executor.
     fn command(&self) -> &'static str;
 
+    /// Returns the binary configured for this executor's CLI, which is used
+    /// instead of looking the command up when it is an executable file.
+    fn path_override(&self) -> Option<&Path> {
+        None
+    }
+
     /// Checks if this executor's CLI tool is available.
     ///
     /// Uses the shell-aware resolution strategy to detect commands available
-    /// via PATH, shell aliases, functions, or shell profile modifications.
+    /// via the path override, PATH, shell aliases, functions, or shell
+    /// profile modifications.
     fn is_available(&self) -> bool {
-        check_cli_available(self.command())
+        crate::core::cli_check::resolve_cli_command(self.command(), self.path_override())
+            .is_available()
     }
 }
 
 /// `OpenAI` Codex CLI executor.
 ///
 /// Executes: `codex exec --dangerously-bypass-approvals-and-sandbox <text>`
-#[derive(Debug, Clone, Copy, Default)]
-pub struct CodexExecutor;
+#[derive(Debug, Clone, Default)]
+pub struct CodexExecutor {
+    /// Binary used instead of `codex` on PATH, when executable.
+    path: Option<PathBuf>,
+}
+
+impl CodexExecutor {
+    /// Creates an executor that runs the binary at `path`, if given and
+    /// executable, instead of looking `codex` up.
+    #[must_use]
+    pub const fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+}
 
 #[async_trait]
 impl AiCliExecutor for CodexExecutor {
@@ -159,6 +190,7 @@ impl AiCliExecutor for CodexExecutor {
     ) -> Result<ExitStatus> {
         run_cli_with_output(
             self.command(),
+            self.path_override(),
             &["exec", "--dangerously-bypass-approvals-and-sandbox", input],
             output_tx,
             shutdown_rx,
@@ -173,6 +205,10 @@ impl AiCliExecutor for CodexExecutor {
     fn command(&self) -> &'static str {
         "codex"
     }
+
+    fn path_override(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
 }
 
 /// Anthropic Claude Code CLI executor.
@@ -182,8 +218,20 @@ impl AiCliExecutor for CodexExecutor {
 /// Uses `stream-json` format for real-time streaming output. The JSON is parsed
 /// internally to extract text content only. The `--verbose` flag is required
 /// when using `stream-json` with `--print` mode.
-#[derive(Debug, Clone, Copy, Default)]
-pub struct ClaudeExecutor;
+#[derive(Debug, Clone, Default)]
+pub struct ClaudeExecutor {
+    /// Binary used instead of `claude` on PATH, when executable.
+    path: Option<PathBuf>,
+}
+
+impl ClaudeExecutor {
+    /// Creates an executor that runs the binary at `path`, if given and
+    /// executable, instead of looking `claude` up.
+    #[must_use]
+    pub const fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+}
 
 #[async_trait]
 impl AiCliExecutor for ClaudeExecutor {
@@ -195,6 +243,7 @@ impl AiCliExecutor for ClaudeExecutor {
     ) -> Result<ExitStatus> {
         run_claude_cli_with_output(
             self.command(),
+            self.path_override(),
             &[
                 "-p",
                 input,
@@ -216,6 +265,10 @@ impl AiCliExecutor for ClaudeExecutor {
     fn command(&self) -> &'static str {
         "claude"
     }
+
+    fn path_override(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
 }
 
 /// Google Gemini CLI executor.
@@ -224,8 +277,20 @@ impl AiCliExecutor for ClaudeExecutor {
 ///
 /// Uses `-y` (YOLO mode) to automatically accept all tool actions.
 /// Output is plain text, streamed line-by-line to the UI.
-#[derive(Debug, Clone, Copy, Default)]
-pub struct GeminiExecutor;
+#[derive(Debug, Clone, Default)]
+pub struct GeminiExecutor {
+    /// Binary used instead of `gemini` on PATH, when executable.
+    path: Option<PathBuf>,
+}
+
+impl GeminiExecutor {
+    /// Creates an executor that runs the binary at `path`, if given and
+    /// executable, instead of looking `gemini` up.
+    #[must_use]
+    pub const fn new(path: Option<PathBuf>) -> Self {
+        Self { path }
+    }
+}
 
 #[async_trait]
 impl AiCliExecutor for GeminiExecutor {
@@ -235,7 +300,14 @@ impl AiCliExecutor for GeminiExecutor {
         output_tx: mpsc::Sender<CliOutput>,
         shutdown_rx: watch::Receiver<bool>,
     ) -> Result<ExitStatus> {
-        run_cli_with_output(self.command(), &["-y", input], output_tx, shutdown_rx).await
+        run_cli_with_output(
+            self.command(),
+            self.path_override(),
+            &["-y", input],
+            output_tx,
+            shutdown_rx,
+        )
+        .await
     }
 
     fn name(&self) -> &'static str {
@@ -245,6 +317,49 @@ impl AiCliExecutor for GeminiExecutor {
     fn command(&self) -> &'static str {
         "gemini"
     }
+
+    fn path_override(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+/// How long a CLI process gets to exit after SIGTERM before it is force-killed.
+const TERMINATION_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
+/// File where spawned child PIDs are recorded, if tracking is enabled.
+static PID_FILE: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Enables recording of spawned CLI process PIDs in the given file.
+///
+/// Once enabled, every process started by an executor is registered in the
+/// file while it runs, so that orphans left behind by a crash can be detected
+/// on the next launch (see [`crate::fs::pids`]).
+pub fn track_child_pids(path: PathBuf) {
+    if let Ok(mut pid_file) = PID_FILE.lock() {
+        *pid_file = Some(path);
+    }
+}
+
+/// Registration of a running child PID, removed from the PID file on drop.
+struct PidRegistration {
+    path: PathBuf,
+    pid: u32,
+}
+
+impl PidRegistration {
+    /// Registers the child's PID if PID tracking is enabled.
+    fn register(child: &Child) -> Option<Self> {
+        let path = PID_FILE.lock().ok()?.clone()?;
+        let pid = child.id()?;
+        crate::fs::pids::register_pid(&path, pid, std::process::id()).ok()?;
+        Some(Self { path, pid })
+    }
+}
+
+impl Drop for PidRegistration {
+    fn drop(&mut self) {
+        let _ = crate::fs::pids::unregister_pid(&self.path, self.pid);
+    }
 }
 
 /// A spawned CLI process with captured stdout and stderr.
@@ -257,8 +372,9 @@ struct SpawnedProcess {
 /// Spawns a CLI process with stdout and stderr captured.
 ///
 /// Uses the shell-aware resolution strategy to find the command:
-/// 1. Resolves the command using [`crate::core::cli_check::resolve_cli_command`]
-/// 2. For `PathExecutable`: spawns directly using the resolved path
+/// 1. Resolves the command, or its `path_override`, using
+///    [`crate::core::cli_check::resolve_cli_command`]
+/// 2. For `PathOverride` or `PathExecutable`: spawns directly using the resolved path
 /// 3. For shell-resolved commands (alias/function/builtin): spawns via shell wrapper
 /// 4. For `NotFound`: returns a contextual error
 ///
@@ -267,18 +383,23 @@ struct SpawnedProcess {
 /// # Arguments
 ///
 /// * `command` - The command name to execute (e.g., "claude", "codex")
+/// * `path_override` - A configured binary to run instead, when executable
 /// * `args` - Arguments to pass to the command
 ///
 /// # Errors
 ///
 /// Returns an error if the command cannot be resolved or if spawning fails.
-fn spawn_cli_process(command: &str, args: &[&str]) -> Result<SpawnedProcess> {
+fn spawn_cli_process(
+    command: &str,
+    path_override: Option<&Path>,
+    args: &[&str],
+) -> Result<SpawnedProcess> {
     use crate::core::cli_check::{CommandResolution, resolve_cli_command};
 
-    let resolution = resolve_cli_command(command);
+    let resolution = resolve_cli_command(command, path_override);
 
-    let mut cmd = match &resolution {
-        CommandResolution::PathExecutable(path) => {
+    let cmd = match &resolution {
+        CommandResolution::PathOverride(path) | CommandResolution::PathExecutable(path) => {
             // Direct execution with resolved path
             let mut c = Command::new(path);
             c.args(args);
@@ -318,6 +439,17 @@ fn spawn_cli_process(command: &str, args: &[&str]) -> Result<SpawnedProcess> {
         }
     };
 
+    spawn_piped(cmd, command)
+}
+
+/// Spawns a prepared command with stdout and stderr captured.
+///
+/// On Linux, configures the child to be killed when the parent dies via `PR_SET_PDEATHSIG`.
+///
+/// # Errors
+///
+/// Returns an error if spawning fails.
+fn spawn_piped(mut cmd: Command, command: &str) -> Result<SpawnedProcess> {
     cmd.stdout(Stdio::piped()).stderr(Stdio::piped());
 
     // On Linux, set up the child to be killed when the parent dies.
@@ -377,8 +509,27 @@ fn shell_escape_arg(arg: &str) -> String {
 /// customize how stdout is processed (e.g., raw lines vs JSON parsing).
 async fn run_process_with_output<F, Fut>(
     command: &str,
+    path_override: Option<&Path>,
     args: &[&str],
     output_tx: mpsc::Sender<CliOutput>,
+    shutdown_rx: watch::Receiver<bool>,
+    create_stdout_task: F,
+) -> Result<ExitStatus>
+where
+    F: FnOnce(ChildStdout, mpsc::Sender<CliOutput>) -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let process = spawn_cli_process(command, path_override, args)?;
+    stream_process_output(process, command, output_tx, shutdown_rx, create_stdout_task).await
+}
+
+/// Streams the output of an already spawned process until it exits.
+///
+/// If shutdown is signaled, the child process will be terminated and an error returned.
+async fn stream_process_output<F, Fut>(
+    process: SpawnedProcess,
+    command: &str,
+    output_tx: mpsc::Sender<CliOutput>,
     mut shutdown_rx: watch::Receiver<bool>,
     create_stdout_task: F,
 ) -> Result<ExitStatus>
@@ -390,20 +541,15 @@ where
         mut child,
         stdout,
         stderr,
-    } = spawn_cli_process(command, args)?;
+    } = process;
+    let _registration = PidRegistration::register(&child);
 
     // Spawn stdout processor task using the provided factory
     let stdout_handle = tokio::spawn(create_stdout_task(stdout, output_tx.clone()));
 
     // Spawn stderr reader task (common for all executors)
     let tx_stderr = output_tx;
-    let stderr_handle = tokio::spawn(async move {
-        let reader = BufReader::new(stderr);
-        let mut lines = reader.lines();
-        while let Ok(Some(line)) = lines.next_line().await {
-            let _ = tx_stderr.send(CliOutput::Stderr(line)).await;
-        }
-    });
+    let stderr_handle = tokio::spawn(forward_output_chunks(stderr, tx_stderr, CliOutput::Stderr));
 
     // Wait for either process completion or shutdown signal
     let status = tokio::select! {
@@ -411,11 +557,11 @@ where
             result.with_context(|| format!("Failed to wait for {command} CLI"))?
         }
         () = wait_for_shutdown(&mut shutdown_rx) => {
-            // Shutdown signaled - kill the child process
-            let _ = child.kill().await;
+            // Shutdown signaled - give the child a chance to clean up, then kill it
+            terminate_child(&mut child, TERMINATION_GRACE_PERIOD).await;
             stdout_handle.abort();
             stderr_handle.abort();
-            anyhow::bail!("Shutdown signaled - {command} process killed");
+            anyhow::bail!("Shutdown signaled - {command} process terminated");
         }
     };
 
@@ -426,27 +572,144 @@ where
     Ok(status)
 }
 
+/// Forwards raw output from a reader as soon as it arrives.
+///
+/// Unlike line-based reading, partial lines (such as progress indicators that
+/// redraw with `\r`) are forwarded without waiting for a newline. Multi-byte
+/// UTF-8 sequences split across reads are held back until complete.
+async fn forward_output_chunks<R>(
+    mut reader: R,
+    tx: mpsc::Sender<CliOutput>,
+    wrap: fn(String) -> CliOutput,
+) where
+    R: AsyncRead + Unpin,
+{
+    let mut buf = [0u8; 4096];
+    let mut pending = Vec::new();
+    loop {
+        let n = match reader.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        pending.extend_from_slice(&buf[..n]);
+        let text = take_utf8_prefix(&mut pending);
+        if !text.is_empty() && tx.send(wrap(text)).await.is_err() {
+            return;
+        }
+    }
+    if !pending.is_empty() {
+        let _ = tx
+            .send(wrap(String::from_utf8_lossy(&pending).into_owned()))
+            .await;
+    }
+}
+
+/// Removes and decodes the longest UTF-8 prefix of `pending`.
+///
+/// Invalid bytes are replaced with U+FFFD. An incomplete multi-byte sequence
+/// at the end is left in `pending` for the next read.
+fn take_utf8_prefix(pending: &mut Vec<u8>) -> String {
+    let mut text = String::new();
+    loop {
+        match std::str::from_utf8(pending) {
+            Ok(valid) => {
+                text.push_str(valid);
+                pending.clear();
+                return text;
+            }
+            Err(e) => {
+                let valid_len = e.valid_up_to();
+                text.push_str(&String::from_utf8_lossy(&pending[..valid_len]));
+                let Some(invalid_len) = e.error_len() else {
+                    pending.drain(..valid_len);
+                    return text;
+                };
+                text.push(char::REPLACEMENT_CHARACTER);
+                pending.drain(..valid_len + invalid_len);
+            }
+        }
+    }
+}
+
+/// Terminates a child process, giving it a chance to exit cleanly.
+///
+/// On Unix, sends SIGTERM first and waits up to `grace` for the process to exit
+/// before falling back to SIGKILL. On other platforms, the process is killed
+/// immediately.
+async fn terminate_child(child: &mut Child, grace: Duration) {
+    #[cfg(unix)]
+    if let Some(pid) = child.id().and_then(|pid| libc::pid_t::try_from(pid).ok()) {
+        // SAFETY: `kill` has no memory-safety preconditions; the PID belongs to
+        // a child we have not yet reaped, so it cannot have been recycled.
+        let sent = unsafe { libc::kill(pid, libc::SIGTERM) } == 0;
+        if sent && tokio::time::timeout(grace, child.wait()).await.is_ok() {
+            return;
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = grace;
+
+    let _ = child.kill().await;
+}
+
 /// Runs a CLI command and streams its output.
 ///
-/// If shutdown is signaled, the child process will be killed and an error returned.
+/// If shutdown is signaled, the child process will be terminated and an error returned.
 async fn run_cli_with_output(
     command: &str,
+    path_override: Option<&Path>,
     args: &[&str],
     output_tx: mpsc::Sender<CliOutput>,
     shutdown_rx: watch::Receiver<bool>,
 ) -> Result<ExitStatus> {
     run_process_with_output(
         command,
+        path_override,
         args,
         output_tx,
         shutdown_rx,
-        |stdout, tx| async move {
-            let reader = BufReader::new(stdout);
-            let mut lines = reader.lines();
-            while let Ok(Some(line)) = lines.next_line().await {
-                let _ = tx.send(CliOutput::Stdout(line)).await;
-            }
-        },
+        |stdout, tx| forward_output_chunks(stdout, tx, CliOutput::Stdout),
+    )
+    .await
+}
+
+/// Runs a user-configured shell command and streams its output.
+///
+/// The command line is interpreted by `sh -c` (`cmd /C` on Windows) with
+/// `cwd` as the working directory, so hooks such as `git pull && make` work
+/// as typed. If shutdown is signaled, the process is terminated and an
+/// error returned.
+///
+/// # Errors
+///
+/// Returns an error if the shell cannot be spawned or shutdown is signaled.
+pub async fn run_shell_command(
+    command_line: &str,
+    cwd: &Path,
+    output_tx: mpsc::Sender<CliOutput>,
+    shutdown_rx: watch::Receiver<bool>,
+) -> Result<ExitStatus> {
+    #[cfg(unix)]
+    let mut cmd = {
+        let mut c = Command::new("sh");
+        c.args(["-c", command_line]);
+        c
+    };
+    #[cfg(windows)]
+    let mut cmd = {
+        let mut c = Command::new("cmd");
+        c.args(["/C", command_line]);
+        c
+    };
+    cmd.current_dir(cwd).stdin(Stdio::null());
+
+    let process = spawn_piped(cmd, command_line)?;
+    stream_process_output(
+        process,
+        command_line,
+        output_tx,
+        shutdown_rx,
+        |stdout, tx| forward_output_chunks(stdout, tx, CliOutput::Stdout),
     )
     .await
 }
@@ -457,35 +720,62 @@ async fn run_cli_with_output(
 /// It parses each JSONL line and extracts text content before forwarding to the output channel.
 /// Non-text messages (tool usage, etc.) are silently filtered out.
 ///
-/// If shutdown is signaled, the child process will be killed and an error returned.
+/// If shutdown is signaled, the child process will be terminated and an error returned.
 async fn run_claude_cli_with_output(
     command: &str,
+    path_override: Option<&Path>,
     args: &[&str],
     output_tx: mpsc::Sender<CliOutput>,
     shutdown_rx: watch::Receiver<bool>,
 ) -> Result<ExitStatus> {
     run_process_with_output(
         command,
+        path_override,
         args,
         output_tx,
         shutdown_rx,
-        |stdout, tx| async move {
-            let reader = BufReader::new(stdout);
-            let mut lines = reader.lines();
-            while let Ok(Some(line)) = lines.next_line().await {
-                // Parse JSON and extract text content
-                if let Some(text) = parse_claude_stream_json(&line) {
-                    let _ = tx.send(CliOutput::Stdout(text)).await;
-                } else if !line.trim().is_empty() {
-                    // Forward unparseable non-empty lines as-is for debugging
-                    let _ = tx.send(CliOutput::Stdout(line)).await;
-                }
-            }
-        },
+        forward_claude_stream_lines,
     )
     .await
 }
 
+/// Forwards the text content of Claude's JSONL stream, one line at a time.
+///
+/// Lines are read as bytes and decoded lossily, so invalid UTF-8 becomes
+/// U+FFFD instead of ending the stream.
+async fn forward_claude_stream_lines<R>(reader: R, tx: mpsc::Sender<CliOutput>)
+where
+    R: AsyncRead + Unpin,
+{
+    let mut reader = BufReader::new(reader);
+    let mut bytes = Vec::new();
+    loop {
+        bytes.clear();
+        match reader.read_until(b'\n', &mut bytes).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let decoded = String::from_utf8_lossy(&bytes);
+        let line = decoded.trim_end_matches(['\n', '\r']);
+        // Parse JSON and extract text content
+        let text = if let Some(text) = parse_claude_stream_json(line) {
+            text
+        } else if !line.trim().is_empty() {
+            // Forward unparseable non-empty lines as-is for debugging
+            line.to_string()
+        } else {
+            continue;
+        };
+        if tx
+            .send(CliOutput::Stdout(format!("{text}\n")))
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
 /// Checks if a CLI tool is available using the shell-aware resolution strategy.
 ///
 /// This function delegates to [`crate::core::cli_check::resolve_cli_command`]
@@ -504,14 +794,14 @@ async fn run_claude_cli_with_output(
 /// `true` if the command is available, `false` otherwise.
 #[must_use]
 pub fn check_cli_available(name: &str) -> bool {
-    crate::core::cli_check::resolve_cli_command(name).is_available()
+    crate::core::cli_check::resolve_cli_command(name, None).is_available()
 }
 
 /// Waits for a shutdown signal on the watch channel.
 ///
 /// This function loops until the shutdown value becomes true, checking after each
 /// change notification. It's designed to be used with `tokio::select!`.
-async fn wait_for_shutdown(rx: &mut watch::Receiver<bool>) {
+pub(crate) async fn wait_for_shutdown(rx: &mut watch::Receiver<bool>) {
     loop {
         // Check current value without holding the lock
         if *rx.borrow() {
@@ -736,29 +1026,29 @@ mod tests {
         /// Tests that `CodexExecutor` returns correct name.
         #[test]
         fn name_returns_codex() {
-            let executor = CodexExecutor;
+            let executor = CodexExecutor::default();
             assert_eq!(executor.name(), "Codex");
         }
 
         /// Tests that `CodexExecutor` returns correct command.
         #[test]
         fn command_returns_codex() {
-            let executor = CodexExecutor;
+            let executor = CodexExecutor::default();
             assert_eq!(executor.command(), "codex");
         }
 
         /// Tests that `CodexExecutor` implements `Default`.
         #[test]
         fn default_creates_instance() {
-            let executor = CodexExecutor;
+            let executor = CodexExecutor::default();
             assert_eq!(executor.name(), "Codex");
         }
 
         /// Tests that `CodexExecutor` can be cloned.
         #[test]
         fn clone_creates_copy() {
-            let original = CodexExecutor;
-            let cloned = original; // Copy, since it's Copy
+            let original = CodexExecutor::default();
+            let cloned = original.clone();
 
             assert_eq!(original.name(), cloned.name());
             assert_eq!(original.command(), cloned.command());
@@ -767,7 +1057,7 @@ mod tests {
         /// Tests Debug trait implementation.
         #[test]
         fn debug_format_is_readable() {
-            let executor = CodexExecutor;
+            let executor = CodexExecutor::default();
             let debug_str = format!("{executor:?}");
 
             assert!(debug_str.contains("CodexExecutor"));
@@ -784,29 +1074,29 @@ mod tests {
         /// Tests that `ClaudeExecutor` returns correct name.
         #[test]
         fn name_returns_claude_code() {
-            let executor = ClaudeExecutor;
+            let executor = ClaudeExecutor::default();
             assert_eq!(executor.name(), "Claude Code");
         }
 
         /// Tests that `ClaudeExecutor` returns correct command.
         #[test]
         fn command_returns_claude() {
-            let executor = ClaudeExecutor;
+            let executor = ClaudeExecutor::default();
             assert_eq!(executor.command(), "claude");
         }
 
         /// Tests that `ClaudeExecutor` implements `Default`.
         #[test]
         fn default_creates_instance() {
-            let executor = ClaudeExecutor;
+            let executor = ClaudeExecutor::default();
             assert_eq!(executor.name(), "Claude Code");
         }
 
-        /// Tests that `ClaudeExecutor` can be copied.
+        /// Tests that `ClaudeExecutor` can be cloned.
         #[test]
-        fn copy_creates_identical_instance() {
-            let original = ClaudeExecutor;
-            let copied = original;
+        fn clone_creates_identical_instance() {
+            let original = ClaudeExecutor::default();
+            let copied = original.clone();
 
             assert_eq!(original.name(), copied.name());
             assert_eq!(original.command(), copied.command());
@@ -815,7 +1105,7 @@ mod tests {
         /// Tests Debug trait implementation.
         #[test]
         fn debug_format_is_readable() {
-            let executor = ClaudeExecutor;
+            let executor = ClaudeExecutor::default();
             let debug_str = format!("{executor:?}");
 
             assert!(debug_str.contains("ClaudeExecutor"));
@@ -832,29 +1122,29 @@ mod tests {
         /// Tests that `GeminiExecutor` returns correct name.
         #[test]
         fn name_returns_gemini() {
-            let executor = GeminiExecutor;
+            let executor = GeminiExecutor::default();
             assert_eq!(executor.name(), "Gemini");
         }
 
         /// Tests that `GeminiExecutor` returns correct command.
         #[test]
         fn command_returns_gemini() {
-            let executor = GeminiExecutor;
+            let executor = GeminiExecutor::default();
             assert_eq!(executor.command(), "gemini");
         }
 
         /// Tests that `GeminiExecutor` implements `Default`.
         #[test]
         fn default_creates_instance() {
-            let executor = GeminiExecutor;
+            let executor = GeminiExecutor::default();
             assert_eq!(executor.name(), "Gemini");
         }
 
-        /// Tests that `GeminiExecutor` can be copied.
+        /// Tests that `GeminiExecutor` can be cloned.
         #[test]
-        fn copy_creates_identical_instance() {
-            let original = GeminiExecutor;
-            let copied = original;
+        fn clone_creates_identical_instance() {
+            let original = GeminiExecutor::default();
+            let copied = original.clone();
 
             assert_eq!(original.name(), copied.name());
             assert_eq!(original.command(), copied.command());
@@ -863,7 +1153,7 @@ mod tests {
         /// Tests Debug trait implementation.
         #[test]
         fn debug_format_is_readable() {
-            let executor = GeminiExecutor;
+            let executor = GeminiExecutor::default();
             let debug_str = format!("{executor:?}");
 
             assert!(debug_str.contains("GeminiExecutor"));
@@ -881,9 +1171,9 @@ mod tests {
         #[test]
         fn executors_work_as_trait_objects() {
             let executors: Vec<Box<dyn AiCliExecutor>> = vec![
-                Box::new(CodexExecutor),
-                Box::new(ClaudeExecutor),
-                Box::new(GeminiExecutor),
+                Box::new(CodexExecutor::default()),
+                Box::new(ClaudeExecutor::default()),
+                Box::new(GeminiExecutor::default()),
             ];
 
             assert_eq!(executors[0].name(), "Codex");
@@ -902,9 +1192,9 @@ mod tests {
         /// Tests that trait object references work correctly.
         #[test]
         fn trait_object_references() {
-            let codex = CodexExecutor;
-            let claude = ClaudeExecutor;
-            let gemini = GeminiExecutor;
+            let codex = CodexExecutor::default();
+            let claude = ClaudeExecutor::default();
+            let gemini = GeminiExecutor::default();
 
             assert_eq!(get_name(&codex), "Codex");
             assert_eq!(get_name(&claude), "Claude Code");
@@ -912,6 +1202,114 @@ mod tests {
         }
     }
 
+    // =========================================================================
+    // take_utf8_prefix Tests
+    // =========================================================================
+
+    mod take_utf8_prefix {
+        use super::*;
+
+        #[test]
+        fn decodes_complete_text() {
+            let mut pending = b"hello\r\n".to_vec();
+            assert_eq!(take_utf8_prefix(&mut pending), "hello\r\n");
+            assert!(pending.is_empty());
+        }
+
+        #[test]
+        fn holds_back_split_multibyte_sequence() {
+            let bytes = "caf\u{e9}".as_bytes();
+            let mut pending = bytes[..bytes.len() - 1].to_vec();
+            assert_eq!(take_utf8_prefix(&mut pending), "caf");
+            assert_eq!(pending.len(), 1);
+
+            pending.push(bytes[bytes.len() - 1]);
+            assert_eq!(take_utf8_prefix(&mut pending), "\u{e9}");
+            assert!(pending.is_empty());
+        }
+
+        #[test]
+        fn replaces_invalid_bytes() {
+            let mut pending = vec![b'a', 0xff, b'b'];
+            assert_eq!(take_utf8_prefix(&mut pending), "a\u{fffd}b");
+            assert!(pending.is_empty());
+        }
+    }
+
+    // =========================================================================
+    // Non-UTF-8 Stream Tests
+    // =========================================================================
+
+    mod non_utf8_streams {
+        use super::*;
+        use std::collections::VecDeque;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+        use tokio::io::ReadBuf;
+
+        /// Reader that returns each chunk from a separate `read` call.
+        struct ChunkedReader {
+            chunks: VecDeque<Vec<u8>>,
+        }
+
+        impl ChunkedReader {
+            fn new(chunks: &[&[u8]]) -> Self {
+                Self {
+                    chunks: chunks.iter().map(|chunk| chunk.to_vec()).collect(),
+                }
+            }
+        }
+
+        impl AsyncRead for ChunkedReader {
+            fn poll_read(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+                buf: &mut ReadBuf<'_>,
+            ) -> Poll<std::io::Result<()>> {
+                if let Some(chunk) = self.chunks.pop_front() {
+                    buf.put_slice(&chunk);
+                }
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        /// Drains the channel into one string.
+        async fn collect_text(mut rx: mpsc::Receiver<CliOutput>) -> String {
+            let mut text = String::new();
+            while let Some(output) = rx.recv().await {
+                match output {
+                    CliOutput::Stdout(chunk) | CliOutput::Stderr(chunk) => text.push_str(&chunk),
+                }
+            }
+            text
+        }
+
+        /// An invalid sequence split across reads becomes U+FFFD, while a
+        /// valid multi-byte character split the same way is kept intact.
+        #[tokio::test]
+        async fn invalid_sequence_split_across_reads_is_replaced() {
+            // `\xE2\x82` starts a three-byte sequence that `X` never completes;
+            // `\xE2\x82\xAC` is a complete euro sign split after two bytes
+            let reader = ChunkedReader::new(&[b"ok \xE2\x82", b"X \xE2\x82", b"\xAC end\n"]);
+            let (tx, rx) = mpsc::channel(16);
+
+            forward_output_chunks(reader, tx, CliOutput::Stdout).await;
+
+            assert_eq!(collect_text(rx).await, "ok \u{fffd}X \u{20ac} end\n");
+        }
+
+        /// Invalid bytes in a Claude JSONL line do not stop later lines.
+        #[tokio::test]
+        async fn claude_stream_continues_after_invalid_line() {
+            let reader = ChunkedReader::new(&[b"not json \xFF\n", b"plain line\n"]);
+            let (tx, rx) = mpsc::channel(16);
+
+            forward_claude_stream_lines(reader, tx).await;
+
+            assert_eq!(collect_text(rx).await, "not json \u{fffd}\nplain line\n");
+        }
+    }
+
     // =========================================================================
     // Async Tests (using tokio::test)
     // =========================================================================
@@ -991,5 +1389,101 @@ mod tests {
             .map_err(|_| anyhow::anyhow!("wait_for_shutdown should have returned on signal"))?;
             Ok(())
         }
+
+        /// Tests that output without a trailing newline is forwarded as-is.
+        #[cfg(unix)]
+        #[tokio::test]
+        async fn forwards_partial_lines_without_newline() -> anyhow::Result<()> {
+            let (output_tx, mut output_rx) = mpsc::channel(10);
+            let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+
+            let status = run_cli_with_output(
+                "sh",
+                None,
+                &["-c", "printf 'progress 10%%\\rprogress 20%%'"],
+                output_tx,
+                shutdown_rx,
+            )
+            .await?;
+            assert!(status.success());
+
+            let mut text = String::new();
+            while let Some(CliOutput::Stdout(chunk)) = output_rx.recv().await {
+                text.push_str(&chunk);
+            }
+            assert_eq!(text, "progress 10%\rprogress 20%");
+            Ok(())
+        }
+
+        /// Tests that an executor runs its binary path override.
+        #[cfg(unix)]
+        #[tokio::test]
+        async fn executor_runs_its_path_override() -> anyhow::Result<()> {
+            use std::os::unix::fs::PermissionsExt;
+
+            let dir = tempfile::TempDir::new()?;
+            let stub = dir.path().join("gemini-stub");
+            std::fs::write(&stub, "#!/bin/sh\necho \"stub $*\"\n")?;
+            std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755))?;
+            let executor = GeminiExecutor::new(Some(stub));
+            assert!(executor.is_available());
+
+            let (output_tx, mut output_rx) = mpsc::channel(10);
+            let (_shutdown_tx, shutdown_rx) = watch::channel(false);
+            let status = executor.execute("hello", output_tx, shutdown_rx).await?;
+            assert!(status.success());
+
+            let mut text = String::new();
+            while let Some(CliOutput::Stdout(chunk)) = output_rx.recv().await {
+                text.push_str(&chunk);
+            }
+            assert_eq!(text, "stub -y hello\n");
+            Ok(())
+        }
+
+        /// Tests that a running child exits on SIGTERM well within the grace period.
+        #[cfg(unix)]
+        #[tokio::test]
+        async fn shutdown_terminates_child_within_grace_period() -> anyhow::Result<()> {
+            let (output_tx, _output_rx) = mpsc::channel(10);
+            let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                let _ = shutdown_tx.send(true);
+            });
+
+            let started = std::time::Instant::now();
+            let result = tokio::time::timeout(
+                TERMINATION_GRACE_PERIOD,
+                run_cli_with_output("sleep", None, &["30"], output_tx, shutdown_rx),
+            )
+            .await
+            .map_err(|_| anyhow::anyhow!("child should have exited on SIGTERM"))?;
+
+            assert!(result.is_err(), "shutdown should be reported as an error");
+            assert!(started.elapsed() < TERMINATION_GRACE_PERIOD);
+            Ok(())
+        }
+
+        /// Tests that a child ignoring SIGTERM is killed once the grace period expires.
+        #[cfg(unix)]
+        #[tokio::test]
+        async fn terminate_child_kills_after_grace_period() -> anyhow::Result<()> {
+            let SpawnedProcess { mut child, .. } =
+                spawn_cli_process("sh", None, &["-c", "trap '' TERM; sleep 5 & wait"])?;
+            // Give the shell time to install its trap
+            tokio::time::sleep(Duration::from_millis(100)).await;
+
+            tokio::time::timeout(
+                Duration::from_secs(2),
+                terminate_child(&mut child, Duration::from_millis(100)),
+            )
+            .await
+            .map_err(|_| anyhow::anyhow!("terminate_child should have killed the child"))?;
+
+            assert!(child.try_wait()?.is_some(), "child should have exited");
+            Ok(())
+        }
     }
 }
@@ -0,0 +1,236 @@
+//! Classification of AI CLI failures.
+//!
+//! When an executor fails, its captured output and exit code are matched
+//! against known failure signatures so the user gets a remediation hint
+//! instead of a bare exit code, and the runner can stop retrying failures
+//! that will not fix themselves (such as missing credentials).
+
+/// Exit code shells use when a command cannot be found.
+const COMMAND_NOT_FOUND_EXIT_CODE: i32 = 127;
+
+/// Output signatures of authentication / credential failures (lowercase).
+const AUTH_SIGNATURES: [&str; 14] = [
+    "unauthorized",
+    "authentication failed",
+    "authentication error",
+    "authentication_error",
+    "not logged in",
+    "please log in",
+    "please login",
+    "invalid api key",
+    "invalid_api_key",
+    "invalid x-api-key",
+    "missing api key",
+    "api key not found",
+    "invalid credentials",
+    "status 401",
+];
+
+/// Output signatures of rate limiting and quota errors (lowercase).
+const RATE_LIMIT_SIGNATURES: [&str; 9] = [
+    "rate limit",
+    "rate_limit",
+    "ratelimit",
+    "too many requests",
+    "status 429",
+    "error 429",
+    "quota exceeded",
+    "resource_exhausted",
+    "overloaded",
+];
+
+/// Output signatures of a missing CLI binary (lowercase).
+const NOT_FOUND_SIGNATURES: [&str; 4] = [
+    "command not found",
+    "no such file or directory",
+    "is not recognized as an internal or external command",
+    "not found. ensure it is installed",
+];
+
+/// Known categories of AI CLI failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutorFailureKind {
+    /// The CLI is not logged in or its API key is missing or invalid.
+    Authentication,
+    /// The provider is rate limiting requests or the quota is exhausted.
+    RateLimit,
+    /// The CLI binary could not be found.
+    CommandNotFound,
+    /// The run was cancelled by shutdown.
+    Cancelled,
+    /// Any other failure.
+    Other,
+}
+
+impl ExecutorFailureKind {
+    /// Classifies a failure from the CLI's captured output and exit code.
+    ///
+    /// Authentication is checked before rate limiting, since an invalid key
+    /// is never fixed by waiting.
+    #[must_use]
+    pub fn classify(output: &str, exit_code: Option<i32>) -> Self {
+        let output = output.to_lowercase();
+        let matches = |signatures: &[&str]| signatures.iter().any(|s| output.contains(s));
+        if matches(&AUTH_SIGNATURES) {
+            Self::Authentication
+        } else if matches(&RATE_LIMIT_SIGNATURES) {
+            Self::RateLimit
+        } else if exit_code == Some(COMMAND_NOT_FOUND_EXIT_CODE) || matches(&NOT_FOUND_SIGNATURES) {
+            Self::CommandNotFound
+        } else {
+            Self::Other
+        }
+    }
+
+    /// Returns true if retrying can succeed.
+    ///
+    /// Authentication and missing-binary failures need user action, so the
+    /// flow should stop instead of retrying or moving on to the next todo.
+    #[must_use]
+    pub const fn is_retryable(self) -> bool {
+        matches!(self, Self::RateLimit | Self::Other)
+    }
+
+    /// Returns a friendly explanation with a remediation hint, or `None` for
+    /// failures without a known signature.
+    ///
+    /// `command` is the CLI command name (e.g. `claude`).
+    #[must_use]
+    pub fn hint(self, command: &str) -> Option<String> {
+        match self {
+            Self::Authentication => Some(format!(
+                "{command} is not authenticated. Run `{command}` once interactively to log in, \
+                 or check that its API key is set."
+            )),
+            Self::RateLimit => Some(format!(
+                "{command} is being rate limited. Retries wait with backoff; if this persists, \
+                 check your plan's quota or try again later."
+            )),
+            Self::CommandNotFound => Some(format!(
+                "{command} was not found. Make sure it is installed and on your PATH \
+                 (`which {command}`)."
+            )),
+            Self::Cancelled | Self::Other => None,
+        }
+    }
+}
+
+/// An AI CLI run that failed after all retries, with its classification.
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct ExecutorFailure {
+    /// What kind of failure this was.
+    pub kind: ExecutorFailureKind,
+    /// The failure message shown to the user.
+    pub message: String,
+}
+
+impl ExecutorFailure {
+    /// Creates a failure of the given kind.
+    #[must_use]
+    pub fn new(kind: ExecutorFailureKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    /// Creates a failure for a run interrupted by shutdown.
+    #[must_use]
+    pub fn cancelled() -> Self {
+        Self::new(ExecutorFailureKind::Cancelled, "Shutdown signaled")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_authentication_errors() {
+        for stderr in [
+            "Error: 401 Unauthorized",
+            "You are not logged in. Please run `codex login`.",
+            "API Error: authentication_error: invalid x-api-key",
+            "Invalid API key · Please run /login",
+            "request failed with status 401",
+        ] {
+            assert_eq!(
+                ExecutorFailureKind::classify(stderr, Some(1)),
+                ExecutorFailureKind::Authentication,
+                "{stderr}"
+            );
+        }
+    }
+
+    #[test]
+    fn classifies_rate_limits() {
+        for stderr in [
+            "Error: Rate limit reached for requests",
+            "HTTP error 429: Too Many Requests",
+            "RESOURCE_EXHAUSTED: Quota exceeded for quota metric",
+            "API Error: Overloaded",
+        ] {
+            assert_eq!(
+                ExecutorFailureKind::classify(stderr, Some(1)),
+                ExecutorFailureKind::RateLimit,
+                "{stderr}"
+            );
+        }
+    }
+
+    #[test]
+    fn classifies_missing_commands() {
+        assert_eq!(
+            ExecutorFailureKind::classify("sh: 1: gemini: command not found", Some(127)),
+            ExecutorFailureKind::CommandNotFound
+        );
+        assert_eq!(
+            ExecutorFailureKind::classify("", Some(127)),
+            ExecutorFailureKind::CommandNotFound
+        );
+        assert_eq!(
+            ExecutorFailureKind::classify(
+                "Failed to spawn codex CLI: No such file or directory (os error 2)",
+                None
+            ),
+            ExecutorFailureKind::CommandNotFound
+        );
+    }
+
+    #[test]
+    fn unknown_output_is_other() {
+        assert_eq!(
+            ExecutorFailureKind::classify("panic: index out of range", Some(2)),
+            ExecutorFailureKind::Other
+        );
+        assert_eq!(
+            ExecutorFailureKind::classify("", Some(1)),
+            ExecutorFailureKind::Other
+        );
+    }
+
+    #[test]
+    fn authentication_wins_over_rate_limit() {
+        assert_eq!(
+            ExecutorFailureKind::classify("401 Unauthorized (rate limit headers present)", None),
+            ExecutorFailureKind::Authentication
+        );
+    }
+
+    #[test]
+    fn only_transient_failures_are_retryable() {
+        assert!(ExecutorFailureKind::RateLimit.is_retryable());
+        assert!(ExecutorFailureKind::Other.is_retryable());
+        assert!(!ExecutorFailureKind::Authentication.is_retryable());
+        assert!(!ExecutorFailureKind::CommandNotFound.is_retryable());
+        assert!(!ExecutorFailureKind::Cancelled.is_retryable());
+    }
+
+    #[test]
+    fn hints_mention_the_command() {
+        let hint = ExecutorFailureKind::CommandNotFound.hint("claude");
+        assert!(hint.is_some_and(|h| h.contains("which claude")));
+        assert!(ExecutorFailureKind::Other.hint("claude").is_none());
+    }
+}
@@ -0,0 +1,169 @@
+//! Extraction of file references from CLI output.
+//!
+//! Executors mention the files they change in many shapes: bare paths,
+//! `path:line`, `path:line:col`, or wrapped in backticks, quotes and
+//! parentheses. `/open` uses these helpers to find the path in an output
+//! line and open it in the editor.
+
+use std::path::PathBuf;
+
+/// A file path mentioned in output, with an optional line number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileRef {
+    /// The path as written (relative paths are relative to the project).
+    pub path: PathBuf,
+    /// 1-based line number, if the reference had one.
+    pub line: Option<usize>,
+}
+
+impl FileRef {
+    /// Creates a reference to `path` without a line number.
+    #[must_use]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            line: None,
+        }
+    }
+}
+
+/// Characters stripped from both ends of a token before parsing.
+const WRAPPING_CHARS: &[char] = &[
+    '`', '"', '\'', '(', ')', '[', ']', '{', '}', '<', '>', ',', ';', '.', ':', '!', '?', '*',
+];
+
+/// Parses a single token like `src/core/flow.rs:42` into a [`FileRef`].
+///
+/// Returns `None` for tokens that do not look like a file path: URLs, words
+/// without a directory separator or extension, and option flags.
+#[must_use]
+pub fn parse_file_ref(token: &str) -> Option<FileRef> {
+    let token = token.trim_matches(WRAPPING_CHARS);
+    if token.is_empty() || token.starts_with('-') || token.contains("://") {
+        return None;
+    }
+
+    // Peel up to two numeric suffixes (`:line` and `:line:col`)
+    let mut path = token;
+    let mut numbers = Vec::new();
+    while numbers.len() < 2 {
+        let Some((rest, suffix)) = path.rsplit_once(':') else {
+            break;
+        };
+        let Ok(number) = suffix.parse::<usize>() else {
+            break;
+        };
+        numbers.push(number);
+        path = rest;
+    }
+    let line = numbers.last().copied().filter(|&line| line > 0);
+
+    looks_like_path(path).then(|| FileRef {
+        path: PathBuf::from(path),
+        line,
+    })
+}
+
+/// Returns all file references in `line`, in order of appearance.
+#[must_use]
+pub fn extract_file_refs(line: &str) -> Vec<FileRef> {
+    line.split_whitespace().filter_map(parse_file_ref).collect()
+}
+
+/// Returns true if `path` has a directory separator or a file extension.
+fn looks_like_path(path: &str) -> bool {
+    if path.is_empty() || path.contains(':') {
+        return false;
+    }
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let has_extension = file_name
+        .rsplit_once('.')
+        .is_some_and(|(stem, ext)| !stem.is_empty() && is_extension(ext));
+    has_extension || (path.contains('/') && !file_name.is_empty())
+}
+
+/// Returns true if `ext` looks like a file extension rather than prose.
+fn is_extension(ext: &str) -> bool {
+    !ext.is_empty()
+        && ext.len() <= 10
+        && ext.chars().all(|c| c.is_ascii_alphanumeric())
+        && ext.chars().any(|c| c.is_ascii_alphabetic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_with_line_is_split() {
+        assert_eq!(
+            parse_file_ref("src/core/flow.rs:42"),
+            Some(FileRef {
+                path: PathBuf::from("src/core/flow.rs"),
+                line: Some(42),
+            })
+        );
+    }
+
+    #[test]
+    fn column_is_dropped() {
+        assert_eq!(
+            parse_file_ref("src/main.rs:10:5"),
+            Some(FileRef {
+                path: PathBuf::from("src/main.rs"),
+                line: Some(10),
+            })
+        );
+    }
+
+    #[test]
+    fn wrapping_punctuation_is_stripped() {
+        for token in [
+            "`src/lib.rs`",
+            "(src/lib.rs)",
+            "\"src/lib.rs\",",
+            "src/lib.rs.",
+        ] {
+            assert_eq!(
+                parse_file_ref(token),
+                Some(FileRef::new("src/lib.rs")),
+                "{token}"
+            );
+        }
+    }
+
+    #[test]
+    fn non_paths_are_rejected() {
+        for token in [
+            "Updated",
+            "https://example.com/a.rs",
+            "--verbose",
+            "3.14",
+            "12:30",
+            "",
+        ] {
+            assert_eq!(parse_file_ref(token), None, "{token:?}");
+        }
+    }
+
+    #[test]
+    fn bare_file_names_and_directories_are_accepted() {
+        assert_eq!(parse_file_ref("README.md"), Some(FileRef::new("README.md")));
+        assert_eq!(parse_file_ref("src/app"), Some(FileRef::new("src/app")));
+    }
+
+    #[test]
+    fn extracts_all_refs_from_a_line() {
+        let refs = extract_file_refs("Modified `src/app/mod.rs:120` and tests/cli.rs to fix it.");
+        assert_eq!(
+            refs,
+            [
+                FileRef {
+                    path: PathBuf::from("src/app/mod.rs"),
+                    line: Some(120),
+                },
+                FileRef::new("tests/cli.rs"),
+            ]
+        );
+    }
+}
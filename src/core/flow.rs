@@ -31,15 +31,29 @@ pub enum FlowPhase {
     },
     /// One cycle complete, preparing for next.
     CycleComplete { iteration: u32 },
+    /// Step mode: waiting for `/next` or `/stop` after a completed cycle.
+    Paused { iteration: u32 },
     /// Updating summary and removing completed todo files.
     MovingCompletedFiles,
     /// All cycles complete successfully.
     Completed,
+    /// Stopped by the user with ESC or `/cancel`.
+    Cancelled,
     /// Flow failed with an error.
     Failed { reason: String },
 }
 
 impl FlowPhase {
+    /// Returns the name of the model running in this phase, if any.
+    #[must_use]
+    pub fn model_name(&self) -> Option<&str> {
+        match self {
+            Self::RunningPlanning { model_name, .. }
+            | Self::RunningExecution { model_name, .. } => Some(model_name),
+            _ => None,
+        }
+    }
+
     /// Returns a human-readable description of the current phase.
     #[must_use]
     pub fn description(&self) -> Cow<'static, str> {
@@ -64,10 +78,12 @@ impl FlowPhase {
                 "Running {model_name} on file {file_index} (attempt {attempt})"
             )),
             Self::CycleComplete { iteration } => Cow::Owned(format!("Cycle {iteration} complete")),
+            Self::Paused { iteration } => Cow::Owned(format!("Paused after cycle {iteration}")),
             Self::MovingCompletedFiles => {
                 Cow::Borrowed("Updating summary, removing completed todos")
             }
             Self::Completed => Cow::Borrowed("Completed"),
+            Self::Cancelled => Cow::Borrowed("Cancelled"),
             Self::Failed { reason } => Cow::Owned(format!("Failed: {reason}")),
         }
     }
@@ -77,11 +93,84 @@ impl FlowPhase {
     pub const fn is_terminal(&self) -> bool {
         matches!(
             self,
-            Self::Completed | Self::Failed { .. } | Self::NoTodoFiles
+            Self::Completed | Self::Cancelled | Self::Failed { .. } | Self::NoTodoFiles
         )
     }
 }
 
+/// Signal sent to a flow paused in step mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StepSignal {
+    /// Run the next cycle.
+    Next,
+    /// End the flow instead of running another cycle.
+    Stop,
+}
+
+/// How many todos of the current cycle's batch have finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TodoProgress {
+    /// Todos finished so far (successfully or not).
+    pub completed: usize,
+    /// Todos in the batch.
+    pub total: usize,
+}
+
+impl TodoProgress {
+    /// Creates progress for `completed` of `total` todos.
+    #[must_use]
+    pub const fn new(completed: usize, total: usize) -> Self {
+        Self { completed, total }
+    }
+
+    /// Returns the finished fraction in `0.0..=1.0` (0 for an empty batch).
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)] // Precision loss acceptable for progress ratio
+    pub fn ratio(self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            (self.completed.min(self.total) as f64) / self.total as f64
+        }
+    }
+
+    /// Returns the finished percentage, rounded down.
+    #[must_use]
+    pub fn percent(self) -> usize {
+        (self.completed.min(self.total) * 100)
+            .checked_div(self.total)
+            .unwrap_or(0)
+    }
+}
+
+/// Todos completed this session and still pending, for the status line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TodoCounts {
+    /// Todos completed (and archived) since the app started.
+    pub done: usize,
+    /// Todos left in the todo directory.
+    pub pending: usize,
+}
+
+impl TodoCounts {
+    /// Refreshes the pending count from a fresh scan of the todo directory.
+    pub const fn set_pending(&mut self, pending: usize) {
+        self.pending = pending;
+    }
+
+    /// Records a completed todo, which is no longer pending.
+    pub const fn complete(&mut self) {
+        self.done += 1;
+        self.pending = self.pending.saturating_sub(1);
+    }
+
+    /// Returns the status line text, e.g. `"3 done / 2 pending"`.
+    #[must_use]
+    pub fn label(self) -> String {
+        format!("{} done / {} pending", self.done, self.pending)
+    }
+}
+
 /// State of the orchestration flow.
 #[derive(Debug, Clone)]
 pub struct FlowState {
@@ -89,24 +178,27 @@ pub struct FlowState {
     pub phase: FlowPhase,
     /// Content of the input (from file or direct text entry).
     pub input_text: String,
-    /// Path to the input file (None if text was entered directly).
-    pub input_path: Option<PathBuf>,
+    /// Paths to the input files (empty if text was entered directly).
+    pub input_paths: Vec<PathBuf>,
     /// List of todo files to process.
     pub todo_files: Vec<PathBuf>,
     /// Current cycle count (how many times we've run the planning phase).
     pub cycle_count: u32,
+    /// Files mentioned with `@` whose contents are attached to planning prompts.
+    pub attached_files: Vec<PathBuf>,
 }
 
 impl FlowState {
-    /// Creates a new flow state with the given input path.
+    /// Creates a new flow state with the given input paths.
     #[must_use]
-    pub fn new(input_path: PathBuf) -> Self {
+    pub fn new(input_paths: Vec<PathBuf>) -> Self {
         Self {
             phase: FlowPhase::Idle,
             input_text: String::new(),
-            input_path: Some(input_path),
+            input_paths,
             todo_files: Vec::new(),
             cycle_count: 0,
+            attached_files: Vec::new(),
         }
     }
 
@@ -116,9 +208,10 @@ impl FlowState {
         Self {
             phase: FlowPhase::Idle,
             input_text: String::new(),
-            input_path: None,
+            input_paths: Vec::new(),
             todo_files: Vec::new(),
             cycle_count: 0,
+            attached_files: Vec::new(),
         }
     }
 
@@ -132,6 +225,31 @@ impl FlowState {
 mod tests {
     use super::*;
 
+    // =========================================================================
+    // TodoProgress Tests
+    // =========================================================================
+
+    mod todo_progress {
+        use super::*;
+
+        #[test]
+        fn percent_and_ratio_follow_completed_todos() {
+            let progress = TodoProgress::new(1, 4);
+            assert_eq!(progress.percent(), 25);
+            assert!((progress.ratio() - 0.25).abs() < f64::EPSILON);
+
+            assert_eq!(TodoProgress::new(3, 3).percent(), 100);
+            assert_eq!(TodoProgress::new(2, 3).percent(), 66);
+        }
+
+        #[test]
+        fn empty_batch_is_zero() {
+            let progress = TodoProgress::new(0, 0);
+            assert_eq!(progress.percent(), 0);
+            assert!(progress.ratio().abs() < f64::EPSILON);
+        }
+    }
+
     // =========================================================================
     // FlowPhase Tests
     // =========================================================================
@@ -150,6 +268,7 @@ mod tests {
                 FlowPhase::NoTodoFiles,
                 FlowPhase::MovingCompletedFiles,
                 FlowPhase::Completed,
+                FlowPhase::Cancelled,
             ];
 
             for phase in static_phases {
@@ -192,6 +311,9 @@ mod tests {
             let cycle = FlowPhase::CycleComplete { iteration: 7 };
             assert_eq!(cycle.description().as_ref(), "Cycle 7 complete");
 
+            let paused = FlowPhase::Paused { iteration: 2 };
+            assert_eq!(paused.description().as_ref(), "Paused after cycle 2");
+
             let failed = FlowPhase::Failed {
                 reason: "timeout".to_string(),
             };
@@ -213,6 +335,12 @@ mod tests {
             assert!(failed.is_terminal());
         }
 
+        /// Tests terminal state detection for a cancelled flow.
+        #[test]
+        fn is_terminal_cancelled_is_true() {
+            assert!(FlowPhase::Cancelled.is_terminal());
+        }
+
         /// Tests terminal state detection when no todo files exist.
         #[test]
         fn is_terminal_no_todo_files_is_true() {
@@ -242,6 +370,7 @@ mod tests {
                     total: 5,
                 },
                 FlowPhase::CycleComplete { iteration: 1 },
+                FlowPhase::Paused { iteration: 1 },
             ];
 
             for phase in non_terminal {
@@ -305,11 +434,11 @@ mod tests {
         #[test]
         fn new_with_path_sets_correct_defaults() {
             let path = PathBuf::from("/test/input.txt");
-            let state = FlowState::new(path.clone());
+            let state = FlowState::new(vec![path.clone()]);
 
             assert_eq!(state.phase, FlowPhase::Idle);
             assert!(state.input_text.is_empty());
-            assert_eq!(state.input_path, Some(path));
+            assert_eq!(state.input_paths, [path]);
             assert!(state.todo_files.is_empty());
             assert_eq!(state.cycle_count, 0);
         }
@@ -321,7 +450,7 @@ mod tests {
 
             assert_eq!(state.phase, FlowPhase::Idle);
             assert!(state.input_text.is_empty());
-            assert!(state.input_path.is_none());
+            assert!(state.input_paths.is_empty());
             assert!(state.todo_files.is_empty());
             assert_eq!(state.cycle_count, 0);
         }
@@ -351,7 +480,7 @@ mod tests {
         /// Tests that `FlowState` can be cloned.
         #[test]
         fn clone_creates_independent_copy() {
-            let mut original = FlowState::new(PathBuf::from("/test.txt"));
+            let mut original = FlowState::new(vec![PathBuf::from("/test.txt")]);
             original.set_input_text("Task".to_string());
             original.cycle_count = 5;
 
@@ -0,0 +1,311 @@
+//! Git integration for reviewing changes made by a flow.
+//!
+//! Git is invoked through the [`GitRunner`] trait so the command assembly
+//! and output handling can be tested without a real repository.
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+use crate::tui::widgets::OutputLine;
+
+/// Runs git commands in a working directory.
+pub trait GitRunner {
+    /// Runs `git <args>` in `dir` and returns its stdout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if git cannot be spawned or exits with a failure status.
+    fn run(&self, dir: &Path, args: &[String]) -> io::Result<String>;
+}
+
+/// [`GitRunner`] that invokes the system `git` binary.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemGit;
+
+impl GitRunner for SystemGit {
+    fn run(&self, dir: &Path, args: &[String]) -> io::Result<String> {
+        let output = Command::new("git").args(args).current_dir(dir).output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!(
+                "git {} failed: {}",
+                args.first().map_or("", String::as_str),
+                stderr.trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+}
+
+/// Returns true if `dir` or one of its ancestors contains a `.git` entry.
+///
+/// A `.git` file (as used by worktrees and submodules) counts as well.
+#[must_use]
+pub fn is_git_repo(dir: &Path) -> bool {
+    dir.ancestors()
+        .any(|ancestor| ancestor.join(".git").exists())
+}
+
+/// Returns the commit `HEAD` points to, or `None` outside a repository or
+/// before the first commit.
+#[must_use]
+pub fn current_head(runner: &dyn GitRunner, dir: &Path) -> Option<String> {
+    let output = runner
+        .run(dir, &["rev-parse".to_string(), "HEAD".to_string()])
+        .ok()?;
+    let head = output.trim();
+    (!head.is_empty()).then(|| head.to_string())
+}
+
+/// Builds the git commands run by `/diff`: `git status --porcelain` followed
+/// by a colored `git diff`, against `base` when the flow's starting commit is
+/// known.
+#[must_use]
+pub fn diff_commands(base: Option<&str>) -> Vec<Vec<String>> {
+    let status = vec!["status".to_string(), "--porcelain".to_string()];
+    let mut diff = vec!["diff".to_string(), "--color=always".to_string()];
+    if let Some(base) = base {
+        diff.push(base.to_string());
+    }
+    vec![status, diff]
+}
+
+/// Maximum bytes of git context added to the planning prompt.
+pub const MAX_GIT_CONTEXT_BYTES: usize = 4000;
+
+/// Number of recent commits listed in the planning git context.
+const GIT_CONTEXT_COMMITS: &str = "10";
+
+/// Gathers recent commit subjects and a `git diff --stat HEAD` of the
+/// uncommitted changes in `dir`, for the planning prompt.
+///
+/// The result is cut to `max_bytes` (on a character boundary) with a
+/// truncation note. A failing command is skipped, and `None` is returned
+/// when nothing was gathered, e.g. outside a git repository.
+#[must_use]
+pub fn collect_git_context(runner: &dyn GitRunner, dir: &Path, max_bytes: usize) -> Option<String> {
+    let run = |args: &[&str]| {
+        let args: Vec<String> = args.iter().map(|&arg| arg.to_string()).collect();
+        runner
+            .run(dir, &args)
+            .ok()
+            .filter(|output| !output.trim().is_empty())
+    };
+    let mut sections = Vec::new();
+    if let Some(log) = run(&["log", "--oneline", "-n", GIT_CONTEXT_COMMITS]) {
+        sections.push(format!("Recent commits:\n{}", log.trim_end()));
+    }
+    if let Some(stat) = run(&["diff", "--stat", "HEAD"]) {
+        sections.push(format!("Uncommitted changes:\n{}", stat.trim_end()));
+    }
+    if sections.is_empty() {
+        return None;
+    }
+    let mut context = sections.join("\n\n");
+    if context.len() > max_bytes {
+        let mut end = max_bytes;
+        while !context.is_char_boundary(end) {
+            end -= 1;
+        }
+        context.truncate(end);
+        context.push_str("\n... (truncated)");
+    }
+    Some(context)
+}
+
+/// Runs the `/diff` commands and returns the output lines to display.
+///
+/// Diff lines keep git's ANSI colors. A failing command is reported as an
+/// error line rather than aborting, so partial results are still shown.
+#[must_use]
+pub fn collect_diff(runner: &dyn GitRunner, dir: &Path, base: Option<&str>) -> Vec<OutputLine> {
+    let mut lines = vec![OutputLine::info(match base {
+        Some(base) => format!(
+            "Changes since flow start ({}):",
+            base.get(..12).unwrap_or(base)
+        ),
+        None => "Uncommitted changes:".to_string(),
+    })];
+    let mut any_changes = false;
+    for args in diff_commands(base) {
+        match runner.run(dir, &args) {
+            Ok(output) => {
+                any_changes |= !output.trim().is_empty();
+                lines.extend(
+                    output
+                        .lines()
+                        .map(|line| OutputLine::stdout(line).with_ansi_styles()),
+                );
+            }
+            Err(e) => lines.push(OutputLine::error(e.to_string())),
+        }
+    }
+    if !any_changes {
+        lines.push(OutputLine::info("No changes"));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use tempfile::TempDir;
+
+    /// Records invoked commands and replies with canned output.
+    #[derive(Default)]
+    struct MockGit {
+        calls: RefCell<Vec<Vec<String>>>,
+        status: String,
+        diff: String,
+        log: String,
+    }
+
+    impl GitRunner for MockGit {
+        fn run(&self, _dir: &Path, args: &[String]) -> io::Result<String> {
+            self.calls.borrow_mut().push(args.to_vec());
+            match args.first().map(String::as_str) {
+                Some("status") => Ok(self.status.clone()),
+                Some("diff") => Ok(self.diff.clone()),
+                Some("log") => Ok(self.log.clone()),
+                Some("rev-parse") => Ok("abc123def4567890\n".to_string()),
+                _ => Err(io::Error::other("unexpected command")),
+            }
+        }
+    }
+
+    #[test]
+    fn detects_git_repo_in_dir_and_ancestors() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        assert!(!is_git_repo(dir.path()));
+
+        std::fs::create_dir(dir.path().join(".git"))?;
+        let nested = dir.path().join("src/nested");
+        std::fs::create_dir_all(&nested)?;
+        assert!(is_git_repo(dir.path()));
+        assert!(is_git_repo(&nested));
+        Ok(())
+    }
+
+    #[test]
+    fn detects_git_file_for_worktrees() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        std::fs::write(dir.path().join(".git"), "gitdir: ../main/.git/worktrees/x")?;
+        assert!(is_git_repo(dir.path()));
+        Ok(())
+    }
+
+    #[test]
+    fn diff_commands_without_base() {
+        assert_eq!(
+            diff_commands(None),
+            vec![
+                vec!["status".to_string(), "--porcelain".to_string()],
+                vec!["diff".to_string(), "--color=always".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_commands_against_flow_start() {
+        let commands = diff_commands(Some("abc123"));
+        assert_eq!(
+            commands[1],
+            vec![
+                "diff".to_string(),
+                "--color=always".to_string(),
+                "abc123".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn git_context_lists_commits_and_diff_stat() {
+        let git = MockGit {
+            log: "abc1234 Add retry\ndef5678 Initial commit\n".to_string(),
+            diff: " src/lib.rs | 3 ++-\n 1 file changed\n".to_string(),
+            ..MockGit::default()
+        };
+
+        let context = collect_git_context(&git, Path::new("."), MAX_GIT_CONTEXT_BYTES);
+
+        assert_eq!(
+            context.as_deref(),
+            Some(
+                "Recent commits:\nabc1234 Add retry\ndef5678 Initial commit\n\n\
+                 Uncommitted changes:\n src/lib.rs | 3 ++-\n 1 file changed"
+            )
+        );
+        assert_eq!(git.calls.borrow()[1], ["diff", "--stat", "HEAD"]);
+    }
+
+    #[test]
+    fn git_context_is_truncated_on_a_char_boundary() {
+        let git = MockGit {
+            log: "abc1234 Fix café menu\n".repeat(10),
+            ..MockGit::default()
+        };
+
+        // Byte 31 falls inside the first "é"
+        let context = collect_git_context(&git, Path::new("."), 31).unwrap_or_default();
+
+        assert_eq!(context, "Recent commits:\nabc1234 Fix caf\n... (truncated)");
+    }
+
+    #[test]
+    fn git_context_is_skipped_when_git_fails_or_is_empty() {
+        struct NoRepo;
+        impl GitRunner for NoRepo {
+            fn run(&self, _dir: &Path, _args: &[String]) -> io::Result<String> {
+                Err(io::Error::other("not a git repository"))
+            }
+        }
+        assert!(collect_git_context(&NoRepo, Path::new("."), MAX_GIT_CONTEXT_BYTES).is_none());
+        let empty = MockGit::default();
+        assert!(collect_git_context(&empty, Path::new("."), MAX_GIT_CONTEXT_BYTES).is_none());
+    }
+
+    #[test]
+    fn collect_diff_runs_status_then_diff() {
+        let git = MockGit {
+            status: " M src/lib.rs\n".to_string(),
+            diff: "\x1b[32m+added\x1b[m\n-removed\n".to_string(),
+            ..MockGit::default()
+        };
+
+        let lines = collect_diff(&git, Path::new("."), Some("abc123def4567890"));
+
+        assert_eq!(git.calls.borrow().len(), 2);
+        assert_eq!(git.calls.borrow()[0][0], "status");
+        assert_eq!(git.calls.borrow()[1][0], "diff");
+        let texts: Vec<&str> = lines.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "  Changes since flow start (abc123def456):",
+                " M src/lib.rs",
+                "+added",
+                "-removed"
+            ]
+        );
+        assert!(!lines[2].styles.is_empty());
+    }
+
+    #[test]
+    fn collect_diff_reports_no_changes() {
+        let git = MockGit::default();
+        let lines = collect_diff(&git, Path::new("."), None);
+        assert_eq!(lines[0].text, "  Uncommitted changes:");
+        assert_eq!(lines.last().map(|l| l.text.as_str()), Some("  No changes"));
+    }
+
+    #[test]
+    fn current_head_trims_output() {
+        let git = MockGit::default();
+        assert_eq!(
+            current_head(&git, Path::new(".")),
+            Some("abc123def4567890".to_string())
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Assembly of streamed CLI output into display lines.
+//!
+//! CLI output arrives as raw chunks that do not necessarily end on a line
+//! boundary. Progress indicators commonly redraw the current line with a
+//! carriage return (`\r`) instead of printing a newline. [`LineBuffer`] turns
+//! such chunks into line updates for the output panel: an incomplete trailing
+//! line is shown immediately and then updated in place as more text arrives.
+
+/// A change to apply to the output panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineUpdate {
+    /// Append a new line.
+    Push(String),
+    /// Replace the most recently shown line (an in-place update).
+    ReplaceLast(String),
+}
+
+/// Accumulates raw output chunks for a single stream into display lines.
+///
+/// - `\n` completes the current line.
+/// - `\r` moves to the start of the current line; the next character
+///   overwrites it (a `\r\n` pair is treated as a plain newline).
+/// - Text without a trailing newline is emitted as a partial line and replaced
+///   in place by later chunks until the line is completed.
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    /// Text of the line currently being assembled.
+    current: String,
+    /// A carriage return was seen; the next character clears the line.
+    pending_cr: bool,
+    /// The emitted partial line is still the most recent panel line.
+    attached: bool,
+    /// The current line changed since it was last emitted.
+    dirty: bool,
+}
+
+impl LineBuffer {
+    /// Creates an empty line buffer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feeds a chunk of output and returns the resulting panel updates.
+    pub fn push(&mut self, chunk: &str) -> Vec<LineUpdate> {
+        let mut updates = Vec::new();
+        for ch in chunk.chars() {
+            match ch {
+                '\n' => {
+                    // An empty line has never been emitted; anything else was
+                    // already shown unless it changed since
+                    if self.dirty || self.current.is_empty() {
+                        updates.push(self.update());
+                    }
+                    self.current.clear();
+                    self.pending_cr = false;
+                    self.attached = false;
+                    self.dirty = false;
+                }
+                '\r' => self.pending_cr = true,
+                _ => {
+                    if self.pending_cr {
+                        self.current.clear();
+                        self.pending_cr = false;
+                    }
+                    self.current.push(ch);
+                    self.dirty = true;
+                }
+            }
+        }
+        if self.dirty {
+            updates.push(self.update());
+            self.attached = true;
+            self.dirty = false;
+        }
+        updates
+    }
+
+    /// Returns whether the last emitted update was a line still waiting for
+    /// its newline, which later chunks update in place.
+    #[must_use]
+    pub const fn is_partial(&self) -> bool {
+        self.attached
+    }
+
+    /// Forgets that the partial line is the most recent panel line.
+    ///
+    /// Call this when another line was pushed after the partial line, so the
+    /// next update appends a new line instead of replacing the wrong one.
+    pub fn detach(&mut self) {
+        self.attached = false;
+    }
+
+    /// Builds the update for the current line.
+    fn update(&self) -> LineUpdate {
+        if self.attached {
+            LineUpdate::ReplaceLast(self.current.clone())
+        } else {
+            LineUpdate::Push(self.current.clone())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(text: &str) -> LineUpdate {
+        LineUpdate::Push(text.to_string())
+    }
+
+    fn replace(text: &str) -> LineUpdate {
+        LineUpdate::ReplaceLast(text.to_string())
+    }
+
+    #[test]
+    fn complete_lines_are_pushed() {
+        let mut buffer = LineBuffer::new();
+        assert_eq!(buffer.push("one\ntwo\n"), vec![push("one"), push("two")]);
+    }
+
+    #[test]
+    fn empty_lines_are_preserved() {
+        let mut buffer = LineBuffer::new();
+        assert_eq!(
+            buffer.push("a\n\nb\n"),
+            vec![push("a"), push(""), push("b")]
+        );
+    }
+
+    #[test]
+    fn carriage_return_overwrites_within_chunk() {
+        let mut buffer = LineBuffer::new();
+        assert_eq!(
+            buffer.push("progress 10%\rprogress 20%\n"),
+            vec![push("progress 20%")]
+        );
+    }
+
+    #[test]
+    fn partial_line_is_shown_then_updated_in_place() {
+        let mut buffer = LineBuffer::new();
+        assert_eq!(buffer.push("Downloading"), vec![push("Downloading")]);
+        assert_eq!(buffer.push("..."), vec![replace("Downloading...")]);
+        assert_eq!(
+            buffer.push(" done\nnext"),
+            vec![replace("Downloading... done"), push("next")]
+        );
+    }
+
+    #[test]
+    fn carriage_return_across_chunks_updates_in_place() {
+        let mut buffer = LineBuffer::new();
+        assert_eq!(buffer.push("progress 10%"), vec![push("progress 10%")]);
+        assert_eq!(buffer.push("\rprogress 20%"), vec![replace("progress 20%")]);
+        // Completing an already-shown line needs no further update
+        assert_eq!(buffer.push("\n"), Vec::<LineUpdate>::new());
+    }
+
+    #[test]
+    fn crlf_is_a_plain_newline() {
+        let mut buffer = LineBuffer::new();
+        assert_eq!(
+            buffer.push("one\r\ntwo\r\n"),
+            vec![push("one"), push("two")]
+        );
+    }
+
+    #[test]
+    fn detach_pushes_partial_line_again() {
+        let mut buffer = LineBuffer::new();
+        assert_eq!(buffer.push("50%"), vec![push("50%")]);
+        buffer.detach();
+        assert_eq!(buffer.push("\r60%"), vec![push("60%")]);
+        buffer.detach();
+        // The line was already shown in full, so completing it adds nothing
+        assert_eq!(buffer.push("\n"), Vec::<LineUpdate>::new());
+    }
+}
@@ -0,0 +1,200 @@
+//! Following an external log file in the output panel (`/tail`).
+//!
+//! [`LogTail`] polls a file like `tail -f`: only lines appended after it
+//! starts are forwarded, each as a [`FlowEvent::Output`] stdout line. A file
+//! that shrinks is assumed to have been truncated and is followed from its
+//! start again.
+
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::{mpsc, watch};
+
+use crate::app::FlowEvent;
+use crate::core::executor::wait_for_shutdown;
+use crate::tui::widgets::OutputLine;
+
+/// How often a followed file is checked for new lines.
+pub const TAIL_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A background task following a log file.
+///
+/// The task stops on [`LogTail::stop`] or when the handle is dropped, which
+/// closes its shutdown channel.
+#[derive(Debug)]
+pub struct LogTail {
+    /// The followed file.
+    path: PathBuf,
+    /// Signals the task to stop, like the flow's shutdown channel.
+    shutdown_tx: watch::Sender<bool>,
+}
+
+impl LogTail {
+    /// Starts following `path`, checking for new lines every `interval`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened, so a mistyped path is
+    /// reported right away instead of being followed silently.
+    pub fn spawn(
+        path: PathBuf,
+        interval: Duration,
+        tx: mpsc::Sender<FlowEvent>,
+    ) -> std::io::Result<Self> {
+        let mut file = std::fs::File::open(&path)?;
+        let offset = std::io::Seek::seek(&mut file, SeekFrom::End(0))?;
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        tokio::spawn(follow(
+            File::from_std(file),
+            offset,
+            interval,
+            tx,
+            shutdown_rx,
+        ));
+        Ok(Self { path, shutdown_tx })
+    }
+
+    /// Returns the followed file.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Stops following the file.
+    pub fn stop(self) {
+        self.shutdown_tx.send_modify(|v| *v = true);
+    }
+}
+
+/// Forwards complete lines appended to `file` after `offset` until shutdown,
+/// a read error, or the receiver going away.
+async fn follow(
+    mut file: File,
+    mut offset: u64,
+    interval: Duration,
+    tx: mpsc::Sender<FlowEvent>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    // Bytes of a line whose newline has not been written yet
+    let mut partial: Vec<u8> = Vec::new();
+    loop {
+        tokio::select! {
+            () = wait_for_shutdown(&mut shutdown_rx) => break,
+            () = tokio::time::sleep(interval) => {}
+        }
+        let appended = match read_appended(&mut file, &mut offset, &mut partial).await {
+            Ok(appended) => appended,
+            Err(e) => {
+                tx.send(FlowEvent::Output(OutputLine::warning(format!(
+                    "Stopped tailing: {e}"
+                ))))
+                .await
+                .ok();
+                break;
+            }
+        };
+        partial.extend_from_slice(&appended);
+        while let Some(end) = partial.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = partial.drain(..=end).collect();
+            let text = String::from_utf8_lossy(&line);
+            let text = text.trim_end_matches(['\n', '\r']);
+            if tx
+                .send(FlowEvent::Output(OutputLine::stdout(text)))
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+    }
+}
+
+/// Reads the bytes written to `file` since `offset`, restarting from the
+/// beginning (and dropping `partial`) when the file was truncated.
+async fn read_appended(
+    file: &mut File,
+    offset: &mut u64,
+    partial: &mut Vec<u8>,
+) -> std::io::Result<Vec<u8>> {
+    let len = file.metadata().await?.len();
+    if len < *offset {
+        *offset = 0;
+        partial.clear();
+    }
+    file.seek(SeekFrom::Start(*offset)).await?;
+    let mut appended = Vec::new();
+    file.read_to_end(&mut appended).await?;
+    *offset += appended.len() as u64;
+    Ok(appended)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::widgets::OutputLineType;
+    use anyhow::Result;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    const INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Receives the next forwarded line, failing after a second.
+    async fn next_line(rx: &mut mpsc::Receiver<FlowEvent>) -> Result<OutputLine> {
+        match tokio::time::timeout(Duration::from_secs(1), rx.recv()).await? {
+            Some(FlowEvent::Output(line)) => Ok(line),
+            other => anyhow::bail!("expected an output line, got {other:?}"),
+        }
+    }
+
+    fn append(path: &Path, text: &str) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new().append(true).open(path)?;
+        file.write_all(text.as_bytes())?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn forwards_lines_appended_after_start() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("build.log");
+        std::fs::write(&path, "old line\n")?;
+        let (tx, mut rx) = mpsc::channel(16);
+        let tail = LogTail::spawn(path.clone(), INTERVAL, tx)?;
+
+        append(&path, "Compiling core\nCompil")?;
+        let line = next_line(&mut rx).await?;
+        assert_eq!(line.text, "Compiling core");
+        assert_eq!(line.line_type, OutputLineType::Stdout);
+
+        // A line is forwarded once its newline is written
+        append(&path, "ing app\r\n")?;
+        assert_eq!(next_line(&mut rx).await?.text, "Compiling app");
+
+        tail.stop();
+        let closed = tokio::time::timeout(Duration::from_secs(1), rx.recv()).await?;
+        assert!(closed.is_none(), "tail should stop: {closed:?}");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn truncated_file_is_followed_from_the_start() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("build.log");
+        std::fs::write(&path, "a long line from the previous build\n")?;
+        let (tx, mut rx) = mpsc::channel(16);
+        let _tail = LogTail::spawn(path.clone(), INTERVAL, tx)?;
+
+        std::fs::write(&path, "rebuilt\n")?;
+        assert_eq!(next_line(&mut rx).await?.text, "rebuilt");
+        Ok(())
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        let (tx, _rx) = mpsc::channel(1);
+        let result = LogTail::spawn(PathBuf::from("/nonexistent/build.log"), INTERVAL, tx);
+        assert!(result.is_err());
+    }
+}
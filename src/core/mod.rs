@@ -1,27 +1,56 @@
 //! Core business logic for orchestration.
 
+pub mod attachments;
+pub mod channels;
 pub mod cli_check;
 pub mod commands;
+pub mod cycle_log;
+pub mod doctor;
 pub mod executor;
+pub mod failure;
+pub mod file_ref;
 pub mod flow;
+pub mod git;
+pub mod line_buffer;
+pub mod log_tail;
+pub mod prompt_stats;
 pub mod prompts;
 pub mod retry;
 pub mod runner;
+pub mod stderr_filter;
 pub mod task_utils;
+pub mod todo_watcher;
 
 pub use cli_check::{
-    CommandResolution, ModelAvailability, check_cli_in_path, is_safe_command_name,
-    resolve_cli_command,
+    CommandResolution, ModelAvailability, check_cli_in_path, check_cli_path_override,
+    describe_resolution, is_safe_command_name, resolve_cli_command,
 };
 pub use commands::{
-    ClearCommand, CommandContext, CommandRegistry, CommandResult, ExitCommand, SettingsCommand,
-    SlashCommand,
+    CancelCommand, ClearCommand, ClsCommand, CommandContext, CommandRegistry, CommandResult,
+    ConfigCommand, CopyErrorCommand, CyclesCommand, DiffCommand, DoctorCommand, EditCommand,
+    ExitCommand, GotoCommand, LoadCommand, ModelsCommand, NextCommand, OpenCommand, PreviewCommand,
+    ReapCommand, ResetSettingsCommand, RestoreCommand, ResummarizeCommand, RmTodoCommand,
+    SaveCommand, SettingsCommand, SlashCommand, SnapshotCommand, StatsCommand, StopCommand,
+    TailCommand, TaskCommand, TodosCommand, UntailCommand, VersionCommand, WhichCommand,
+    WorkingDirCommand,
 };
-pub use executor::{AiCliExecutor, ClaudeExecutor, CliOutput, CodexExecutor, GeminiExecutor};
-pub use flow::{FlowPhase, FlowState};
-pub use prompts::{wrap_for_execution, wrap_for_planning, wrap_for_task_summary};
+pub use executor::{
+    AiCliExecutor, ClaudeExecutor, CliOutput, CodexExecutor, GeminiExecutor, run_shell_command,
+    track_child_pids,
+};
+pub use failure::{ExecutorFailure, ExecutorFailureKind};
+pub use file_ref::{FileRef, extract_file_refs, parse_file_ref};
+pub use flow::{FlowPhase, FlowState, StepSignal, TodoCounts, TodoProgress};
+pub use log_tail::LogTail;
+pub use prompt_stats::{PromptKind, PromptSize, PromptStats, PromptTotals};
+pub use prompts::{preview_prompt, wrap_for_execution, wrap_for_planning, wrap_for_task_summary};
 pub use retry::RetryConfig;
-pub use runner::run_flow;
+pub use runner::{FlowOptions, plan_todos, resummarize_last_task, run_flow};
+pub use stderr_filter::StderrFilter;
+pub use task_utils::TodoSpec;
+pub use todo_watcher::TodoWatcher;
+
+use std::path::PathBuf;
 
 /// Available AI CLI models for orchestration.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -68,16 +97,31 @@ impl Model {
         }
     }
 
+    /// Parses a model from its display name or CLI command name.
+    ///
+    /// Matching ignores ASCII case and surrounding whitespace, so `"Claude Code"`,
+    /// `"claude"` and `" CODEX "` are all accepted. Returns `None` for
+    /// unrecognized input.
+    #[must_use]
+    pub fn from_name(s: &str) -> Option<Self> {
+        let s = s.trim();
+        Self::all().iter().copied().find(|model| {
+            s.eq_ignore_ascii_case(model.name()) || s.eq_ignore_ascii_case(model.command())
+        })
+    }
+
     /// Creates an executor instance for this model.
     ///
     /// This is a factory method that returns a boxed trait object,
-    /// allowing the flow runner to work with any model uniformly.
+    /// allowing the flow runner to work with any model uniformly. The
+    /// executor runs `path_override`, when it is an executable file, instead
+    /// of looking the model's command up.
     #[must_use]
-    pub fn executor(&self) -> Box<dyn AiCliExecutor> {
+    pub fn executor(&self, path_override: Option<PathBuf>) -> Box<dyn AiCliExecutor> {
         match self {
-            Self::Codex => Box::new(CodexExecutor),
-            Self::Claude => Box::new(ClaudeExecutor),
-            Self::Gemini => Box::new(GeminiExecutor),
+            Self::Codex => Box::new(CodexExecutor::new(path_override)),
+            Self::Claude => Box::new(ClaudeExecutor::new(path_override)),
+            Self::Gemini => Box::new(GeminiExecutor::new(path_override)),
         }
     }
 
@@ -130,6 +174,43 @@ mod model_tests {
         assert_eq!(Model::Gemini.prev(), Model::Claude);
     }
 
+    #[test]
+    fn model_from_name_accepts_display_names() {
+        assert_eq!(Model::from_name("Codex"), Some(Model::Codex));
+        assert_eq!(Model::from_name("Claude Code"), Some(Model::Claude));
+        assert_eq!(Model::from_name("Gemini"), Some(Model::Gemini));
+        assert_eq!(Model::from_name("claude code"), Some(Model::Claude));
+    }
+
+    #[test]
+    fn model_from_name_accepts_command_names() {
+        assert_eq!(Model::from_name("codex"), Some(Model::Codex));
+        assert_eq!(Model::from_name("claude"), Some(Model::Claude));
+        assert_eq!(Model::from_name("GEMINI"), Some(Model::Gemini));
+    }
+
+    #[test]
+    fn model_from_name_trims_whitespace() {
+        assert_eq!(Model::from_name("  claude \n"), Some(Model::Claude));
+        assert_eq!(Model::from_name("\tClaude Code "), Some(Model::Claude));
+    }
+
+    #[test]
+    fn model_from_name_rejects_unknown_input() {
+        assert_eq!(Model::from_name(""), None);
+        assert_eq!(Model::from_name("   "), None);
+        assert_eq!(Model::from_name("gpt"), None);
+        assert_eq!(Model::from_name("Claude-Code"), None);
+    }
+
+    #[test]
+    fn model_from_name_round_trips_all_models() {
+        for model in Model::all() {
+            assert_eq!(Model::from_name(model.name()), Some(*model));
+            assert_eq!(Model::from_name(model.command()), Some(*model));
+        }
+    }
+
     #[test]
     fn model_next_and_prev_are_inverse() {
         for model in Model::all() {
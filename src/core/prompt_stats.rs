@@ -0,0 +1,189 @@
+//! Prompt size accounting.
+//!
+//! Tracks how large the wrapped prompts sent to each model are, to give a
+//! rough picture of what drives API cost. Token counts are approximated as
+//! one token per four bytes.
+
+use std::fmt;
+
+/// Approximate number of bytes per token used for estimates.
+const BYTES_PER_TOKEN: usize = 4;
+
+/// Which phase a prompt was built for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptKind {
+    /// Planning prompt (`wrap_for_planning`).
+    Planning,
+    /// Execution prompt for a todo file (`wrap_for_execution`).
+    Execution,
+    /// Task summary prompt (`wrap_for_task_summary`).
+    Summary,
+}
+
+impl PromptKind {
+    /// Returns the display name of this phase.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Planning => "Planning",
+            Self::Execution => "Execution",
+            Self::Summary => "Summary",
+        }
+    }
+}
+
+/// Size of one or more prompts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PromptSize {
+    /// Total size in bytes.
+    pub bytes: usize,
+    /// Approximate token count (`bytes / 4`, rounded up).
+    pub approx_tokens: usize,
+}
+
+impl PromptSize {
+    /// Measures a prompt.
+    #[must_use]
+    pub const fn of(prompt: &str) -> Self {
+        let bytes = prompt.len();
+        Self {
+            bytes,
+            approx_tokens: bytes.div_ceil(BYTES_PER_TOKEN),
+        }
+    }
+}
+
+impl fmt::Display for PromptSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bytes (~{} tokens)", self.bytes, self.approx_tokens)
+    }
+}
+
+/// Totals for prompts of one kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PromptTotals {
+    /// Number of prompts recorded.
+    pub count: usize,
+    /// Combined size of the recorded prompts.
+    pub size: PromptSize,
+}
+
+impl PromptTotals {
+    fn add(&mut self, size: PromptSize) {
+        self.count += 1;
+        self.size.bytes += size.bytes;
+        self.size.approx_tokens += size.approx_tokens;
+    }
+}
+
+/// Per-phase and cumulative prompt size totals for a flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PromptStats {
+    /// Planning prompt totals.
+    pub planning: PromptTotals,
+    /// Execution prompt totals.
+    pub execution: PromptTotals,
+    /// Summary prompt totals.
+    pub summary: PromptTotals,
+}
+
+impl PromptStats {
+    /// Records a prompt of the given kind.
+    pub fn record(&mut self, kind: PromptKind, size: PromptSize) {
+        self.totals_mut(kind).add(size);
+    }
+
+    /// Returns the totals for one kind of prompt.
+    #[must_use]
+    pub const fn totals(&self, kind: PromptKind) -> PromptTotals {
+        match kind {
+            PromptKind::Planning => self.planning,
+            PromptKind::Execution => self.execution,
+            PromptKind::Summary => self.summary,
+        }
+    }
+
+    /// Returns the totals across all kinds.
+    #[must_use]
+    pub fn cumulative(&self) -> PromptTotals {
+        let mut total = PromptTotals::default();
+        for totals in [self.planning, self.execution, self.summary] {
+            total.count += totals.count;
+            total.size.bytes += totals.size.bytes;
+            total.size.approx_tokens += totals.size.approx_tokens;
+        }
+        total
+    }
+
+    const fn totals_mut(&mut self, kind: PromptKind) -> &mut PromptTotals {
+        match kind {
+            PromptKind::Planning => &mut self.planning,
+            PromptKind::Execution => &mut self.execution,
+            PromptKind::Summary => &mut self.summary,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::wrap_for_execution;
+
+    #[test]
+    fn size_of_known_input() {
+        assert_eq!(
+            PromptSize::of("12345678"),
+            PromptSize {
+                bytes: 8,
+                approx_tokens: 2
+            }
+        );
+        // Partial tokens round up; multi-byte characters count as bytes
+        assert_eq!(
+            PromptSize::of("héllo"),
+            PromptSize {
+                bytes: 6,
+                approx_tokens: 2
+            }
+        );
+        assert_eq!(PromptSize::of(""), PromptSize::default());
+    }
+
+    #[test]
+    fn size_of_wrapped_prompt_matches_its_length() {
+        let wrapped = wrap_for_execution("# Task 001\nDo it.", "");
+        let size = PromptSize::of(&wrapped);
+        assert_eq!(size.bytes, wrapped.len());
+        assert_eq!(size.approx_tokens, wrapped.len().div_ceil(4));
+    }
+
+    #[test]
+    fn totals_accumulate_across_phases() {
+        let mut stats = PromptStats::default();
+        stats.record(PromptKind::Planning, PromptSize::of("aaaa"));
+        stats.record(PromptKind::Execution, PromptSize::of("bbbbbbbb"));
+        stats.record(PromptKind::Execution, PromptSize::of("cc"));
+
+        assert_eq!(stats.totals(PromptKind::Planning).count, 1);
+        assert_eq!(
+            stats.totals(PromptKind::Execution),
+            PromptTotals {
+                count: 2,
+                size: PromptSize {
+                    bytes: 10,
+                    approx_tokens: 3
+                }
+            }
+        );
+        assert_eq!(stats.totals(PromptKind::Summary), PromptTotals::default());
+        let cumulative = stats.cumulative();
+        assert_eq!(cumulative.count, 3);
+        assert_eq!(cumulative.size.bytes, 14);
+        assert_eq!(cumulative.size.approx_tokens, 4);
+    }
+
+    #[test]
+    fn display_shows_bytes_and_tokens() {
+        assert_eq!(PromptSize::of("12345").to_string(), "5 bytes (~2 tokens)");
+    }
+}
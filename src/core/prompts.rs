@@ -5,8 +5,17 @@ use std::fmt::Write;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-/// Discovers guideline files in the project.
-fn discover_guideline_files(base_dir: &Path) -> Vec<String> {
+use crate::app::state::TodoNaming;
+
+use super::attachments::attach_files;
+use super::git::{GitRunner, MAX_GIT_CONTEXT_BYTES, SystemGit, collect_git_context};
+use super::prompt_stats::PromptKind;
+use super::runner::FlowOptions;
+use super::task_utils::extract_completed_tasks_summary;
+
+/// Discovers guideline files in the project rooted at `base_dir`.
+#[must_use]
+pub fn discover_guideline_files(base_dir: &Path) -> Vec<String> {
     let mut files = HashSet::new();
     let mut add_file = |path: PathBuf| {
         if path.exists()
@@ -74,6 +83,27 @@ fn discover_guideline_files(base_dir: &Path) -> Vec<String> {
     sorted_files.sort();
     sorted_files
 }
+
+/// Discovers guideline files in the project rooted at `base_dir` and in each
+/// of its `extra_roots`.
+///
+/// Files found in an extra root are prefixed with the root as given (e.g.
+/// `../api/AGENTS.md`); relative roots are resolved against `base_dir`.
+#[must_use]
+pub fn discover_guideline_files_with_roots(
+    base_dir: &Path,
+    extra_roots: &[PathBuf],
+) -> Vec<String> {
+    let mut files = discover_guideline_files(base_dir);
+    for root in extra_roots {
+        files.extend(
+            discover_guideline_files(&base_dir.join(root))
+                .into_iter()
+                .map(|file| root.join(file).to_string_lossy().into_owned()),
+        );
+    }
+    files
+}
 /// Renders the guideline files into a markdown block.
 fn render_guidelines_block(files: &[String]) -> String {
     if files.is_empty() {
@@ -116,6 +146,10 @@ You will receive three pieces of information:
 - **<PENDING_TASKS>**: Summaries of existing todo files awaiting implementation
 - **<COMPLETED_TASKS>**: Short inline summaries of previously completed tasks (for awareness only)
 
+When enabled, a **<GIT_CONTEXT>** section also lists recent commits and uncommitted changes, so you can see what has already changed in the repository.
+
+The plan may end with an **<ATTACHED_FILES>** section holding the contents of files the user referenced with `@`, possibly truncated. They were read when this prompt was built: use them to understand the request, and read the files themselves for their complete content.
+
 Your output will be task files written to the `.mcgravity/todo/` directory that will be executed by a separate AI model.
 
 # Analysis Process
@@ -155,7 +189,7 @@ Review the <PENDING_TASKS> and <COMPLETED_TASKS> sections:
 
 ## Step 4: Create Task Files
 
-Create task files in `.mcgravity/todo/` using the next available sequential numbers based on existing tasks (pending + done). Do NOT reuse or overwrite existing task numbers. Use zero-padded numbering: `.mcgravity/todo/task-001.md`, `.mcgravity/todo/task-002.md`, etc.
+Create task files in `.mcgravity/todo/`. {{TODO_NAMING}}
 
 # Quality Standards
 
@@ -252,8 +286,7 @@ pub const PLANNING_POSTFIX_TEMPLATE: &str = r"
 
 ## Expected Output
 
-Create task files in the `.mcgravity/todo/` directory with the naming convention: `.mcgravity/todo/task-NNN.md`
-where NNN is a zero-padded number (e.g., task-001.md, task-002.md) that continues from existing tasks without reuse.
+Create task files in the `.mcgravity/todo/` directory. {{TODO_NAMING}}
 If all <PLAN> requirements are already satisfied by <COMPLETED_TASKS>, create NO new task files and exit.
 
 Each task file must include:
@@ -463,6 +496,29 @@ After completing the task, summarize your work:
 If any quality checks could not be run, state which checks were skipped and why.
 ";
 
+/// Returns the file naming instructions substituted for `{{TODO_NAMING}}` in
+/// the planning templates.
+///
+/// Todos are executed in the order [`crate::fs::scan_todo_files`] sorts them,
+/// so each scheme tells the planner how to make names sort in execution order.
+#[must_use]
+pub const fn todo_naming_instructions(naming: TodoNaming) -> &'static str {
+    match naming {
+        TodoNaming::Sequential => {
+            "Name them `.mcgravity/todo/task-NNN.md`, where NNN is a zero-padded number (e.g., task-001.md, task-002.md) that continues from the next available number based on existing tasks (pending + done). Do NOT reuse or overwrite existing task numbers. Tasks run in numeric order."
+        }
+        TodoNaming::Timestamp => {
+            "Name them `.mcgravity/todo/YYYYMMDD-HHMMSS-topic.md`, where the timestamp is later than every existing task (pending + done) and increases in the order the tasks must run, and topic is a short kebab-case description (e.g., 20260117-143000-add-login.md, 20260117-143100-login-tests.md). Do NOT reuse or overwrite existing task names. Tasks run in timestamp order."
+        }
+    }
+}
+
+/// Returns the planning postfix with the naming instructions for `naming`.
+#[must_use]
+pub fn planning_postfix(naming: TodoNaming) -> String {
+    PLANNING_POSTFIX_TEMPLATE.replace("{{TODO_NAMING}}", todo_naming_instructions(naming))
+}
+
 /// Wraps input text with planning prefix, pending tasks context, and postfix.
 ///
 /// Used during the planning phase to instruct the AI model to analyze the input
@@ -485,21 +541,43 @@ pub fn wrap_for_planning(
         pending_tasks_summary,
         completed_tasks_summary,
         &guidelines,
+        TodoNaming::default(),
+        None,
     )
 }
 
 /// Wraps input text with planning prefix, injected guidelines, pending tasks context, and postfix.
+///
+/// `todo_naming` selects the file naming instructions given to the planner.
+/// A non-empty `git_context` (see [`collect_git_context`]) is added in a
+/// `<GIT_CONTEXT>` section before the pending tasks.
+///
+/// [`collect_git_context`]: crate::core::git::collect_git_context
 #[must_use]
 pub fn wrap_for_planning_with_guidelines(
     input: &str,
     pending_tasks_summary: &str,
     completed_tasks_summary: &str,
     guideline_files: &[String],
+    todo_naming: TodoNaming,
+    git_context: Option<&str>,
 ) -> String {
     let guidelines_block = render_guidelines_block(guideline_files);
-    let prefix = PLANNING_PREFIX_TEMPLATE.replace("{{GUIDELINES_LIST}}", &guidelines_block);
+    let prefix = PLANNING_PREFIX_TEMPLATE
+        .replace("{{GUIDELINES_LIST}}", &guidelines_block)
+        .replace("{{TODO_NAMING}}", todo_naming_instructions(todo_naming));
+    let postfix = planning_postfix(todo_naming);
+    let git_section = git_context
+        .filter(|context| !context.trim().is_empty())
+        .map(|context| {
+            format!(
+                "<GIT_CONTEXT>\nRecent commits and uncommitted changes in the repository, \
+                 for awareness only:\n\n{context}\n</GIT_CONTEXT>\n\n"
+            )
+        })
+        .unwrap_or_default();
     format!(
-        "{prefix}<PENDING_TASKS>\n{pending_tasks_summary}\n</PENDING_TASKS>\n\n<COMPLETED_TASKS>\n{completed_tasks_summary}\n</COMPLETED_TASKS>\n\n<PLAN>\n{input}{PLANNING_POSTFIX_TEMPLATE}"
+        "{prefix}{git_section}<PENDING_TASKS>\n{pending_tasks_summary}\n</PENDING_TASKS>\n\n<COMPLETED_TASKS>\n{completed_tasks_summary}\n</COMPLETED_TASKS>\n\n<PLAN>\n{input}{postfix}"
     )
 }
 
@@ -532,6 +610,69 @@ pub fn wrap_for_execution_with_guidelines(
     )
 }
 
+/// Builds the planning prompt for `task_text` from the settings that shape
+/// it. Flow cycles, `mcgravity plan`, `--print-prompt` and `/preview` all
+/// build the planning prompt here, so they send the same prompt.
+///
+/// Guidelines are discovered in `base_dir` and `options.extra_roots`, the
+/// todo naming rule follows `options.todo_naming`, recent history is
+/// gathered with `git` when `options.include_git_context` is on, and the
+/// current contents of `options.attached_files` follow the task text. This
+/// runs git and reads the file system.
+#[must_use]
+pub fn build_planning_prompt(
+    task_text: &str,
+    pending_tasks_summary: &str,
+    base_dir: &Path,
+    options: &FlowOptions,
+    git: &dyn GitRunner,
+) -> String {
+    // Outside a git repository this is None and the section is left out
+    let git_context = options
+        .include_git_context
+        .then(|| collect_git_context(git, base_dir, MAX_GIT_CONTEXT_BYTES))
+        .flatten();
+    let attachments = attach_files(base_dir, &options.extra_roots, &options.attached_files).block;
+    let input = if attachments.is_empty() {
+        task_text.to_string()
+    } else {
+        format!("{task_text}\n\n{attachments}")
+    };
+    wrap_for_planning_with_guidelines(
+        &input,
+        pending_tasks_summary,
+        &extract_completed_tasks_summary(task_text),
+        &discover_guideline_files_with_roots(base_dir, &options.extra_roots),
+        options.todo_naming,
+        git_context.as_deref(),
+    )
+}
+
+/// Builds the prompt a phase would send for the given task text, with the
+/// flow settings in `options` and the project at `base_dir`.
+///
+/// Used to preview prompts without running a flow. The completed-tasks
+/// summary is taken from the text's `<COMPLETED_TASKS>` block (empty if there
+/// is none), pending todos are left empty, and the summary prompt gets an
+/// empty execution output.
+#[must_use]
+pub fn preview_prompt(
+    kind: PromptKind,
+    task_text: &str,
+    base_dir: &Path,
+    options: &FlowOptions,
+) -> String {
+    match kind {
+        PromptKind::Planning => build_planning_prompt(task_text, "", base_dir, options, &SystemGit),
+        PromptKind::Execution => wrap_for_execution_with_guidelines(
+            task_text,
+            &extract_completed_tasks_summary(task_text),
+            &discover_guideline_files_with_roots(base_dir, &options.extra_roots),
+        ),
+        PromptKind::Summary => wrap_for_task_summary(task_text, ""),
+    }
+}
+
 /// Prompt template for generating a short post-execution summary of a completed task.
 ///
 /// This instructs the AI model to produce a concise summary of what was accomplished,
@@ -674,13 +815,15 @@ mod tests {
             pending_tasks,
             completed_tasks,
             &mock_guidelines(),
+            TodoNaming::Sequential,
+            None,
         );
 
         assert!(wrapped.contains("software architect"));
         assert!(wrapped.contains(input));
         assert!(wrapped.contains(pending_tasks));
         assert!(wrapped.contains("- `CLAUDE.md`"));
-        assert!(wrapped.ends_with(PLANNING_POSTFIX_TEMPLATE));
+        assert!(wrapped.ends_with(&planning_postfix(TodoNaming::Sequential)));
     }
 
     #[test]
@@ -693,13 +836,15 @@ mod tests {
             pending_tasks,
             completed_tasks,
             &mock_guidelines(),
+            TodoNaming::Sequential,
+            None,
         );
 
         assert!(wrapped.contains("software architect"));
         assert!(wrapped.contains(input));
         assert!(wrapped.contains("<PENDING_TASKS>\n\n</PENDING_TASKS>"));
         assert!(wrapped.contains("<COMPLETED_TASKS>\n\n</COMPLETED_TASKS>"));
-        assert!(wrapped.ends_with(PLANNING_POSTFIX_TEMPLATE));
+        assert!(wrapped.ends_with(&planning_postfix(TodoNaming::Sequential)));
     }
 
     #[test]
@@ -712,6 +857,8 @@ mod tests {
             pending_tasks,
             completed_tasks,
             &mock_guidelines(),
+            TodoNaming::Sequential,
+            None,
         );
 
         assert!(wrapped.contains("<PENDING_TASKS>"));
@@ -721,6 +868,53 @@ mod tests {
         assert!(wrapped.contains("<PLAN>"));
     }
 
+    #[test]
+    fn test_wrap_for_planning_includes_git_context_when_given() {
+        let wrap = |git_context| {
+            wrap_for_planning_with_guidelines(
+                "Build a REST API",
+                "",
+                "",
+                &mock_guidelines(),
+                TodoNaming::Sequential,
+                git_context,
+            )
+        };
+
+        let wrapped = wrap(Some("Recent commits:\nabc1234 Add login"));
+        assert!(wrapped.contains("abc1234 Add login"));
+        let git = wrapped.find("<GIT_CONTEXT>\n").unwrap_or(usize::MAX);
+        let pending = wrapped.find("<PENDING_TASKS>\n").unwrap_or(0);
+        assert!(git < pending, "git context should precede pending tasks");
+
+        assert!(!wrap(None).contains("<GIT_CONTEXT>\n"));
+        assert!(!wrap(Some("  ")).contains("<GIT_CONTEXT>\n"));
+    }
+
+    #[test]
+    fn test_wrap_for_planning_substitutes_todo_naming() {
+        for (naming, pattern, other) in [
+            (
+                TodoNaming::Sequential,
+                "`.mcgravity/todo/task-NNN.md`",
+                "YYYYMMDD-HHMMSS",
+            ),
+            (
+                TodoNaming::Timestamp,
+                "`.mcgravity/todo/YYYYMMDD-HHMMSS-topic.md`",
+                "task-NNN",
+            ),
+        ] {
+            let wrapped =
+                wrap_for_planning_with_guidelines("Build a REST API", "", "", &[], naming, None);
+
+            assert!(!wrapped.contains("{{TODO_NAMING}}"), "{naming:?}");
+            // Both Step 4 and Expected Output describe the scheme
+            assert_eq!(wrapped.matches(pattern).count(), 2, "{naming:?}");
+            assert!(!wrapped.contains(other), "{naming:?}");
+        }
+    }
+
     #[test]
     fn test_wrap_for_execution() {
         let task = "Create user authentication";
@@ -1311,6 +1505,79 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_discover_guideline_files_with_roots() -> anyhow::Result<()> {
+        use std::fs::File;
+        use tempfile::tempdir;
+
+        let dir = tempdir()?;
+        let app = dir.path().join("app");
+        let api = dir.path().join("api");
+        fs::create_dir_all(&app)?;
+        fs::create_dir_all(api.join(".github"))?;
+        File::create(app.join("AGENTS.md"))?;
+        File::create(api.join("AGENTS.md"))?;
+        File::create(api.join(".github/copilot-instructions.md"))?;
+
+        let discovered = discover_guideline_files_with_roots(&app, &[PathBuf::from("../api")]);
+        assert_eq!(
+            discovered,
+            vec![
+                "AGENTS.md",
+                "../api/.github/copilot-instructions.md",
+                "../api/AGENTS.md",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_planning_prompt_follows_flow_settings() -> anyhow::Result<()> {
+        use crate::core::git::GitRunner;
+        use std::fs::File;
+        use tempfile::tempdir;
+
+        struct FakeGit;
+        impl GitRunner for FakeGit {
+            fn run(&self, _dir: &Path, args: &[String]) -> std::io::Result<String> {
+                match args.first().map(String::as_str) {
+                    Some("log") => Ok("abc1234 Add login form\n".to_string()),
+                    _ => Ok(String::new()),
+                }
+            }
+        }
+
+        let dir = tempdir()?;
+        let app = dir.path().join("app");
+        let api = dir.path().join("api");
+        fs::create_dir_all(&app)?;
+        fs::create_dir_all(&api)?;
+        File::create(api.join("AGENTS.md"))?;
+        let options = FlowOptions {
+            extra_roots: vec![PathBuf::from("../api")],
+            todo_naming: TodoNaming::Timestamp,
+            include_git_context: true,
+            ..FlowOptions::default()
+        };
+
+        let prompt = build_planning_prompt("Add auth", "", &app, &options, &FakeGit);
+        assert!(prompt.contains("- `../api/AGENTS.md`"));
+        assert!(prompt.contains(todo_naming_instructions(TodoNaming::Timestamp)));
+        assert!(prompt.contains("abc1234 Add login form"));
+        assert!(prompt.ends_with(&planning_postfix(TodoNaming::Timestamp)));
+
+        // The preview builds the same prompt
+        let options = FlowOptions {
+            include_git_context: false,
+            ..options
+        };
+        assert_eq!(
+            preview_prompt(PromptKind::Planning, "Add auth", &app, &options),
+            build_planning_prompt("Add auth", "", &app, &options, &FakeGit)
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_execution_prefix_has_autonomy_note() {
         assert!(
@@ -2065,6 +2332,8 @@ mod tests {
             pending,
             completed_summaries,
             &mock_guidelines(),
+            TodoNaming::Sequential,
+            None,
         );
 
         // Verify inline summaries appear
@@ -2,8 +2,19 @@
 
 use std::time::Duration;
 
+use tokio::sync::watch;
+
+use crate::app::state::{DEFAULT_IDLE_NOTICE_SECS, Verbosity};
+use crate::core::channels::EXECUTOR_OUTPUT_CHANNEL_SIZE;
+
+/// Default time without executor output before a "still working" notice.
+const DEFAULT_IDLE_NOTICE: Duration = Duration::from_secs(DEFAULT_IDLE_NOTICE_SECS);
+
 /// Configuration for retry behavior.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Configurations are equal when their values match and they follow the same
+/// verbosity channel (or none).
+#[derive(Debug, Clone)]
 pub struct RetryConfig {
     /// Maximum number of retry attempts.
     pub max_attempts: u32,
@@ -11,14 +22,42 @@ pub struct RetryConfig {
     pub base_interval_secs: u64,
     /// Amount to increase interval after each failure.
     pub interval_increment_secs: u64,
+    /// How long an attempt may go without output before a "still working"
+    /// notice is shown, repeated at the same interval (`None` disables it).
+    pub idle_notice: Option<Duration>,
+    /// Capacity of the channel carrying each attempt's output (at least 1).
+    pub output_buffer: usize,
+    /// Live panel verbosity, read for every forwarded line so a toggle applies
+    /// mid-attempt (`None` means [`Verbosity::Normal`]).
+    pub verbosity: Option<watch::Receiver<Verbosity>>,
+}
+
+impl PartialEq for RetryConfig {
+    fn eq(&self, other: &Self) -> bool {
+        self.max_attempts == other.max_attempts
+            && self.base_interval_secs == other.base_interval_secs
+            && self.interval_increment_secs == other.interval_increment_secs
+            && self.idle_notice == other.idle_notice
+            && self.output_buffer == other.output_buffer
+            && match (&self.verbosity, &other.verbosity) {
+                (Some(a), Some(b)) => a.same_channel(b),
+                (None, None) => true,
+                _ => false,
+            }
+    }
 }
 
+impl Eq for RetryConfig {}
+
 impl Default for RetryConfig {
     fn default() -> Self {
         Self {
             max_attempts: 100,
             base_interval_secs: 10,
             interval_increment_secs: 10,
+            idle_notice: Some(DEFAULT_IDLE_NOTICE),
+            output_buffer: EXECUTOR_OUTPUT_CHANNEL_SIZE,
+            verbosity: None,
         }
     }
 }
@@ -26,11 +65,23 @@ impl Default for RetryConfig {
 impl RetryConfig {
     /// Creates a new retry configuration with custom values.
     ///
+    /// A `max_attempts` of 0 is raised to 1, since the first attempt always
+    /// runs; with 1 attempt a failure is never retried.
+    ///
     /// # Arguments
     ///
-    /// * `max_attempts` - Maximum number of retry attempts
-    /// * `base_interval_secs` - Initial wait time in seconds
-    /// * `interval_increment_secs` - Amount to add after each failure
+    /// * `max_attempts` - Maximum number of attempts, including the first
+    /// * `base_interval_secs` - Wait in seconds after the first failed attempt
+    /// * `interval_increment_secs` - Amount added to the wait after each
+    ///   further failure (see [`wait_duration`](Self::wait_duration))
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mcgravity::core::RetryConfig;
+    ///
+    /// assert_eq!(RetryConfig::new(0, 5, 2).max_attempts, 1);
+    /// ```
     #[must_use]
     pub const fn new(
         max_attempts: u32,
@@ -38,15 +89,44 @@ impl RetryConfig {
         interval_increment_secs: u64,
     ) -> Self {
         Self {
-            max_attempts,
+            max_attempts: if max_attempts == 0 { 1 } else { max_attempts },
             base_interval_secs,
             interval_increment_secs,
+            idle_notice: Some(DEFAULT_IDLE_NOTICE),
+            output_buffer: EXECUTOR_OUTPUT_CHANNEL_SIZE,
+            verbosity: None,
         }
     }
 
+    /// Sets the idle notice interval (`None` disables the notice).
+    #[must_use]
+    pub const fn with_idle_notice(mut self, idle_notice: Option<Duration>) -> Self {
+        self.idle_notice = idle_notice;
+        self
+    }
+
+    /// Sets the capacity of the attempt output channel, raised to 1 since a
+    /// channel cannot be empty.
+    #[must_use]
+    pub const fn with_output_buffer(mut self, output_buffer: usize) -> Self {
+        self.output_buffer = if output_buffer == 0 { 1 } else { output_buffer };
+        self
+    }
+
+    /// Follows the panel verbosity published on `verbosity`.
+    #[must_use]
+    pub fn with_verbosity(mut self, verbosity: watch::Receiver<Verbosity>) -> Self {
+        self.verbosity = Some(verbosity);
+        self
+    }
+
     /// Calculates the wait duration for a given attempt number.
     ///
-    /// Uses linear backoff: base + (attempt * increment)
+    /// Uses linear backoff: `base + attempt * increment` seconds, saturating
+    /// instead of overflowing. `attempt` is 0-based: the wait after the first
+    /// failure is `wait_duration(0)`. With `max_attempts` attempts the last
+    /// wait is `wait_duration(max_attempts - 2)`, since no wait follows the
+    /// final attempt.
     ///
     /// # Examples
     ///
@@ -61,8 +141,8 @@ impl RetryConfig {
     /// ```
     #[must_use]
     pub const fn wait_duration(&self, attempt: u32) -> Duration {
-        let secs = self.base_interval_secs + (attempt as u64 * self.interval_increment_secs);
-        Duration::from_secs(secs)
+        let backoff = (attempt as u64).saturating_mul(self.interval_increment_secs);
+        Duration::from_secs(self.base_interval_secs.saturating_add(backoff))
     }
 
     /// Returns true if the given attempt number is within the allowed limit.
@@ -88,6 +168,7 @@ mod tests {
         assert_eq!(config.max_attempts, 100);
         assert_eq!(config.base_interval_secs, 10);
         assert_eq!(config.interval_increment_secs, 10);
+        assert_eq!(config.idle_notice, Some(DEFAULT_IDLE_NOTICE));
     }
 
     /// Tests creating a custom configuration.
@@ -130,6 +211,50 @@ mod tests {
         assert_eq!(config.wait_duration(100), Duration::from_secs(5));
     }
 
+    /// Tests that zero attempts are raised to a single attempt.
+    #[test]
+    fn new_clamps_zero_attempts_to_one() {
+        let config = RetryConfig::new(0, 5, 2);
+
+        assert_eq!(config.max_attempts, 1);
+        assert!(config.has_attempts_remaining(0));
+        assert!(!config.has_attempts_remaining(1));
+    }
+
+    /// Tests that valid attempt counts are kept as given.
+    #[test]
+    fn new_keeps_valid_attempts() {
+        assert_eq!(RetryConfig::new(1, 0, 0).max_attempts, 1);
+        assert_eq!(RetryConfig::new(u32::MAX, 0, 0).max_attempts, u32::MAX);
+    }
+
+    /// Tests the full wait schedule up to the `max_attempts` boundary.
+    #[test]
+    fn wait_duration_schedule_up_to_max_attempts() {
+        let config = RetryConfig::new(4, 3, 2);
+
+        // Waits follow failed attempts 1..=3; attempt 4 is the last
+        let schedule: Vec<u64> = (0..config.max_attempts - 1)
+            .map(|attempt| config.wait_duration(attempt).as_secs())
+            .collect();
+        assert_eq!(schedule, [3, 5, 7]);
+        assert!(config.has_attempts_remaining(3));
+        assert!(!config.has_attempts_remaining(4));
+    }
+
+    /// Tests that large values saturate instead of overflowing.
+    #[test]
+    fn wait_duration_saturates() {
+        let config = RetryConfig::new(10, u64::MAX - 1, u64::MAX);
+
+        assert_eq!(config.wait_duration(0), Duration::from_secs(u64::MAX - 1));
+        assert_eq!(config.wait_duration(1), Duration::from_secs(u64::MAX));
+        assert_eq!(
+            config.wait_duration(u32::MAX),
+            Duration::from_secs(u64::MAX)
+        );
+    }
+
     /// Tests wait duration with zero base (starts from zero).
     #[test]
     fn wait_duration_zero_base() {
@@ -0,0 +1,58 @@
+//! Filtering of executor stderr in the output panel.
+//!
+//! Some AI CLIs print progress bars and warnings on stderr that clutter the
+//! panel. When suppression is enabled, stderr lines are hidden from the live
+//! output unless they contain one of the configured keep patterns. Hidden
+//! lines are still captured for summaries.
+
+/// Decides which executor stderr lines are shown in the output panel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StderrFilter {
+    /// Whether stderr lines are hidden.
+    suppress: bool,
+    /// Substrings that keep a stderr line visible while suppression is on.
+    keep_patterns: Vec<String>,
+}
+
+impl StderrFilter {
+    /// Creates a filter. Blank keep patterns are ignored.
+    #[must_use]
+    pub fn new(suppress: bool, keep_patterns: Vec<String>) -> Self {
+        Self {
+            suppress,
+            keep_patterns: keep_patterns
+                .into_iter()
+                .filter(|pattern| !pattern.trim().is_empty())
+                .collect(),
+        }
+    }
+
+    /// Returns true if a stderr line should be shown in the panel.
+    #[must_use]
+    pub fn shows(&self, line: &str) -> bool {
+        !self.suppress
+            || self
+                .keep_patterns
+                .iter()
+                .any(|pattern| line.contains(pattern.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_shows_everything() {
+        assert!(StderrFilter::default().shows("warning: progress 10%"));
+    }
+
+    #[test]
+    fn suppression_hides_lines_without_keep_pattern() {
+        let filter = StderrFilter::new(true, vec!["error".to_string(), "  ".to_string()]);
+        assert!(!filter.shows("progress 10%"));
+        assert!(filter.shows("fatal error: out of tokens"));
+        // Blank patterns would otherwise match nearly every line
+        assert!(!filter.shows("two  spaces"));
+    }
+}
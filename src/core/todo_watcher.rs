@@ -0,0 +1,150 @@
+//! Live reporting of todo files created during planning.
+//!
+//! The planner writes todo files one by one, but the flow only scans the todo
+//! directory once planning finishes. While planning runs, [`TodoWatcher`]
+//! polls the directory and emits [`FlowEvent::TodoFileCreated`] for each new
+//! file, so long planning runs show progress as tasks appear.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+
+use crate::app::FlowEvent;
+use crate::app::state::TodoNaming;
+use crate::fs::scan_todo_files;
+
+/// How often the todo directory is scanned while planning runs.
+pub const TODO_WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Background poller that reports todo files as they appear.
+#[derive(Debug)]
+pub struct TodoWatcher {
+    /// Signals the poller to run a final scan and exit.
+    stop_tx: oneshot::Sender<()>,
+    /// The polling task.
+    handle: JoinHandle<()>,
+}
+
+impl TodoWatcher {
+    /// Starts polling `todo_dir` every `interval`.
+    ///
+    /// Files in `existing` are already known and are not reported. New files
+    /// found in the same scan are reported in execution order for `naming`.
+    #[must_use]
+    pub fn spawn(
+        todo_dir: PathBuf,
+        existing: &[PathBuf],
+        naming: TodoNaming,
+        interval: Duration,
+        tx: mpsc::Sender<FlowEvent>,
+    ) -> Self {
+        let mut known: HashSet<PathBuf> = existing.iter().cloned().collect();
+        let (stop_tx, mut stop_rx) = oneshot::channel();
+        let handle = tokio::spawn(async move {
+            loop {
+                let stopped = tokio::select! {
+                    _ = &mut stop_rx => true,
+                    () = tokio::time::sleep(interval) => false,
+                };
+                // A failed scan is retried on the next tick; the flow reports
+                // real scan errors after planning.
+                if let Ok(files) = scan_todo_files(&todo_dir, naming).await {
+                    for file in files {
+                        if known.insert(file.clone()) {
+                            tx.send(FlowEvent::TodoFileCreated(file)).await.ok();
+                        }
+                    }
+                }
+                if stopped {
+                    break;
+                }
+            }
+        });
+        Self { stop_tx, handle }
+    }
+
+    /// Stops polling after one final scan, so files written just before
+    /// planning ended are still reported.
+    pub async fn finish(self) {
+        self.stop_tx.send(()).ok();
+        self.handle.await.ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use tempfile::TempDir;
+
+    /// Collects the paths of all `TodoFileCreated` events in `rx`.
+    fn created_files(rx: &mut mpsc::Receiver<FlowEvent>) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let FlowEvent::TodoFileCreated(path) = event {
+                files.push(path);
+            }
+        }
+        files
+    }
+
+    #[tokio::test]
+    async fn reports_files_created_while_watching() -> Result<()> {
+        let dir = TempDir::new()?;
+        let existing = dir.path().join("task-001.md");
+        std::fs::write(&existing, "Existing")?;
+        let (tx, mut rx) = mpsc::channel(16);
+
+        let watcher = TodoWatcher::spawn(
+            dir.path().to_path_buf(),
+            std::slice::from_ref(&existing),
+            TodoNaming::Sequential,
+            Duration::from_millis(10),
+            tx,
+        );
+        std::fs::write(dir.path().join("task-002.md"), "Second")?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        std::fs::write(dir.path().join("notes.txt"), "Not a todo")?;
+        std::fs::write(dir.path().join("task-003.md"), "Third")?;
+        watcher.finish().await;
+
+        assert_eq!(
+            created_files(&mut rx),
+            [
+                dir.path().join("task-002.md"),
+                dir.path().join("task-003.md")
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn final_scan_reports_files_written_before_finish() -> Result<()> {
+        let dir = TempDir::new()?;
+        let (tx, mut rx) = mpsc::channel(16);
+
+        // The interval never elapses, so only the final scan sees the files
+        let watcher = TodoWatcher::spawn(
+            dir.path().to_path_buf(),
+            &[],
+            TodoNaming::Sequential,
+            Duration::from_hours(1),
+            tx,
+        );
+        std::fs::write(dir.path().join("task-010.md"), "Tenth")?;
+        std::fs::write(dir.path().join("task-002.md"), "Second")?;
+        watcher.finish().await;
+
+        assert_eq!(
+            created_files(&mut rx),
+            [
+                dir.path().join("task-002.md"),
+                dir.path().join("task-010.md")
+            ]
+        );
+        Ok(())
+    }
+}
@@ -4,10 +4,13 @@
 //! @ file tagging feature. It uses the `ignore` crate for efficient
 //! directory traversal and `nucleo-matcher` for fuzzy matching.
 
-use ignore::WalkBuilder;
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::{Walk, WalkBuilder};
 use nucleo_matcher::pattern::{Atom, AtomKind, CaseMatching, Normalization};
 use nucleo_matcher::{Config, Matcher, Utf32Str};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 /// Maximum number of file matches to return.
 const MAX_FILE_MATCHES: usize = 8;
@@ -16,10 +19,112 @@ const MAX_FILE_MATCHES: usize = 8;
 /// This ensures directories appear prominently when their names match well.
 const DIRECTORY_SCORE_BOOST: u32 = 50;
 
+/// Number of matches after which [`search_files_streaming`] hands a partial
+/// batch to its caller.
+pub const PARTIAL_BATCH_SIZE: usize = 50;
+
+/// Longest time [`search_files_streaming`] holds back found matches before
+/// handing them to its caller.
+pub const PARTIAL_BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Heavy directories that are always excluded from search, even outside git
+/// repositories where `.gitignore` rules may not apply.
+pub const DEFAULT_IGNORED_DIRS: [&str; 3] = [".git", "node_modules", "target"];
+
+/// Name of the file with `McGravity`-only ignore rules for `@` search.
+///
+/// It uses gitignore syntax and layers on top of `.gitignore`, hiding paths
+/// from suggestions without affecting git.
+pub const MCGRAVITY_IGNORE_FILE: &str = ".mcgravityignore";
+
+/// Options controlling which paths [`search_files_with_options`] walks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Extra gitignore-style globs to exclude, on top of `.gitignore` and
+    /// [`DEFAULT_IGNORED_DIRS`].
+    pub ignore_globs: Vec<String>,
+    /// Whether hidden files and directories (dotfiles) are included.
+    pub include_hidden: bool,
+    /// Directory, relative to the working directory, the search is limited
+    /// to (see [`split_scoped_query`]).
+    ///
+    /// The query is matched against paths inside it, and an empty query
+    /// lists only its immediate children.
+    pub scope: Option<PathBuf>,
+    /// How many directory levels below the working directory are walked,
+    /// where 1 means top-level entries only (`None` for no limit).
+    ///
+    /// A scoped search always walks at least the scope's own entries.
+    pub max_depth: Option<usize>,
+    /// Additional directories searched after the working directory, either
+    /// absolute or relative to it.
+    ///
+    /// Their matches are labeled with the root as given (e.g.
+    /// `../api/src/main.rs`), so identical relative paths stay distinct.
+    pub extra_roots: Vec<PathBuf>,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            ignore_globs: Vec::new(),
+            include_hidden: true,
+            scope: None,
+            max_depth: None,
+            extra_roots: Vec::new(),
+        }
+    }
+}
+
+/// Splits an `@` query into the directory it is scoped to and the rest.
+///
+/// A query like `src/app/ma`, whose text up to the last `/` names a
+/// directory under `working_dir`, is scoped to that directory (`src/app`)
+/// with the remaining query `ma`, so `@src/` lists the contents of `src`.
+/// Other queries, and ones reaching outside `working_dir`, are not scoped.
+#[must_use]
+pub fn split_scoped_query<'a>(query: &'a str, working_dir: &Path) -> (Option<PathBuf>, &'a str) {
+    let Some((dir, rest)) = query.rsplit_once('/') else {
+        return (None, query);
+    };
+    let dir = Path::new(dir);
+    let inside = dir.components().next().is_some()
+        && dir
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+    if inside && working_dir.join(dir).is_dir() {
+        (Some(dir.to_path_buf()), rest)
+    } else {
+        (None, query)
+    }
+}
+
+/// Builds the walker overrides that exclude the built-in heavy directories
+/// and the user's ignore globs.
+///
+/// Overrides are whitelists by default, so every glob is negated to turn it
+/// into an ignore rule. Blank globs are skipped.
+fn build_ignore_overrides(
+    working_dir: &Path,
+    ignore_globs: &[String],
+) -> Result<Override, ignore::Error> {
+    let mut builder = OverrideBuilder::new(working_dir);
+    for dir in DEFAULT_IGNORED_DIRS {
+        builder.add(&format!("!{dir}/"))?;
+    }
+    for glob in ignore_globs.iter().map(|glob| glob.trim()) {
+        if !glob.is_empty() {
+            builder.add(&format!("!{glob}"))?;
+        }
+    }
+    builder.build()
+}
+
 /// A single file match from a search operation.
 #[derive(Debug, Clone)]
 pub struct FileMatch {
-    /// The relative path from the working directory.
+    /// The path relative to the working directory, or prefixed with its extra
+    /// root (see [`SearchOptions::extra_roots`]).
     pub path: PathBuf,
     /// The fuzzy match score (higher is better).
     pub score: u32,
@@ -63,87 +168,265 @@ impl SearchResult {
 /// Returns at most `MAX_FILE_MATCHES` results.
 #[must_use]
 pub fn search_files(query: &str, working_dir: &Path) -> SearchResult {
-    let mut result = SearchResult::default();
+    search_files_with_options(query, working_dir, &SearchOptions::default())
+}
+
+/// Searches for files matching the given query, using custom [`SearchOptions`].
+///
+/// Behaves like [`search_files`], but also excludes paths matching
+/// `options.ignore_globs` and skips hidden files unless
+/// `options.include_hidden` is set. An invalid glob is reported through
+/// [`SearchResult::had_errors`], and the search falls back to the built-in
+/// exclusions only.
+#[must_use]
+pub fn search_files_with_options(
+    query: &str,
+    working_dir: &Path,
+    options: &SearchOptions,
+) -> SearchResult {
+    search_files_streaming(
+        query,
+        working_dir,
+        options,
+        &AtomicBool::new(false),
+        &mut |_| true,
+    )
+}
+
+/// Searches like [`search_files_with_options`], handing matches to
+/// `on_batch` while the walk is still running.
+///
+/// Matches are passed on unsorted, every [`PARTIAL_BATCH_SIZE`] matches or
+/// after [`PARTIAL_BATCH_INTERVAL`], whichever comes first; callers combine
+/// them with [`merge_matches`]. Matches still pending when the walk ends are
+/// only part of the returned result, which is always complete. The walk stops
+/// early when `on_batch` returns `false` or once `cancelled` is set, e.g.
+/// because a newer query superseded this one.
+pub fn search_files_streaming(
+    query: &str,
+    working_dir: &Path,
+    options: &SearchOptions,
+    cancelled: &AtomicBool,
+    on_batch: &mut dyn FnMut(Vec<FileMatch>) -> bool,
+) -> SearchResult {
+    let mut stream = MatchStream {
+        result: SearchResult::default(),
+        scorer: Scorer::new(query),
+        pending: Vec::new(),
+        last_flush: Instant::now(),
+        cancelled,
+        on_batch,
+    };
 
     // Build the walker for directory traversal
-    let walker = WalkBuilder::new(working_dir)
-        .hidden(false) // Don't skip hidden files by default
+    let root = options.scope.as_ref().map_or_else(
+        || working_dir.to_path_buf(),
+        |scope| working_dir.join(scope),
+    );
+    let walker = build_walker(
+        &root,
+        working_dir,
+        options,
+        walk_depth(options, query),
+        &mut stream.result,
+    );
+    let finished = stream.walk(walker, &root, options.scope.as_deref());
+
+    // Extra roots are only searched by unscoped queries
+    if finished && options.scope.is_none() {
+        for extra_root in &options.extra_roots {
+            let root = working_dir.join(extra_root);
+            let walker = build_walker(&root, &root, options, options.max_depth, &mut stream.result);
+            if !stream.walk(walker, &root, Some(extra_root)) {
+                break;
+            }
+        }
+    }
+
+    let MatchStream {
+        mut result,
+        pending,
+        ..
+    } = stream;
+    merge_matches(&mut result.matches, pending);
+    result
+}
+
+/// Builds the walker over `root`, whose ignore globs are relative to
+/// `ignore_root`.
+///
+/// An invalid glob is recorded in `result`, and the walker falls back to the
+/// built-in exclusions only.
+fn build_walker(
+    root: &Path,
+    ignore_root: &Path,
+    options: &SearchOptions,
+    max_depth: Option<usize>,
+    result: &mut SearchResult,
+) -> Walk {
+    let overrides = build_ignore_overrides(ignore_root, &options.ignore_globs).or_else(|_| {
+        result.had_errors = true;
+        build_ignore_overrides(ignore_root, &[])
+    });
+
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .hidden(!options.include_hidden) // Skip dotfiles only when asked to
         .git_ignore(true) // Respect .gitignore in git repos
         .git_global(true) // Respect global git excludes
         .git_exclude(true) // Respect .git/info/exclude
         .follow_links(true) // Follow symlinks
         .add_custom_ignore_filename(".gitignore") // Also support .gitignore in non-git dirs
-        .build();
-
-    // Collect file and directory paths while tracking errors
-    let mut entries: Vec<(PathBuf, bool)> = Vec::new(); // (path, is_dir)
-
-    for entry_result in walker {
-        match entry_result {
-            Ok(entry) => {
-                if let Some(ft) = entry.file_type() {
-                    // Include both files and directories (skip root directory)
-                    if let Ok(relative_path) = entry.path().strip_prefix(working_dir) {
-                        if relative_path.as_os_str().is_empty() {
-                            // Skip the root directory itself
-                            continue;
+        .add_custom_ignore_filename(MCGRAVITY_IGNORE_FILE); // Search-only ignores
+    if let Ok(overrides) = overrides {
+        builder.overrides(overrides);
+    }
+    builder.max_depth(max_depth);
+    builder.build()
+}
+
+/// Matches found by a streaming search, handed out in batches.
+struct MatchStream<'a> {
+    /// Result with every match handed out so far.
+    result: SearchResult,
+    scorer: Scorer,
+    /// Matches found since the last batch.
+    pending: Vec<FileMatch>,
+    last_flush: Instant,
+    cancelled: &'a AtomicBool,
+    on_batch: &'a mut dyn FnMut(Vec<FileMatch>) -> bool,
+}
+
+impl MatchStream<'_> {
+    /// Scores the paths `walker` finds below `root`, prefixing matched paths
+    /// with `label`.
+    ///
+    /// Returns `false` if the search was cancelled or a batch was refused.
+    fn walk(&mut self, walker: Walk, root: &Path, label: Option<&Path>) -> bool {
+        for entry_result in walker {
+            if self.cancelled.load(Ordering::Relaxed) {
+                return false;
+            }
+            match entry_result {
+                Ok(entry) => {
+                    if let Some(ft) = entry.file_type() {
+                        // Include both files and directories (skip root directory)
+                        if let Ok(relative_path) = entry.path().strip_prefix(root) {
+                            if relative_path.as_os_str().is_empty() {
+                                // Skip the root directory itself
+                                continue;
+                            }
+                            if let Some(mut file_match) =
+                                self.scorer.score(relative_path, ft.is_dir())
+                            {
+                                if let Some(label) = label {
+                                    file_match.path = label.join(&file_match.path);
+                                }
+                                self.pending.push(file_match);
+                            }
                         }
-                        entries.push((relative_path.to_path_buf(), ft.is_dir()));
+                    }
+                }
+                Err(e) => {
+                    // Track errors
+                    self.result.had_errors = true;
+
+                    // Count permission-related errors
+                    if let Some(io_error) = e.io_error()
+                        && io_error.kind() == std::io::ErrorKind::PermissionDenied
+                    {
+                        self.result.inaccessible_dirs += 1;
                     }
                 }
             }
-            Err(e) => {
-                // Track errors
-                result.had_errors = true;
-
-                // Count permission-related errors
-                if let Some(io_error) = e.io_error()
-                    && io_error.kind() == std::io::ErrorKind::PermissionDenied
-                {
-                    result.inaccessible_dirs += 1;
+
+            let batch_due = self.pending.len() >= PARTIAL_BATCH_SIZE
+                || (!self.pending.is_empty()
+                    && self.last_flush.elapsed() >= PARTIAL_BATCH_INTERVAL);
+            if batch_due {
+                let batch = std::mem::take(&mut self.pending);
+                merge_matches(&mut self.result.matches, batch.clone());
+                self.last_flush = Instant::now();
+                if !(self.on_batch)(batch) {
+                    return false;
                 }
             }
         }
+        true
     }
+}
 
-    // Handle empty query: return entries with directories first, then sorted alphabetically
+/// Returns the walker's maximum depth, counted from the directory it starts
+/// in, for `options` and `query`.
+fn walk_depth(options: &SearchOptions, query: &str) -> Option<usize> {
+    let Some(scope) = &options.scope else {
+        return options.max_depth;
+    };
     if query.is_empty() {
-        // Sort by: directories first, then alphabetically by path
-        entries.sort_by(|a, b| {
-            // is_dir: true sorts before false (reverse order because true > false in bool comparison)
-            match (a.1, b.1) {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.0.cmp(&b.0),
-            }
-        });
-        result.matches = entries
-            .into_iter()
-            .take(MAX_FILE_MATCHES)
-            .map(|(path, is_dir)| FileMatch {
-                path,
-                score: 0,
-                is_dir,
-            })
-            .collect();
-        return result;
+        // A bare `@dir/` lists the directory's contents, not its whole tree
+        return Some(1);
     }
+    let scope_depth = scope.components().count();
+    options
+        .max_depth
+        .map(|depth| depth.saturating_sub(scope_depth).max(1))
+}
 
-    // Set up fuzzy matcher
-    let mut fuzzy_matcher = Matcher::new(Config::DEFAULT);
-    let atom = Atom::new(
-        query,
-        CaseMatching::Ignore,
-        Normalization::Smart,
-        AtomKind::Fuzzy,
-        false,
-    );
+/// Merges `batch` into `matches`, keeping them sorted best first and capped
+/// at the popup's maximum number of matches.
+///
+/// Matches are ordered by score (descending), then directories before files,
+/// then alphabetically by path. An empty query scores everything 0, so its
+/// matches list directories first.
+pub fn merge_matches(matches: &mut Vec<FileMatch>, batch: Vec<FileMatch>) {
+    matches.extend(batch);
+    matches.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| b.is_dir.cmp(&a.is_dir))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    matches.truncate(MAX_FILE_MATCHES);
+}
+
+/// Scores walked paths against a search query.
+struct Scorer {
+    matcher: Matcher,
+    /// The fuzzy pattern, or `None` for an empty query, which matches every
+    /// path with score 0.
+    atom: Option<Atom>,
+}
+
+impl Scorer {
+    fn new(query: &str) -> Self {
+        let atom = (!query.is_empty()).then(|| {
+            Atom::new(
+                query,
+                CaseMatching::Ignore,
+                Normalization::Smart,
+                AtomKind::Fuzzy,
+                false,
+            )
+        });
+        Self {
+            matcher: Matcher::new(Config::DEFAULT),
+            atom,
+        }
+    }
 
-    // Score each entry
-    // For directories, append "/" to the haystack so queries like "dir/" match well
-    // Also give directories a score boost to ensure they appear prominently
-    let mut matches: Vec<FileMatch> = Vec::new();
-    for (path, is_dir) in entries {
+    /// Returns the match for `path`, or `None` if it does not match the query.
+    ///
+    /// For directories, "/" is appended to the haystack so queries like "dir/"
+    /// match well, and the score is boosted so directories stay visible among
+    /// many file matches.
+    fn score(&mut self, path: &Path, is_dir: bool) -> Option<FileMatch> {
+        let Some(atom) = &self.atom else {
+            return Some(FileMatch {
+                path: path.to_path_buf(),
+                score: 0,
+                is_dir,
+            });
+        };
         let path_str = path.to_string_lossy();
         let haystack_str = if is_dir {
             format!("{path_str}/")
@@ -153,28 +436,17 @@ pub fn search_files(query: &str, working_dir: &Path) -> SearchResult {
         let mut haystack_buf = Vec::new();
         let haystack = Utf32Str::new(&haystack_str, &mut haystack_buf);
 
-        if let Some(score) = atom.score(haystack, &mut fuzzy_matcher) {
-            // Apply a score boost to directories so they stay visible among many file matches
-            let final_score = if is_dir {
-                u32::from(score).saturating_add(DIRECTORY_SCORE_BOOST)
+        let score = u32::from(atom.score(haystack, &mut self.matcher)?);
+        Some(FileMatch {
+            path: path.to_path_buf(),
+            score: if is_dir {
+                score.saturating_add(DIRECTORY_SCORE_BOOST)
             } else {
-                u32::from(score)
-            };
-            matches.push(FileMatch {
-                path,
-                score: final_score,
-                is_dir,
-            });
-        }
+                score
+            },
+            is_dir,
+        })
     }
-
-    // Sort by score (descending), then alphabetically for ties
-    matches.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.path.cmp(&b.path)));
-
-    // Limit results
-    matches.truncate(MAX_FILE_MATCHES);
-    result.matches = matches;
-    result
 }
 
 #[cfg(test)]
@@ -303,6 +575,97 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_search_respects_mcgravityignore_on_top_of_gitignore() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        fs::write(temp_dir.path().join(".gitignore"), "*.log\n")?;
+        fs::write(
+            temp_dir.path().join(MCGRAVITY_IGNORE_FILE),
+            "fixtures/\nsnapshot.txt\n",
+        )?;
+        create_test_files(
+            temp_dir.path(),
+            &["keep.txt", "snapshot.txt", "fixtures/big.json", "debug.log"],
+        )?;
+
+        let result = search_files("", temp_dir.path());
+        let paths: Vec<_> = result.matches.iter().map(|m| m.path.clone()).collect();
+
+        assert!(paths.contains(&PathBuf::from("keep.txt")));
+        for hidden in ["snapshot.txt", "fixtures", "fixtures/big.json", "debug.log"] {
+            assert!(!paths.contains(&PathBuf::from(hidden)), "{hidden}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_excludes_default_and_custom_ignored_dirs() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        // No .gitignore: heavy directories must be skipped by the built-in defaults
+        create_test_files(
+            temp_dir.path(),
+            &[
+                "src/main.rs",
+                "target/debug/build.rs",
+                "node_modules/pkg/index.js",
+                ".git/config",
+                "generated/schema.rs",
+            ],
+        )?;
+        let options = SearchOptions {
+            ignore_globs: vec!["generated".to_string()],
+            ..SearchOptions::default()
+        };
+
+        let result = search_files_with_options("", temp_dir.path(), &options);
+
+        let mut paths: Vec<_> = result.matches.iter().map(|m| m.path.clone()).collect();
+        paths.sort();
+        assert_eq!(paths, [Path::new("src"), Path::new("src/main.rs")]);
+        assert!(!result.had_errors);
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_hidden_files_toggle() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(temp_dir.path(), &[".env", "env.txt"])?;
+
+        let shown = search_files_with_options("env", temp_dir.path(), &SearchOptions::default());
+        assert!(shown.matches.iter().any(|m| m.path == Path::new(".env")));
+
+        let options = SearchOptions {
+            include_hidden: false,
+            ..SearchOptions::default()
+        };
+        let hidden = search_files_with_options("env", temp_dir.path(), &options);
+        assert!(!hidden.matches.iter().any(|m| m.path == Path::new(".env")));
+        assert!(
+            hidden
+                .matches
+                .iter()
+                .any(|m| m.path == Path::new("env.txt"))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_invalid_glob_reports_error_and_keeps_defaults() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(temp_dir.path(), &["a.txt", "target/b.txt"])?;
+        let options = SearchOptions {
+            ignore_globs: vec!["[unclosed".to_string()],
+            ..SearchOptions::default()
+        };
+
+        let result = search_files_with_options("", temp_dir.path(), &options);
+
+        assert!(result.had_errors);
+        assert!(result.matches.iter().any(|m| m.path == Path::new("a.txt")));
+        assert!(!result.matches.iter().any(|m| m.path.starts_with("target")));
+        Ok(())
+    }
+
     #[test]
     fn test_search_files_limits_results() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -485,6 +848,126 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_scoped_empty_query_lists_immediate_children() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(
+            temp_dir.path(),
+            &["src/main.rs", "src/app/mod.rs", "lib.rs"],
+        )?;
+        let options = SearchOptions {
+            scope: Some(PathBuf::from("src")),
+            ..SearchOptions::default()
+        };
+
+        let result = search_files_with_options("", temp_dir.path(), &options);
+
+        let paths: Vec<_> = result.matches.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(paths, [Path::new("src/app"), Path::new("src/main.rs")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_scoped_query_matches_inside_the_scope_only() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(
+            temp_dir.path(),
+            &["src/app/mod.rs", "src/core/mod.rs", "tests/mod.rs"],
+        )?;
+        let options = SearchOptions {
+            scope: Some(PathBuf::from("src")),
+            ..SearchOptions::default()
+        };
+
+        let result = search_files_with_options("mod", temp_dir.path(), &options);
+
+        let mut paths: Vec<_> = result.matches.iter().map(|m| m.path.clone()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [Path::new("src/app/mod.rs"), Path::new("src/core/mod.rs")]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_depth_limits_how_far_the_walk_goes() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(
+            temp_dir.path(),
+            &["README.md", "crates/core/src/lib.rs", "crates/app/main.rs"],
+        )?;
+        let search = |max_depth| {
+            let options = SearchOptions {
+                max_depth,
+                ..SearchOptions::default()
+            };
+            let mut paths: Vec<PathBuf> = search_files_with_options("", temp_dir.path(), &options)
+                .matches
+                .into_iter()
+                .map(|m| m.path)
+                .collect();
+            paths.sort();
+            paths
+        };
+
+        assert_eq!(
+            search(Some(1)),
+            [PathBuf::from("README.md"), PathBuf::from("crates")]
+        );
+        let deep = search(Some(4));
+        assert!(deep.contains(&PathBuf::from("crates/core/src/lib.rs")));
+        assert_eq!(deep, search(None));
+        Ok(())
+    }
+
+    #[test]
+    fn test_scoped_search_depth_counts_from_the_working_dir() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(temp_dir.path(), &["crates/core/src/lib.rs"])?;
+        let options = SearchOptions {
+            scope: Some(PathBuf::from("crates")),
+            max_depth: Some(3),
+            ..SearchOptions::default()
+        };
+
+        let result = search_files_with_options("lib", temp_dir.path(), &options);
+        assert!(result.matches.is_empty(), "{:?}", result.matches);
+
+        // Scoped deeper than the limit, the scope's own entries are still listed
+        let options = SearchOptions {
+            scope: Some(PathBuf::from("crates/core/src")),
+            max_depth: Some(1),
+            ..SearchOptions::default()
+        };
+        let result = search_files_with_options("lib", temp_dir.path(), &options);
+        let paths: Vec<_> = result.matches.iter().map(|m| m.path.clone()).collect();
+        assert_eq!(paths, [PathBuf::from("crates/core/src/lib.rs")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_scoped_query() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        create_test_files(temp_dir.path(), &["src/app/mod.rs"])?;
+        let dir = temp_dir.path();
+
+        assert_eq!(
+            split_scoped_query("src/", dir),
+            (Some(PathBuf::from("src")), "")
+        );
+        assert_eq!(
+            split_scoped_query("src/app/mo", dir),
+            (Some(PathBuf::from("src/app")), "mo")
+        );
+        // Plain queries, missing directories and escapes are not scoped
+        assert_eq!(split_scoped_query("src", dir), (None, "src"));
+        assert_eq!(split_scoped_query("nope/x", dir), (None, "nope/x"));
+        assert_eq!(split_scoped_query("../", dir), (None, "../"));
+        assert_eq!(split_scoped_query("/tmp/", dir), (None, "/tmp/"));
+        Ok(())
+    }
+
     #[test]
     fn test_nested_directory_found() -> Result<()> {
         let temp_dir = TempDir::new()?;
@@ -626,6 +1109,129 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_streaming_search_sends_partial_batches() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let files: Vec<String> = (0..120).map(|i| format!("module_{i:03}.rs")).collect();
+        let names: Vec<&str> = files.iter().map(String::as_str).collect();
+        create_test_files(temp_dir.path(), &names)?;
+
+        let mut batches: Vec<Vec<FileMatch>> = Vec::new();
+        let result = search_files_streaming(
+            "module",
+            temp_dir.path(),
+            &SearchOptions::default(),
+            &AtomicBool::new(false),
+            &mut |batch| {
+                batches.push(batch);
+                true
+            },
+        );
+
+        assert!(batches.len() >= 2, "got {} batches", batches.len());
+        assert!(
+            batches
+                .iter()
+                .all(|batch| batch.len() <= PARTIAL_BATCH_SIZE)
+        );
+        // Only the matches found after the last batch are held back
+        let streamed: usize = batches.iter().map(Vec::len).sum();
+        assert!(streamed > files.len() - PARTIAL_BATCH_SIZE);
+
+        let mut merged = Vec::new();
+        for batch in batches {
+            merge_matches(&mut merged, batch);
+        }
+        assert_eq!(merged.len(), MAX_FILE_MATCHES);
+        assert_eq!(result.matches.len(), MAX_FILE_MATCHES);
+        assert!(merged.windows(2).all(|pair| pair[0].score >= pair[1].score));
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_search_stops_when_batch_is_refused() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let files: Vec<String> = (0..200).map(|i| format!("file_{i:03}.txt")).collect();
+        let names: Vec<&str> = files.iter().map(String::as_str).collect();
+        create_test_files(temp_dir.path(), &names)?;
+
+        let mut calls = 0;
+        search_files_streaming(
+            "file",
+            temp_dir.path(),
+            &SearchOptions::default(),
+            &AtomicBool::new(false),
+            &mut |_| {
+                calls += 1;
+                false
+            },
+        );
+
+        assert_eq!(calls, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_search_walks_extra_roots_with_labels() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let app = temp_dir.path().join("app");
+        let api = temp_dir.path().join("api");
+        create_test_files(&app, &["src/main.rs", "src/ui.rs"])?;
+        create_test_files(&api, &["src/main.rs", "src/routes.rs"])?;
+
+        let options = SearchOptions {
+            extra_roots: vec![PathBuf::from("../api")],
+            ..SearchOptions::default()
+        };
+        let result = search_files_with_options("main", &app, &options);
+        let mut paths: Vec<PathBuf> = result.matches.into_iter().map(|m| m.path).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("../api/src/main.rs"),
+                PathBuf::from("src/main.rs")
+            ]
+        );
+
+        // Files only in the extra root are found too, and absolute roots work
+        let options = SearchOptions {
+            extra_roots: vec![api.clone()],
+            ..SearchOptions::default()
+        };
+        let result = search_files_with_options("routes", &app, &options);
+        let paths: Vec<PathBuf> = result.matches.into_iter().map(|m| m.path).collect();
+        assert_eq!(paths, [api.join("src/routes.rs")]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_streaming_search_stops_when_cancelled() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let files: Vec<String> = (0..200).map(|i| format!("file_{i:03}.txt")).collect();
+        let names: Vec<&str> = files.iter().map(String::as_str).collect();
+        create_test_files(temp_dir.path(), &names)?;
+
+        // The first batch accepts its matches but supersedes the search
+        let cancelled = AtomicBool::new(false);
+        let mut calls = 0;
+        search_files_streaming(
+            "file",
+            temp_dir.path(),
+            &SearchOptions::default(),
+            &cancelled,
+            &mut |_| {
+                calls += 1;
+                cancelled.store(true, Ordering::Relaxed);
+                true
+            },
+        );
+
+        // The walk ends right after the batch instead of visiting every file
+        assert_eq!(calls, 1);
+        Ok(())
+    }
 }
 
 #[cfg(all(test, unix))]
@@ -0,0 +1,123 @@
+//! Atomic file replacement.
+//!
+//! Files the user would lose work over (`settings.json`, `task.md`) are
+//! written to a uniquely named temporary file in the same directory and then
+//! renamed over the target. A rename within one filesystem is atomic, so a
+//! crash leaves either the old or the new content, never a half-written file,
+//! and concurrent writers never share a temporary file.
+
+use std::io::Write;
+use std::path::Path;
+
+/// Replaces `path` with `contents` atomically.
+///
+/// The content is synced before the rename, and the directory after it, so
+/// the replacement survives a power loss.
+///
+/// # Errors
+///
+/// Returns an error if the temporary file cannot be written or renamed. The
+/// temporary file is removed on failure, and the target keeps its old content.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    let name = path
+        .file_name()
+        .map_or_else(String::new, |name| name.to_string_lossy().into_owned());
+    // A hidden sibling of the target, so the rename never crosses filesystems
+    let mut temp = tempfile::Builder::new()
+        .prefix(&format!(".{name}."))
+        .suffix(".tmp")
+        .tempfile_in(dir)?;
+    temp.write_all(contents.as_ref())?;
+    temp.as_file().sync_all()?;
+    temp.persist(path).map_err(|e| e.error)?;
+    sync_dir(dir)
+}
+
+/// Syncs the directory entry of a rename inside `dir` to disk.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> std::io::Result<()> {
+    std::fs::File::open(dir)?.sync_all()
+}
+
+/// Directories cannot be opened for syncing on this platform.
+#[cfg(not(unix))]
+#[allow(clippy::unnecessary_wraps)]
+fn sync_dir(_dir: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// Async version of [`write_atomic`] for use inside the flow runner.
+///
+/// # Errors
+///
+/// Returns an error if the temporary file cannot be written or renamed.
+pub async fn write_atomic_async(path: &Path, contents: String) -> std::io::Result<()> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || write_atomic(&path, contents))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Returns the names of the entries in `dir`, sorted.
+    fn entries(dir: &Path) -> anyhow::Result<Vec<String>> {
+        let mut names = std::fs::read_dir(dir)?
+            .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        names.sort();
+        Ok(names)
+    }
+
+    #[test]
+    fn successful_write_replaces_file() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("settings.json");
+        std::fs::write(&path, "old")?;
+
+        write_atomic(&path, "new")?;
+
+        assert_eq!(std::fs::read_to_string(&path)?, "new");
+        assert_eq!(entries(dir.path())?, vec!["settings.json"]);
+        Ok(())
+    }
+
+    #[test]
+    fn leftover_temp_file_is_not_reused() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join("task.md");
+        std::fs::write(&path, "original")?;
+        // A temp file left behind by a crash, or in use by another writer
+        let stale = dir.path().join(".task.md.tmp");
+        std::fs::write(&stale, "partial")?;
+
+        write_atomic(&path, "replacement")?;
+
+        assert_eq!(std::fs::read_to_string(&path)?, "replacement");
+        assert_eq!(std::fs::read_to_string(&stale)?, "partial");
+        assert_eq!(entries(dir.path())?, vec![".task.md.tmp", "task.md"]);
+        Ok(())
+    }
+
+    #[test]
+    fn failed_rename_removes_temp_file() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        // A directory cannot be replaced by a file
+        let path = dir.path().join("occupied");
+        std::fs::create_dir(&path)?;
+        std::fs::write(path.join("keep"), "data")?;
+
+        assert!(write_atomic(&path, "new").is_err());
+
+        assert!(path.join("keep").exists());
+        assert_eq!(entries(dir.path())?, vec!["occupied"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+//! Persistence of the command input history.
+//!
+//! Submitted inputs are stored in `.mcgravity/history`, oldest first, one
+//! JSON-encoded string per line so multi-line inputs survive the round trip.
+
+use std::path::Path;
+
+/// Name of the history file inside the `.mcgravity` directory.
+pub const HISTORY_FILE: &str = "history";
+
+/// Reads the history entries stored in the given file, oldest first.
+///
+/// A missing file and unparseable lines are treated as "no entries".
+#[must_use]
+pub fn read_history(path: &Path) -> Vec<String> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Overwrites the history file with the given entries.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be written.
+pub fn write_history<'a>(
+    path: &Path,
+    entries: impl IntoIterator<Item = &'a str>,
+) -> std::io::Result<()> {
+    let mut content = String::new();
+    for entry in entries {
+        content.push_str(&serde_json::to_string(entry).map_err(std::io::Error::other)?);
+        content.push('\n');
+    }
+    std::fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn write_and_read_roundtrip_preserves_newlines() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join(HISTORY_FILE);
+
+        write_history(&path, ["first", "multi\nline \"task\"", "/diff"])?;
+
+        assert_eq!(
+            read_history(&path),
+            ["first", "multi\nline \"task\"", "/diff"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn read_ignores_missing_file_and_bad_lines() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().join(HISTORY_FILE);
+        assert!(read_history(&path).is_empty());
+
+        std::fs::write(&path, "\"ok\"\nnot json\n\"also ok\"\n")?;
+        assert_eq!(read_history(&path), ["ok", "also ok"]);
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+//! Per-cycle output logs kept when the `log_cycles` setting is on.
+//!
+//! Each cycle's CLI output is written to
+//! `.mcgravity/logs/cycle-<run>-NNN.log`, where `<run>` is the time the flow
+//! started, so logs of earlier flows are never overwritten. Only the
+//! [`MAX_CYCLE_LOGS`] most recently written logs are kept.
+//!
+//! With the `save_captured_output` setting on, the output captured for each
+//! completed todo's summary is also saved here as `<todo>.out`, keeping the
+//! [`MAX_CAPTURED_OUTPUTS`] most recently written.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Name of the logs directory inside the `.mcgravity` directory.
+pub const LOGS_DIR: &str = "logs";
+
+/// How many cycle logs are kept before the oldest are removed.
+pub const MAX_CYCLE_LOGS: usize = 50;
+
+/// How many captured `<todo>.out` files are kept, the same as cycle logs.
+pub const MAX_CAPTURED_OUTPUTS: usize = MAX_CYCLE_LOGS;
+
+/// Format of the run id that keeps the cycle logs of each flow apart.
+const RUN_ID_FORMAT: &str = "%Y%m%d_%H%M%S_%3f";
+
+/// Returns a run id for a flow starting now, such as `20250102_030405_678`.
+#[must_use]
+pub fn new_run_id() -> String {
+    chrono::Local::now().format(RUN_ID_FORMAT).to_string()
+}
+
+/// Returns the log file for `cycle` (1-based) of the flow `run_id` inside
+/// `logs_dir`.
+#[must_use]
+pub fn cycle_log_file(logs_dir: &Path, run_id: &str, cycle: u32) -> PathBuf {
+    logs_dir.join(format!("cycle-{run_id}-{cycle:03}.log"))
+}
+
+/// Returns the file inside `logs_dir` that keeps the captured output of the
+/// todo named `todo_file_name`, such as `task-001.out` for `task-001.md`.
+#[must_use]
+pub fn captured_output_file(logs_dir: &Path, todo_file_name: &str) -> PathBuf {
+    let stem = Path::new(todo_file_name)
+        .file_stem()
+        .map_or_else(|| todo_file_name.into(), |stem| stem.to_string_lossy());
+    logs_dir.join(format!("{stem}.out"))
+}
+
+/// Removes the least recently written cycle logs in `logs_dir` so that at
+/// most `keep` remain. Other files in the directory are left alone.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read or a log cannot be removed.
+pub fn prune_cycle_logs(logs_dir: &Path, keep: usize) -> std::io::Result<()> {
+    prune_oldest(logs_dir, keep, is_cycle_log)
+}
+
+/// Removes the least recently written captured outputs (`*.out`) in
+/// `logs_dir` so that at most `keep` remain. Other files are left alone.
+///
+/// # Errors
+///
+/// Returns an error if the directory cannot be read or a file cannot be
+/// removed.
+pub fn prune_captured_outputs(logs_dir: &Path, keep: usize) -> std::io::Result<()> {
+    prune_oldest(logs_dir, keep, is_captured_output)
+}
+
+/// Removes the least recently written files in `logs_dir` matching
+/// `is_kind` so that at most `keep` remain.
+fn prune_oldest(logs_dir: &Path, keep: usize, is_kind: fn(&Path) -> bool) -> std::io::Result<()> {
+    let mut logs: Vec<(SystemTime, PathBuf)> = std::fs::read_dir(logs_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| is_kind(path))
+        .map(|path| {
+            let modified = std::fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH);
+            (modified, path)
+        })
+        .collect();
+    // Oldest first; files written in the same instant go by name
+    logs.sort();
+    let excess = logs.len().saturating_sub(keep);
+    for (_, path) in logs.into_iter().take(excess) {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Returns whether `path` is named like a cycle log (`cycle-*.log`).
+fn is_cycle_log(path: &Path) -> bool {
+    path.is_file()
+        && path.extension().is_some_and(|ext| ext == "log")
+        && path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("cycle-"))
+}
+
+/// Returns whether `path` is named like a captured output (`*.out`).
+fn is_captured_output(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|ext| ext == "out")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[test]
+    fn cycle_log_file_pads_cycle_number() {
+        let dir = Path::new("/logs");
+        assert_eq!(cycle_log_file(dir, "r1", 7), dir.join("cycle-r1-007.log"));
+        assert_eq!(
+            cycle_log_file(dir, "r1", 1234),
+            dir.join("cycle-r1-1234.log")
+        );
+    }
+
+    #[test]
+    fn run_ids_of_later_flows_sort_after_earlier_ones() {
+        let first = new_run_id();
+        std::thread::sleep(Duration::from_millis(2));
+        assert!(new_run_id() > first);
+    }
+
+    #[test]
+    fn captured_output_file_replaces_extension() {
+        let dir = Path::new("/logs");
+        assert_eq!(
+            captured_output_file(dir, "task-001.md"),
+            dir.join("task-001.out")
+        );
+        assert_eq!(captured_output_file(dir, "notes"), dir.join("notes.out"));
+    }
+
+    #[test]
+    fn prune_removes_oldest_logs_only() -> Result<()> {
+        let dir = TempDir::new()?;
+        let base = SystemTime::now();
+        // cycle 1 was written last
+        for (cycle, age_secs) in [(1, 0), (2, 30), (3, 20)] {
+            let path = cycle_log_file(dir.path(), "run", cycle);
+            let file = std::fs::File::create(&path)?;
+            file.set_modified(base - Duration::from_secs(age_secs))?;
+        }
+        std::fs::write(dir.path().join("notes.txt"), "keep")?;
+
+        prune_cycle_logs(dir.path(), 2)?;
+
+        assert!(cycle_log_file(dir.path(), "run", 1).exists());
+        assert!(!cycle_log_file(dir.path(), "run", 2).exists());
+        assert!(cycle_log_file(dir.path(), "run", 3).exists());
+        assert!(dir.path().join("notes.txt").exists());
+        Ok(())
+    }
+
+    #[test]
+    fn prune_captured_outputs_keeps_newest_and_cycle_logs() -> Result<()> {
+        let dir = TempDir::new()?;
+        let base = SystemTime::now();
+        for (name, age_secs) in [("task-001.md", 30), ("task-002.md", 0), ("task-003.md", 20)] {
+            let file = std::fs::File::create(captured_output_file(dir.path(), name))?;
+            file.set_modified(base - Duration::from_secs(age_secs))?;
+        }
+        let log = std::fs::File::create(cycle_log_file(dir.path(), "run", 1))?;
+        log.set_modified(base - Duration::from_secs(45))?;
+
+        prune_captured_outputs(dir.path(), 2)?;
+
+        assert!(!dir.path().join("task-001.out").exists());
+        assert!(dir.path().join("task-002.out").exists());
+        assert!(dir.path().join("task-003.out").exists());
+        assert!(cycle_log_file(dir.path(), "run", 1).exists());
+        Ok(())
+    }
+}
@@ -2,11 +2,19 @@
 
 use std::path::{Path, PathBuf};
 
+pub mod atomic;
+pub mod history;
+pub mod logs;
+pub mod pids;
 pub mod settings;
+pub mod snapshot;
 pub mod todo;
 
-pub use settings::{PersistedSettings, load_settings, save_settings};
-pub use todo::{move_to_done, read_file_content, remove_done_files, scan_todo_files};
+pub use settings::{PersistedSettings, is_parse_error, load_settings, save_settings};
+pub use todo::{
+    list_editable_todo_files, list_todo_files, move_to_done, read_file_content, remove_done_files,
+    remove_todo_file, scan_todo_files, select_numbered_file,
+};
 
 // Legacy constants for backward compatibility during migration
 pub use settings::{MCGRAVITY_DIR, SETTINGS_FILE};
@@ -15,6 +23,45 @@ pub use todo::{DONE_DIR, TODO_DIR};
 /// Path to the task file for persistence (legacy constant).
 pub const TASK_FILE: &str = ".mcgravity/task.md";
 
+/// Temporary file used to probe whether a state directory is writable.
+const WRITE_PROBE_FILE: &str = ".write-probe";
+
+/// Returns true if `err` is a permission or read-only filesystem error.
+#[must_use]
+pub fn is_permission_error(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        std::io::ErrorKind::PermissionDenied | std::io::ErrorKind::ReadOnlyFilesystem
+    )
+}
+
+/// Returns the first permission or read-only filesystem error in the chain.
+#[must_use]
+pub fn find_permission_error(err: &anyhow::Error) -> Option<&std::io::Error> {
+    err.chain()
+        .filter_map(|cause| cause.downcast_ref::<std::io::Error>())
+        .find(|io_err| is_permission_error(io_err))
+}
+
+/// Builds the user-facing message for state that cannot be written to `path`.
+///
+/// Permission errors get a specific explanation and a suggested fix, since
+/// the usual cause is a read-only mount or a directory owned by another user.
+#[must_use]
+pub fn unwritable_state_message(path: &Path, err: &std::io::Error) -> String {
+    if is_permission_error(err) {
+        format!(
+            "Cannot write to {} (permission denied or read-only filesystem). \
+             McGravity stores task progress there and cannot run without it. \
+             Make it writable (e.g. `chmod -R u+w .mcgravity`) or start McGravity \
+             from a writable directory.",
+            path.display()
+        )
+    } else {
+        format!("Cannot write to {}: {err}", path.display())
+    }
+}
+
 /// Holds all mcgravity-related paths derived from a base directory.
 ///
 /// This struct enables dependency injection of filesystem paths, allowing
@@ -72,6 +119,24 @@ impl McgravityPaths {
         &self.base
     }
 
+    /// Returns the nearest ancestor of the base directory that has its own
+    /// `.mcgravity` directory, or `None` if there is none.
+    ///
+    /// A hit means this directory may be inside a project that another
+    /// `McGravity` flow is already orchestrating. The base itself is not
+    /// considered.
+    #[must_use]
+    pub fn find_ancestor_mcgravity(&self) -> Option<PathBuf> {
+        let base = self
+            .base
+            .canonicalize()
+            .unwrap_or_else(|_| self.base.clone());
+        base.ancestors()
+            .skip(1)
+            .find(|dir| dir.join(MCGRAVITY_DIR).is_dir())
+            .map(Path::to_path_buf)
+    }
+
     /// Returns the `.mcgravity` directory path.
     #[must_use]
     pub fn mcgravity_dir(&self) -> PathBuf {
@@ -84,6 +149,13 @@ impl McgravityPaths {
         self.base.join(".mcgravity/settings.json")
     }
 
+    /// Returns the path a corrupt settings file is moved to
+    /// (`.mcgravity/settings.json.bak`).
+    #[must_use]
+    pub fn settings_backup_file(&self) -> PathBuf {
+        self.base.join(".mcgravity/settings.json.bak")
+    }
+
     /// Returns the task file path (`.mcgravity/task.md`).
     #[must_use]
     pub fn task_file(&self) -> PathBuf {
@@ -102,6 +174,30 @@ impl McgravityPaths {
         self.base.join(".mcgravity/todo/done")
     }
 
+    /// Returns the file recording spawned child PIDs (`.mcgravity/pids`).
+    #[must_use]
+    pub fn pids_file(&self) -> PathBuf {
+        self.mcgravity_dir().join(pids::PIDS_FILE)
+    }
+
+    /// Returns the snapshots directory (`.mcgravity/snapshots`).
+    #[must_use]
+    pub fn snapshots_dir(&self) -> PathBuf {
+        self.mcgravity_dir().join(snapshot::SNAPSHOTS_DIR)
+    }
+
+    /// Returns the per-cycle logs directory (`.mcgravity/logs`).
+    #[must_use]
+    pub fn logs_dir(&self) -> PathBuf {
+        self.mcgravity_dir().join(logs::LOGS_DIR)
+    }
+
+    /// Returns the command input history file (`.mcgravity/history`).
+    #[must_use]
+    pub fn history_file(&self) -> PathBuf {
+        self.mcgravity_dir().join(history::HISTORY_FILE)
+    }
+
     /// Ensures the `.mcgravity` directory exists.
     ///
     /// # Errors
@@ -137,6 +233,28 @@ impl McgravityPaths {
         Ok(())
     }
 
+    /// Finds the first state directory that cannot be created or written to.
+    ///
+    /// Creates `.mcgravity/`, `.mcgravity/todo/` and `.mcgravity/todo/done/`
+    /// if needed, then probes each one by writing and removing a small file,
+    /// since an existing directory on a read-only mount can still be created
+    /// "successfully".
+    ///
+    /// Returns `None` when all state can be persisted.
+    #[must_use]
+    pub fn find_unwritable_dir(&self) -> Option<(PathBuf, std::io::Error)> {
+        for dir in [self.mcgravity_dir(), self.todo_dir(), self.done_dir()] {
+            let probe = dir.join(WRITE_PROBE_FILE);
+            let result = std::fs::create_dir_all(&dir)
+                .and_then(|()| std::fs::write(&probe, b""))
+                .and_then(|()| std::fs::remove_file(&probe));
+            if let Err(e) = result {
+                return Some((dir, e));
+            }
+        }
+        None
+    }
+
     /// Checks whether this is a first run (no settings file exists).
     #[must_use]
     pub fn is_first_run(&self) -> bool {
@@ -154,6 +272,21 @@ impl McgravityPaths {
         load_settings(&self.settings_file())
     }
 
+    /// Moves the settings file to [`Self::settings_backup_file`], replacing
+    /// any older backup, and returns the backup path.
+    ///
+    /// Used when the settings file cannot be parsed, so the next run starts
+    /// from defaults without losing the user's edits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be renamed.
+    pub fn backup_settings(&self) -> std::io::Result<PathBuf> {
+        let backup = self.settings_backup_file();
+        std::fs::rename(self.settings_file(), &backup)?;
+        Ok(backup)
+    }
+
     /// Saves settings to the settings file.
     ///
     /// Creates the `.mcgravity` directory if it doesn't exist.
@@ -181,6 +314,56 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn find_unwritable_dir_creates_and_accepts_writable_dirs() {
+        let temp = TempDir::new().unwrap();
+        let paths = McgravityPaths::new(temp.path());
+
+        assert!(paths.find_unwritable_dir().is_none());
+        assert!(paths.done_dir().is_dir());
+        assert!(!paths.mcgravity_dir().join(WRITE_PROBE_FILE).exists());
+    }
+
+    #[test]
+    fn unwritable_state_message_explains_permission_errors() {
+        let err = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        let message = unwritable_state_message(Path::new("/mnt/ro/.mcgravity"), &err);
+        assert!(message.contains("/mnt/ro/.mcgravity"));
+        assert!(message.contains("read-only"));
+        assert!(message.contains("chmod"));
+
+        let other = std::io::Error::other("disk full");
+        let message = unwritable_state_message(Path::new("/x"), &other);
+        assert_eq!(message, "Cannot write to /x: disk full");
+    }
+
+    #[test]
+    fn find_permission_error_searches_the_chain() {
+        let err = anyhow::Error::from(std::io::Error::from(std::io::ErrorKind::ReadOnlyFilesystem))
+            .context("Failed to write task file");
+        assert!(find_permission_error(&err).is_some());
+        assert!(find_permission_error(&anyhow::anyhow!("other")).is_none());
+    }
+
+    #[test]
+    fn find_ancestor_mcgravity_detects_nested_layout() {
+        let temp = TempDir::new().unwrap();
+        let outer = temp.path().join("outer");
+        let inner = outer.join("crates/inner");
+        std::fs::create_dir_all(&inner).unwrap();
+        let outer = outer.canonicalize().unwrap();
+
+        // A .mcgravity in the base itself is not a nesting
+        std::fs::create_dir_all(inner.join(MCGRAVITY_DIR)).unwrap();
+        std::fs::create_dir_all(outer.join(MCGRAVITY_DIR)).unwrap();
+        let inner_paths = McgravityPaths::new(&inner);
+        assert_eq!(inner_paths.find_ancestor_mcgravity(), Some(outer.clone()));
+        assert_eq!(
+            McgravityPaths::new(&outer.join("crates")).find_ancestor_mcgravity(),
+            Some(outer)
+        );
+    }
+
     #[test]
     fn paths_are_derived_from_base() {
         let base = Path::new("/test/base");
@@ -201,6 +384,11 @@ mod tests {
             paths.done_dir(),
             Path::new("/test/base/.mcgravity/todo/done")
         );
+        assert_eq!(paths.pids_file(), Path::new("/test/base/.mcgravity/pids"));
+        assert_eq!(
+            paths.settings_backup_file(),
+            Path::new("/test/base/.mcgravity/settings.json.bak")
+        );
     }
 
     #[test]
@@ -253,6 +441,48 @@ mod tests {
             enter_behavior: "Submit".to_string(),
             max_iterations: "5".to_string(),
             summary_generation: String::new(),
+            autosave_debounce_ms: None,
+            max_task_bytes: None,
+            archive_mode: None,
+            archive_dir: None,
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: None,
+            bell_on_complete: None,
+            confirm_unlimited: None,
+            show_timestamps: None,
+            suppress_stderr: None,
+            stderr_keep_patterns: None,
+            idle_notice_secs: None,
+            ignore_globs: None,
+            search_hidden: None,
+            search_max_depth: None,
+            reset_clears_task: None,
+            sticky_scroll: None,
+            migrate_done_files: None,
+            flow_mode: None,
+            step_mode: None,
+            summary_max_len: None,
+            collapse_repeated_lines: None,
+            todo_naming: None,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: None,
+            render_markdown: None,
+            extra_roots: None,
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: None,
+            fail_fast: None,
+            confirm_suspicious_todos: None,
+            include_git_context: None,
+            attach_mentioned_files: None,
+            verbosity: None,
+            output_buffer_size: None,
+            open_settings_on_missing_cli: None,
+            theme: None,
         };
 
         paths.save_settings(&settings).unwrap();
@@ -0,0 +1,464 @@
+//! Tracking of spawned CLI child processes.
+//!
+//! Every AI CLI process spawned by an executor is recorded in `.mcgravity/pids`
+//! and removed again once it exits. If `McGravity` crashes (or is killed with
+//! SIGKILL) while a child is running, its PID stays in the file. On the next
+//! launch, any recorded PID that still refers to the same live process, and
+//! whose owning `McGravity` instance has exited, is reported as an orphan so
+//! the user can reap it with `/reap`.
+//!
+//! Each line records `<pid> <identity> <owner pid> <owner identity>`, where an
+//! identity is the process start time and command name (`-` when unknown).
+//! Checking identities keeps a recycled PID, or the children of another
+//! instance still running in the same directory, from being signaled. The file
+//! is read and rewritten under an exclusive lock on `pids.lock`.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// Name of the PID file inside the `.mcgravity` directory.
+pub const PIDS_FILE: &str = "pids";
+
+/// A spawned child process recorded in the PID file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PidEntry {
+    /// PID of the child process.
+    pub pid: u32,
+    /// Identity of the child when it was recorded, if it could be read.
+    pub identity: Option<String>,
+    /// PID of the `McGravity` instance that spawned the child.
+    pub owner: u32,
+    /// Identity of the owning instance when the child was recorded.
+    pub owner_identity: Option<String>,
+}
+
+impl PidEntry {
+    /// Records `pid` as a child of `owner`, capturing both identities now.
+    #[must_use]
+    pub fn new(pid: u32, owner: u32) -> Self {
+        Self {
+            pid,
+            identity: process_identity(pid),
+            owner,
+            owner_identity: process_identity(owner),
+        }
+    }
+
+    /// Returns true if the recorded child is still running as the same process.
+    ///
+    /// Entries without a recorded identity can never be confirmed.
+    #[must_use]
+    pub fn is_running(&self) -> bool {
+        self.identity.is_some() && process_identity(self.pid) == self.identity
+    }
+
+    /// Returns true if the instance that spawned the child is still running.
+    #[must_use]
+    pub fn owner_is_running(&self) -> bool {
+        self.owner_identity.is_some() && process_identity(self.owner) == self.owner_identity
+    }
+
+    /// Parses one line of the PID file. Lines in any other format, including
+    /// the bare PIDs written by older versions, are rejected.
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split_whitespace();
+        let pid = fields.next()?.parse().ok()?;
+        let identity = parse_identity(fields.next()?);
+        let owner = fields.next()?.parse().ok()?;
+        let owner_identity = parse_identity(fields.next()?);
+        Some(Self {
+            pid,
+            identity,
+            owner,
+            owner_identity,
+        })
+    }
+
+    fn format(&self) -> String {
+        format!(
+            "{} {} {} {}",
+            self.pid,
+            self.identity.as_deref().unwrap_or("-"),
+            self.owner,
+            self.owner_identity.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+fn parse_identity(field: &str) -> Option<String> {
+    (field != "-").then(|| field.to_string())
+}
+
+/// Returns an identity for the process with the given PID: its start time and
+/// command name, joined without whitespace. `None` if the process does not
+/// exist or its identity cannot be read on this platform.
+#[must_use]
+pub fn process_identity(pid: u32) -> Option<String> {
+    if pid == 0 {
+        return None;
+    }
+    #[cfg(target_os = "linux")]
+    {
+        // Format: "pid (comm) state ppid ...", where comm may contain spaces
+        // and parentheses, and the start time is the 22nd field overall.
+        let stat = std::fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+        let open = stat.find('(')?;
+        let close = stat.rfind(')')?;
+        let comm = stat.get(open + 1..close)?;
+        let start_time = stat.get(close + 1..)?.split_whitespace().nth(19)?;
+        Some(format!("{start_time}:{}", sanitize(comm)))
+    }
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        let output = std::process::Command::new("ps")
+            .args(["-o", "lstart=,comm=", "-p", &pid.to_string()])
+            .output()
+            .ok()?;
+        let text = String::from_utf8_lossy(&output.stdout);
+        let text = text.trim();
+        (output.status.success() && !text.is_empty()).then(|| sanitize(text))
+    }
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// Replaces whitespace so an identity stays a single field in the PID file.
+fn sanitize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join("_")
+}
+
+/// Exclusive lock on the PID file, released when dropped.
+struct PidsLock {
+    _file: File,
+}
+
+impl PidsLock {
+    /// Blocks until the lock next to `path` is held.
+    fn acquire(path: &Path) -> std::io::Result<Self> {
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(lock_path(path))?;
+        #[cfg(unix)]
+        {
+            use std::os::fd::AsRawFd;
+            // SAFETY: the descriptor stays open for the lifetime of `file`,
+            // and closing it releases the lock.
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+        Ok(Self { _file: file })
+    }
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    path.with_extension("lock")
+}
+
+/// Reads all entries recorded in the given file.
+///
+/// A missing file and unparseable lines are treated as "no entries".
+#[must_use]
+pub fn read_entries(path: &Path) -> Vec<PidEntry> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content.lines().filter_map(PidEntry::parse).collect()
+}
+
+/// Overwrites the PID file with the given entries, removing it when empty.
+fn write_entries(path: &Path, entries: &[PidEntry]) -> std::io::Result<()> {
+    if entries.is_empty() {
+        return match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    let mut content = entries
+        .iter()
+        .map(PidEntry::format)
+        .collect::<Vec<_>>()
+        .join("\n");
+    content.push('\n');
+    std::fs::write(path, content)
+}
+
+/// Applies `update` to the recorded entries while holding the lock, writing
+/// the result back if it changed.
+fn update_entries<T>(
+    path: &Path,
+    update: impl FnOnce(&mut Vec<PidEntry>) -> T,
+) -> std::io::Result<T> {
+    let _lock = PidsLock::acquire(path)?;
+    let mut entries = read_entries(path);
+    let before = entries.clone();
+    let result = update(&mut entries);
+    if entries != before {
+        write_entries(path, &entries)?;
+    }
+    Ok(result)
+}
+
+/// Records a child PID spawned by the instance `owner`.
+///
+/// # Errors
+///
+/// Returns an error if the PID file cannot be locked or written.
+pub fn register_pid(path: &Path, pid: u32, owner: u32) -> std::io::Result<()> {
+    let entry = PidEntry::new(pid, owner);
+    update_entries(path, |entries| {
+        entries.retain(|e| e.pid != pid);
+        entries.push(entry);
+    })
+}
+
+/// Removes a PID once its process has exited.
+///
+/// # Errors
+///
+/// Returns an error if the PID file cannot be locked or written.
+pub fn unregister_pid(path: &Path, pid: u32) -> std::io::Result<()> {
+    update_entries(path, |entries| entries.retain(|e| e.pid != pid))
+}
+
+/// Drops entries whose child is no longer the recorded process and returns
+/// the orphans among the rest: children whose owning instance has exited.
+fn take_orphans(entries: &mut Vec<PidEntry>) -> Vec<PidEntry> {
+    entries.retain(PidEntry::is_running);
+    entries
+        .iter()
+        .filter(|e| !e.owner_is_running())
+        .cloned()
+        .collect()
+}
+
+/// Finds PIDs recorded by a previous session that are still running.
+///
+/// Stale entries (processes that already exited, or whose PID now belongs to
+/// a different process) are dropped from the file so they are not reported
+/// again. Live orphans are kept until they are reaped, and children of other
+/// running instances are left alone.
+#[must_use]
+pub fn find_orphaned_pids(path: &Path) -> Vec<u32> {
+    update_entries(path, take_orphans)
+        .unwrap_or_default()
+        .iter()
+        .map(|e| e.pid)
+        .collect()
+}
+
+/// PIDs affected by [`reap_orphaned_pids`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReapOutcome {
+    /// Orphans that were sent SIGTERM.
+    pub reaped: Vec<u32>,
+    /// Orphans that could not be signaled.
+    pub failed: Vec<u32>,
+}
+
+/// Sends SIGTERM to each orphan recorded in the given file, re-checking its
+/// identity first, and drops the signaled ones from the file.
+///
+/// On non-Unix platforms nothing is signaled.
+#[must_use]
+pub fn reap_orphaned_pids(path: &Path) -> ReapOutcome {
+    update_entries(path, |entries| {
+        let mut outcome = ReapOutcome::default();
+        for orphan in take_orphans(entries) {
+            if terminate(&orphan) {
+                entries.retain(|e| e.pid != orphan.pid);
+                outcome.reaped.push(orphan.pid);
+            } else {
+                outcome.failed.push(orphan.pid);
+            }
+        }
+        outcome
+    })
+    .unwrap_or_default()
+}
+
+/// Sends SIGTERM to the recorded child if it is still the same process.
+fn terminate(entry: &PidEntry) -> bool {
+    #[cfg(unix)]
+    {
+        entry.is_running()
+            && libc::pid_t::try_from(entry.pid).is_ok_and(|raw| {
+                // SAFETY: `kill` has no memory-safety preconditions.
+                raw > 0 && unsafe { libc::kill(raw, libc::SIGTERM) } == 0
+            })
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = entry;
+        false
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn pids(path: &Path) -> Vec<u32> {
+        read_entries(path).iter().map(|e| e.pid).collect()
+    }
+
+    /// PID of a process that has already exited.
+    #[cfg(unix)]
+    fn exited_pid() -> u32 {
+        let mut exited = std::process::Command::new("true").spawn().unwrap();
+        exited.wait().unwrap();
+        exited.id()
+    }
+
+    #[test]
+    fn read_entries_missing_file_is_empty() {
+        let temp = TempDir::new().unwrap();
+        assert!(read_entries(&temp.path().join(PIDS_FILE)).is_empty());
+    }
+
+    #[test]
+    fn register_and_unregister_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(PIDS_FILE);
+        let owner = std::process::id();
+
+        register_pid(&path, 100, owner).unwrap();
+        register_pid(&path, 200, owner).unwrap();
+        register_pid(&path, 100, owner).unwrap();
+        assert_eq!(pids(&path), vec![200, 100]);
+        assert!(read_entries(&path).iter().all(|e| e.owner == owner));
+
+        unregister_pid(&path, 100).unwrap();
+        assert_eq!(pids(&path), vec![200]);
+
+        unregister_pid(&path, 200).unwrap();
+        assert!(!path.exists(), "empty PID file should be removed");
+    }
+
+    #[test]
+    fn read_entries_skips_garbage_and_legacy_lines() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(PIDS_FILE);
+        std::fs::write(&path, "42 1:sleep 7 2:mcgravity\nnot-a-pid\n\n7\n").unwrap();
+        assert_eq!(
+            read_entries(&path),
+            vec![PidEntry {
+                pid: 42,
+                identity: Some("1:sleep".to_string()),
+                owner: 7,
+                owner_identity: Some("2:mcgravity".to_string()),
+            }]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_orphaned_pids_detects_running_and_ignores_stale() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(PIDS_FILE);
+        let dead_owner = exited_pid();
+
+        let mut running = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+
+        register_pid(&path, exited_pid(), dead_owner).unwrap();
+        register_pid(&path, running.id(), dead_owner).unwrap();
+
+        let orphaned = find_orphaned_pids(&path);
+
+        let _ = running.kill();
+        let _ = running.wait();
+
+        assert_eq!(orphaned, vec![running.id()]);
+        assert_eq!(
+            pids(&path),
+            vec![running.id()],
+            "stale PID should be dropped from the file"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn children_of_a_running_instance_are_not_orphans() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(PIDS_FILE);
+
+        let mut running = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        register_pid(&path, running.id(), std::process::id()).unwrap();
+
+        let orphaned = find_orphaned_pids(&path);
+        let outcome = reap_orphaned_pids(&path);
+        let still_running = running.try_wait().unwrap().is_none();
+
+        let _ = running.kill();
+        let _ = running.wait();
+
+        assert!(orphaned.is_empty());
+        assert_eq!(outcome, ReapOutcome::default());
+        assert!(
+            still_running,
+            "another instance's child must not be signaled"
+        );
+        assert_eq!(pids(&path), vec![running.id()]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn recycled_pid_is_not_signaled() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(PIDS_FILE);
+
+        let mut running = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        // Simulate the PID having been reused by a different process
+        let entry = PidEntry {
+            identity: Some("1:other".to_string()),
+            ..PidEntry::new(running.id(), exited_pid())
+        };
+        std::fs::write(&path, format!("{}\n", entry.format())).unwrap();
+
+        let outcome = reap_orphaned_pids(&path);
+        let still_running = running.try_wait().unwrap().is_none();
+
+        let _ = running.kill();
+        let _ = running.wait();
+
+        assert_eq!(outcome, ReapOutcome::default());
+        assert!(still_running, "a recycled PID must not be signaled");
+        assert!(!path.exists(), "the stale entry should be dropped");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reap_orphaned_pids_terminates_orphans() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(PIDS_FILE);
+
+        let mut orphan = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        register_pid(&path, orphan.id(), exited_pid()).unwrap();
+
+        let outcome = reap_orphaned_pids(&path);
+        let status = orphan.wait().unwrap();
+
+        assert_eq!(outcome.reaped, vec![orphan.id()]);
+        assert!(outcome.failed.is_empty());
+        assert!(!status.success());
+        assert!(!path.exists());
+    }
+}
@@ -5,10 +5,16 @@
 
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::app::state::{EnterBehavior, MaxIterations, SettingsState, SummaryGeneration};
-use crate::core::Model;
+use crate::app::state::{
+    ArchiveMode, ColorTheme, DEFAULT_AUTOSAVE_DEBOUNCE_MS, DEFAULT_IDLE_NOTICE_SECS,
+    DEFAULT_MAX_TASK_BYTES, DEFAULT_SUMMARY_MAX_LEN, EnterBehavior, FlowMode, MAX_SUMMARY_MAX_LEN,
+    MIN_AUTOSAVE_DEBOUNCE_MS, MIN_MAX_TASK_BYTES, MIN_SUMMARY_MAX_LEN, MaxIterations,
+    SettingsState, SummaryGeneration, TodoNaming, Verbosity,
+};
+use crate::core::channels::EXECUTOR_OUTPUT_CHANNEL_SIZE;
+use crate::core::{Model, check_cli_path_override};
 
 /// Directory for mcgravity configuration files.
 pub const MCGRAVITY_DIR: &str = ".mcgravity";
@@ -33,17 +39,232 @@ pub struct PersistedSettings {
     /// The summary generation strategy ("Inline Only" or "Model Fallback").
     #[serde(default)]
     pub summary_generation: String,
+    /// Milliseconds of inactivity before task text is autosaved.
+    ///
+    /// Missing values use the default; values below the minimum are raised to it.
+    #[serde(default)]
+    pub autosave_debounce_ms: Option<u64>,
+    /// Byte budget for task.md before the oldest completed-task summaries are
+    /// trimmed.
+    ///
+    /// Missing values use the default; values below the minimum are raised to it.
+    #[serde(default)]
+    pub max_task_bytes: Option<usize>,
+    /// What happens to completed todo files ("Move to Done", "Delete", or
+    /// "Move To").
+    ///
+    /// Missing or unrecognized values use "Move to Done".
+    #[serde(default)]
+    pub archive_mode: Option<String>,
+    /// Directory completed todos are moved to when `archive_mode` is "Move To".
+    ///
+    /// Relative paths are resolved against the project directory.
+    #[serde(default)]
+    pub archive_dir: Option<PathBuf>,
+    /// Shell command run at the start of each cycle, before planning.
+    ///
+    /// A failing hook (non-zero exit) stops the flow. Missing or blank values
+    /// disable the hook.
+    #[serde(default)]
+    pub pre_cycle_hook: Option<String>,
+    /// Shell command run once when the flow completes successfully.
+    ///
+    /// It does not run when the flow is cancelled or fails. Missing or blank
+    /// values disable the hook.
+    #[serde(default)]
+    pub post_run_hook: Option<String>,
+    /// Whether to show a desktop notification when a flow completes or fails
+    /// (defaults to `false`; requires the `desktop-notifications` feature).
+    #[serde(default)]
+    pub desktop_notifications: Option<bool>,
+    /// Whether to ring the terminal bell when a flow completes or fails
+    /// (defaults to `false`).
+    #[serde(default)]
+    pub bell_on_complete: Option<bool>,
+    /// Whether starting a flow with unlimited iterations asks for confirmation
+    /// (defaults to `true`).
+    #[serde(default)]
+    pub confirm_unlimited: Option<bool>,
+    /// Whether output lines are prefixed with a timestamp (defaults to `false`).
+    #[serde(default)]
+    pub show_timestamps: Option<bool>,
+    /// Whether executor stderr lines are hidden from the output panel
+    /// (defaults to `false`). Hidden lines are still used for summaries.
+    #[serde(default)]
+    pub suppress_stderr: Option<bool>,
+    /// Substrings that keep a stderr line visible while `suppress_stderr` is on.
+    #[serde(default)]
+    pub stderr_keep_patterns: Option<Vec<String>>,
+    /// How much executor output is shown: "Quiet", "Normal" or "Verbose"
+    /// (defaults to "Normal").
+    #[serde(default)]
+    pub verbosity: Option<String>,
+    /// Seconds a model call may go without output before a "still working"
+    /// notice is shown (defaults to 30; 0 disables the notice).
+    #[serde(default)]
+    pub idle_notice_secs: Option<u64>,
+    /// Extra globs excluded from `@` file search, on top of `.gitignore`.
+    #[serde(default)]
+    pub ignore_globs: Option<Vec<String>>,
+    /// Whether `@` file search includes hidden files (defaults to `true`).
+    #[serde(default)]
+    pub search_hidden: Option<bool>,
+    /// How many directory levels `@` file search descends; 1 lists only
+    /// top-level entries.
+    ///
+    /// Missing or `0` means no limit.
+    #[serde(default)]
+    pub search_max_depth: Option<usize>,
+    /// Whether starting a new session clears the task text (defaults to `true`).
+    #[serde(default)]
+    pub reset_clears_task: Option<bool>,
+    /// Whether new output only follows the output panel while it is scrolled
+    /// to the bottom (defaults to `true`). When `false`, new output always
+    /// jumps to the bottom.
+    #[serde(default)]
+    pub sticky_scroll: Option<bool>,
+    /// Whether the first cycle of a flow migrates legacy files in
+    /// `.mcgravity/todo/done/` into `<COMPLETED_TASKS>` (defaults to `true`).
+    #[serde(default)]
+    pub migrate_done_files: Option<bool>,
+    /// Which phases a flow runs ("Full", "Plan Only", or "Execute Only").
+    ///
+    /// Missing or unrecognized values use "Full".
+    #[serde(default)]
+    pub flow_mode: Option<String>,
+    /// Color theme ("Dark", "Light", or "High Contrast").
+    ///
+    /// Missing or unrecognized values use "Dark".
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// Whether the flow pauses after each cycle until `/next` or `/stop`
+    /// (defaults to `false`).
+    #[serde(default)]
+    pub step_mode: Option<bool>,
+    /// Maximum characters of each `<COMPLETED_TASKS>` summary entry.
+    ///
+    /// Missing values use the default; others are clamped to the allowed range.
+    #[serde(default)]
+    pub summary_max_len: Option<usize>,
+    /// Whether consecutive identical output lines collapse into one line with
+    /// a `(×N)` count (defaults to `false`).
+    #[serde(default)]
+    pub collapse_repeated_lines: Option<bool>,
+    /// How the planner names todo files ("Sequential" or "Timestamp"), which
+    /// also decides the order they run in.
+    ///
+    /// Missing or unrecognized values use "Sequential".
+    #[serde(default)]
+    pub todo_naming: Option<String>,
+    /// Model used for task summaries instead of the execution model.
+    ///
+    /// Missing or unrecognized values use the execution model.
+    #[serde(default)]
+    pub summary_model: Option<String>,
+    /// Wall-clock seconds after which a flow stops, regardless of cycles.
+    ///
+    /// Missing or `0` means no limit.
+    #[serde(default)]
+    pub max_runtime_secs: Option<u64>,
+    /// Wall-clock seconds a single todo may take, across retries and its
+    /// summary, before it is skipped and left pending.
+    ///
+    /// Missing or `0` means no limit.
+    #[serde(default)]
+    pub per_todo_timeout_secs: Option<u64>,
+    /// Whether the output captured for each completed todo's summary is saved
+    /// to `.mcgravity/logs/<todo>.out`, for debugging poor summaries
+    /// (defaults to `false`).
+    #[serde(default)]
+    pub save_captured_output: Option<bool>,
+    /// Whether model output is styled as markdown in the output panel
+    /// (defaults to `false`, since it can alter literal output).
+    #[serde(default)]
+    pub render_markdown: Option<bool>,
+    /// Sibling directories searched by `@` file search and scanned for
+    /// guideline files, in addition to the project directory.
+    ///
+    /// Relative paths are resolved against the project directory.
+    #[serde(default)]
+    pub extra_roots: Option<Vec<PathBuf>>,
+    /// Binary run for the Codex model instead of `codex` from `PATH`.
+    #[serde(default)]
+    pub codex_path: Option<PathBuf>,
+    /// Binary run for the Claude model instead of `claude` from `PATH`.
+    #[serde(default)]
+    pub claude_path: Option<PathBuf>,
+    /// Binary run for the Gemini model instead of `gemini` from `PATH`.
+    #[serde(default)]
+    pub gemini_path: Option<PathBuf>,
+    /// Whether each cycle's CLI output is saved to `.mcgravity/logs`.
+    ///
+    /// Missing means off.
+    #[serde(default)]
+    pub log_cycles: Option<bool>,
+    /// Whether the first todo that fails after retries stops the flow.
+    ///
+    /// Missing means off: failed todos are logged and the next one runs.
+    #[serde(default)]
+    pub fail_fast: Option<bool>,
+    /// Whether the flow pauses for `/next` before executing todos flagged as
+    /// possible prompt injections.
+    ///
+    /// Missing means off: flagged todos are only warned about.
+    #[serde(default)]
+    pub confirm_suspicious_todos: Option<bool>,
+    /// Whether the planning prompt includes recent commits and the
+    /// uncommitted diff stat of the project's git repository.
+    ///
+    /// Missing means off. Outside a git repository the section is skipped.
+    #[serde(default)]
+    pub include_git_context: Option<bool>,
+    /// Whether the contents of files mentioned with `@` are appended to a
+    /// submitted task in an `<ATTACHED_FILES>` block.
+    ///
+    /// Missing means off: only the paths are submitted.
+    #[serde(default)]
+    pub attach_mentioned_files: Option<bool>,
+    /// Whether startup opens the settings panel when a configured model's
+    /// CLI is not installed (defaults to `true`).
+    #[serde(default)]
+    pub open_settings_on_missing_cli: Option<bool>,
+    /// Capacity of the executor output channel (defaults to 1000, at
+    /// least 1). Smaller values save memory but make chatty executors wait
+    /// for the output panel; larger values absorb bursts.
+    #[serde(default)]
+    pub output_buffer_size: Option<usize>,
 }
 
-/// Parses a model from its string name.
+/// Parses a persisted model name via [`Model::from_name`].
 ///
-/// Returns `Model::Codex` as the default for unrecognized values.
-fn parse_model(s: &str) -> Model {
-    match s {
-        "Claude Code" => Model::Claude,
-        "Gemini" => Model::Gemini,
-        _ => Model::Codex, // Default
-    }
+/// Returns the default model and a warning for unrecognized values.
+fn parse_model(field: &str, s: &str) -> (Model, Option<String>) {
+    Model::from_name(s).map_or_else(
+        || {
+            let fallback = Model::default();
+            let warning = format!(
+                "Unrecognized {field} \"{s}\" in settings; using {}",
+                fallback.name()
+            );
+            (fallback, Some(warning))
+        },
+        |model| (model, None),
+    )
+}
+
+/// Parses the optional summary model override via [`Model::from_name`].
+///
+/// Returns `None` (use the execution model) when the field is missing, plus a
+/// warning when the name is not recognized.
+fn parse_summary_model(s: Option<&str>) -> (Option<Model>, Option<String>) {
+    let Some(name) = s else {
+        return (None, None);
+    };
+    let model = Model::from_name(name);
+    let warning = model.is_none().then(|| {
+        format!("Unrecognized summary model \"{name}\" in settings; using the execution model")
+    });
+    (model, warning)
 }
 
 /// Parses enter behavior from its string name.
@@ -79,6 +300,104 @@ fn parse_summary_generation(s: &str) -> SummaryGeneration {
     }
 }
 
+/// Resolves the autosave debounce, clamping it to `MIN_AUTOSAVE_DEBOUNCE_MS`.
+///
+/// Returns `DEFAULT_AUTOSAVE_DEBOUNCE_MS` when no value is set.
+fn parse_autosave_debounce_ms(value: Option<u64>) -> u64 {
+    value.map_or(DEFAULT_AUTOSAVE_DEBOUNCE_MS, |ms| {
+        ms.max(MIN_AUTOSAVE_DEBOUNCE_MS)
+    })
+}
+
+/// Resolves the task.md byte budget, clamping it to `MIN_MAX_TASK_BYTES`.
+///
+/// Returns `DEFAULT_MAX_TASK_BYTES` when no value is set.
+fn parse_max_task_bytes(value: Option<usize>) -> usize {
+    value.map_or(DEFAULT_MAX_TASK_BYTES, |bytes| {
+        bytes.max(MIN_MAX_TASK_BYTES)
+    })
+}
+
+/// Resolves the summary entry cap, clamping it to
+/// `MIN_SUMMARY_MAX_LEN..=MAX_SUMMARY_MAX_LEN`.
+///
+/// Returns `DEFAULT_SUMMARY_MAX_LEN` when no value is set.
+fn parse_summary_max_len(value: Option<usize>) -> usize {
+    value.map_or(DEFAULT_SUMMARY_MAX_LEN, |len| {
+        len.clamp(MIN_SUMMARY_MAX_LEN, MAX_SUMMARY_MAX_LEN)
+    })
+}
+
+/// Parses the flow mode from its name.
+///
+/// Returns `FlowMode::Full` as the default for missing or unrecognized values.
+fn parse_flow_mode(s: Option<&str>) -> FlowMode {
+    match s {
+        Some("Plan Only") => FlowMode::PlanOnly,
+        Some("Execute Only") => FlowMode::ExecuteOnly,
+        _ => FlowMode::Full, // Default
+    }
+}
+
+/// Parses the color theme from its name.
+///
+/// Returns `ColorTheme::Dark` as the default for missing or unrecognized values.
+fn parse_color_theme(s: Option<&str>) -> ColorTheme {
+    match s {
+        Some("Light") => ColorTheme::Light,
+        Some("High Contrast") => ColorTheme::HighContrast,
+        _ => ColorTheme::Dark, // Default
+    }
+}
+
+/// Parses the todo naming scheme from its name.
+///
+/// Returns `TodoNaming::Sequential` as the default for missing or
+/// unrecognized values.
+fn parse_todo_naming(s: Option<&str>) -> TodoNaming {
+    match s {
+        Some("Timestamp") => TodoNaming::Timestamp,
+        _ => TodoNaming::Sequential, // Default
+    }
+}
+
+/// Parses verbosity from its settings.json name.
+///
+/// Returns `Verbosity::Normal` as the default for missing or unrecognized values.
+fn parse_verbosity(s: Option<&str>) -> Verbosity {
+    match s {
+        Some("Quiet") => Verbosity::Quiet,
+        Some("Verbose") => Verbosity::Verbose,
+        _ => Verbosity::Normal, // Default
+    }
+}
+
+/// Resolves the archive mode from its name and optional custom directory.
+///
+/// Returns the default mode and a warning when "Move To" has no directory.
+fn parse_archive_mode(mode: Option<&str>, dir: Option<&Path>) -> (ArchiveMode, Option<String>) {
+    match (mode, dir) {
+        (Some("Delete"), _) => (ArchiveMode::Delete, None),
+        (Some("Move To"), Some(dir)) => (ArchiveMode::MoveTo(dir.to_path_buf()), None),
+        (Some("Move To"), None) => (
+            ArchiveMode::MoveToDone,
+            Some(
+                "archive_mode \"Move To\" requires archive_dir in settings; using Move to Done"
+                    .to_string(),
+            ),
+        ),
+        _ => (ArchiveMode::MoveToDone, None), // Default
+    }
+}
+
+/// Parses a persisted hook command, treating blank commands as no hook.
+fn parse_hook(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|command| !command.is_empty())
+        .map(str::to_string)
+}
+
 impl From<&SettingsState> for PersistedSettings {
     fn from(state: &SettingsState) -> Self {
         Self {
@@ -87,22 +406,152 @@ impl From<&SettingsState> for PersistedSettings {
             enter_behavior: state.enter_behavior.name().to_string(),
             max_iterations: state.max_iterations.name().to_string(),
             summary_generation: state.summary_generation.name().to_string(),
+            autosave_debounce_ms: Some(state.autosave_debounce_ms),
+            max_task_bytes: Some(state.max_task_bytes),
+            archive_mode: Some(state.archive_mode.name().to_string()),
+            archive_dir: match &state.archive_mode {
+                ArchiveMode::MoveTo(dir) => Some(dir.clone()),
+                ArchiveMode::MoveToDone | ArchiveMode::Delete => state.archive_dir.clone(),
+            },
+            pre_cycle_hook: state.pre_cycle_hook.clone(),
+            post_run_hook: state.post_run_hook.clone(),
+            desktop_notifications: Some(state.desktop_notifications),
+            bell_on_complete: Some(state.bell_on_complete),
+            confirm_unlimited: Some(state.confirm_unlimited),
+            show_timestamps: Some(state.show_timestamps),
+            suppress_stderr: Some(state.suppress_stderr),
+            stderr_keep_patterns: Some(state.stderr_keep_patterns.clone()),
+            verbosity: Some(state.verbosity.name().to_string()),
+            idle_notice_secs: Some(state.idle_notice_secs),
+            ignore_globs: Some(state.ignore_globs.clone()),
+            search_hidden: Some(state.search_hidden),
+            search_max_depth: state.search_max_depth,
+            reset_clears_task: Some(state.reset_clears_task),
+            sticky_scroll: Some(state.sticky_scroll),
+            migrate_done_files: Some(state.migrate_done_files),
+            flow_mode: Some(state.flow_mode.name().to_string()),
+            theme: Some(state.color_theme.name().to_string()),
+            step_mode: Some(state.step_mode),
+            summary_max_len: Some(state.summary_max_len),
+            collapse_repeated_lines: Some(state.collapse_repeated_lines),
+            todo_naming: Some(state.todo_naming.name().to_string()),
+            summary_model: state.summary_model.map(|model| model.name().to_string()),
+            max_runtime_secs: state.max_runtime_secs,
+            per_todo_timeout_secs: state.per_todo_timeout_secs,
+            save_captured_output: Some(state.save_captured_output),
+            render_markdown: Some(state.render_markdown),
+            extra_roots: Some(state.extra_roots.clone()),
+            codex_path: state.codex_path.clone(),
+            claude_path: state.claude_path.clone(),
+            gemini_path: state.gemini_path.clone(),
+            log_cycles: Some(state.log_cycles),
+            fail_fast: Some(state.fail_fast),
+            confirm_suspicious_todos: Some(state.confirm_suspicious_todos),
+            include_git_context: Some(state.include_git_context),
+            attach_mentioned_files: Some(state.attach_mentioned_files),
+            open_settings_on_missing_cli: Some(state.open_settings_on_missing_cli),
+            output_buffer_size: Some(state.output_buffer_size),
         }
     }
 }
 
 impl PersistedSettings {
+    /// Returns the persisted form of the default settings.
+    ///
+    /// Unlike [`Default`], which leaves the enum fields empty, every field
+    /// names its default value, so applying the result yields no warnings.
+    #[must_use]
+    pub fn defaults() -> Self {
+        Self {
+            planning_model: Model::default().name().to_string(),
+            execution_model: Model::default().name().to_string(),
+            enter_behavior: EnterBehavior::default().name().to_string(),
+            max_iterations: MaxIterations::default().name().to_string(),
+            summary_generation: SummaryGeneration::default().name().to_string(),
+            ..Self::default()
+        }
+    }
+
     /// Applies these persisted settings to a mutable `SettingsState`.
     ///
     /// This updates the planning model, execution model, enter behavior,
     /// and max iterations fields based on the persisted string values.
     /// Invalid or unrecognized values are replaced with sensible defaults.
-    pub fn apply_to(&self, state: &mut SettingsState) {
-        state.planning_model = parse_model(&self.planning_model);
-        state.execution_model = parse_model(&self.execution_model);
+    ///
+    /// Returns a warning for each model name that could not be recognized
+    /// and for an incomplete archive configuration, so callers can surface
+    /// them to the user.
+    pub fn apply_to(&self, state: &mut SettingsState) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let (planning_model, warning) = parse_model("planning model", &self.planning_model);
+        state.planning_model = planning_model;
+        warnings.extend(warning);
+        let (execution_model, warning) = parse_model("execution model", &self.execution_model);
+        state.execution_model = execution_model;
+        warnings.extend(warning);
         state.enter_behavior = parse_enter_behavior(&self.enter_behavior);
         state.max_iterations = parse_max_iterations(&self.max_iterations);
         state.summary_generation = parse_summary_generation(&self.summary_generation);
+        state.autosave_debounce_ms = parse_autosave_debounce_ms(self.autosave_debounce_ms);
+        state.max_task_bytes = parse_max_task_bytes(self.max_task_bytes);
+        let (archive_mode, warning) =
+            parse_archive_mode(self.archive_mode.as_deref(), self.archive_dir.as_deref());
+        state.archive_mode = archive_mode;
+        state.archive_dir.clone_from(&self.archive_dir);
+        warnings.extend(warning);
+        state.pre_cycle_hook = parse_hook(self.pre_cycle_hook.as_deref());
+        state.post_run_hook = parse_hook(self.post_run_hook.as_deref());
+        state.desktop_notifications = self.desktop_notifications.unwrap_or(false);
+        state.bell_on_complete = self.bell_on_complete.unwrap_or(false);
+        state.confirm_unlimited = self.confirm_unlimited.unwrap_or(true);
+        state.show_timestamps = self.show_timestamps.unwrap_or(false);
+        state.suppress_stderr = self.suppress_stderr.unwrap_or(false);
+        state.stderr_keep_patterns = self.stderr_keep_patterns.clone().unwrap_or_default();
+        state.verbosity = parse_verbosity(self.verbosity.as_deref());
+        state.idle_notice_secs = self.idle_notice_secs.unwrap_or(DEFAULT_IDLE_NOTICE_SECS);
+        state.ignore_globs = self.ignore_globs.clone().unwrap_or_default();
+        state.search_hidden = self.search_hidden.unwrap_or(true);
+        state.search_max_depth = self.search_max_depth.filter(|&depth| depth > 0);
+        state.reset_clears_task = self.reset_clears_task.unwrap_or(true);
+        state.sticky_scroll = self.sticky_scroll.unwrap_or(true);
+        state.migrate_done_files = self.migrate_done_files.unwrap_or(true);
+        state.flow_mode = parse_flow_mode(self.flow_mode.as_deref());
+        state.color_theme = parse_color_theme(self.theme.as_deref());
+        state.step_mode = self.step_mode.unwrap_or(false);
+        state.summary_max_len = parse_summary_max_len(self.summary_max_len);
+        state.collapse_repeated_lines = self.collapse_repeated_lines.unwrap_or(false);
+        state.todo_naming = parse_todo_naming(self.todo_naming.as_deref());
+        let (summary_model, warning) = parse_summary_model(self.summary_model.as_deref());
+        state.summary_model = summary_model;
+        warnings.extend(warning);
+        state.max_runtime_secs = self.max_runtime_secs.filter(|&secs| secs > 0);
+        state.per_todo_timeout_secs = self.per_todo_timeout_secs.filter(|&secs| secs > 0);
+        state.save_captured_output = self.save_captured_output.unwrap_or(false);
+        state.render_markdown = self.render_markdown.unwrap_or(false);
+        state.extra_roots = self.extra_roots.clone().unwrap_or_default();
+        for (setting, path, field) in [
+            ("codex_path", &self.codex_path, &mut state.codex_path),
+            ("claude_path", &self.claude_path, &mut state.claude_path),
+            ("gemini_path", &self.gemini_path, &mut state.gemini_path),
+        ] {
+            // Unusable paths are kept so they survive a save, but warned about
+            warnings.extend(
+                path.as_deref()
+                    .and_then(|path| check_cli_path_override(setting, path)),
+            );
+            field.clone_from(path);
+        }
+        state.log_cycles = self.log_cycles.unwrap_or(false);
+        state.fail_fast = self.fail_fast.unwrap_or(false);
+        state.confirm_suspicious_todos = self.confirm_suspicious_todos.unwrap_or(false);
+        state.include_git_context = self.include_git_context.unwrap_or(false);
+        state.attach_mentioned_files = self.attach_mentioned_files.unwrap_or(false);
+        state.open_settings_on_missing_cli = self.open_settings_on_missing_cli.unwrap_or(true);
+        state.output_buffer_size = self
+            .output_buffer_size
+            .unwrap_or(EXECUTOR_OUTPUT_CHANNEL_SIZE)
+            .max(1);
+        warnings
     }
 }
 
@@ -153,10 +602,18 @@ pub fn load_settings(path: &Path) -> Result<PersistedSettings> {
     serde_json::from_str(&content).context("Failed to parse settings file")
 }
 
+/// Returns true if a [`load_settings`] error means the file is not valid
+/// settings JSON, as opposed to being unreadable.
+#[must_use]
+pub fn is_parse_error(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<serde_json::Error>().is_some()
+}
+
 /// Saves settings to the specified settings file path.
 ///
 /// The parent directory must exist (caller should ensure this).
-/// Serializes settings to pretty-printed JSON.
+/// Serializes settings to pretty-printed JSON and replaces the file
+/// atomically, so a crash mid-write cannot corrupt it.
 ///
 /// # Arguments
 ///
@@ -169,7 +626,7 @@ pub fn load_settings(path: &Path) -> Result<PersistedSettings> {
 pub fn save_settings(path: &Path, settings: &PersistedSettings) -> Result<()> {
     let json = serde_json::to_string_pretty(settings).context("Failed to serialize settings")?;
 
-    std::fs::write(path, json).context("Failed to write settings file")
+    super::atomic::write_atomic(path, json).context("Failed to write settings file")
 }
 
 #[cfg(test)]
@@ -200,6 +657,48 @@ mod tests {
             enter_behavior: "Submit".to_string(),
             max_iterations: "5".to_string(),
             summary_generation: String::new(),
+            autosave_debounce_ms: None,
+            max_task_bytes: None,
+            archive_mode: None,
+            archive_dir: None,
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: None,
+            bell_on_complete: None,
+            confirm_unlimited: None,
+            show_timestamps: None,
+            suppress_stderr: None,
+            stderr_keep_patterns: None,
+            idle_notice_secs: None,
+            ignore_globs: None,
+            search_hidden: None,
+            search_max_depth: None,
+            reset_clears_task: None,
+            sticky_scroll: None,
+            migrate_done_files: None,
+            flow_mode: None,
+            step_mode: None,
+            summary_max_len: None,
+            collapse_repeated_lines: None,
+            todo_naming: None,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: None,
+            render_markdown: None,
+            extra_roots: None,
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: None,
+            fail_fast: None,
+            confirm_suspicious_todos: None,
+            include_git_context: None,
+            attach_mentioned_files: None,
+            verbosity: None,
+            output_buffer_size: None,
+            open_settings_on_missing_cli: None,
+            theme: None,
         };
 
         let json = serde_json::to_string_pretty(&settings)?;
@@ -218,6 +717,48 @@ mod tests {
             enter_behavior: "Newline".to_string(),
             max_iterations: "10".to_string(),
             summary_generation: String::new(),
+            autosave_debounce_ms: None,
+            max_task_bytes: None,
+            archive_mode: None,
+            archive_dir: None,
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: None,
+            bell_on_complete: None,
+            confirm_unlimited: None,
+            show_timestamps: None,
+            suppress_stderr: None,
+            stderr_keep_patterns: None,
+            idle_notice_secs: None,
+            ignore_globs: None,
+            search_hidden: None,
+            search_max_depth: None,
+            reset_clears_task: None,
+            sticky_scroll: None,
+            migrate_done_files: None,
+            flow_mode: None,
+            step_mode: None,
+            summary_max_len: None,
+            collapse_repeated_lines: None,
+            todo_naming: None,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: None,
+            render_markdown: None,
+            extra_roots: None,
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: None,
+            fail_fast: None,
+            confirm_suspicious_todos: None,
+            include_git_context: None,
+            attach_mentioned_files: None,
+            verbosity: None,
+            output_buffer_size: None,
+            open_settings_on_missing_cli: None,
+            theme: None,
         };
 
         let json = serde_json::to_string_pretty(&settings)?;
@@ -257,6 +798,48 @@ mod tests {
             enter_behavior: "Submit".to_string(),
             max_iterations: "Unlimited".to_string(),
             summary_generation: String::new(),
+            autosave_debounce_ms: None,
+            max_task_bytes: None,
+            archive_mode: None,
+            archive_dir: None,
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: None,
+            bell_on_complete: None,
+            confirm_unlimited: None,
+            show_timestamps: None,
+            suppress_stderr: None,
+            stderr_keep_patterns: None,
+            idle_notice_secs: None,
+            ignore_globs: None,
+            search_hidden: None,
+            search_max_depth: None,
+            reset_clears_task: None,
+            sticky_scroll: None,
+            migrate_done_files: None,
+            flow_mode: None,
+            step_mode: None,
+            summary_max_len: None,
+            collapse_repeated_lines: None,
+            todo_naming: None,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: None,
+            render_markdown: None,
+            extra_roots: None,
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: None,
+            fail_fast: None,
+            confirm_suspicious_todos: None,
+            include_git_context: None,
+            attach_mentioned_files: None,
+            verbosity: None,
+            output_buffer_size: None,
+            open_settings_on_missing_cli: None,
+            theme: None,
         };
 
         paths.save_settings(&settings)?;
@@ -405,17 +988,39 @@ mod tests {
     /// Tests `parse_model` for all valid values.
     #[test]
     fn parse_model_valid_values() {
-        assert_eq!(super::parse_model("Codex"), Model::Codex);
-        assert_eq!(super::parse_model("Claude Code"), Model::Claude);
-        assert_eq!(super::parse_model("Gemini"), Model::Gemini);
+        assert_eq!(super::parse_model("m", "Codex"), (Model::Codex, None));
+        assert_eq!(
+            super::parse_model("m", "Claude Code"),
+            (Model::Claude, None)
+        );
+        assert_eq!(super::parse_model("m", "Gemini"), (Model::Gemini, None));
+        assert_eq!(super::parse_model("m", "claude"), (Model::Claude, None));
     }
 
     /// Tests `parse_model` returns default for invalid values.
     #[test]
     fn parse_model_invalid_returns_default() {
-        assert_eq!(super::parse_model(""), Model::Codex);
-        assert_eq!(super::parse_model("Unknown"), Model::Codex);
-        assert_eq!(super::parse_model("claude"), Model::Codex); // Case sensitive
+        for input in ["", "Unknown", "gpt-4"] {
+            let (model, warning) = super::parse_model("planning model", input);
+            assert_eq!(model, Model::Codex);
+            assert!(warning.is_some_and(|w| {
+                w.contains("planning model") && w.contains(&format!("\"{input}\""))
+            }));
+        }
+    }
+
+    /// Tests `parse_summary_model` keeps a missing override unset and warns on
+    /// unknown names.
+    #[test]
+    fn parse_summary_model_values() {
+        assert_eq!(super::parse_summary_model(None), (None, None));
+        assert_eq!(
+            super::parse_summary_model(Some("Gemini")),
+            (Some(Model::Gemini), None)
+        );
+        let (model, warning) = super::parse_summary_model(Some("gpt-4"));
+        assert_eq!(model, None);
+        assert!(warning.is_some_and(|w| w.contains("summary model") && w.contains("\"gpt-4\"")));
     }
 
     /// Tests `parse_enter_behavior` for all valid values.
@@ -464,6 +1069,223 @@ mod tests {
         ); // Case sensitive
     }
 
+    /// Tests `parse_autosave_debounce_ms` defaults and clamping.
+    #[test]
+    fn parse_autosave_debounce_ms_defaults_and_clamps() {
+        assert_eq!(
+            super::parse_autosave_debounce_ms(None),
+            DEFAULT_AUTOSAVE_DEBOUNCE_MS
+        );
+        assert_eq!(super::parse_autosave_debounce_ms(Some(5000)), 5000);
+        assert_eq!(
+            super::parse_autosave_debounce_ms(Some(0)),
+            MIN_AUTOSAVE_DEBOUNCE_MS
+        );
+    }
+
+    /// Tests that each archive mode round-trips through persisted settings.
+    #[test]
+    fn archive_mode_round_trips() {
+        for mode in [
+            ArchiveMode::MoveToDone,
+            ArchiveMode::Delete,
+            ArchiveMode::MoveTo(PathBuf::from("../archive")),
+        ] {
+            let state = SettingsState {
+                archive_mode: mode.clone(),
+                ..SettingsState::default()
+            };
+            let persisted = PersistedSettings::from(&state);
+            let mut restored = SettingsState::default();
+            assert!(persisted.apply_to(&mut restored).is_empty());
+            assert_eq!(restored.archive_mode, mode);
+        }
+    }
+
+    /// Tests that the custom archive directory survives cycling away from
+    /// "Move To" and saving, so cycling can return to it.
+    #[test]
+    fn archive_dir_is_kept_while_another_mode_is_selected() {
+        let dir = PathBuf::from("../archive");
+        let persisted = PersistedSettings {
+            archive_mode: Some("Move To".to_string()),
+            archive_dir: Some(dir.clone()),
+            ..PersistedSettings::from(&SettingsState::default())
+        };
+        let mut state = SettingsState::default();
+        assert!(persisted.apply_to(&mut state).is_empty());
+
+        state.archive_mode = state.archive_mode.next(state.archive_dir.as_deref());
+        assert_eq!(state.archive_mode, ArchiveMode::MoveToDone);
+        let mut restored = SettingsState::default();
+        assert!(
+            PersistedSettings::from(&state)
+                .apply_to(&mut restored)
+                .is_empty()
+        );
+        assert_eq!(restored.archive_dir, Some(dir.clone()));
+
+        let mode = restored.archive_mode.next(restored.archive_dir.as_deref());
+        assert_eq!(mode, ArchiveMode::Delete);
+        assert_eq!(
+            mode.next(restored.archive_dir.as_deref()),
+            ArchiveMode::MoveTo(dir)
+        );
+        assert_eq!(ArchiveMode::Delete.next(None), ArchiveMode::MoveToDone);
+    }
+
+    /// Tests that hook commands are trimmed and blank hooks are disabled.
+    #[test]
+    fn parse_hook_trims_and_ignores_blank() {
+        assert_eq!(super::parse_hook(None), None);
+        assert_eq!(super::parse_hook(Some("   ")), None);
+        assert_eq!(
+            super::parse_hook(Some("  git pull  ")),
+            Some("git pull".to_string())
+        );
+    }
+
+    /// Tests archive mode defaults and the missing-directory warning.
+    #[test]
+    fn parse_archive_mode_defaults_and_warns() {
+        assert_eq!(
+            super::parse_archive_mode(None, None),
+            (ArchiveMode::MoveToDone, None)
+        );
+        assert_eq!(
+            super::parse_archive_mode(Some("Shred"), None),
+            (ArchiveMode::MoveToDone, None)
+        );
+        let (mode, warning) = super::parse_archive_mode(Some("Move To"), None);
+        assert_eq!(mode, ArchiveMode::MoveToDone);
+        assert!(warning.is_some_and(|w| w.contains("archive_dir")));
+    }
+
+    /// Tests `parse_max_task_bytes` defaulting and clamping.
+    #[test]
+    fn parse_max_task_bytes_defaults_and_clamps() {
+        assert_eq!(super::parse_max_task_bytes(None), DEFAULT_MAX_TASK_BYTES);
+        assert_eq!(super::parse_max_task_bytes(Some(100_000)), 100_000);
+        assert_eq!(super::parse_max_task_bytes(Some(10)), MIN_MAX_TASK_BYTES);
+    }
+
+    /// Tests `parse_summary_max_len` defaulting and clamping to its range.
+    #[test]
+    fn parse_summary_max_len_defaults_and_clamps() {
+        assert_eq!(super::parse_summary_max_len(None), DEFAULT_SUMMARY_MAX_LEN);
+        assert_eq!(super::parse_summary_max_len(Some(200)), 200);
+        assert_eq!(super::parse_summary_max_len(Some(0)), MIN_SUMMARY_MAX_LEN);
+        assert_eq!(
+            super::parse_summary_max_len(Some(usize::MAX)),
+            MAX_SUMMARY_MAX_LEN
+        );
+    }
+
+    /// Tests that settings files without `autosave_debounce_ms` still load.
+    #[test]
+    fn missing_autosave_debounce_uses_default() -> Result<()> {
+        let json = r#"{
+            "planning_model": "Codex",
+            "execution_model": "Codex",
+            "enter_behavior": "Submit",
+            "max_iterations": "5"
+        }"#;
+        let persisted: PersistedSettings = serde_json::from_str(json)?;
+        assert_eq!(persisted.autosave_debounce_ms, None);
+
+        let mut state = SettingsState::default();
+        persisted.apply_to(&mut state);
+        assert_eq!(state.autosave_debounce_ms, DEFAULT_AUTOSAVE_DEBOUNCE_MS);
+        Ok(())
+    }
+
+    /// Tests that a `max_runtime_secs` of 0 means no limit.
+    #[test]
+    fn zero_max_runtime_means_no_limit() {
+        let mut state = SettingsState::default();
+        for (secs, expected) in [(Some(1800), Some(1800)), (Some(0), None), (None, None)] {
+            let persisted = PersistedSettings {
+                max_runtime_secs: secs,
+                ..PersistedSettings::defaults()
+            };
+            persisted.apply_to(&mut state);
+            assert_eq!(state.max_runtime_secs, expected, "{secs:?}");
+        }
+    }
+
+    /// Tests that a binary path override that does not exist is kept but
+    /// warned about.
+    #[test]
+    fn missing_cli_path_override_warns() {
+        let mut state = SettingsState::default();
+        let persisted = PersistedSettings {
+            codex_path: Some(PathBuf::from("/nonexistent/bin/codex")),
+            ..PersistedSettings::defaults()
+        };
+        let warnings = persisted.apply_to(&mut state);
+        assert_eq!(
+            warnings,
+            vec![
+                "codex_path /nonexistent/bin/codex does not exist; falling back to PATH lookup"
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            state.codex_path.as_deref(),
+            Some(Path::new("/nonexistent/bin/codex"))
+        );
+        assert_eq!(
+            PersistedSettings::from(&state).codex_path,
+            persisted.codex_path
+        );
+    }
+
+    /// Tests that a `per_todo_timeout_secs` of 0 means no limit.
+    #[test]
+    fn zero_per_todo_timeout_means_no_limit() {
+        let mut state = SettingsState::default();
+        for (secs, expected) in [(Some(600), Some(600)), (Some(0), None), (None, None)] {
+            let persisted = PersistedSettings {
+                per_todo_timeout_secs: secs,
+                ..PersistedSettings::defaults()
+            };
+            persisted.apply_to(&mut state);
+            assert_eq!(state.per_todo_timeout_secs, expected, "{secs:?}");
+        }
+    }
+
+    /// Tests that a `search_max_depth` of 0 means no limit.
+    #[test]
+    fn zero_search_max_depth_means_no_limit() {
+        let mut state = SettingsState::default();
+        for (depth, expected) in [(Some(2), Some(2)), (Some(0), None), (None, None)] {
+            let persisted = PersistedSettings {
+                search_max_depth: depth,
+                ..PersistedSettings::defaults()
+            };
+            persisted.apply_to(&mut state);
+            assert_eq!(state.search_max_depth, expected, "{depth:?}");
+        }
+    }
+
+    /// Tests that the output buffer defaults to 1000 and is at least 1.
+    #[test]
+    fn output_buffer_size_defaults_and_is_positive() {
+        let mut state = SettingsState::default();
+        for (size, expected) in [
+            (None, EXECUTOR_OUTPUT_CHANNEL_SIZE),
+            (Some(64), 64),
+            (Some(0), 1),
+        ] {
+            let persisted = PersistedSettings {
+                output_buffer_size: size,
+                ..PersistedSettings::defaults()
+            };
+            persisted.apply_to(&mut state);
+            assert_eq!(state.output_buffer_size, expected, "{size:?}");
+        }
+    }
+
     /// Tests `apply_to` with valid values.
     #[test]
     fn apply_to_valid_values() {
@@ -473,11 +1295,54 @@ mod tests {
             enter_behavior: "Newline".to_string(),
             max_iterations: "10".to_string(),
             summary_generation: String::new(),
+            autosave_debounce_ms: None,
+            max_task_bytes: None,
+            archive_mode: None,
+            archive_dir: None,
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: None,
+            bell_on_complete: None,
+            confirm_unlimited: None,
+            show_timestamps: None,
+            suppress_stderr: None,
+            stderr_keep_patterns: None,
+            idle_notice_secs: None,
+            ignore_globs: None,
+            search_hidden: None,
+            search_max_depth: None,
+            reset_clears_task: None,
+            sticky_scroll: None,
+            migrate_done_files: None,
+            flow_mode: None,
+            step_mode: None,
+            summary_max_len: None,
+            collapse_repeated_lines: None,
+            todo_naming: None,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: None,
+            render_markdown: None,
+            extra_roots: None,
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: None,
+            fail_fast: None,
+            confirm_suspicious_todos: None,
+            include_git_context: None,
+            attach_mentioned_files: None,
+            verbosity: None,
+            output_buffer_size: None,
+            open_settings_on_missing_cli: None,
+            theme: None,
         };
 
         let mut state = SettingsState::default();
-        persisted.apply_to(&mut state);
+        let warnings = persisted.apply_to(&mut state);
 
+        assert!(warnings.is_empty());
         assert_eq!(state.planning_model, Model::Claude);
         assert_eq!(state.execution_model, Model::Gemini);
         assert_eq!(state.enter_behavior, EnterBehavior::Newline);
@@ -493,6 +1358,48 @@ mod tests {
             enter_behavior: "invalid".to_string(),
             max_iterations: "99".to_string(),
             summary_generation: String::new(),
+            autosave_debounce_ms: None,
+            max_task_bytes: None,
+            archive_mode: None,
+            archive_dir: None,
+            pre_cycle_hook: None,
+            post_run_hook: None,
+            desktop_notifications: None,
+            bell_on_complete: None,
+            confirm_unlimited: None,
+            show_timestamps: None,
+            suppress_stderr: None,
+            stderr_keep_patterns: None,
+            idle_notice_secs: None,
+            ignore_globs: None,
+            search_hidden: None,
+            search_max_depth: None,
+            reset_clears_task: None,
+            sticky_scroll: None,
+            migrate_done_files: None,
+            flow_mode: None,
+            step_mode: None,
+            summary_max_len: None,
+            collapse_repeated_lines: None,
+            todo_naming: None,
+            summary_model: None,
+            max_runtime_secs: None,
+            per_todo_timeout_secs: None,
+            save_captured_output: None,
+            render_markdown: None,
+            extra_roots: None,
+            codex_path: None,
+            claude_path: None,
+            gemini_path: None,
+            log_cycles: None,
+            fail_fast: None,
+            confirm_suspicious_todos: None,
+            include_git_context: None,
+            attach_mentioned_files: None,
+            verbosity: None,
+            output_buffer_size: None,
+            open_settings_on_missing_cli: None,
+            theme: None,
         };
 
         let mut state = SettingsState {
@@ -503,13 +1410,15 @@ mod tests {
             ..Default::default()
         };
 
-        persisted.apply_to(&mut state);
+        let warnings = persisted.apply_to(&mut state);
 
         // All should be reset to defaults due to invalid input
         assert_eq!(state.planning_model, Model::Codex);
         assert_eq!(state.execution_model, Model::Codex);
         assert_eq!(state.enter_behavior, EnterBehavior::Submit);
         assert_eq!(state.max_iterations, MaxIterations::Five);
+        // Both unrecognized model names are reported
+        assert_eq!(warnings.len(), 2);
     }
 
     /// Tests roundtrip: `SettingsState` -> `PersistedSettings` -> `apply_to` -> same values.
@@ -590,6 +1499,23 @@ mod tests {
         }
     }
 
+    /// Tests roundtrip for the new-session task behavior.
+    #[test]
+    fn roundtrip_reset_clears_task() {
+        for reset_clears_task in [true, false] {
+            let original = SettingsState {
+                reset_clears_task,
+                ..Default::default()
+            };
+
+            let persisted = PersistedSettings::from(&original);
+            let mut restored = SettingsState::default();
+            persisted.apply_to(&mut restored);
+
+            assert_eq!(restored.reset_clears_task, reset_clears_task);
+        }
+    }
+
     /// Tests roundtrip for all max iterations variants.
     #[test]
     fn roundtrip_all_max_iterations() {
@@ -0,0 +1,309 @@
+//! Snapshots of the flow state for rolling back a risky run.
+//!
+//! `/snapshot` copies `task.md` and the todo directory (including `todo/done`)
+//! into `.mcgravity/snapshots/<name>/`, where the name is a timestamp.
+//! `/restore <name> confirm` replaces the live files with the snapshot.
+
+use std::path::{Path, PathBuf};
+
+use tempfile::TempDir;
+
+use super::McgravityPaths;
+
+/// Name of the snapshots directory inside the `.mcgravity` directory.
+pub const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Format of snapshot names, sortable and safe in file names.
+const SNAPSHOT_NAME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Name of the task file inside the `.mcgravity` directory and a snapshot.
+const TASK_FILE_NAME: &str = "task.md";
+
+/// Name of the todo directory inside the `.mcgravity` directory and a snapshot.
+const TODO_DIR_NAME: &str = "todo";
+
+/// Copies `task.md` and the todo directory into a new snapshot named after
+/// the current local time, and returns the snapshot name.
+///
+/// A `-2`, `-3`, ... suffix keeps snapshots taken within the same second apart.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot directory or a copy cannot be written.
+pub fn create_snapshot(paths: &McgravityPaths) -> std::io::Result<String> {
+    let timestamp = chrono::Local::now()
+        .format(SNAPSHOT_NAME_FORMAT)
+        .to_string();
+    let snapshots_dir = paths.snapshots_dir();
+    let mut name = timestamp.clone();
+    let mut suffix = 2;
+    while snapshots_dir.join(&name).exists() {
+        name = format!("{timestamp}-{suffix}");
+        suffix += 1;
+    }
+    create_snapshot_named(paths, &name)?;
+    Ok(name)
+}
+
+/// Copies `task.md` and the todo directory into the snapshot `name`.
+fn create_snapshot_named(paths: &McgravityPaths, name: &str) -> std::io::Result<()> {
+    let dest = paths.snapshots_dir().join(name);
+    std::fs::create_dir_all(&dest)?;
+    let task_file = paths.task_file();
+    if task_file.is_file() {
+        std::fs::copy(&task_file, dest.join(TASK_FILE_NAME))?;
+    }
+    let todo_dir = paths.todo_dir();
+    if todo_dir.is_dir() {
+        copy_dir_recursive(&todo_dir, &dest.join(TODO_DIR_NAME))?;
+    }
+    Ok(())
+}
+
+/// Replaces the live `task.md` and todo directory with the snapshot `name`.
+///
+/// Files missing from the snapshot are removed from the live state, so the
+/// result matches the moment the snapshot was taken. The todo directory is
+/// copied into a sibling staging directory first and renamed into place, and
+/// `task.md` is only written after that, so a failed copy or rename leaves
+/// the live files untouched.
+///
+/// # Errors
+///
+/// Returns an error if the name is not an existing snapshot or the files
+/// cannot be replaced.
+pub fn restore_snapshot(paths: &McgravityPaths, name: &str) -> std::io::Result<()> {
+    let source = snapshot_dir(paths, name)?;
+
+    let todo_dir = paths.todo_dir();
+    let snapshot_todo = source.join(TODO_DIR_NAME);
+    let staged = if snapshot_todo.is_dir() {
+        let staged = staging_dir(&todo_dir)?;
+        copy_dir_recursive(&snapshot_todo, staged.path())?;
+        Some(staged)
+    } else {
+        None
+    };
+
+    let snapshot_task = source.join(TASK_FILE_NAME);
+    let task = if snapshot_task.is_file() {
+        Some(std::fs::read(&snapshot_task)?)
+    } else {
+        None
+    };
+
+    // task.md is only touched once the todo directory is in place
+    replace_dir(&todo_dir, staged)?;
+    let task_file = paths.task_file();
+    match task {
+        Some(task) => super::atomic::write_atomic(&task_file, task),
+        None if task_file.exists() => std::fs::remove_file(&task_file),
+        None => Ok(()),
+    }
+}
+
+/// Creates an empty hidden directory next to `path`, removed when dropped.
+fn staging_dir(path: &Path) -> std::io::Result<TempDir> {
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(parent)?;
+    tempfile::Builder::new()
+        .prefix(".restore-")
+        .tempdir_in(parent)
+}
+
+/// Replaces the directory `dir` with `staged`, or removes it when there is
+/// nothing staged.
+///
+/// The old directory is moved aside before the rename and moved back if the
+/// rename fails.
+fn replace_dir(dir: &Path, staged: Option<TempDir>) -> std::io::Result<()> {
+    // Dropping `aside` removes the old directory once it is replaced
+    let aside = if dir.exists() {
+        let aside = staging_dir(dir)?;
+        let old = aside.path().join(TODO_DIR_NAME);
+        std::fs::rename(dir, &old)?;
+        Some((aside, old))
+    } else {
+        None
+    };
+    if let Some(staged) = staged {
+        let staged = staged.keep();
+        if let Err(e) = std::fs::rename(&staged, dir) {
+            let _ = std::fs::remove_dir_all(&staged);
+            if let Some((_, old)) = &aside {
+                let _ = std::fs::rename(old, dir);
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// Returns the names of all snapshots, oldest first.
+#[must_use]
+pub fn list_snapshots(paths: &McgravityPaths) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(paths.snapshots_dir()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    names
+}
+
+/// Returns the directory of the snapshot `name`, rejecting names that are
+/// not a plain directory name inside the snapshots directory.
+fn snapshot_dir(paths: &McgravityPaths, name: &str) -> std::io::Result<PathBuf> {
+    let is_plain_name = !name.is_empty()
+        && Path::new(name).file_name().is_some_and(|file| file == name)
+        && name != "..";
+    let dir = paths.snapshots_dir().join(name);
+    if !is_plain_name || !dir.is_dir() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("No snapshot named {name}"),
+        ));
+    }
+    Ok(dir)
+}
+
+/// Copies the directory `from` to `to`, including subdirectories.
+fn copy_dir_recursive(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir_recursive(&path, &dest)?;
+        } else {
+            std::fs::copy(&path, &dest)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)
+    }
+
+    #[test]
+    fn snapshot_captures_task_todo_and_done() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let paths = McgravityPaths::new(dir.path());
+        write(&paths.task_file(), "Build the parser")?;
+        write(&paths.todo_dir().join("task-001.md"), "Lexer")?;
+        write(&paths.done_dir().join("task-000.md"), "Setup")?;
+
+        let name = create_snapshot(&paths)?;
+
+        let snapshot = paths.snapshots_dir().join(&name);
+        assert_eq!(
+            std::fs::read_to_string(snapshot.join("task.md"))?,
+            "Build the parser"
+        );
+        assert_eq!(
+            std::fs::read_to_string(snapshot.join("todo/task-001.md"))?,
+            "Lexer"
+        );
+        assert_eq!(
+            std::fs::read_to_string(snapshot.join("todo/done/task-000.md"))?,
+            "Setup"
+        );
+        assert_eq!(list_snapshots(&paths), [name]);
+        Ok(())
+    }
+
+    #[test]
+    fn snapshots_in_the_same_second_get_distinct_names() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let paths = McgravityPaths::new(dir.path());
+        write(&paths.task_file(), "Task")?;
+
+        let first = create_snapshot(&paths)?;
+        let second = create_snapshot(&paths)?;
+
+        assert_ne!(first, second);
+        assert_eq!(list_snapshots(&paths).len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn restore_replaces_live_files() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let paths = McgravityPaths::new(dir.path());
+        write(&paths.task_file(), "Original task")?;
+        write(&paths.todo_dir().join("task-001.md"), "Lexer")?;
+        let name = create_snapshot(&paths)?;
+
+        // A run rewrites the task, completes the todo and plans a new one
+        write(&paths.task_file(), "Task with summaries")?;
+        std::fs::remove_file(paths.todo_dir().join("task-001.md"))?;
+        write(&paths.done_dir().join("task-001.md"), "Lexer")?;
+        write(&paths.todo_dir().join("task-002.md"), "Parser")?;
+
+        restore_snapshot(&paths, &name)?;
+
+        assert_eq!(std::fs::read_to_string(paths.task_file())?, "Original task");
+        assert!(paths.todo_dir().join("task-001.md").is_file());
+        assert!(!paths.todo_dir().join("task-002.md").exists());
+        assert!(!paths.done_dir().exists());
+        Ok(())
+    }
+
+    /// Tests that a todo directory that cannot be copied leaves the live
+    /// files untouched.
+    #[cfg(unix)]
+    #[test]
+    fn failed_restore_keeps_live_files() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let paths = McgravityPaths::new(dir.path());
+        write(&paths.task_file(), "Original task")?;
+        write(&paths.todo_dir().join("task-001.md"), "Lexer")?;
+        let name = create_snapshot(&paths)?;
+        // A dangling link in the snapshot fails the copy
+        let snapshot_todo = paths.snapshots_dir().join(&name).join("todo");
+        std::os::unix::fs::symlink(
+            dir.path().join("missing"),
+            snapshot_todo.join("task-002.md"),
+        )?;
+        write(&paths.task_file(), "Current task")?;
+        write(&paths.todo_dir().join("task-003.md"), "Parser")?;
+
+        assert!(restore_snapshot(&paths, &name).is_err());
+
+        assert_eq!(std::fs::read_to_string(paths.task_file())?, "Current task");
+        assert!(paths.todo_dir().join("task-001.md").is_file());
+        assert!(paths.todo_dir().join("task-003.md").is_file());
+        let leftovers = std::fs::read_dir(paths.todo_dir().parent().unwrap_or(dir.path()))?
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(".restore-"))
+            .count();
+        assert_eq!(leftovers, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn restore_rejects_unknown_and_escaping_names() -> anyhow::Result<()> {
+        let dir = TempDir::new()?;
+        let paths = McgravityPaths::new(dir.path());
+        write(&paths.task_file(), "Task")?;
+        create_snapshot(&paths)?;
+
+        for name in ["missing", "", "..", "../todo", "/tmp"] {
+            assert!(restore_snapshot(&paths, name).is_err(), "{name:?}");
+        }
+        assert_eq!(std::fs::read_to_string(paths.task_file())?, "Task");
+        Ok(())
+    }
+}
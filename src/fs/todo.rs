@@ -2,8 +2,11 @@
 
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use tokio::fs;
 
+use crate::app::state::TodoNaming;
+
 /// Default directory containing todo files.
 pub const TODO_DIR: &str = ".mcgravity/todo";
 
@@ -24,22 +27,26 @@ pub fn ensure_todo_dirs() -> Result<()> {
 
 /// Scans the specified directory for markdown files.
 ///
-/// Returns files sorted by creation time (oldest first).
+/// Returns files in execution order for the `naming` scheme: by task number
+/// (`task-002.md` before `task-010.md`) or by leading timestamp
+/// (`20260117-143000-topic.md`). Files whose names do not follow the scheme
+/// come last, and ties are broken by creation time (oldest first).
 ///
 /// # Arguments
 ///
 /// * `todo_dir` - Path to the directory containing todo files
+/// * `naming` - The todo naming scheme that decides the order
 ///
 /// # Errors
 ///
 /// Returns an error if the directory cannot be read or file metadata is inaccessible.
-pub async fn scan_todo_files(todo_dir: &Path) -> Result<Vec<PathBuf>> {
+pub async fn scan_todo_files(todo_dir: &Path, naming: TodoNaming) -> Result<Vec<PathBuf>> {
     // Check if directory exists using tokio::fs::try_exists
     if !fs::try_exists(todo_dir).await.unwrap_or(false) {
         return Ok(Vec::new());
     }
 
-    let mut files: Vec<(PathBuf, std::time::SystemTime)> = Vec::new();
+    let mut files: Vec<(PathBuf, SystemTime)> = Vec::new();
     let mut read_dir = fs::read_dir(todo_dir)
         .await
         .context("Failed to read todo directory")?;
@@ -57,12 +64,107 @@ pub async fn scan_todo_files(todo_dir: &Path) -> Result<Vec<PathBuf>> {
         }
     }
 
-    // Sort by creation time (oldest first)
-    files.sort_by(|a, b| a.1.cmp(&b.1));
+    sort_todo_files(&mut files, naming);
 
     Ok(files.into_iter().map(|(path, _)| path).collect())
 }
 
+/// Sorts `(path, created)` pairs into execution order for `naming`.
+fn sort_todo_files(files: &mut [(PathBuf, SystemTime)], naming: TodoNaming) {
+    files.sort_by_cached_key(|(path, created)| {
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let key = match naming {
+            TodoNaming::Sequential => sequence_number(&stem).map(|n| (n, String::new())),
+            TodoNaming::Timestamp => leading_timestamp(&stem).map(|digits| (0, digits)),
+        };
+        // Files without a key sort after all keyed files
+        (key.is_none(), key, *created)
+    });
+}
+
+/// Returns the first number in a todo file stem, e.g. `2` for `task-002`.
+fn sequence_number(stem: &str) -> Option<u64> {
+    let start = stem.find(|c: char| c.is_ascii_digit())?;
+    let digits: String = stem[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Returns the digits of the timestamp a todo file stem starts with, e.g.
+/// `20260117143000` for `20260117-143000-add-login`.
+///
+/// The timestamp is the leading run of digits, `-`, `_` and `T`, and must
+/// contain at least a full date (8 digits).
+fn leading_timestamp(stem: &str) -> Option<String> {
+    let digits: String = stem
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || matches!(c, '-' | '_' | 'T'))
+        .filter(char::is_ascii_digit)
+        .collect();
+    (digits.len() >= 8).then_some(digits)
+}
+
+/// Lists the todo files that can be opened for editing.
+///
+/// Returns pending files in `todo_dir` followed by completed files in
+/// `done_dir`, each group sorted by file name so the numbering shown to the
+/// user is stable. Missing or unreadable directories contribute no files.
+#[must_use]
+pub fn list_editable_todo_files(todo_dir: &Path, done_dir: &Path) -> Vec<PathBuf> {
+    let mut files = list_markdown_files(todo_dir);
+    files.extend(list_markdown_files(done_dir));
+    files
+}
+
+/// Selects a file from a list by its 1-based number, as shown by `/edit`.
+#[must_use]
+pub fn select_numbered_file(files: &[PathBuf], number: usize) -> Option<&Path> {
+    files.get(number.checked_sub(1)?).map(PathBuf::as_path)
+}
+
+/// Lists the pending todo files in `todo_dir`, sorted by file name.
+///
+/// This is the numbering used by `/todos` and `/rm-todo`. A missing or
+/// unreadable directory yields an empty list.
+#[must_use]
+pub fn list_todo_files(todo_dir: &Path) -> Vec<PathBuf> {
+    list_markdown_files(todo_dir)
+}
+
+/// Deletes the pending todo file with the 1-based `number` shown by
+/// [`list_todo_files`], returning its path.
+///
+/// # Errors
+///
+/// Returns an error if no file has that number or the file cannot be removed.
+pub fn remove_todo_file(todo_dir: &Path, number: usize) -> Result<PathBuf> {
+    let files = list_todo_files(todo_dir);
+    let path = select_numbered_file(&files, number)
+        .with_context(|| format!("No todo file numbered {number} (found {})", files.len()))?
+        .to_path_buf();
+    std::fs::remove_file(&path)
+        .with_context(|| format!("Failed to remove todo file: {}", path.display()))?;
+    Ok(path)
+}
+
+/// Lists `.md` files directly inside `dir`, sorted by file name.
+fn list_markdown_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+        .collect();
+    files.sort();
+    files
+}
+
 /// Moves completed todo files to the specified done directory.
 ///
 /// Creates the done directory if it doesn't exist. Returns the final destination
@@ -381,7 +483,7 @@ mod tests {
         #[tokio::test]
         async fn empty_directory_returns_empty_vec() -> Result<()> {
             let dir = TempDir::new()?;
-            let files = scan_todo_files(dir.path()).await?;
+            let files = scan_todo_files(dir.path(), TodoNaming::Sequential).await?;
             assert!(files.is_empty());
             Ok(())
         }
@@ -391,7 +493,7 @@ mod tests {
         async fn nonexistent_directory_returns_empty_vec() -> Result<()> {
             let dir = TempDir::new()?;
             let nonexistent = dir.path().join("does_not_exist");
-            let files = scan_todo_files(&nonexistent).await?;
+            let files = scan_todo_files(&nonexistent, TodoNaming::Sequential).await?;
             assert!(files.is_empty());
             Ok(())
         }
@@ -407,7 +509,7 @@ mod tests {
             fs::write(dir.path().join("task3.md"), "Task 3").await?;
             fs::write(dir.path().join("readme.rst"), "Readme").await?;
 
-            let files = scan_todo_files(dir.path()).await?;
+            let files = scan_todo_files(dir.path(), TodoNaming::Sequential).await?;
 
             assert_eq!(files.len(), 2);
             assert!(
@@ -429,7 +531,7 @@ mod tests {
             fs::create_dir(&subdir).await?;
             fs::write(subdir.join("nested.md"), "Nested").await?;
 
-            let files = scan_todo_files(dir.path()).await?;
+            let files = scan_todo_files(dir.path(), TodoNaming::Sequential).await?;
 
             assert_eq!(files.len(), 1);
             assert!(files[0].file_name().is_some_and(|n| n == "task.md"));
@@ -448,7 +550,7 @@ mod tests {
             tokio::time::sleep(std::time::Duration::from_millis(50)).await;
             fs::write(dir.path().join("third.md"), "Third").await?;
 
-            let files = scan_todo_files(dir.path()).await?;
+            let files = scan_todo_files(dir.path(), TodoNaming::Sequential).await?;
 
             assert_eq!(files.len(), 3);
             // Files should be sorted oldest first
@@ -457,6 +559,67 @@ mod tests {
             assert!(files[2].file_name().is_some_and(|n| n == "third.md"));
             Ok(())
         }
+
+        /// Returns the file names of `files`, in order.
+        fn names(files: &[PathBuf]) -> Vec<String> {
+            files
+                .iter()
+                .filter_map(|f| f.file_name())
+                .map(|n| n.to_string_lossy().into_owned())
+                .collect()
+        }
+
+        /// Tests that sequential names sort by task number, not lexically or by
+        /// creation time.
+        #[tokio::test]
+        async fn sequential_names_sort_by_task_number() -> Result<()> {
+            let dir = TempDir::new()?;
+
+            for name in ["task-10.md", "task-2.md", "notes.md", "task-001.md"] {
+                fs::write(dir.path().join(name), name).await?;
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+
+            let files = scan_todo_files(dir.path(), TodoNaming::Sequential).await?;
+
+            assert_eq!(
+                names(&files),
+                ["task-001.md", "task-2.md", "task-10.md", "notes.md"]
+            );
+            Ok(())
+        }
+
+        /// Tests that timestamp names sort by their timestamp regardless of
+        /// creation time.
+        #[tokio::test]
+        async fn timestamp_names_sort_by_timestamp() -> Result<()> {
+            let dir = TempDir::new()?;
+
+            for name in [
+                "20260117-150000-deploy.md",
+                "notes.md",
+                "20260117-093000-schema.md",
+                "20251231-235959-setup.md",
+                "20260117-093000-api.md",
+            ] {
+                fs::write(dir.path().join(name), name).await?;
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+
+            let files = scan_todo_files(dir.path(), TodoNaming::Timestamp).await?;
+
+            assert_eq!(
+                names(&files),
+                [
+                    "20251231-235959-setup.md",
+                    "20260117-093000-schema.md",
+                    "20260117-093000-api.md",
+                    "20260117-150000-deploy.md",
+                    "notes.md",
+                ]
+            );
+            Ok(())
+        }
     }
 
     // =========================================================================
@@ -751,4 +914,109 @@ mod tests {
             Ok(())
         }
     }
+
+    // =========================================================================
+    // list_editable_todo_files / select_numbered_file Tests
+    // =========================================================================
+
+    mod editable_todo_files_tests {
+        use super::*;
+
+        /// Tests that pending files come first, then done files, each sorted by name.
+        #[test]
+        fn lists_todo_then_done_sorted_by_name() -> Result<()> {
+            let dir = TempDir::new()?;
+            let todo_dir = dir.path().join("todo");
+            let done_dir = todo_dir.join("done");
+            std::fs::create_dir_all(&done_dir)?;
+            std::fs::write(todo_dir.join("task-002.md"), "b")?;
+            std::fs::write(todo_dir.join("task-001.md"), "a")?;
+            std::fs::write(todo_dir.join("notes.txt"), "ignored")?;
+            std::fs::write(done_dir.join("task-000.md"), "done")?;
+
+            let files = list_editable_todo_files(&todo_dir, &done_dir);
+
+            assert_eq!(
+                files,
+                vec![
+                    todo_dir.join("task-001.md"),
+                    todo_dir.join("task-002.md"),
+                    done_dir.join("task-000.md"),
+                ]
+            );
+            Ok(())
+        }
+
+        /// Tests that missing directories yield an empty list.
+        #[test]
+        fn missing_directories_yield_empty_list() -> Result<()> {
+            let dir = TempDir::new()?;
+            let todo_dir = dir.path().join("todo");
+            let files = list_editable_todo_files(&todo_dir, &todo_dir.join("done"));
+            assert!(files.is_empty());
+            Ok(())
+        }
+
+        /// Tests 1-based selection, including out-of-range numbers.
+        #[test]
+        fn selects_by_one_based_number() {
+            let files = vec![PathBuf::from("a.md"), PathBuf::from("b.md")];
+            assert_eq!(select_numbered_file(&files, 1), Some(Path::new("a.md")));
+            assert_eq!(select_numbered_file(&files, 2), Some(Path::new("b.md")));
+            assert_eq!(select_numbered_file(&files, 0), None);
+            assert_eq!(select_numbered_file(&files, 3), None);
+        }
+
+        /// Tests that only pending files are listed, sorted by name.
+        #[test]
+        fn lists_pending_todo_files_only() -> Result<()> {
+            let dir = TempDir::new()?;
+            let todo_dir = dir.path().join("todo");
+            let done_dir = todo_dir.join("done");
+            std::fs::create_dir_all(&done_dir)?;
+            std::fs::write(todo_dir.join("task-002.md"), "b")?;
+            std::fs::write(todo_dir.join("task-001.md"), "a")?;
+            std::fs::write(done_dir.join("task-000.md"), "done")?;
+
+            assert_eq!(
+                list_todo_files(&todo_dir),
+                vec![todo_dir.join("task-001.md"), todo_dir.join("task-002.md")]
+            );
+            Ok(())
+        }
+
+        /// Tests that removing a numbered file leaves the others intact.
+        #[test]
+        fn removes_only_the_numbered_file() -> Result<()> {
+            let dir = TempDir::new()?;
+            let todo_dir = dir.path().to_path_buf();
+            for name in ["task-001.md", "task-002.md", "task-003.md"] {
+                std::fs::write(todo_dir.join(name), name)?;
+            }
+
+            let removed = remove_todo_file(&todo_dir, 2)?;
+
+            assert_eq!(removed, todo_dir.join("task-002.md"));
+            assert_eq!(
+                list_todo_files(&todo_dir),
+                vec![todo_dir.join("task-001.md"), todo_dir.join("task-003.md")]
+            );
+            assert_eq!(
+                std::fs::read_to_string(todo_dir.join("task-003.md"))?,
+                "task-003.md"
+            );
+            Ok(())
+        }
+
+        /// Tests that an out-of-range number removes nothing.
+        #[test]
+        fn remove_rejects_unknown_number() -> Result<()> {
+            let dir = TempDir::new()?;
+            std::fs::write(dir.path().join("task-001.md"), "a")?;
+
+            assert!(remove_todo_file(dir.path(), 2).is_err());
+            assert_eq!(list_todo_files(dir.path()).len(), 1);
+            Ok(())
+        }
+    }
 }
@@ -2,24 +2,112 @@
 //!
 //! Entry point for the application.
 
+use std::io::IsTerminal;
 use std::time::Duration;
 
 use clap::Parser;
 use ratatui::crossterm::event::{self, Event, KeyEventKind};
 
 use mcgravity::app::App;
-use mcgravity::cli::Args;
+use mcgravity::cli::{
+    Args, Command, absolutize_inputs, confirm_nested_project, format_doctor_report,
+    read_stdin_input, render_prompt, render_prompt_for_text, resolve_working_dir,
+    run_doctor_command, run_plan, which_model, write_completions,
+};
+use mcgravity::core::doctor::DoctorCheck;
+use mcgravity::core::track_child_pids;
+use mcgravity::fs::McgravityPaths;
 use mcgravity::tui::TerminalEventGuard;
+use mcgravity::tui::editor::edit_file_suspended;
 
 #[tokio::main]
 async fn main() -> std::io::Result<()> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+
+    // Run everything, including the subcommands and the spawned AI CLIs, in
+    // the project directory
+    if let Some(dir) = args.working_dir.take() {
+        let dir = resolve_working_dir(&dir).map_err(std::io::Error::other)?;
+        let launch_dir = std::env::current_dir()?;
+        args.input_files = absolutize_inputs(std::mem::take(&mut args.input_files), &launch_dir);
+        if let Some(Command::Plan { input_files, .. }) = &mut args.command {
+            *input_files = absolutize_inputs(std::mem::take(input_files), &launch_dir);
+        }
+        std::env::set_current_dir(dir)?;
+    }
+
+    match args.command.take() {
+        Some(Command::Completions { shell }) => {
+            write_completions(shell, &mut std::io::stdout());
+            return Ok(());
+        }
+        Some(Command::Plan { json, input_files }) => {
+            let output = run_plan(&input_files, json, &McgravityPaths::from_cwd())
+                .await
+                .map_err(std::io::Error::other)?;
+            print!("{output}");
+            return Ok(());
+        }
+        Some(Command::Doctor) => {
+            let checks = run_doctor_command(&McgravityPaths::from_cwd());
+            print!(
+                "{}",
+                format_doctor_report(&checks, std::io::stdout().is_terminal())
+            );
+            if checks.iter().any(DoctorCheck::is_blocking) {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        None => {}
+    }
+
+    // Piped task text is read before the TUI takes over the terminal
+    let stdin_text = read_stdin_input(
+        &mut args.input_files,
+        std::io::stdin().is_terminal(),
+        &mut std::io::stdin().lock(),
+    )
+    .map_err(std::io::Error::other)?;
+
+    if let Some(model) = args.which {
+        let (description, found) = which_model(model, &McgravityPaths::from_cwd());
+        println!("{description}");
+        if !found {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    if let Some(phase) = args.print_prompt {
+        let paths = McgravityPaths::from_cwd();
+        let prompt = match &stdin_text {
+            Some(text) => render_prompt_for_text(phase, text, &paths),
+            None => {
+                render_prompt(phase, &args.input_files, &paths).map_err(std::io::Error::other)?
+            }
+        };
+        print!("{prompt}");
+        return Ok(());
+    }
+
+    // Confirm before orchestrating a project nested inside another flow's
+    if let Some(ancestor) = McgravityPaths::from_cwd().find_ancestor_mcgravity()
+        && std::io::stdin().is_terminal()
+        && !confirm_nested_project(
+            &ancestor,
+            &mut std::io::stdin().lock(),
+            &mut std::io::stderr(),
+        )?
+    {
+        return Ok(());
+    }
 
     // Initialize the terminal with crossterm backend
     let mut terminal = ratatui::init();
 
     // Run the application
-    let result = run_app(&mut terminal, args);
+    let result = run_app(&mut terminal, args, stdin_text);
 
     // Restore the terminal
     ratatui::restore();
@@ -27,17 +115,31 @@ async fn main() -> std::io::Result<()> {
     result
 }
 
-fn run_app(terminal: &mut ratatui::DefaultTerminal, args: Args) -> std::io::Result<()> {
+fn run_app(
+    terminal: &mut ratatui::DefaultTerminal,
+    args: Args,
+    stdin_text: Option<String>,
+) -> std::io::Result<()> {
     // Enable terminal event modes (bracketed paste, keyboard enhancement).
     // The guard ensures cleanup even if the application panics.
     //
     // IMPORTANT: This must be initialized inside run_app (after ratatui::run
     // sets up the terminal) because ratatui's terminal initialization can
     // reset terminal flags.
-    let _event_guard = TerminalEventGuard::new();
+    let mut event_guard = TerminalEventGuard::new();
+
+    // Record spawned CLI PIDs so a crashed session's orphans can be detected
+    track_child_pids(McgravityPaths::from_cwd().pids_file());
 
     // Create application (starts in text input mode if no file, else flow running)
-    let mut app = App::new(args.input_file).map_err(std::io::Error::other)?;
+    let mut app = App::new(args.input_files).map_err(std::io::Error::other)?;
+    app.set_terminal_modes(
+        event_guard.bracketed_paste_enabled(),
+        event_guard.keyboard_enhancement_enabled(),
+    );
+    if let Some(text) = stdin_text {
+        app.start_with_input_text(&text);
+    }
 
     // Main event loop
     // Flow will be spawned after user submits task
@@ -99,6 +201,13 @@ fn run_app(terminal: &mut ratatui::DefaultTerminal, args: Args) -> std::io::Resu
         // Process periodic tasks (autosave, etc.)
         app.tick();
 
+        // Hand the terminal to the user's editor if /edit or /open selected a file
+        if let Some(file) = app.take_pending_edit() {
+            let (guard, result) = edit_file_suspended(terminal, event_guard, &file.path, file.line);
+            event_guard = guard;
+            app.finish_edit(&file.path, result);
+        }
+
         // Check if we should quit
         if app.should_quit() {
             break;
@@ -0,0 +1,279 @@
+//! ANSI escape sequence parsing for CLI output.
+//!
+//! AI CLIs emit ANSI escape sequences for colored diffs and highlights. This
+//! module converts SGR (Select Graphic Rendition) color and attribute codes
+//! into ratatui [`Style`] runs over the plain text, and strips every other
+//! escape sequence (cursor movement, erase, OSC titles/hyperlinks) so it
+//! never shows up as garbage in the output panel.
+
+use ratatui::style::{Color, Modifier, Style};
+
+/// ASCII escape character that introduces an ANSI sequence.
+const ESC: char = '\u{1b}';
+/// ASCII bell, one of the two OSC terminators.
+const BEL: char = '\u{7}';
+
+/// A styled byte range of plain (escape-free) text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StyleRun {
+    /// Start byte offset (inclusive).
+    pub start: usize,
+    /// End byte offset (exclusive).
+    pub end: usize,
+    /// Style to patch onto the line's base style.
+    pub style: Style,
+}
+
+/// Parses ANSI escape sequences out of `input`.
+///
+/// Returns the plain text with all escape sequences removed, and the style
+/// runs produced by SGR codes. Text with the default style gets no run, so
+/// input without color codes yields an empty run list.
+#[must_use]
+pub fn parse_ansi(input: &str) -> (String, Vec<StyleRun>) {
+    let mut text = String::with_capacity(input.len());
+    let mut runs: Vec<StyleRun> = Vec::new();
+    let mut style = Style::default();
+    let mut run_start = 0;
+    let mut chars = input.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        if ch != ESC {
+            text.push(ch);
+            continue;
+        }
+        match chars.next() {
+            // CSI: ESC [ params... final byte in 0x40..=0x7e
+            Some('[') => {
+                let mut params = String::new();
+                let mut final_byte = None;
+                for c in chars.by_ref() {
+                    if ('\u{40}'..='\u{7e}').contains(&c) {
+                        final_byte = Some(c);
+                        break;
+                    }
+                    params.push(c);
+                }
+                if final_byte == Some('m') {
+                    let next = apply_sgr(style, &params);
+                    if next != style {
+                        push_run(&mut runs, run_start, text.len(), style);
+                        run_start = text.len();
+                        style = next;
+                    }
+                }
+            }
+            // OSC: ESC ] ... terminated by BEL or ESC \
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == BEL {
+                        break;
+                    }
+                    if c == ESC && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Any other two-character escape (or a lone trailing ESC)
+            _ => {}
+        }
+    }
+    push_run(&mut runs, run_start, text.len(), style);
+    (text, runs)
+}
+
+/// Records a run if it is non-empty and not the default style.
+fn push_run(runs: &mut Vec<StyleRun>, start: usize, end: usize, style: Style) {
+    if start < end && style != Style::default() {
+        runs.push(StyleRun { start, end, style });
+    }
+}
+
+/// Applies the SGR parameter list (the part between `ESC [` and `m`) to `style`.
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    let codes: Vec<u16> = params
+        .split(';')
+        .map(|code| code.parse().unwrap_or(0))
+        .collect();
+    let mut codes = codes.into_iter();
+
+    while let Some(code) = codes.next() {
+        style = match code {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(basic_color(code - 30)),
+            38 => match extended_color(&mut codes) {
+                Some(color) => style.fg(color),
+                None => style,
+            },
+            39 => Style { fg: None, ..style },
+            40..=47 => style.bg(basic_color(code - 40)),
+            48 => match extended_color(&mut codes) {
+                Some(color) => style.bg(color),
+                None => style,
+            },
+            49 => Style { bg: None, ..style },
+            90..=97 => style.fg(bright_color(code - 90)),
+            100..=107 => style.bg(bright_color(code - 100)),
+            _ => style,
+        };
+    }
+    style
+}
+
+/// Parses the `5;n` (256-color) or `2;r;g;b` (truecolor) tail of a 38/48 code.
+fn extended_color(codes: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    match codes.next()? {
+        5 => Some(Color::Indexed(u8::try_from(codes.next()?).ok()?)),
+        2 => {
+            let r = u8::try_from(codes.next()?).ok()?;
+            let g = u8::try_from(codes.next()?).ok()?;
+            let b = u8::try_from(codes.next()?).ok()?;
+            Some(Color::Rgb(r, g, b))
+        }
+        _ => None,
+    }
+}
+
+/// Maps an SGR color offset (0-7) to a standard color.
+const fn basic_color(offset: u16) -> Color {
+    match offset {
+        0 => Color::Black,
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        _ => Color::Gray,
+    }
+}
+
+/// Maps an SGR bright color offset (0-7) to a bright color.
+const fn bright_color(offset: u16) -> Color {
+    match offset {
+        0 => Color::DarkGray,
+        1 => Color::LightRed,
+        2 => Color::LightGreen,
+        3 => Color::LightYellow,
+        4 => Color::LightBlue,
+        5 => Color::LightMagenta,
+        6 => Color::LightCyan,
+        _ => Color::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(start: usize, end: usize, style: Style) -> StyleRun {
+        StyleRun { start, end, style }
+    }
+
+    #[test]
+    fn plain_text_has_no_runs() {
+        assert_eq!(
+            parse_ansi("hello world"),
+            ("hello world".to_string(), vec![])
+        );
+    }
+
+    #[test]
+    fn foreground_color_and_reset() {
+        let (text, runs) = parse_ansi("a \u{1b}[31mred\u{1b}[0m b");
+        assert_eq!(text, "a red b");
+        assert_eq!(runs, vec![run(2, 5, Style::default().fg(Color::Red))]);
+    }
+
+    #[test]
+    fn empty_sgr_resets() {
+        let (text, runs) = parse_ansi("\u{1b}[32m+added\u{1b}[m");
+        assert_eq!(text, "+added");
+        assert_eq!(runs, vec![run(0, 6, Style::default().fg(Color::Green))]);
+    }
+
+    #[test]
+    fn combined_bold_and_color() {
+        let (text, runs) = parse_ansi("\u{1b}[1;33mwarn\u{1b}[22mnot bold\u{1b}[0m");
+        assert_eq!(text, "warnnot bold");
+        let bold_yellow = Style::default()
+            .add_modifier(Modifier::BOLD)
+            .fg(Color::Yellow);
+        assert_eq!(
+            runs,
+            vec![
+                run(0, 4, bold_yellow),
+                run(
+                    4,
+                    12,
+                    bold_yellow.remove_modifier(Modifier::BOLD | Modifier::DIM)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn bright_and_background_colors() {
+        let (text, runs) = parse_ansi("\u{1b}[92;41mok\u{1b}[49mx\u{1b}[39m");
+        assert_eq!(text, "okx");
+        assert_eq!(
+            runs,
+            vec![
+                run(0, 2, Style::default().fg(Color::LightGreen).bg(Color::Red)),
+                run(2, 3, Style::default().fg(Color::LightGreen)),
+            ]
+        );
+    }
+
+    #[test]
+    fn indexed_and_rgb_colors() {
+        let (text, runs) = parse_ansi("\u{1b}[38;5;208mo\u{1b}[38;2;10;20;30mr\u{1b}[0m");
+        assert_eq!(text, "or");
+        assert_eq!(
+            runs,
+            vec![
+                run(0, 1, Style::default().fg(Color::Indexed(208))),
+                run(1, 2, Style::default().fg(Color::Rgb(10, 20, 30))),
+            ]
+        );
+    }
+
+    #[test]
+    fn cursor_movement_codes_are_stripped() {
+        let (text, runs) = parse_ansi("\u{1b}[2K\u{1b}[1Gdone\u{1b}[?25h");
+        assert_eq!(text, "done");
+        assert!(runs.is_empty());
+    }
+
+    #[test]
+    fn osc_sequences_are_stripped() {
+        let (text, _) = parse_ansi("\u{1b}]0;title\u{7}a\u{1b}]8;;http://x\u{1b}\\link");
+        assert_eq!(text, "alink");
+    }
+
+    #[test]
+    fn unterminated_style_runs_to_end() {
+        let (text, runs) = parse_ansi("x\u{1b}[4munder");
+        assert_eq!(text, "xunder");
+        assert_eq!(
+            runs,
+            vec![run(
+                1,
+                6,
+                Style::default().add_modifier(Modifier::UNDERLINED)
+            )]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+//! Copying text to the system clipboard.
+//!
+//! Uses the OSC 52 terminal escape sequence, so the terminal emulator does
+//! the copying. This needs no clipboard libraries and works over SSH, but the
+//! terminal must support OSC 52 (inside tmux, `set-clipboard` must be on).
+
+use std::io::Write;
+
+/// Alphabet of standard base64 encoding.
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as padded standard base64.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let indices = [
+            b[0] >> 2,
+            ((b[0] & 0b11) << 4) | (b[1] >> 4),
+            ((b[1] & 0b1111) << 2) | (b[2] >> 6),
+            b[2] & 0b11_1111,
+        ];
+        for (i, index) in indices.into_iter().enumerate() {
+            if i <= chunk.len() {
+                encoded.push(char::from(BASE64_ALPHABET[usize::from(index)]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Returns the OSC 52 sequence that sets the clipboard to `text`.
+#[must_use]
+pub fn osc52_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+}
+
+/// Asks the terminal to copy `text` to the clipboard.
+///
+/// # Errors
+///
+/// Returns an error if the sequence cannot be written to stdout. Terminals
+/// without OSC 52 support ignore the sequence, which is not detectable.
+pub fn copy(text: &str) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text).as_bytes())?;
+    stdout.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_pads_partial_chunks() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn osc52_sequence_wraps_encoded_text() {
+        assert_eq!(osc52_sequence("hé"), "\x1b]52;c;aMOp\x07");
+    }
+}
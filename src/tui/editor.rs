@@ -0,0 +1,102 @@
+//! Launching an external editor from the TUI.
+//!
+//! The editor needs the real terminal, so the TUI is torn down first (event
+//! modes, raw mode, alternate screen) and re-initialized once the editor exits.
+
+use std::io;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+use ratatui::DefaultTerminal;
+
+use super::TerminalEventGuard;
+
+/// Editor used when neither `$VISUAL` nor `$EDITOR` is set.
+const FALLBACK_EDITOR: &str = "vi";
+
+/// Editors that accept a `+<line>` argument before the file to jump to a line.
+const PLUS_LINE_EDITORS: &[&str] = &[
+    "vi",
+    "vim",
+    "nvim",
+    "view",
+    "nano",
+    "emacs",
+    "emacsclient",
+    "micro",
+    "kak",
+    "mg",
+    "joe",
+];
+
+/// Returns the user's editor command: `$VISUAL`, then `$EDITOR`, then `vi`.
+#[must_use]
+pub fn editor_command() -> String {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| FALLBACK_EDITOR.to_string())
+}
+
+/// Suspends the TUI, opens `path` in the user's editor, and restores the TUI.
+///
+/// With a `line`, editors that understand `+<line>` open the file at that
+/// line; others just open the file.
+///
+/// Consumes the current event guard so bracketed paste and keyboard
+/// enhancement are disabled while the editor runs, and returns a fresh guard
+/// together with the editor's exit status.
+pub fn edit_file_suspended(
+    terminal: &mut DefaultTerminal,
+    event_guard: TerminalEventGuard,
+    path: &Path,
+    line: Option<usize>,
+) -> (TerminalEventGuard, io::Result<ExitStatus>) {
+    drop(event_guard);
+    ratatui::restore();
+
+    let result = run_editor(&editor_command(), path, line);
+
+    *terminal = ratatui::init();
+    let event_guard = TerminalEventGuard::new();
+    let result = terminal.clear().and(result);
+    (event_guard, result)
+}
+
+/// Runs `editor` (which may include arguments, e.g. `code --wait`) on `path`
+/// and waits for it to exit.
+fn run_editor(editor: &str, path: &Path, line: Option<usize>) -> io::Result<ExitStatus> {
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(FALLBACK_EDITOR);
+    Command::new(program)
+        .args(parts)
+        .args(line_argument(program, line))
+        .arg(path)
+        .status()
+}
+
+/// Returns the `+<line>` argument for `program`, if it supports one.
+fn line_argument(program: &str, line: Option<usize>) -> Option<String> {
+    let name = Path::new(program).file_name()?.to_str()?;
+    let line = line?;
+    PLUS_LINE_EDITORS
+        .contains(&name)
+        .then(|| format!("+{line}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_argument_only_for_known_editors() {
+        assert_eq!(line_argument("vim", Some(42)), Some("+42".to_string()));
+        assert_eq!(
+            line_argument("/usr/bin/nano", Some(7)),
+            Some("+7".to_string())
+        );
+        assert_eq!(line_argument("vim", None), None);
+        assert_eq!(line_argument("code", Some(42)), None);
+    }
+}
@@ -0,0 +1,263 @@
+//! Markdown styling for AI CLI output.
+//!
+//! With the `render_markdown` setting on, model output lines are styled like
+//! the markdown they usually are: `#` headings and `**bold**` become bold,
+//! `` `code` `` spans are colored, and list markers become bullets. The
+//! markers are removed from the text and the styles are returned as
+//! [`StyleRun`]s, the same way ANSI colors are handled.
+//!
+//! Lines inside ```` ``` ```` code fences are left exactly as written. Lines
+//! arrive one at a time, so [`MarkdownRenderer`] remembers whether a fence is
+//! open.
+
+use ratatui::style::{Color, Modifier, Style};
+
+use crate::tui::ansi::StyleRun;
+
+/// Marker opening and closing a code fence.
+const FENCE: &str = "```";
+
+/// Style of `**bold**` text and headings.
+const BOLD: Style = Style::new().add_modifier(Modifier::BOLD);
+
+/// Style of top-level `#` headings.
+const TOP_HEADING: Style = Style::new()
+    .add_modifier(Modifier::BOLD)
+    .add_modifier(Modifier::UNDERLINED);
+
+/// Style of `` `code` `` spans.
+const CODE: Style = Style::new().fg(Color::Cyan);
+
+/// Style of list bullets and numbers.
+const LIST_MARKER: Style = Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD);
+
+/// Styles markdown output line by line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MarkdownRenderer {
+    /// Whether a code fence was opened and not yet closed.
+    in_code_fence: bool,
+}
+
+impl MarkdownRenderer {
+    /// Styles one line of output.
+    ///
+    /// Returns the text with markdown markers removed and the style runs over
+    /// it. Fence lines and the lines between them are returned unchanged.
+    #[must_use]
+    pub fn render_line(&mut self, line: &str) -> (String, Vec<StyleRun>) {
+        if line.trim_start().starts_with(FENCE) {
+            self.in_code_fence = !self.in_code_fence;
+            return (line.to_string(), Vec::new());
+        }
+        if self.in_code_fence {
+            return (line.to_string(), Vec::new());
+        }
+        if let Some((level, title)) = heading(line) {
+            let (text, runs) = render_inline(title);
+            let style = if level == 1 { TOP_HEADING } else { BOLD };
+            let runs = cover(text.len(), &runs, style);
+            return (text, runs);
+        }
+        if let Some((indent, marker, rest)) = list_item(line) {
+            let mut text = format!("{indent}{marker} ");
+            let mut runs = vec![StyleRun {
+                start: indent.len(),
+                end: indent.len() + marker.len(),
+                style: LIST_MARKER,
+            }];
+            let offset = text.len();
+            let (rest, rest_runs) = render_inline(rest);
+            text.push_str(&rest);
+            runs.extend(rest_runs.into_iter().map(|run| StyleRun {
+                start: run.start + offset,
+                end: run.end + offset,
+                style: run.style,
+            }));
+            return (text, runs);
+        }
+        render_inline(line)
+    }
+}
+
+/// Splits a `#` heading into its level and title.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.bytes().take_while(|&b| b == b'#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let title = line[level..].strip_prefix(' ')?;
+    Some((level, title.trim()))
+}
+
+/// Splits a list item into its indentation, display marker and content.
+///
+/// `-`, `*` and `+` markers are shown as `•`; numbered markers like `1.` are
+/// kept as written.
+fn list_item(line: &str) -> Option<(&str, &str, &str)> {
+    let content = line.trim_start();
+    let indent = &line[..line.len() - content.len()];
+    let (marker, rest) = content.split_once(' ')?;
+    if matches!(marker, "-" | "*" | "+") {
+        return Some((indent, "•", rest));
+    }
+    let number = marker.strip_suffix(['.', ')'])?;
+    (!number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+        .then_some((indent, marker, rest))
+}
+
+/// Styles the `**bold**` and `` `code` `` spans of `text`.
+///
+/// Markers without a closing partner are kept as literal text, and nothing
+/// inside a code span is styled further.
+fn render_inline(text: &str) -> (String, Vec<StyleRun>) {
+    let mut out = String::with_capacity(text.len());
+    let mut runs = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let span = if rest.starts_with('`') {
+            closed_span(rest, "`").map(|(inner, len)| (inner, len, CODE))
+        } else if rest.starts_with("**") {
+            closed_span(rest, "**").map(|(inner, len)| (inner, len, BOLD))
+        } else {
+            None
+        };
+        if let Some((inner, len, style)) = span {
+            let start = out.len();
+            out.push_str(inner);
+            runs.push(StyleRun {
+                start,
+                end: out.len(),
+                style,
+            });
+            rest = &rest[len..];
+            continue;
+        }
+        // Copy up to the next possible marker
+        let first = rest.chars().next().map_or(1, char::len_utf8);
+        let next = rest[first..]
+            .find(['`', '*'])
+            .map_or(rest.len(), |index| index + first);
+        out.push_str(&rest[..next]);
+        rest = &rest[next..];
+    }
+    (out, runs)
+}
+
+/// Returns the non-empty content of a span that opens `text` with `marker`
+/// and closes with it, along with the byte length of the whole span.
+fn closed_span<'a>(text: &'a str, marker: &str) -> Option<(&'a str, usize)> {
+    let body = &text[marker.len()..];
+    let end = body.find(marker)?;
+    (end > 0).then(|| (&body[..end], marker.len() * 2 + end))
+}
+
+/// Returns runs covering `0..len` with `style`, patched by the given runs.
+fn cover(len: usize, runs: &[StyleRun], style: Style) -> Vec<StyleRun> {
+    let mut covered = Vec::with_capacity(runs.len() * 2 + 1);
+    let mut pos = 0;
+    for run in runs {
+        if pos < run.start {
+            covered.push(StyleRun {
+                start: pos,
+                end: run.start,
+                style,
+            });
+        }
+        covered.push(StyleRun {
+            start: run.start,
+            end: run.end,
+            style: style.patch(run.style),
+        });
+        pos = run.end;
+    }
+    if pos < len {
+        covered.push(StyleRun {
+            start: pos,
+            end: len,
+            style,
+        });
+    }
+    covered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(line: &str) -> (String, Vec<StyleRun>) {
+        MarkdownRenderer::default().render_line(line)
+    }
+
+    fn run(start: usize, end: usize, style: Style) -> StyleRun {
+        StyleRun { start, end, style }
+    }
+
+    #[test]
+    fn headings_drop_hashes_and_are_bold() {
+        assert_eq!(
+            render("# Plan"),
+            ("Plan".to_string(), vec![run(0, 4, TOP_HEADING)])
+        );
+        assert_eq!(
+            render("### Step `two`"),
+            (
+                "Step two".to_string(),
+                vec![run(0, 5, BOLD), run(5, 8, BOLD.patch(CODE))]
+            )
+        );
+        // A hash without a space is not a heading
+        assert_eq!(render("#123 fixed"), ("#123 fixed".to_string(), vec![]));
+    }
+
+    #[test]
+    fn bold_and_code_spans_drop_markers() {
+        assert_eq!(
+            render("Run **cargo** with `--release` now"),
+            (
+                "Run cargo with --release now".to_string(),
+                vec![run(4, 9, BOLD), run(15, 24, CODE)]
+            )
+        );
+        // Code spans are not styled further
+        assert_eq!(
+            render("`a**b**`"),
+            ("a**b**".to_string(), vec![run(0, 6, CODE)])
+        );
+    }
+
+    #[test]
+    fn unclosed_markers_stay_literal() {
+        for line in ["2 * 3 = 6", "a ** b", "it`s", "``", "é*ü"] {
+            assert_eq!(render(line), (line.to_string(), vec![]), "{line}");
+        }
+    }
+
+    #[test]
+    fn list_markers_become_styled_bullets() {
+        assert_eq!(
+            render("  - Added **tests**"),
+            (
+                "  • Added tests".to_string(),
+                vec![run(2, 5, LIST_MARKER), run(12, 17, BOLD)]
+            )
+        );
+        assert_eq!(
+            render("12. Ship it"),
+            ("12. Ship it".to_string(), vec![run(0, 3, LIST_MARKER)])
+        );
+        assert_eq!(render("-5 degrees"), ("-5 degrees".to_string(), vec![]));
+    }
+
+    #[test]
+    fn code_fences_are_left_unstyled() {
+        let mut renderer = MarkdownRenderer::default();
+        let lines = ["```rust", "# not a heading", "let x = `y`;", "```"];
+        for line in lines {
+            assert_eq!(renderer.render_line(line), (line.to_string(), vec![]));
+        }
+        assert_eq!(
+            renderer.render_line("# After"),
+            ("After".to_string(), vec![run(0, 5, TOP_HEADING)])
+        );
+    }
+}
@@ -1,5 +1,10 @@
 //! TUI presentation layer.
 
+pub mod ansi;
+pub mod clipboard;
+pub mod editor;
+pub mod markdown;
+pub mod notification;
 pub mod setup;
 pub mod theme;
 pub mod widgets;
@@ -0,0 +1,165 @@
+//! Desktop notifications and the terminal bell for unattended runs.
+//!
+//! Whether a notification fires is decided by [`flow_notification`], which is
+//! independent of the OS. Delivery requires the `desktop-notifications` cargo
+//! feature; without it, or when no notification service is available (e.g.
+//! headless sessions), [`send`] does nothing.
+//!
+//! Likewise, [`should_bell`] decides whether a finished flow rings the
+//! terminal bell and [`ring_bell`] emits it.
+
+use std::io::Write;
+
+use crate::core::FlowPhase;
+
+/// A desktop notification to show.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Notification title.
+    pub summary: String,
+    /// Notification body text.
+    pub body: String,
+}
+
+/// Returns the notification for a finished flow, if one should fire.
+///
+/// Notifications fire only when enabled and the flow ended on its own, either
+/// successfully (`Completed` / `NoTodoFiles`) or with `Failed`. Flows the user
+/// cancelled do not notify, since the user is already at the terminal.
+#[must_use]
+pub fn flow_notification(enabled: bool, phase: &FlowPhase, cycles: u32) -> Option<Notification> {
+    if !enabled {
+        return None;
+    }
+    let cycles_text = if cycles == 1 {
+        "1 cycle".to_string()
+    } else {
+        format!("{cycles} cycles")
+    };
+    match phase {
+        FlowPhase::Completed | FlowPhase::NoTodoFiles => Some(Notification {
+            summary: "McGravity flow completed".to_string(),
+            body: format!("Finished after {cycles_text}."),
+        }),
+        FlowPhase::Failed { reason } => Some(Notification {
+            summary: "McGravity flow failed".to_string(),
+            body: format!("Failed during {cycles_text}: {reason}"),
+        }),
+        _ => None,
+    }
+}
+
+/// Returns true if a finished flow should ring the terminal bell.
+///
+/// Like notifications, the bell rings only when enabled and the flow ended on
+/// its own, successfully or with `Failed`; cancelled flows stay silent.
+#[must_use]
+pub const fn should_bell(enabled: bool, phase: &FlowPhase) -> bool {
+    enabled
+        && matches!(
+            phase,
+            FlowPhase::Completed | FlowPhase::NoTodoFiles | FlowPhase::Failed { .. }
+        )
+}
+
+/// Rings the terminal bell, ignoring any errors.
+///
+/// The BEL byte is written straight to stdout rather than through the
+/// ratatui buffer, which would not pass a control character through.
+pub fn ring_bell() {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+}
+
+/// Shows a notification on a background thread, ignoring any errors.
+#[cfg(feature = "desktop-notifications")]
+pub fn send(notification: &Notification) {
+    let notification = notification.clone();
+    std::thread::spawn(move || {
+        let _ = notify_rust::Notification::new()
+            .summary(&notification.summary)
+            .body(&notification.body)
+            .show();
+    });
+}
+
+/// Shows a notification; a no-op without the `desktop-notifications` feature.
+#[cfg(not(feature = "desktop-notifications"))]
+pub fn send(_notification: &Notification) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::borrow::Cow;
+
+    #[test]
+    fn disabled_never_notifies() {
+        assert_eq!(flow_notification(false, &FlowPhase::Completed, 3), None);
+        let failed = FlowPhase::Failed {
+            reason: "boom".to_string(),
+        };
+        assert_eq!(flow_notification(false, &failed, 3), None);
+    }
+
+    #[test]
+    fn success_phases_notify_with_cycle_count() {
+        for phase in [FlowPhase::Completed, FlowPhase::NoTodoFiles] {
+            let notification = flow_notification(true, &phase, 3);
+            assert_eq!(
+                notification,
+                Some(Notification {
+                    summary: "McGravity flow completed".to_string(),
+                    body: "Finished after 3 cycles.".to_string(),
+                })
+            );
+        }
+        let notification = flow_notification(true, &FlowPhase::Completed, 1);
+        assert!(notification.is_some_and(|n| n.body == "Finished after 1 cycle."));
+    }
+
+    #[test]
+    fn failure_notifies_with_reason() {
+        let failed = FlowPhase::Failed {
+            reason: "Codex exited with code 1".to_string(),
+        };
+        let notification = flow_notification(true, &failed, 2);
+        assert!(
+            notification.is_some_and(|n| n.summary == "McGravity flow failed"
+                && n.body == "Failed during 2 cycles: Codex exited with code 1")
+        );
+    }
+
+    #[test]
+    fn cancelled_flow_does_not_notify() {
+        let running = FlowPhase::RunningExecution {
+            model_name: Cow::Borrowed("Codex"),
+            file_index: 0,
+            attempt: 1,
+        };
+        assert_eq!(flow_notification(true, &running, 2), None);
+        assert_eq!(flow_notification(true, &FlowPhase::Idle, 0), None);
+    }
+
+    #[test]
+    fn bell_rings_for_finished_flows_when_enabled() {
+        let failed = FlowPhase::Failed {
+            reason: "boom".to_string(),
+        };
+        for phase in [FlowPhase::Completed, FlowPhase::NoTodoFiles, failed] {
+            assert!(should_bell(true, &phase), "{phase:?} should ring");
+            assert!(!should_bell(false, &phase), "{phase:?} rang while disabled");
+        }
+    }
+
+    #[test]
+    fn bell_is_silent_for_cancelled_flows() {
+        let running = FlowPhase::RunningPlanning {
+            model_name: Cow::Borrowed("Codex"),
+            attempt: 1,
+        };
+        for phase in [FlowPhase::Cancelled, running, FlowPhase::Idle] {
+            assert!(!should_bell(true, &phase), "{phase:?} should not ring");
+        }
+    }
+}
@@ -11,6 +11,7 @@ use ratatui::crossterm::event::{
     PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
 };
 use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::supports_keyboard_enhancement;
 
 /// Guard to ensure terminal event modes are disabled on drop.
 ///
@@ -53,6 +54,14 @@ impl TerminalEventGuard {
 
         // Enable Keyboard Enhancement (Kitty Protocol)
         // This is required to reliably detect Shift+Enter vs Enter
+        if !supports_keyboard_enhancement().unwrap_or(false) {
+            if std::env::var("MCGRAVITY_DEBUG_KEYS").is_ok() {
+                eprintln!(
+                    "[DEBUG INIT] Keyboard enhancement NOT SUPPORTED (fallback to rapid input detection)"
+                );
+            }
+            return guard;
+        }
         match execute!(
             stdout(),
             PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
@@ -73,6 +82,18 @@ impl TerminalEventGuard {
 
         guard
     }
+
+    /// Returns whether bracketed paste mode was enabled.
+    #[must_use]
+    pub const fn bracketed_paste_enabled(&self) -> bool {
+        self.bracketed_paste_enabled
+    }
+
+    /// Returns whether keyboard enhancement was detected and enabled.
+    #[must_use]
+    pub const fn keyboard_enhancement_enabled(&self) -> bool {
+        self.keyboard_enhancement_enabled
+    }
 }
 
 impl Default for TerminalEventGuard {
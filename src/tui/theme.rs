@@ -2,6 +2,8 @@
 
 use ratatui::style::{Color, Modifier, Style};
 
+use crate::core::Model;
+
 /// Application theme with consistent colors and styles.
 #[derive(Debug, Clone)]
 pub struct Theme {
@@ -45,6 +47,40 @@ impl Default for Theme {
 }
 
 impl Theme {
+    /// Theme for terminals with a light background.
+    #[must_use]
+    pub const fn light() -> Self {
+        Self {
+            bg: Color::Reset,
+            fg: Color::Black,
+            accent: Color::Blue,
+            success: Color::Green,
+            warning: Color::Indexed(130),
+            error: Color::Red,
+            muted: Color::Gray,
+            progress_complete: Color::Blue,
+            progress_remaining: Color::Gray,
+            border: Color::DarkGray,
+        }
+    }
+
+    /// Theme using only bright colors, for low-contrast terminals.
+    #[must_use]
+    pub const fn high_contrast() -> Self {
+        Self {
+            bg: Color::Reset,
+            fg: Color::White,
+            accent: Color::LightCyan,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+            muted: Color::Gray,
+            progress_complete: Color::LightCyan,
+            progress_remaining: Color::Gray,
+            border: Color::White,
+        }
+    }
+
     /// Style for the header/title.
     #[must_use]
     pub fn header_style(&self) -> Style {
@@ -89,6 +125,12 @@ impl Theme {
         Style::default().fg(self.border)
     }
 
+    /// Style for the border of the focused chat region.
+    #[must_use]
+    pub fn focused_border_style(&self) -> Style {
+        Style::default().fg(self.accent)
+    }
+
     /// Style for highlighted/selected items.
     #[must_use]
     pub fn highlight_style(&self) -> Style {
@@ -97,6 +139,14 @@ impl Theme {
             .add_modifier(Modifier::BOLD)
     }
 
+    /// Style patched onto the rows of the output line selected for copying.
+    ///
+    /// Reversing keeps the line's own colors recognizable in every theme.
+    #[must_use]
+    pub fn selected_line_style(&self) -> Style {
+        Style::default().add_modifier(Modifier::REVERSED)
+    }
+
     /// Style for scrollbar thumb.
     #[must_use]
     pub fn scrollbar_thumb_style(&self) -> Style {
@@ -109,6 +159,21 @@ impl Theme {
         Style::default().fg(self.muted)
     }
 
+    /// Style for the output margin of lines produced by the named model.
+    ///
+    /// Each known model gets its own color so alternating planning and
+    /// execution output can be told apart; unknown names use the accent.
+    #[must_use]
+    pub fn model_style(&self, model_name: &str) -> Style {
+        let color = match Model::from_name(model_name) {
+            Some(Model::Codex) => Color::Blue,
+            Some(Model::Claude) => Color::Magenta,
+            Some(Model::Gemini) => Color::LightGreen,
+            None => self.accent,
+        };
+        Style::default().fg(color)
+    }
+
     /// Style for placeholder text (visible on both light and dark backgrounds).
     ///
     /// Uses `Color::Gray` which is brighter than `DarkGray` and visible on dark terminals,
@@ -201,6 +266,27 @@ mod tests {
         }
     }
 
+    // =========================================================================
+    // Built-in Theme Tests
+    // =========================================================================
+
+    mod builtin_themes {
+        use super::*;
+
+        /// Tests that every built-in theme keeps message colors distinct.
+        #[test]
+        fn message_colors_are_distinct() {
+            for theme in [Theme::default(), Theme::light(), Theme::high_contrast()] {
+                let colors = [theme.error, theme.success, theme.warning, theme.muted];
+                for (i, a) in colors.iter().enumerate() {
+                    for b in &colors[i + 1..] {
+                        assert_ne!(a, b, "{theme:?}");
+                    }
+                }
+            }
+        }
+    }
+
     // =========================================================================
     // Style Method Tests
     // =========================================================================
@@ -272,6 +358,20 @@ mod tests {
             assert_eq!(style.fg, Some(theme.border));
         }
 
+        /// Tests that each model gets a distinct margin color.
+        #[test]
+        fn model_styles_are_distinct() {
+            let theme = Theme::default();
+            let codex = theme.model_style("Codex");
+            let claude = theme.model_style("Claude Code");
+            let gemini = theme.model_style("Gemini");
+
+            assert_ne!(codex, claude);
+            assert_ne!(claude, gemini);
+            assert_ne!(codex, gemini);
+            assert_eq!(theme.model_style("Mock").fg, Some(theme.accent));
+        }
+
         /// Tests that highlight style uses accent with bold modifier.
         #[test]
         fn highlight_style_uses_accent_and_bold() {
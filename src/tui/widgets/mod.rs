@@ -9,5 +9,6 @@ pub use command_popup::{CommandMatch, CommandPopup, CommandPopupState, MAX_COMMA
 pub use file_popup::{FileSuggestionPopup, MAX_POPUP_ROWS, PopupState};
 pub use output::{
     MAX_OUTPUT_LINES, OutputLine, OutputLineType, OutputWidget, calculate_visual_line_count,
+    char_display_width,
 };
-pub use status_indicator::StatusIndicatorWidget;
+pub use status_indicator::{SPINNER_FRAMES, StatusIndicatorWidget};
@@ -7,21 +7,32 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
+    style::Style,
     text::{Line, Span},
     widgets::{
         Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
         Widget,
     },
 };
+use std::ops::Range;
+use std::time::SystemTime;
 use unicode_width::UnicodeWidthChar;
 
 use crate::tui::Theme;
+use crate::tui::ansi::{StyleRun, parse_ansi};
+use crate::tui::markdown::MarkdownRenderer;
 
 /// Maximum number of output lines to keep in buffer.
 /// Lines beyond this are truncated from the beginning to prevent unbounded memory growth.
 /// The value of 5000 provides better history retention while still preventing memory issues.
 pub const MAX_OUTPUT_LINES: usize = 5000;
 
+/// Width of the `HH:MM:SS ` timestamp column shown when timestamps are enabled.
+pub const TIMESTAMP_WIDTH: usize = 9;
+
+/// Width of the colored margin shown before lines that carry a model source.
+pub const SOURCE_MARGIN_WIDTH: usize = 2;
+
 /// Types of output lines for different styling.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum OutputLineType {
@@ -40,6 +51,23 @@ pub enum OutputLineType {
     SystemError,
     /// Progress/running message.
     SystemRunning,
+    /// Separator marking a flow phase boundary.
+    PhaseSeparator,
+}
+
+impl OutputLineType {
+    /// Returns the style lines of this type are drawn with in `theme`.
+    #[must_use]
+    pub fn style(self, theme: &Theme) -> Style {
+        match self {
+            Self::Stdout => theme.normal_style(),
+            Self::Stderr | Self::SystemWarning => theme.warning_style(),
+            Self::SystemInfo | Self::PhaseSeparator => theme.muted_style(),
+            Self::SystemSuccess => theme.success_style(),
+            Self::SystemError => theme.error_style(),
+            Self::SystemRunning => theme.highlight_style(),
+        }
+    }
 }
 
 /// A line of output with type for styling.
@@ -49,6 +77,16 @@ pub struct OutputLine {
     pub text: String,
     /// The line type for styling.
     pub line_type: OutputLineType,
+    /// Styled ranges of `text` parsed from ANSI color codes.
+    ///
+    /// Each run is patched onto the line type's base style; text outside any
+    /// run uses the base style unchanged.
+    pub styles: Vec<StyleRun>,
+    /// When the line was created.
+    pub created_at: SystemTime,
+    /// Name of the model whose CLI produced the line, for CLI output
+    /// forwarded during a planning or execution phase.
+    pub source: Option<String>,
 }
 
 impl OutputLine {
@@ -58,6 +96,9 @@ impl OutputLine {
         Self {
             text: text.into(),
             line_type: OutputLineType::Stdout,
+            styles: Vec::new(),
+            created_at: SystemTime::now(),
+            source: None,
         }
     }
 
@@ -67,6 +108,9 @@ impl OutputLine {
         Self {
             text: text.into(),
             line_type: OutputLineType::Stderr,
+            styles: Vec::new(),
+            created_at: SystemTime::now(),
+            source: None,
         }
     }
 
@@ -76,6 +120,9 @@ impl OutputLine {
         Self {
             text: format!("  {}", text.into()),
             line_type: OutputLineType::SystemInfo,
+            styles: Vec::new(),
+            created_at: SystemTime::now(),
+            source: None,
         }
     }
 
@@ -85,6 +132,9 @@ impl OutputLine {
         Self {
             text: format!("+ {}", text.into()),
             line_type: OutputLineType::SystemSuccess,
+            styles: Vec::new(),
+            created_at: SystemTime::now(),
+            source: None,
         }
     }
 
@@ -94,6 +144,9 @@ impl OutputLine {
         Self {
             text: format!("! {}", text.into()),
             line_type: OutputLineType::SystemWarning,
+            styles: Vec::new(),
+            created_at: SystemTime::now(),
+            source: None,
         }
     }
 
@@ -103,6 +156,9 @@ impl OutputLine {
         Self {
             text: format!("✗ {}", text.into()),
             line_type: OutputLineType::SystemError,
+            styles: Vec::new(),
+            created_at: SystemTime::now(),
+            source: None,
         }
     }
 
@@ -112,6 +168,68 @@ impl OutputLine {
         Self {
             text: format!("> {}", text.into()),
             line_type: OutputLineType::SystemRunning,
+            styles: Vec::new(),
+            created_at: SystemTime::now(),
+            source: None,
+        }
+    }
+
+    /// Creates a separator line marking the start of a flow phase.
+    #[must_use]
+    pub fn phase_separator(label: impl Into<String>) -> Self {
+        Self {
+            text: format!("── {} ──", label.into()),
+            line_type: OutputLineType::PhaseSeparator,
+            styles: Vec::new(),
+            created_at: SystemTime::now(),
+            source: None,
+        }
+    }
+
+    /// Converts ANSI escape sequences in the text into style runs.
+    ///
+    /// SGR color codes become [`StyleRun`]s and all escape sequences are
+    /// removed from the text. Used for raw CLI output.
+    #[must_use]
+    pub fn with_ansi_styles(mut self) -> Self {
+        if self.text.contains('\u{1b}') {
+            let (text, styles) = parse_ansi(&self.text);
+            self.text = text;
+            self.styles = styles;
+        }
+        self
+    }
+
+    /// Styles the text as markdown with `renderer`, which tracks code fences
+    /// across lines.
+    ///
+    /// Lines already styled by ANSI codes keep their text and styles; they
+    /// still pass through `renderer` so fences stay balanced.
+    #[must_use]
+    pub fn with_markdown_styles(mut self, renderer: &mut MarkdownRenderer) -> Self {
+        let (text, styles) = renderer.render_line(&self.text);
+        if self.styles.is_empty() {
+            self.text = text;
+            self.styles = styles;
+        }
+        self
+    }
+
+    /// Tags the line with the model that produced it.
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Returns the width available for this line's text within
+    /// `content_width`, leaving room for the source margin if it has one.
+    #[must_use]
+    pub const fn text_width(&self, content_width: usize) -> usize {
+        if self.source.is_some() {
+            content_width.saturating_sub(SOURCE_MARGIN_WIDTH)
+        } else {
+            content_width
         }
     }
 
@@ -134,6 +252,12 @@ pub struct OutputWidget<'a> {
     theme: &'a Theme,
     /// Whether some lines have been truncated from the beginning.
     is_truncated: bool,
+    /// Whether each line is prefixed with its creation time.
+    show_timestamps: bool,
+    /// Whether the panel has keyboard focus (highlights the border).
+    focused: bool,
+    /// Index of the line whose rows are highlighted as selected.
+    selected_line: Option<usize>,
 }
 
 impl<'a> OutputWidget<'a> {
@@ -151,6 +275,9 @@ impl<'a> OutputWidget<'a> {
             title,
             theme,
             is_truncated: false,
+            show_timestamps: false,
+            focused: false,
+            selected_line: None,
         }
     }
 
@@ -169,10 +296,75 @@ impl<'a> OutputWidget<'a> {
             title,
             theme,
             is_truncated,
+            show_timestamps: false,
+            focused: false,
+            selected_line: None,
+        }
+    }
+
+    /// Prefixes each line with its `HH:MM:SS` creation time.
+    ///
+    /// The timestamp column is [`TIMESTAMP_WIDTH`] wide and is taken from the
+    /// text width, so wrapped rows stay aligned.
+    #[must_use]
+    pub const fn with_timestamps(mut self, show_timestamps: bool) -> Self {
+        self.show_timestamps = show_timestamps;
+        self
+    }
+
+    /// Highlights the border when the panel has keyboard focus.
+    #[must_use]
+    pub const fn with_focus(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// Highlights every wrapped row of the line at `index` in `lines`.
+    #[must_use]
+    pub const fn with_selected_line(mut self, index: Option<usize>) -> Self {
+        self.selected_line = index;
+        self
+    }
+
+    /// Returns the border style for the current focus.
+    fn border_style(&self) -> Style {
+        if self.focused {
+            self.theme.focused_border_style()
+        } else {
+            self.theme.border_style()
         }
     }
 }
 
+/// Formats a line's creation time as local `HH:MM:SS`.
+#[must_use]
+pub fn format_timestamp(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Local>::from(time)
+        .format("%H:%M:%S")
+        .to_string()
+}
+
+/// Returns the width available for line text once the timestamp column,
+/// if shown, is subtracted from `content_width`.
+#[must_use]
+pub const fn text_width(content_width: usize, show_timestamps: bool) -> usize {
+    if show_timestamps {
+        content_width.saturating_sub(TIMESTAMP_WIDTH)
+    } else {
+        content_width
+    }
+}
+
+/// Returns the number of terminal columns a character occupies.
+///
+/// Wrapping is done in display columns rather than characters: CJK ideographs
+/// and most emoji take 2 columns, combining marks take none. Control
+/// characters also take none, since ratatui skips them when drawing.
+#[must_use]
+pub fn char_display_width(ch: char) -> usize {
+    ch.width().unwrap_or(0)
+}
+
 /// Calculates the total number of visual lines after wrapping for scroll calculations.
 ///
 /// This is used by the App to determine proper scroll offsets when navigating
@@ -189,7 +381,7 @@ pub fn calculate_visual_line_count(lines: &[OutputLine], content_width: usize) -
             if line.text.is_empty() {
                 1
             } else {
-                wrap_line_to_width(&line.text, content_width).len()
+                wrap_line_to_width(&line.text, line.text_width(content_width)).len()
             }
         })
         .sum()
@@ -200,42 +392,77 @@ pub fn calculate_visual_line_count(lines: &[OutputLine], content_width: usize) -
 /// Uses Unicode-aware width calculation to properly handle multi-byte characters.
 /// Each output line represents one visual row in the terminal.
 fn wrap_line_to_width(text: &str, width: usize) -> Vec<String> {
-    if width == 0 {
-        return vec![String::new()];
-    }
+    wrap_line_ranges(text, width)
+        .into_iter()
+        .map(|range| text[range].to_string())
+        .collect()
+}
 
-    if text.is_empty() {
-        return vec![String::new()];
+/// Computes the byte ranges of `text` that make up each wrapped visual row.
+///
+/// Always returns at least one (possibly empty) range.
+fn wrap_line_ranges(text: &str, width: usize) -> Vec<Range<usize>> {
+    if width == 0 || text.is_empty() {
+        return vec![Range::default()];
     }
 
     let mut result = Vec::new();
-    let mut current_line = String::new();
+    let mut row_start = 0;
     let mut current_width = 0;
 
-    for ch in text.chars() {
-        let char_width = ch.width().unwrap_or(0);
+    for (idx, ch) in text.char_indices() {
+        let char_width = char_display_width(ch);
 
-        if current_width + char_width > width {
-            // Start a new line
-            result.push(current_line);
-            current_line = String::new();
+        // A character wider than the whole row still gets a row of its own
+        if current_width + char_width > width && idx > row_start {
+            // Start a new row
+            result.push(row_start..idx);
+            row_start = idx;
             current_width = 0;
         }
 
-        current_line.push(ch);
         current_width += char_width;
     }
 
-    // Don't forget the last line
-    result.push(current_line);
+    // Don't forget the last row
+    result.push(row_start..text.len());
 
     result
 }
 
-/// A wrapped visual line with style information.
-struct VisualLine {
-    text: String,
-    line_type: OutputLineType,
+/// Splits one visual row of a line into spans, applying its ANSI style runs.
+fn styled_row_spans(line: &OutputLine, row: Range<usize>, base: Style) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut pos = row.start;
+    for run in &line.styles {
+        let start = run.start.max(row.start);
+        let end = run.end.min(row.end);
+        if start >= end {
+            continue;
+        }
+        if pos < start {
+            spans.push(Span::styled(line.text[pos..start].to_string(), base));
+        }
+        spans.push(Span::styled(
+            line.text[start..end].to_string(),
+            base.patch(run.style),
+        ));
+        pos = end;
+    }
+    if pos < row.end || spans.is_empty() {
+        spans.push(Span::styled(line.text[pos..row.end].to_string(), base));
+    }
+    spans
+}
+
+/// A wrapped visual row: the source line and the byte range it shows.
+struct VisualLine<'a> {
+    line: &'a OutputLine,
+    /// Index of `line` in the widget's lines.
+    index: usize,
+    range: Range<usize>,
+    /// Whether this is the line's first row (which carries the timestamp).
+    first_row: bool,
 }
 
 impl Widget for OutputWidget<'_> {
@@ -247,25 +474,34 @@ impl Widget for OutputWidget<'_> {
                 self.theme.header_style(),
             )]))
             .borders(Borders::ALL)
-            .border_style(self.theme.border_style());
+            .border_style(self.border_style());
 
         // Get inner area for content (excluding borders)
         let inner_area = block.inner(area);
         let visible_height = inner_area.height as usize;
 
-        // Account for scrollbar width (1 character on the right)
-        let content_width = inner_area.width.saturating_sub(1) as usize;
+        // Account for scrollbar width (1 character on the right) and the
+        // timestamp column
+        let content_width = text_width(
+            inner_area.width.saturating_sub(1) as usize,
+            self.show_timestamps,
+        );
 
         // Pre-wrap all lines to calculate visual line count
         let visual_lines: Vec<VisualLine> = self
             .lines
             .iter()
-            .flat_map(|line| {
-                let wrapped = wrap_line_to_width(&line.text, content_width);
-                wrapped.into_iter().map(move |text| VisualLine {
-                    text,
-                    line_type: line.line_type,
-                })
+            .enumerate()
+            .flat_map(|(index, line)| {
+                wrap_line_ranges(&line.text, line.text_width(content_width))
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(row, range)| VisualLine {
+                        line,
+                        index,
+                        range,
+                        first_row: row == 0,
+                    })
             })
             .collect();
 
@@ -277,17 +513,26 @@ impl Widget for OutputWidget<'_> {
             .skip(self.scroll_offset)
             .take(visible_height)
             .map(|vline| {
-                let style = match vline.line_type {
-                    OutputLineType::Stdout => self.theme.normal_style(),
-                    OutputLineType::Stderr | OutputLineType::SystemWarning => {
-                        self.theme.warning_style()
-                    }
-                    OutputLineType::SystemInfo => self.theme.muted_style(),
-                    OutputLineType::SystemSuccess => self.theme.success_style(),
-                    OutputLineType::SystemError => self.theme.error_style(),
-                    OutputLineType::SystemRunning => self.theme.highlight_style(),
-                };
-                Line::from(Span::styled(vline.text, style))
+                let style = vline.line.line_type.style(self.theme);
+                let mut spans = Vec::new();
+                if self.show_timestamps {
+                    let stamp = if vline.first_row {
+                        format!("{} ", format_timestamp(vline.line.created_at))
+                    } else {
+                        " ".repeat(TIMESTAMP_WIDTH)
+                    };
+                    spans.push(Span::styled(stamp, self.theme.muted_style()));
+                }
+                if let Some(source) = &vline.line.source {
+                    spans.push(Span::styled("▌ ", self.theme.model_style(source)));
+                }
+                spans.extend(styled_row_spans(vline.line, vline.range, style));
+                let line = Line::from(spans);
+                if self.selected_line == Some(vline.index) {
+                    line.patch_style(self.theme.selected_line_style())
+                } else {
+                    line
+                }
             })
             .collect();
 
@@ -317,7 +562,7 @@ impl Widget for OutputWidget<'_> {
                 self.theme.header_style(),
             )]))
             .borders(Borders::ALL)
-            .border_style(self.theme.border_style());
+            .border_style(self.border_style());
 
         // Render the block
         block.render(area, buf);
@@ -357,6 +602,7 @@ mod tests {
 
     mod render_tests {
         use super::*;
+        use ratatui::style::Modifier;
         use ratatui::{Terminal, backend::TestBackend};
 
         /// Tests that `OutputWidget` renders an empty output correctly.
@@ -453,6 +699,154 @@ mod tests {
             Ok(())
         }
 
+        /// Renders `lines` into a 24x6 buffer and returns the inner rows.
+        fn render_rows(lines: &[OutputLine], show_timestamps: bool) -> Result<Vec<String>> {
+            let backend = TestBackend::new(24, 6);
+            let mut terminal = Terminal::new(backend)?;
+            let theme = Theme::default();
+
+            terminal.draw(|frame| {
+                let widget =
+                    OutputWidget::new(lines, 0, "Output", &theme).with_timestamps(show_timestamps);
+                frame.render_widget(widget, frame.area());
+            })?;
+
+            let buffer = terminal.backend().buffer();
+            Ok((1..5)
+                .map(|y| (1..23).map(|x| buffer[(x, y)].symbol()).collect())
+                .collect())
+        }
+
+        /// Tests that timestamps are only rendered when enabled.
+        #[test]
+        fn timestamps_shown_only_when_enabled() -> Result<()> {
+            let lines = vec![OutputLine::stdout("hello")];
+            let stamp = format_timestamp(lines[0].created_at);
+
+            let plain = render_rows(&lines, false)?;
+            assert!(plain[0].starts_with("hello"));
+            assert!(!plain[0].contains(&stamp));
+
+            let stamped = render_rows(&lines, true)?;
+            assert!(stamped[0].starts_with(&format!("{stamp} hello")));
+            Ok(())
+        }
+
+        /// Tests that every wrapped row of the selected line is highlighted.
+        #[test]
+        fn selected_line_rows_are_reversed() -> Result<()> {
+            let lines = vec![
+                OutputLine::stdout("first"),
+                OutputLine::stdout("abcdefghijklmnopqrstuvwxyz"),
+                OutputLine::stdout("last"),
+            ];
+            let backend = TestBackend::new(24, 6);
+            let mut terminal = Terminal::new(backend)?;
+            let theme = Theme::default();
+            terminal.draw(|frame| {
+                let widget =
+                    OutputWidget::new(&lines, 0, "Output", &theme).with_selected_line(Some(1));
+                frame.render_widget(widget, frame.area());
+            })?;
+
+            let buffer = terminal.backend().buffer();
+            let reversed = |y: u16| {
+                buffer[(1, y)]
+                    .style()
+                    .add_modifier
+                    .contains(Modifier::REVERSED)
+            };
+            // The second line wraps onto rows 2 and 3
+            assert_eq!(
+                (1..5).map(reversed).collect::<Vec<_>>(),
+                vec![false, true, true, false]
+            );
+            Ok(())
+        }
+
+        /// Tests that lines tagged with a model get a colored margin that is
+        /// subtracted from the wrap width.
+        #[test]
+        fn source_lines_get_colored_margin() -> Result<()> {
+            let lines = vec![
+                OutputLine::stdout("abcdefghijklmnopqrstu").with_source("Claude Code"),
+                OutputLine::stdout("untagged"),
+            ];
+            // 21 columns minus the 2-column margin leaves 19 for text
+            assert_eq!(calculate_visual_line_count(&lines, 21), 3);
+
+            let backend = TestBackend::new(24, 6);
+            let mut terminal = Terminal::new(backend)?;
+            let theme = Theme::default();
+            terminal.draw(|frame| {
+                let widget = OutputWidget::new(&lines, 0, "Output", &theme);
+                frame.render_widget(widget, frame.area());
+            })?;
+
+            let rows = render_rows(&lines, false)?;
+            assert!(rows[0].starts_with("▌ abcdefghijklmnopqrs"));
+            assert!(rows[1].starts_with("▌ tu"));
+            assert!(rows[2].starts_with("untagged"));
+            let buffer = terminal.backend().buffer();
+            assert_eq!(
+                buffer[(1, 1)].style().fg,
+                theme.model_style("Claude Code").fg
+            );
+            Ok(())
+        }
+
+        /// Tests that the timestamp column is subtracted from the wrap width
+        /// and continuation rows are indented under it.
+        #[test]
+        fn timestamps_reduce_wrap_width() -> Result<()> {
+            // Inner width is 22, minus 1 for the scrollbar column = 21; with
+            // timestamps 12 columns remain for text.
+            let lines = vec![OutputLine::stdout("abcdefghijklmnop")];
+
+            let plain = render_rows(&lines, false)?;
+            assert!(plain[0].starts_with("abcdefghijklmnop"));
+
+            let stamped = render_rows(&lines, true)?;
+            assert!(stamped[0].ends_with("abcdefghijkl "));
+            assert_eq!(stamped[1].trim_end(), format!("{}mnop", " ".repeat(9)));
+            assert_eq!(calculate_visual_line_count(&lines, text_width(21, true)), 2);
+            Ok(())
+        }
+
+        /// Tests that ANSI color codes render as styled spans, including
+        /// across a wrapped row boundary.
+        #[test]
+        fn ansi_colors_render_as_styles() -> Result<()> {
+            use ratatui::style::{Color, Modifier};
+
+            let backend = TestBackend::new(12, 5);
+            let mut terminal = Terminal::new(backend)?;
+
+            let theme = Theme::default();
+            let lines =
+                vec![OutputLine::stdout("ab\u{1b}[1;31mred text\u{1b}[0mz").with_ansi_styles()];
+
+            terminal.draw(|frame| {
+                let widget = OutputWidget::new(&lines, 0, "Out", &theme);
+                frame.render_widget(widget, frame.area());
+            })?;
+
+            let buffer = terminal.backend().buffer();
+
+            // Content width is 9 (12 - 2 borders - 1 scrollbar column)
+            assert_eq!(buffer[(1, 1)].symbol(), "a");
+            assert_eq!(buffer[(1, 1)].style().fg, Some(theme.fg));
+            assert_eq!(buffer[(3, 1)].symbol(), "r");
+            assert_eq!(buffer[(3, 1)].style().fg, Some(Color::Red));
+            assert!(buffer[(3, 1)].style().add_modifier.contains(Modifier::BOLD));
+            // "red text" wraps onto the second row and keeps its color
+            assert_eq!(buffer[(1, 2)].symbol(), "t");
+            assert_eq!(buffer[(1, 2)].style().fg, Some(Color::Red));
+            assert_eq!(buffer[(2, 2)].symbol(), "z");
+            assert_eq!(buffer[(2, 2)].style().fg, Some(theme.fg));
+            Ok(())
+        }
+
         /// Tests that stderr uses warning style.
         #[test]
         fn stderr_uses_warning_style() -> Result<()> {
@@ -766,7 +1160,35 @@ mod tests {
             assert_eq!(info.text, "  owned info");
         }
 
+        /// Tests that `with_ansi_styles` strips escapes and records style runs.
+        #[test]
+        fn with_ansi_styles_parses_sgr_codes() {
+            let line = OutputLine::stdout("\u{1b}[32m+ added\u{1b}[0m line").with_ansi_styles();
+            assert_eq!(line.text, "+ added line");
+            assert_eq!(line.styles.len(), 1);
+            assert_eq!((line.styles[0].start, line.styles[0].end), (0, 7));
+
+            let plain = OutputLine::stdout("plain").with_ansi_styles();
+            assert_eq!(plain.text, "plain");
+            assert!(plain.styles.is_empty());
+        }
+
         /// Tests that `OutputLine` can be cloned.
+        /// Tests that markdown styling skips lines already styled by ANSI codes.
+        #[test]
+        fn with_markdown_styles_keeps_ansi_lines() {
+            let mut renderer = MarkdownRenderer::default();
+            let line = OutputLine::stdout("- **done**").with_markdown_styles(&mut renderer);
+            assert_eq!(line.text, "• done");
+            assert_eq!(line.styles.len(), 2);
+
+            let colored = OutputLine::stdout("\u{1b}[32m- **done**\u{1b}[0m")
+                .with_ansi_styles()
+                .with_markdown_styles(&mut renderer);
+            assert_eq!(colored.text, "- **done**");
+            assert_eq!(colored.styles.len(), 1);
+        }
+
         #[test]
         fn clone_preserves_all_fields() {
             let original = OutputLine::stderr("Clone test");
@@ -974,6 +1396,44 @@ mod tests {
             let result = wrap_line_to_width("ab cd ef", 4);
             assert_eq!(result, vec!["ab c", "d ef"]);
         }
+
+        /// Tests that CJK characters count as two columns.
+        #[test]
+        fn wraps_cjk_on_column_boundaries() {
+            assert_eq!(wrap_line_to_width("ab中文cd", 4), vec!["ab中", "文cd"]);
+            // A double-width glyph that would straddle the edge moves to the next row
+            assert_eq!(
+                wrap_line_to_width("ab中文cd", 3),
+                vec!["ab", "中", "文c", "d"]
+            );
+        }
+
+        /// Tests that emoji count as two columns.
+        #[test]
+        fn wraps_emoji_on_column_boundaries() {
+            assert_eq!(wrap_line_to_width("👍ok👍", 3), vec!["👍o", "k👍"]);
+        }
+
+        /// Tests that combining marks stay with their base character.
+        #[test]
+        fn combining_marks_take_no_columns() {
+            assert_eq!(wrap_line_to_width("e\u{301}xy", 2), vec!["e\u{301}x", "y"]);
+        }
+
+        /// Tests that no wrapped row is wider than the available columns.
+        #[test]
+        fn rows_never_exceed_width() {
+            let text = "mixed ASCII, 漢字かな, and emoji 🎉🚀 in one line";
+            for width in 2..=12 {
+                for row in wrap_line_to_width(text, width) {
+                    let columns: usize = row.chars().map(char_display_width).sum();
+                    assert!(
+                        columns <= width,
+                        "{row:?} is {columns} columns at width {width}"
+                    );
+                }
+            }
+        }
     }
 
     // =========================================================================
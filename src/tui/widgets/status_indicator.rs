@@ -7,9 +7,12 @@ use ratatui::{
     widgets::{Paragraph, Widget},
 };
 
-use crate::core::FlowPhase;
+use crate::core::{FlowPhase, TodoCounts};
 use crate::tui::Theme;
 
+/// Spinner frames shown next to the status while the flow is running.
+pub const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
 /// A compact 2-line status indicator widget.
 ///
 /// Displays the current flow status without borders, designed for
@@ -29,6 +32,10 @@ pub struct StatusIndicatorWidget<'a> {
     theme: &'a Theme,
     /// Maximum iterations (None = unlimited).
     max_iterations: Option<u32>,
+    /// Index into [`SPINNER_FRAMES`] of the spinner frame to draw.
+    spinner_frame: usize,
+    /// Done and pending todo counts, shown after the secondary status.
+    todo_counts: Option<TodoCounts>,
 }
 
 impl<'a> StatusIndicatorWidget<'a> {
@@ -51,6 +58,36 @@ impl<'a> StatusIndicatorWidget<'a> {
             is_running,
             theme,
             max_iterations,
+            spinner_frame: 0,
+            todo_counts: None,
+        }
+    }
+
+    /// Sets the spinner frame drawn next to the status while running.
+    ///
+    /// The index wraps around [`SPINNER_FRAMES`].
+    #[must_use]
+    pub const fn with_spinner_frame(mut self, spinner_frame: usize) -> Self {
+        self.spinner_frame = spinner_frame;
+        self
+    }
+
+    /// Sets the done and pending todo counts shown on line 2.
+    #[must_use]
+    pub const fn with_todo_counts(mut self, todo_counts: Option<TodoCounts>) -> Self {
+        self.todo_counts = todo_counts;
+        self
+    }
+
+    /// Gets the spinner prefix for the status text, empty when not running.
+    fn spinner(&self) -> String {
+        if self.is_running {
+            format!(
+                "{} ",
+                SPINNER_FRAMES[self.spinner_frame % SPINNER_FRAMES.len()]
+            )
+        } else {
+            String::new()
         }
     }
 
@@ -66,6 +103,8 @@ impl<'a> StatusIndicatorWidget<'a> {
             | FlowPhase::RunningExecution { .. }
             | FlowPhase::MovingCompletedFiles => "▶",
             FlowPhase::NoTodoFiles | FlowPhase::CycleComplete { .. } | FlowPhase::Completed => "✓",
+            FlowPhase::Paused { .. } => "‖",
+            FlowPhase::Cancelled => "■",
             FlowPhase::Failed { .. } => "✗",
         }
     }
@@ -138,10 +177,14 @@ impl<'a> StatusIndicatorWidget<'a> {
             FlowPhase::CycleComplete { iteration } => {
                 format!("Iteration #{iteration} complete")
             }
+            FlowPhase::Paused { iteration } => {
+                format!("Iteration #{iteration} complete | Paused — /next to continue")
+            }
             FlowPhase::MovingCompletedFiles => {
                 format!("{} | Updating summary...", self.iteration_prefix())
             }
             FlowPhase::Completed => "All iterations completed!".to_string(),
+            FlowPhase::Cancelled => "Flow cancelled".to_string(),
             FlowPhase::Failed { reason } => format!("Failed: {reason}"),
         }
     }
@@ -150,13 +193,16 @@ impl<'a> StatusIndicatorWidget<'a> {
     fn secondary_status(&self) -> String {
         match self.phase {
             FlowPhase::Idle => "Ready to process tasks".to_string(),
-            FlowPhase::Completed | FlowPhase::NoTodoFiles => {
+            FlowPhase::Completed | FlowPhase::NoTodoFiles | FlowPhase::Cancelled => {
                 "Enter a new task to continue".to_string()
             }
             FlowPhase::Failed { .. } => "Check logs for details".to_string(),
             FlowPhase::RunningPlanning { .. } => "Creating task breakdown...".to_string(),
             FlowPhase::RunningExecution { .. } => "Implementing task...".to_string(),
             FlowPhase::CycleComplete { .. } => "Preparing next iteration...".to_string(),
+            FlowPhase::Paused { .. } => {
+                "Step mode: type /next, or /stop to end the flow".to_string()
+            }
             _ => self.phase.description().to_string(),
         }
     }
@@ -169,6 +215,7 @@ impl<'a> StatusIndicatorWidget<'a> {
                 self.theme.success_style()
             }
             FlowPhase::Failed { .. } => self.theme.error_style(),
+            FlowPhase::Paused { .. } | FlowPhase::Cancelled => self.theme.warning_style(),
             _ => self.theme.highlight_style(),
         }
     }
@@ -180,6 +227,7 @@ impl Widget for StatusIndicatorWidget<'_> {
             // Not enough space, render just the primary status
             let line = Line::from(vec![
                 Span::styled(format!(" {} ", self.phase_icon()), self.icon_style()),
+                Span::styled(self.spinner(), self.theme.highlight_style()),
                 Span::styled(self.primary_status(), self.theme.normal_style()),
             ]);
             Paragraph::new(line).render(area, buf);
@@ -197,15 +245,21 @@ impl Widget for StatusIndicatorWidget<'_> {
             self.theme.muted_style()
         };
 
+        let mut second_line = vec![
+            Span::styled("   ", text_style), // Indent to align with text above
+            Span::styled(secondary, self.theme.muted_style()),
+        ];
+        if let Some(counts) = self.todo_counts {
+            second_line.push(Span::styled(" · ", self.theme.muted_style()));
+            second_line.push(Span::styled(counts.label(), text_style));
+        }
         let lines = vec![
             Line::from(vec![
                 Span::styled(format!(" {icon} "), icon_style),
+                Span::styled(self.spinner(), self.theme.highlight_style()),
                 Span::styled(primary, text_style),
             ]),
-            Line::from(vec![
-                Span::styled("   ", text_style), // Indent to align with text above
-                Span::styled(secondary, self.theme.muted_style()),
-            ]),
+            Line::from(second_line),
         ];
 
         Paragraph::new(lines).render(area, buf);
@@ -338,6 +392,18 @@ mod tests {
             assert_eq!(widget.phase_icon(), "✓");
         }
 
+        /// Tests that a cancelled flow shows the stop icon.
+        #[test]
+        fn cancelled_shows_stop_icon() {
+            let theme = Theme::default();
+            let phase = FlowPhase::Cancelled;
+            let widget = StatusIndicatorWidget::new(&phase, None, 2, None, false, &theme, None);
+
+            assert_eq!(widget.primary_status(), "Flow cancelled");
+            assert_eq!(widget.secondary_status(), "Enter a new task to continue");
+            assert_eq!(widget.phase_icon(), "■");
+        }
+
         /// Tests that processing todos shows progress with iteration.
         #[test]
         fn processing_todos_shows_progress() {
@@ -367,6 +433,20 @@ mod tests {
             assert_eq!(widget.phase_icon(), "✓");
         }
 
+        /// Tests that a step-mode pause tells the user how to continue.
+        #[test]
+        fn paused_shows_next_hint() {
+            let theme = Theme::default();
+            let phase = FlowPhase::Paused { iteration: 2 };
+            let widget = StatusIndicatorWidget::new(&phase, None, 2, None, true, &theme, None);
+
+            assert_eq!(
+                widget.primary_status(),
+                "Iteration #2 complete | Paused — /next to continue"
+            );
+            assert_eq!(widget.phase_icon(), "‖");
+        }
+
         /// Tests that no todo files shows complete message.
         #[test]
         fn no_todo_files_shows_complete() {
@@ -379,6 +459,24 @@ mod tests {
             assert_eq!(widget.phase_icon(), "✓");
         }
 
+        /// Tests that the spinner is only drawn while running and wraps around.
+        #[test]
+        fn spinner_shows_frame_only_while_running() {
+            let theme = Theme::default();
+            let phase = FlowPhase::ReadingInput;
+            let running = StatusIndicatorWidget::new(&phase, None, 1, None, true, &theme, None);
+
+            assert_eq!(running.spinner(), "⠋ ");
+            let running = running.with_spinner_frame(3);
+            assert_eq!(running.spinner(), "⠸ ");
+            let running = running.with_spinner_frame(SPINNER_FRAMES.len() + 1);
+            assert_eq!(running.spinner(), "⠙ ");
+
+            let idle = StatusIndicatorWidget::new(&phase, None, 1, None, false, &theme, None)
+                .with_spinner_frame(3);
+            assert_eq!(idle.spinner(), "");
+        }
+
         /// Tests icon style for idle phase.
         #[test]
         fn icon_style_idle_is_muted() {